@@ -20,6 +20,31 @@ defmodule Icu do
   This library ships with data for all locales with coverage levels
   `basic`, `moderate` or `modern` in
   https://github.com/unicode-org/cldr-json/blob/main/cldr-json/cldr-core/coverageLevels.json
+
+  ## Unknown options
+
+  Options a function does not support return `{:error, {:bad_option, key}}`
+  by default. Setting `config :icu, unknown_options: :ignore` drops them
+  instead, and `:warn` drops them with a logged warning, which helps when
+  sharing option lists between functions or across library versions.
+  Formatters list the options they dropped in `warnings/1`.
+
+  ## Concurrency
+
+  Formatters, collators and language tags are immutable once built. Their NIF
+  resources are reference counted, so passing a formatter to another process,
+  storing it in ETS or `:persistent_term` only copies a reference, and any
+  number of processes may format with the same one at the same time without
+  locking. Building one formatter per locale and sharing it is the intended
+  way to use them. `formatter_clone/1` builds an independent copy for the rare
+  case where a resource should not be shared, such as keeping its lifetime
+  apart from the original.
+
+  ## Usage statistics
+
+  `enable_stats/1` turns on counters of the calls, errors and time spent in
+  the formatting NIFs, read with `stats/0`, to see which formatters dominate
+  latency and whether errors occur in production.
   """
 
   @pd_key :icu_locale
@@ -85,6 +110,308 @@ defmodule Icu do
     end
   end
 
+  @doc """
+  Returns the locale whose data a formatter actually loaded.
+
+  Locales without dedicated data fall back along the CLDR parent chain, so a
+  formatter requested for "en-NZ" may use the data of "en-001". This is
+  useful for logging and testing fallback behaviour.
+
+  Accepts any formatter struct from this library, including `Icu.Collator`.
+
+  ## Examples
+
+      iex> formatter = Icu.Number.Formatter.new!(locale: "de-CH")
+      iex> Icu.data_locale(formatter)
+      {:ok, "de-CH"}
+
+      iex> formatter = Icu.List.Formatter.new!(locale: "de-CH")
+      iex> Icu.data_locale(formatter)
+      {:ok, "de"}
+  """
+  @spec data_locale(%{resource: reference()}) ::
+          {:ok, String.t()} | {:error, :invalid_formatter | :invalid_locale}
+  def data_locale(%{resource: resource}) do
+    Icu.Nif.formatter_data_locale(resource)
+  end
+
+  def data_locale(_formatter), do: {:error, :invalid_formatter}
+
+  @doc """
+  Returns an estimate of the memory, in bytes, held by a formatter.
+
+  The BEAM only counts the fixed-size part of a NIF resource, so heap owned by
+  the formatter (custom patterns, currency symbols, locale extensions) does
+  not show up in `:erlang.memory/0`. Locale data itself is compiled into the
+  NIF library and shared by all formatters, so it is not included.
+
+  Accepts any formatter struct from this library, `Icu.Collator` and
+  `Icu.LanguageTag`.
+
+  ## Examples
+
+      iex> {:ok, bytes} = Icu.memory(Icu.Number.Formatter.new!(locale: "en"))
+      iex> bytes > 0
+      true
+  """
+  @spec memory(%{resource: reference()}) ::
+          {:ok, non_neg_integer()} | {:error, :invalid_formatter}
+  def memory(%{resource: resource}) do
+    Icu.Nif.formatter_memory(resource)
+  end
+
+  def memory(_formatter), do: {:error, :invalid_formatter}
+
+  @doc """
+  Returns a plain map describing how `formatter` was built.
+
+  Formatters wrap NIF resources that cannot be sent to another node, stored
+  outside the VM or kept across a hot code upgrade. The config holds only
+  atoms, strings, numbers and maps, with the locale resolved to a string, so
+  it can go anywhere a term can. Pass it to `formatter_from_config/1` to get
+  an equivalent formatter back.
+
+  Accepts any formatter struct from this library, including `Icu.Collator`.
+
+  ## Examples
+
+      iex> formatter = Icu.Number.Formatter.new!(locale: "de", grouping: :always)
+      iex> {:ok, config} = Icu.formatter_to_config(formatter)
+      iex> config.args
+      [%{grouping: :always, locale: "de"}]
+  """
+  @spec formatter_to_config(struct()) :: {:ok, map()} | {:error, :invalid_formatter}
+  def formatter_to_config(%{resource: _, config: %{} = config}), do: {:ok, config}
+  def formatter_to_config(_formatter), do: {:error, :invalid_formatter}
+
+  @doc """
+  Rebuilds a formatter from a config returned by `formatter_to_config/1`.
+
+  Only this library's formatter constructors are called, so configs from
+  untrusted sources cannot invoke arbitrary functions. Errors from the
+  constructor, such as an unknown locale on an older node, are returned as
+  is.
+
+  ## Examples
+
+      iex> config = Icu.formatter_to_config(Icu.List.Formatter.new!(locale: "fr")) |> elem(1)
+      iex> {:ok, formatter} = Icu.formatter_from_config(config)
+      iex> Icu.List.Formatter.format(formatter, ["a", "b"])
+      {:ok, "a et b"}
+  """
+  @spec formatter_from_config(map()) :: {:ok, struct()} | {:error, term()}
+  def formatter_from_config(config) do
+    Icu.Formatter.Config.rebuild(config)
+  end
+
+  @doc """
+  Builds a new formatter with the same configuration as `formatter`.
+
+  The copy holds its own NIF resource, built from `formatter_to_config/1`.
+  Formatters are safe to share between processes as they are, so this is
+  only needed to give a copy its own lifetime. Since locale data is compiled
+  into the NIF library, the copy only allocates the formatter itself.
+
+  Accepts any formatter struct from this library, including `Icu.Collator`.
+
+  ## Examples
+
+      iex> formatter = Icu.List.Formatter.new!(locale: "en")
+      iex> {:ok, copy} = Icu.formatter_clone(formatter)
+      iex> copy.resource == formatter.resource
+      false
+      iex> Icu.List.Formatter.format(copy, ["a", "b"])
+      {:ok, "a and b"}
+  """
+  @spec formatter_clone(struct()) :: {:ok, struct()} | {:error, term()}
+  def formatter_clone(formatter) do
+    with {:ok, config} <- formatter_to_config(formatter) do
+      formatter_from_config(config)
+    end
+  end
+
+  @typedoc """
+  An option a formatter was given but does not act on. `{:ignored_option,
+  key}` covers unknown keys dropped by the `:unknown_options` policy or set
+  to `nil`, and options that depend on another one, such as
+  `:compact_threshold` without compact notation.
+  """
+  @type warning :: {:ignored_option, atom()}
+
+  @typedoc """
+  The unit of the `:start` and `:end` offsets that `format_to_parts`
+  functions add to each part when given `:index_mode`: UTF-8 bytes as taken
+  by `binary_part/3`, grapheme clusters as counted by `String.slice/3`, or
+  UTF-16 code units as JavaScript indexes strings.
+  """
+  @type index_mode :: :byte | :grapheme | :utf16
+
+  @typedoc """
+  Locale preferences given to a formatter constructor as `:preferences`,
+  instead of as `-u-` keywords of the locale. Each one replaces the keyword
+  the locale has: `:calendar` its `-u-ca`, `:hour_cycle` its `-u-hc` and
+  `:numbering_system` its `-u-nu`.
+  """
+  @type preferences ::
+          [
+            {:calendar, Icu.Calendar.calendar() | String.t()}
+            | {:hour_cycle, :h11 | :h12 | :h23 | :h24}
+            | {:numbering_system, String.t()}
+          ]
+          | %{optional(atom()) => term()}
+
+  @typedoc """
+  The `:part_type` of a part returned by a `format_to_parts` function.
+
+  Parts ICU4X annotates but this library has no atom for yet are kept as
+  `{:unknown, category, value}`, with the category and name ICU4X gives the
+  part, such as `{:unknown, "decimal", "exponent"}`.
+  """
+  @type part_type :: atom() | {:unknown, String.t(), String.t()}
+
+  @doc """
+  Returns the options `formatter` was built with but ignores.
+
+  Constructors accept these silently, so the formatter falls back to its
+  default behaviour. Checking the warnings in tests or during development
+  surfaces such misconfiguration early.
+
+  Accepts any formatter struct from this library, including `Icu.Collator`.
+
+  ## Examples
+
+      iex> formatter = Icu.Number.Formatter.new!(locale: "en", grouping: :always)
+      iex> Icu.warnings(formatter)
+      []
+
+      iex> formatter =
+      ...>   Icu.Experimental.Currency.Formatter.new!(currency: "USD", compact_threshold: 1000)
+      iex> Icu.warnings(formatter)
+      [{:ignored_option, :compact_threshold}]
+  """
+  @spec warnings(struct()) :: [warning()]
+  def warnings(%{resource: _, config: %{warnings: warnings}}), do: warnings
+  def warnings(_formatter), do: []
+
+  @typedoc """
+  Usage counters of one NIF, as returned by `stats/0`. `:total_time` is in
+  nanoseconds.
+  """
+  @type nif_stats :: %{
+          calls: non_neg_integer(),
+          errors: non_neg_integer(),
+          total_time: non_neg_integer()
+        }
+
+  @doc """
+  Turns the NIF usage counters read by `stats/0` on or off.
+
+  Counting is off by default. While on, every NIF but the ones reading and
+  resetting the counters counts its calls, the calls that returned an error
+  and the time spent in it. The counters are atomics shared by all
+  schedulers and keep their values when counting is turned off.
+
+  ## Examples
+
+      iex> Icu.enable_stats(true)
+      :ok
+      iex> Icu.enable_stats(false)
+      :ok
+  """
+  @spec enable_stats(boolean()) :: :ok
+  def enable_stats(enabled) when is_boolean(enabled) do
+    Icu.Nif.icu_stats_enable(enabled)
+  end
+
+  @doc """
+  Returns the usage counters of every NIF called while counting was on, keyed
+  by NIF name.
+
+  The time is spent inside the NIF only, so it excludes option normalization
+  in Elixir. The dirty and iodata variants of list formatting count towards
+  `:list_format` and `:list_format_to_parts`. Use `reset_stats/0` to start
+  over, for example after reading the counters into a metrics system.
+
+  ## Examples
+
+      iex> Icu.enable_stats(true)
+      iex> {:ok, _} = Icu.Number.format(12, locale: "en")
+      iex> Icu.enable_stats(false)
+      iex> %{number_format: %{calls: calls}} = Icu.stats()
+      iex> calls > 0
+      true
+  """
+  @spec stats() :: %{atom() => nif_stats()}
+  def stats do
+    Map.new(Icu.Nif.icu_stats())
+  end
+
+  @doc """
+  Sets the usage counters read by `stats/0` back to zero.
+  """
+  @spec reset_stats() :: :ok
+  def reset_stats do
+    Icu.Nif.icu_stats_reset()
+  end
+
+  @warmup_kinds [:number, :temporal, :list, :display_names, :currency, :units, :collator]
+
+  @typedoc "A formatter family that `warmup/2` can prepare."
+  @type warmup_kind ::
+          :number | :temporal | :list | :display_names | :currency | :units | :collator
+
+  @doc """
+  Eagerly loads the locale data used by the given formatter kinds.
+
+  The first formatter built for a locale pays for paging in its compiled data
+  and building shared singletons such as the likely-subtags expander, the
+  parent locales and the locale fallback data. Call this from your
+  application's `start/2` callback to move that cost to boot instead of the
+  first request. The work runs on a dirty CPU scheduler, so it does not block
+  normal schedulers.
+
+  `kinds` defaults to every kind: #{Enum.map_join(@warmup_kinds, ", ", &"`#{inspect(&1)}`")}.
+
+  ## Examples
+
+      iex> Icu.warmup(["en", "de-CH"], [:number, :list])
+      :ok
+
+      iex> Icu.warmup(["en"], [:calendar])
+      {:error, :invalid_options}
+  """
+  @spec warmup([LanguageTag.parsable()], [warmup_kind()]) ::
+          :ok | {:error, :invalid_locale | :invalid_options}
+  def warmup(locales, kinds \\ @warmup_kinds) when is_list(locales) and is_list(kinds) do
+    with {:ok, tags} <- parse_locales(locales) do
+      Icu.Nif.formatter_warmup(Enum.map(tags, & &1.resource), kinds)
+    end
+  end
+
+  @doc """
+  Same as `warmup/2` but raises on failure.
+  """
+  @spec warmup!([LanguageTag.parsable()], [warmup_kind()]) :: :ok
+  def warmup!(locales, kinds \\ @warmup_kinds) do
+    case warmup(locales, kinds) do
+      :ok -> :ok
+      {:error, reason} -> raise "warmup failed: #{inspect(reason)}"
+    end
+  end
+
+  defp parse_locales(locales) do
+    Enum.reduce_while(locales, {:ok, []}, fn locale, {:ok, acc} ->
+      case LanguageTag.parse(locale) do
+        {:ok, tag} -> {:cont, {:ok, [tag | acc]}}
+        _ -> {:halt, {:error, :invalid_locale}}
+      end
+    end)
+    |> case do
+      {:ok, tags} -> {:ok, Enum.reverse(tags)}
+      error -> error
+    end
+  end
+
   if @has_gettext? do
     def put_gettext_locale(backend) do
       known_locales = Gettext.known_locales(backend)
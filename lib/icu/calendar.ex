@@ -1,11 +1,655 @@
 defmodule Icu.Calendar do
-  @moduledoc false
+  @moduledoc """
+  Date conversion between the calendars supported by ICU4X.
 
-  @known_atoms [:gregorian, :buddhist, :japanese]
+  Calendars are named by their BCP-47 identifier, as a string or an atom with
+  underscores for dashes: `:gregory` (or `:gregorian`), `:iso8601` (or `:iso`),
+  `:buddhist`, `:chinese`, `:coptic`, `:dangi`, `:ethiopic`, `:ethioaa`, `:hebrew`,
+  `:indian`, `:islamic`, `:islamic_civil`, `:islamic_rgsa`, `:islamic_tbla`,
+  `:islamic_umalqura`, `:japanese`, `:japanext`, `:persian` and `:roc`.
+
+  Dates are given either as an Elixir `Date`, which fixes the day regardless of the
+  calendar argument, or as a map of fields in the calendar:
+
+  - `:year` – the calendar's extended year, or `:era` and `:era_year` for a year
+    counted in one of its eras.
+  - `:month_code` – the CLDR month code, such as `"M05"` or `"M05L"` for a leap month.
+    Alternatively `:month` gives the number of a non-leap month.
+  - `:day` – the day of the month.
+
+  Dates are returned as `t:date/0` maps, which can be passed back in as they are.
+  `convert/2` moves a date into another calendar, and `Icu.Temporal` formats these maps
+  in the calendar of the formatter's locale.
+
+  ## Chinese and Dangi calendars
+
+  The lunisolar `:chinese` and `:dangi` calendars insert a leap month after some
+  months. Its code adds an `L` to the code of the month it follows, such as `"M03L"`,
+  and `:leap_month` is set. Years are counted in a 60-year cycle, given as `:cyclic_year`
+  together with the ISO year in which the year starts as `:related_iso`. Formatting with
+  a `-u-ca-chinese` or `-u-ca-dangi` locale renders the cyclic year as a `:year_name`
+  part.
+
+      iex> {:ok, date} = Icu.Calendar.convert(~D[2023-04-20], :chinese)
+      iex> {date.cyclic_year, date.related_iso, date.month_code, date.leap_month, date.day}
+      {40, 2023, "M02L", true, 1}
+
+  ## Islamic calendars
+
+  The Islamic calendars differ in how they decide when a month starts, so the same day
+  can fall one day apart between them:
+
+  - `:islamic` and `:islamic_rgsa` – the observational calendar, approximated by
+    simulating the sighting of the crescent moon in Mecca.
+  - `:islamic_umalqura` – the Umm al-Qura calendar, used officially in Saudi Arabia.
+  - `:islamic_civil` – the tabular calendar with a Friday epoch (16 July 622 Julian).
+  - `:islamic_tbla` – the tabular calendar with a Thursday epoch (15 July 622 Julian),
+    used in astronomy.
+
+  `Icu.Temporal` formats in any of them with its `:calendar` option.
+
+      iex> for calendar <- [:islamic_umalqura, :islamic_civil, :islamic_tbla] do
+      ...>   Icu.Calendar.convert!(~D[2024-03-20], calendar).day
+      ...> end
+      [10, 10, 11]
+
+  ## Japanese eras
+
+  `japanese_eras/1` lists the eras of the `:japanese` calendar from Meiji onwards, with
+  their codes, localized names and start dates, and `japanese_era_for_date/2` looks up the
+  era of a date, which helps validate wareki (era year) input: an era year is only valid
+  while the era lasts, until the start of the next one.
+
+      iex> {:ok, era} = Icu.Calendar.japanese_era_for_date(~D[2024-04-09], locale: "ja")
+      iex> {era.code, era.name, era.start}
+      {"reiwa", "令和", ~D[2019-05-01]}
+
+  ## Weekends
+
+  `weekend_days/1`, `weekend?/2` and `add_business_days/3` follow the weekend of the
+  locale's region from CLDR, such as Saturday and Sunday in most of Europe and the
+  Americas or Friday and Saturday in much of the Middle East. Public holidays are not
+  taken into account.
+
+      iex> Icu.Calendar.add_business_days!(~D[2024-06-06], 1, locale: "ar-SA")
+      ~D[2024-06-09]
+
+  ## Rata die and Julian day numbers
+
+  The rata die counts days from 0001-01-01 (ISO), which is day 1. The Julian day number
+  counts days from noon on 1 January 4713 BC in the Julian calendar, so it is the rata die
+  plus 1721425.
+
+      iex> Icu.Calendar.date_to_rata_die(~D[2000-01-01])
+      {:ok, 730_120}
+
+      iex> {:ok, date} = Icu.Calendar.date_from_rata_die(730_120, :hebrew)
+      iex> {date.year, date.month_code, date.day}
+      {5760, "M04", 23}
+  """
+
+  alias Icu.Formatter.Options
+  alias Icu.LanguageTag
+  alias Icu.Nif
+
+  @julian_day_offset 1_721_425
+
+  @typedoc "A BCP-47 calendar identifier, such as `:hebrew` or `\"islamic-civil\"`."
+  @type calendar :: atom() | String.t()
+
+  @typedoc """
+  A date in a calendar.
+
+  `:year` is the extended year, which `:era` and `:era_year` express in the
+  calendar's eras when it has any. `:cyclic_year` (1 to 60) and `:related_iso` are
+  set for the calendars with a year cycle. `:month` is the ordinal month within the
+  year, which differs from the number in `:month_code` after a leap month.
+  """
+  @type date :: %{
+          calendar: String.t(),
+          year: integer(),
+          era: String.t() | nil,
+          era_year: integer() | nil,
+          cyclic_year: 1..60 | nil,
+          related_iso: integer() | nil,
+          month: pos_integer(),
+          month_code: String.t(),
+          leap_month: boolean(),
+          day: pos_integer()
+        }
+
+  @typedoc "A date as accepted by the conversion functions."
+  @type date_input ::
+          Date.t()
+          | %{
+              required(:day) => pos_integer(),
+              optional(:year) => integer(),
+              optional(:era) => String.t(),
+              optional(:era_year) => integer(),
+              optional(:month) => pos_integer(),
+              optional(:month_code) => String.t()
+            }
+
+  @typedoc "Amounts of each unit to add to a date, as a map or keyword list."
+  @type duration ::
+          %{optional(duration_unit()) => integer()} | [{duration_unit(), integer()}]
+
+  @type duration_unit :: :years | :months | :weeks | :days
+
+  @type error :: {:error, :invalid_calendar | :invalid_date | :invalid_duration}
+
+  @typedoc """
+  An era of the Japanese calendar.
+
+  `:code` is the era code used in `t:date/0` maps, and `:name` the era's name in the
+  requested locale. `:start` is the first day of the era.
+  """
+  @type japanese_era :: %{code: String.t(), name: String.t() | nil, start: Date.t()}
+
+  @typedoc """
+  A year in a calendar, as returned by `year_info/2`.
+  """
+  @type year_info :: %{
+          leap_year: boolean(),
+          months_in_year: pos_integer(),
+          days_in_year: pos_integer()
+        }
+
+  @typedoc """
+  A month of a year, as returned by `months/3`.
+
+  `:ordinal` is the position of the month in the year and `:name` its standalone name
+  in the requested locale, or `nil` when the calendar has no formatting data.
+  """
+  @type month :: %{
+          ordinal: pos_integer(),
+          month_code: String.t(),
+          leap_month: boolean(),
+          name: String.t() | nil
+        }
+
+  @type locale_options :: [{:locale, LanguageTag.t() | String.t() | nil}]
+
+  @doc """
+  Returns the rata die of `date`, read in `calendar`.
+
+  ## Examples
+
+      iex> Icu.Calendar.date_to_rata_die(%{year: 5784, month_code: "M07", day: 1}, :hebrew)
+      {:ok, 738_985}
+  """
+  @spec date_to_rata_die(date_input(), calendar()) :: {:ok, integer()} | error()
+  def date_to_rata_die(date, calendar \\ :iso8601) do
+    with {:ok, date} <- normalize_date(date) do
+      Nif.calendar_date_to_rata_die(date, calendar_id(calendar))
+    end
+  end
+
+  @doc """
+  Returns the rata die of `date` and raises on error.
+  """
+  @spec date_to_rata_die!(date_input(), calendar()) :: integer()
+  def date_to_rata_die!(date, calendar \\ :iso8601) do
+    date |> date_to_rata_die(calendar) |> unwrap!()
+  end
+
+  @doc """
+  Returns the date of a rata die in `calendar`.
+
+  ## Examples
+
+      iex> {:ok, date} = Icu.Calendar.date_from_rata_die(738_985, :iso8601)
+      iex> {date.year, date.month, date.day}
+      {2024, 4, 9}
+  """
+  @spec date_from_rata_die(integer(), calendar()) :: {:ok, date()} | error()
+  def date_from_rata_die(rata_die, calendar) when is_integer(rata_die) do
+    Nif.calendar_date_from_rata_die(rata_die, calendar_id(calendar))
+  end
+
+  @doc """
+  Returns the date of a rata die in `calendar` and raises on error.
+  """
+  @spec date_from_rata_die!(integer(), calendar()) :: date()
+  def date_from_rata_die!(rata_die, calendar) do
+    rata_die |> date_from_rata_die(calendar) |> unwrap!()
+  end
+
+  @doc """
+  Converts `date` into `calendar`.
+
+  `date` is an Elixir `Date` or a `t:date/0` map carrying its own `:calendar`.
+
+  ## Examples
+
+      iex> {:ok, date} = Icu.Calendar.convert(~D[2024-04-09], :hebrew)
+      iex> {date.year, date.month_code, date.day}
+      {5784, "M07", 1}
+  """
+  @spec convert(Date.t() | date(), calendar()) :: {:ok, date()} | error()
+  def convert(date, calendar) do
+    source = if is_struct(date, Date), do: :iso8601, else: Map.get(date, :calendar)
+
+    with {:ok, rata_die} <- date_to_rata_die(date, source || :iso8601) do
+      date_from_rata_die(rata_die, calendar)
+    end
+  end
+
+  @doc """
+  Converts `date` into `calendar` and raises on error.
+  """
+  @spec convert!(Date.t() | date(), calendar()) :: date()
+  def convert!(date, calendar) do
+    date |> convert(calendar) |> unwrap!()
+  end
+
+  @doc """
+  Returns the Julian day number of `date`, read in `calendar`.
+
+  ## Examples
+
+      iex> Icu.Calendar.date_to_julian_day(~D[2000-01-01])
+      {:ok, 2_451_545}
+  """
+  @spec date_to_julian_day(date_input(), calendar()) :: {:ok, integer()} | error()
+  def date_to_julian_day(date, calendar \\ :iso8601) do
+    with {:ok, rata_die} <- date_to_rata_die(date, calendar) do
+      {:ok, rata_die + @julian_day_offset}
+    end
+  end
+
+  @doc """
+  Returns the Julian day number of `date` and raises on error.
+  """
+  @spec date_to_julian_day!(date_input(), calendar()) :: integer()
+  def date_to_julian_day!(date, calendar \\ :iso8601) do
+    date |> date_to_julian_day(calendar) |> unwrap!()
+  end
+
+  @doc """
+  Returns the date of a Julian day number in `calendar`.
+  """
+  @spec date_from_julian_day(integer(), calendar()) :: {:ok, date()} | error()
+  def date_from_julian_day(julian_day, calendar) when is_integer(julian_day) do
+    date_from_rata_die(julian_day - @julian_day_offset, calendar)
+  end
+
+  @doc """
+  Returns the date of a Julian day number in `calendar` and raises on error.
+  """
+  @spec date_from_julian_day!(integer(), calendar()) :: date()
+  def date_from_julian_day!(julian_day, calendar) do
+    julian_day |> date_from_julian_day(calendar) |> unwrap!()
+  end
+
+  @doc """
+  Adds `duration` to `date` in `calendar`.
+
+  The units are added from years down to days. Adding months moves through the
+  calendar's own months, including leap months.
+
+  ## Examples
+
+      iex> {:ok, date} = Icu.Calendar.date_add(~D[2024-01-15], %{months: 1, days: -3})
+      iex> {date.year, date.month, date.day}
+      {2024, 2, 12}
+
+      iex> shevat = %{year: 5784, month_code: "M05", day: 10}
+      iex> {:ok, date} = Icu.Calendar.date_add(shevat, [months: 1], :hebrew)
+      iex> date.month_code
+      "M05L"
+  """
+  @spec date_add(date_input(), duration(), calendar()) :: {:ok, date()} | error()
+  def date_add(date, duration, calendar \\ :iso8601) do
+    with {:ok, date} <- normalize_date(date),
+         {:ok, duration} <- normalize_duration(duration) do
+      Nif.calendar_date_add(date, duration, calendar_id(calendar))
+    end
+  end
+
+  @doc """
+  Adds `duration` to `date` in `calendar` and raises on error.
+  """
+  @spec date_add!(date_input(), duration(), calendar()) :: date()
+  def date_add!(date, duration, calendar \\ :iso8601) do
+    date |> date_add(duration, calendar) |> unwrap!()
+  end
+
+  @doc """
+  Returns the number of whole `unit`s from `other` to `date` in `calendar`.
+
+  As with `Date.diff/2`, the result is negative when `date` is earlier than `other`.
+  Months and years are counted in the calendar, so a Hebrew leap year has 13 months.
+
+  ## Examples
+
+      iex> Icu.Calendar.date_diff(~D[2024-03-15], ~D[2024-01-31], :months)
+      {:ok, 1}
+
+      iex> Icu.Calendar.date_diff(~D[2024-10-03], ~D[2023-09-16], :months, :hebrew)
+      {:ok, 13}
+  """
+  @spec date_diff(date_input(), date_input(), duration_unit(), calendar()) ::
+          {:ok, integer()} | error()
+  def date_diff(date, other, unit, calendar \\ :iso8601)
+      when unit in [:years, :months, :weeks, :days] do
+    with {:ok, date} <- normalize_date(date),
+         {:ok, other} <- normalize_date(other) do
+      Nif.calendar_date_diff(date, other, unit, calendar_id(calendar))
+    end
+  end
+
+  @doc """
+  Returns the number of whole `unit`s from `other` to `date` and raises on error.
+  """
+  @spec date_diff!(date_input(), date_input(), duration_unit(), calendar()) :: integer()
+  def date_diff!(date, other, unit, calendar \\ :iso8601) do
+    date |> date_diff(other, unit, calendar) |> unwrap!()
+  end
+
+  @doc """
+  Returns whether `year` of `calendar` is a leap year, with its number of months and
+  days.
+
+  `year` is the calendar's extended year. A leap year has an extra day in solar
+  calendars and an extra month in lunisolar ones such as `:hebrew` and `:chinese`.
+
+  ## Examples
+
+      iex> Icu.Calendar.year_info(5784, :hebrew)
+      {:ok, %{leap_year: true, months_in_year: 13, days_in_year: 383}}
+  """
+  @spec year_info(integer(), calendar()) :: {:ok, year_info()} | error()
+  def year_info(year, calendar \\ :iso8601) when is_integer(year) do
+    Nif.calendar_year_info(year, calendar_id(calendar))
+  end
+
+  @doc """
+  Returns facts about `year` of `calendar` and raises on error.
+  """
+  @spec year_info!(integer(), calendar()) :: year_info()
+  def year_info!(year, calendar \\ :iso8601) do
+    year |> year_info(calendar) |> unwrap!()
+  end
+
+  @doc """
+  Returns whether `year` of `calendar` is a leap year and raises on error.
+
+  ## Examples
+
+      iex> Icu.Calendar.leap_year?(2024)
+      true
+
+      iex> Icu.Calendar.leap_year?(5785, :hebrew)
+      false
+  """
+  @spec leap_year?(integer(), calendar()) :: boolean()
+  def leap_year?(year, calendar \\ :iso8601) do
+    year_info!(year, calendar).leap_year
+  end
+
+  @doc """
+  Returns the months of `year` of `calendar` in order, with their localized names.
+
+  The list follows the calendar's leap months, such as the two months of Adar in a
+  Hebrew leap year, so it suits month pickers for non-Gregorian calendars.
+
+  ## Options
+
+    * `:locale` - the locale of the month names. Defaults to the current locale.
+
+  ## Examples
+
+      iex> {:ok, months} = Icu.Calendar.months(5784, :hebrew, locale: "en")
+      iex> months |> Enum.slice(4..6) |> Enum.map(&{&1.month_code, &1.name})
+      [{"M05", "Shevat"}, {"M05L", "Adar I"}, {"M06", "Adar II"}]
+  """
+  @spec months(integer(), calendar(), locale_options()) ::
+          {:ok, [month()]} | error() | Options.error()
+  def months(year, calendar, options \\ []) when is_integer(year) do
+    with {:ok, %{locale: locale}} <- normalize_locale_options(options) do
+      Nif.calendar_months(year, calendar_id(calendar), locale)
+    end
+  end
+
+  @doc """
+  Returns the months of `year` of `calendar` and raises on error.
+  """
+  @spec months!(integer(), calendar(), locale_options()) :: [month()]
+  def months!(year, calendar, options \\ []) do
+    year |> months(calendar, options) |> unwrap!()
+  end
+
+  @doc """
+  Returns the eras of the Japanese calendar from Meiji onwards, oldest first.
+
+  ## Options
+
+    * `:locale` - the locale of the era names. Defaults to the current locale.
+
+  ## Examples
+
+      iex> {:ok, eras} = Icu.Calendar.japanese_eras(locale: "en")
+      iex> eras |> Enum.map(& &1.code) |> Enum.take(-2)
+      ["heisei", "reiwa"]
+      iex> List.last(eras).start
+      ~D[2019-05-01]
+  """
+  @spec japanese_eras(locale_options()) :: {:ok, [japanese_era()]} | Options.error()
+  def japanese_eras(options \\ []) do
+    with {:ok, %{locale: locale}} <- normalize_locale_options(options),
+         {:ok, eras} <- Nif.calendar_japanese_eras(locale) do
+      {:ok, Enum.map(eras, &normalize_era/1)}
+    end
+  end
+
+  @doc """
+  Returns the eras of the Japanese calendar and raises on error.
+  """
+  @spec japanese_eras!(locale_options()) :: [japanese_era()]
+  def japanese_eras!(options \\ []) do
+    options |> japanese_eras() |> unwrap!()
+  end
+
+  @doc """
+  Returns the Japanese era `date` falls in, or `nil` for dates before the Meiji era.
+
+  `date` is read as in `date_to_rata_die/2` in the Gregorian calendar. See
+  `japanese_eras/1` for the options.
+
+  ## Examples
+
+      iex> {:ok, era} = Icu.Calendar.japanese_era_for_date(~D[1989-01-07], locale: "en")
+      iex> {era.code, era.name}
+      {"showa", "Shōwa"}
+
+      iex> Icu.Calendar.japanese_era_for_date(~D[1850-01-01])
+      {:ok, nil}
+  """
+  @spec japanese_era_for_date(date_input(), locale_options()) ::
+          {:ok, japanese_era() | nil} | error() | Options.error()
+  def japanese_era_for_date(date, options \\ []) do
+    with {:ok, date} <- normalize_date(date),
+         {:ok, %{locale: locale}} <- normalize_locale_options(options),
+         {:ok, era} <- Nif.calendar_japanese_era_for_date(date, locale) do
+      {:ok, era && normalize_era(era)}
+    end
+  end
+
+  @doc """
+  Returns the Japanese era `date` falls in and raises on error.
+  """
+  @spec japanese_era_for_date!(date_input(), locale_options()) :: japanese_era() | nil
+  def japanese_era_for_date!(date, options \\ []) do
+    date |> japanese_era_for_date(options) |> unwrap!()
+  end
+
+  @doc """
+  Returns the weekend days of the locale's region as ISO day-of-week numbers, as returned
+  by `Date.day_of_week/1`, with Monday being 1.
+
+  ## Options
+
+    * `:locale` - the locale whose region decides the weekend. Defaults to the current
+      locale.
+
+  ## Examples
+
+      iex> Icu.Calendar.weekend_days(locale: "en-US")
+      {:ok, [6, 7]}
+
+      iex> Icu.Calendar.weekend_days(locale: "ar-SA")
+      {:ok, [5, 6]}
+  """
+  @spec weekend_days(locale_options()) :: {:ok, [1..7]} | Options.error()
+  def weekend_days(options \\ []) do
+    with {:ok, %{locale: locale}} <- normalize_locale_options(options) do
+      Nif.calendar_weekend(locale)
+    end
+  end
+
+  @doc """
+  Returns the weekend days of the locale's region and raises on error.
+  """
+  @spec weekend_days!(locale_options()) :: [1..7]
+  def weekend_days!(options \\ []) do
+    options |> weekend_days() |> unwrap!()
+  end
 
   @doc """
-  Normalizes calendar identifiers into a format understood by the NIF layer.
+  Returns whether `date` falls on a weekend in the locale's region.
+
+  Raises when the options are invalid. See `weekend_days/1` for the options.
+
+  ## Examples
+
+      iex> Icu.Calendar.weekend?(~D[2024-06-07], locale: "de-DE")
+      false
+
+      iex> Icu.Calendar.weekend?(~D[2024-06-07], locale: "he-IL")
+      true
   """
+  @spec weekend?(Date.t(), locale_options()) :: boolean()
+  def weekend?(%Date{} = date, options \\ []) do
+    Date.day_of_week(date) in weekend_days!(options)
+  end
+
+  @doc """
+  Adds `days` business days to `date`, skipping the weekend days of the locale's region.
+
+  A negative `days` moves backwards. Adding zero days returns `date` even when it falls
+  on a weekend. See `weekend_days/1` for the options.
+
+  ## Examples
+
+      iex> Icu.Calendar.add_business_days(~D[2024-06-07], 1, locale: "en-US")
+      {:ok, ~D[2024-06-10]}
+
+      iex> Icu.Calendar.add_business_days(~D[2024-06-10], -1, locale: "en-US")
+      {:ok, ~D[2024-06-07]}
+  """
+  @spec add_business_days(Date.t(), integer(), locale_options()) ::
+          {:ok, Date.t()} | Options.error()
+  def add_business_days(%Date{} = date, days, options \\ []) when is_integer(days) do
+    with {:ok, weekend} <- weekend_days(options) do
+      {:ok, shift_business_days(date, days, weekend)}
+    end
+  end
+
+  @doc """
+  Adds `days` business days to `date` and raises on error.
+  """
+  @spec add_business_days!(Date.t(), integer(), locale_options()) :: Date.t()
+  def add_business_days!(date, days, options \\ []) do
+    date |> add_business_days(days, options) |> unwrap!()
+  end
+
+  defp shift_business_days(date, 0, _weekend), do: date
+
+  defp shift_business_days(date, days, weekend) do
+    step = if days > 0, do: 1, else: -1
+
+    # Every seven days hold each business day once. Whole weeks are skipped
+    # while at least one day is left to step through, so the result never
+    # lands on a weekend.
+    weeks = div(days - step, 7 - length(weekend))
+    date = Date.add(date, weeks * 7)
+    days = days - weeks * (7 - length(weekend))
+
+    step_business_days(date, days, step, weekend)
+  end
+
+  defp step_business_days(date, 0, _step, _weekend), do: date
+
+  defp step_business_days(date, days, step, weekend) do
+    date = Date.add(date, step)
+
+    if Date.day_of_week(date) in weekend do
+      step_business_days(date, days, step, weekend)
+    else
+      step_business_days(date, days - step, step, weekend)
+    end
+  end
+
+  defp normalize_locale_options(options) do
+    Options.normalize_options(:calendar, options, &(&1 == :locale))
+  end
+
+  defp normalize_era(%{start: {year, month, day}} = era) do
+    %{era | start: Date.new!(year, month, day)}
+  end
+
+  @int32_range -2_147_483_648..2_147_483_647
+
+  defp normalize_duration(duration) when is_list(duration) or is_map(duration) do
+    Enum.reduce_while(duration, {:ok, {0, 0, 0, 0}}, fn
+      {unit, amount}, {:ok, {years, months, weeks, days}} when amount in @int32_range ->
+        case unit do
+          :years -> {:cont, {:ok, {years + amount, months, weeks, days}}}
+          :months -> {:cont, {:ok, {years, months + amount, weeks, days}}}
+          :weeks -> {:cont, {:ok, {years, months, weeks + amount, days}}}
+          :days -> {:cont, {:ok, {years, months, weeks, days + amount}}}
+          _ -> {:halt, {:error, :invalid_duration}}
+        end
+
+      _entry, _acc ->
+        {:halt, {:error, :invalid_duration}}
+    end)
+  end
+
+  defp normalize_duration(_duration), do: {:error, :invalid_duration}
+
+  defp normalize_date(%Date{calendar: Calendar.ISO} = date) do
+    {:ok, {date.year, date.month, date.day}}
+  end
+
+  defp normalize_date(%Date{} = date) do
+    case Date.convert(date, Calendar.ISO) do
+      {:ok, iso} -> normalize_date(iso)
+      {:error, _} -> {:error, :invalid_date}
+    end
+  end
+
+  defp normalize_date(%{day: _} = fields) when not is_struct(fields), do: {:ok, fields}
+  defp normalize_date(_date), do: {:error, :invalid_date}
+
+  @doc false
+  @spec calendar_id(calendar()) :: String.t()
+  def calendar_id(:gregorian), do: "gregory"
+  def calendar_id(:iso), do: "iso8601"
+
+  def calendar_id(calendar) when is_atom(calendar),
+    do: calendar |> Atom.to_string() |> String.replace("_", "-")
+
+  def calendar_id(calendar) when is_binary(calendar), do: calendar
+
+  defp unwrap!({:ok, value}), do: value
+  defp unwrap!({:error, reason}), do: raise("calendar conversion failed: #{inspect(reason)}")
+
+  @known_atoms [:gregorian, :buddhist, :japanese]
+
+  # Normalizes calendar identifiers into a format understood by the NIF layer.
+  @doc false
   @spec normalize_identifier(term()) ::
           {:ok, atom() | String.t()} | {:error, :unsupported_calendar}
   def normalize_identifier(nil), do: {:ok, :gregorian}
@@ -0,0 +1,290 @@
+defmodule Icu.Collator do
+  @moduledoc """
+  Locale-aware string comparison and search.
+
+  Build a collator with `new/1` and reuse it across comparisons. The collator
+  follows the locale's alphabetical ordering and can be configured to ignore
+  accents and case through the `:strength` option.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "de")
+      iex> Icu.Collator.sort(collator, ["Zebra", "Äpfel", "Apfel"])
+      ["Apfel", "Äpfel", "Zebra"]
+
+      iex> collator = Icu.Collator.new!(locale: "de", strength: :primary)
+      iex> Icu.Collator.find(collator, "Herr Müller kommt", "muller")
+      [{5, 7}]
+
+  ## Options
+
+  - `:strength` – comparison level (`:primary`, `:secondary`, `:tertiary`,
+    `:quaternary`, `:identical`). `:primary` ignores accents and case,
+    `:secondary` ignores case only. Defaults to `:tertiary`.
+  - `:numeric` – when `true`, sequences of digits are compared by their
+    numeric value, so `"item2"` sorts before `"item10"`. Defaults to the
+    locale's `-u-kn-` keyword, which is off unless set.
+  - `:case_first` – `:upper` sorts uppercase before lowercase, `:lower` the
+    reverse, and `:off` uses the default tertiary ordering. Defaults to the
+    locale's `-u-kf-` keyword.
+  - `:locale` – override the locale; defaults to the application locale.
+
+  `resolved_options/1` reports the values in effect after locale defaults
+  are applied.
+
+  Custom script reordering (the `-u-kr-` keyword, e.g. Cyrillic before
+  Latin) is not supported by ICU4X; only the reordering built into a locale's
+  tailoring (such as `ru` placing Cyrillic first) applies.
+  """
+
+  alias Icu.Formatter.Config
+  alias Icu.Formatter.Options
+  alias Icu.LanguageTag
+  alias Icu.Nif
+
+  defstruct [:resource, :config]
+
+  @opaque t :: %__MODULE__{}
+
+  @option_keys [:locale, :strength, :numeric, :case_first]
+
+  @typedoc "Comparison level of the collator."
+  @type strength :: :primary | :secondary | :tertiary | :quaternary | :identical
+
+  @typedoc "Ordering between uppercase and lowercase variants."
+  @type case_first :: :upper | :lower | :off
+
+  @typedoc "Options in effect for a collator."
+  @type resolved_options :: %{
+          strength: strength(),
+          case_first: case_first(),
+          numeric: boolean()
+        }
+
+  @typedoc "Keyword form of the supported options."
+  @type options_list :: [
+          {:strength, strength()}
+          | {:numeric, boolean()}
+          | {:case_first, case_first()}
+          | {:locale, LanguageTag.t() | String.t() | nil}
+        ]
+
+  @type options_input :: options_list() | map()
+
+  @typedoc "Differences `equals_ignoring/4` can disregard."
+  @type ignorable :: :case | :accents | :both
+
+  @typedoc "Byte offset and byte length of a match, as accepted by `binary_part/3`."
+  @type match :: {non_neg_integer(), pos_integer()}
+
+  @doc """
+  Creates a collator.
+  """
+  @spec new(options_input()) :: {:ok, t()} | {:error, term()}
+  def new(options \\ []) do
+    with {:ok, opts} <- normalize_options(options),
+         {:ok, resource} <-
+           Nif.collator_new(Map.fetch!(opts, :locale), Map.delete(opts, :locale)) do
+      warnings = Options.warnings(:collator, options, opts, &(&1 in @option_keys))
+      config = Config.new(__MODULE__, :new, [Config.put_locale(options, opts.locale)], warnings)
+      {:ok, %__MODULE__{resource: resource, config: config}}
+    end
+  end
+
+  @doc """
+  Creates a collator and raises on error.
+  """
+  @spec new!(options_input()) :: t()
+  def new!(options \\ []) do
+    case new(options) do
+      {:ok, collator} -> collator
+      {:error, reason} -> raise "collator creation failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the options in effect for the collator.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "en-u-kn", case_first: :upper)
+      iex> Icu.Collator.resolved_options(collator)
+      %{strength: :tertiary, case_first: :upper, numeric: true}
+  """
+  @spec resolved_options(t()) :: resolved_options()
+  def resolved_options(%__MODULE__{resource: resource}) do
+    {:ok, options} = Nif.collator_resolved_options(resource)
+    options
+  end
+
+  @doc """
+  Compares two strings.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "sv")
+      iex> Icu.Collator.compare(collator, "ö", "z")
+      :gt
+  """
+  @spec compare(t(), String.t(), String.t()) :: :lt | :eq | :gt
+  def compare(%__MODULE__{resource: resource}, left, right)
+      when is_binary(left) and is_binary(right) do
+    {:ok, ordering} = Nif.collator_compare(resource, left, right)
+    ordering
+  end
+
+  @doc """
+  Returns `true` when two strings are equal under the collator.
+
+  Equality follows the collator's strength, so a `:primary` collator treats
+  `"Muller"` and `"müller"` as equal.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "en", strength: :secondary)
+      iex> Icu.Collator.equal?(collator, "Zoë", "ZOË")
+      true
+      iex> Icu.Collator.equal?(collator, "Zoë", "Zoe")
+      false
+  """
+  @spec equal?(t(), String.t(), String.t()) :: boolean()
+  def equal?(%__MODULE__{resource: resource}, left, right)
+      when is_binary(left) and is_binary(right) do
+    case Nif.collator_equal(resource, left, right) do
+      {:ok, equal} -> equal
+      {:error, reason} -> raise ArgumentError, "collator comparison failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns whether two strings are equal in the locale once case, accents,
+  or both are disregarded.
+
+  A shorthand for comparing names or addresses without building a
+  collator. Only the given differences are ignored: ignoring `:case` still
+  tells `"resume"` from `"résumé"`, and ignoring `:accents` still tells
+  `"resume"` from `"Resume"`. What counts as an accent is up to the locale;
+  in Swedish, `"ö"` is a letter of its own and not an accented `"o"`.
+
+  ## Options
+
+  - `:locale` – override the locale; defaults to the application locale.
+
+  ## Examples
+
+      iex> Icu.Collator.equals_ignoring("Zoë", "ZOË", :case, locale: "en")
+      {:ok, true}
+
+      iex> Icu.Collator.equals_ignoring("Zoë", "Zoe", :accents, locale: "en")
+      {:ok, true}
+
+      iex> Icu.Collator.equals_ignoring("Zoë", "zoe", :accents, locale: "en")
+      {:ok, false}
+
+      iex> Icu.Collator.equals_ignoring("Zoë", "zoe", :both, locale: "en")
+      {:ok, true}
+  """
+  @spec equals_ignoring(String.t(), String.t(), ignorable(), keyword()) ::
+          {:ok, boolean()} | {:error, term()}
+  def equals_ignoring(left, right, ignoring, options \\ [])
+
+  def equals_ignoring(left, right, ignoring, options)
+      when is_binary(left) and is_binary(right) and ignoring in [:case, :accents, :both] do
+    with {:ok, %{locale: locale}} <-
+           Options.normalize_options(:collator, options, &(&1 == :locale)) do
+      Nif.collator_equal_ignoring(locale, left, right, ignoring)
+    end
+  end
+
+  def equals_ignoring(_left, _right, _ignoring, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Sorts a list of strings in collation order.
+  """
+  @spec sort(t(), [String.t()]) :: [String.t()]
+  def sort(%__MODULE__{resource: resource}, strings) when is_list(strings) do
+    {:ok, sorted} = Nif.collator_sort(resource, strings)
+    sorted
+  end
+
+  @doc """
+  Removes strings that are equal under the collator, keeping the first
+  occurrence of each in the original order.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "en", strength: :primary)
+      iex> Icu.Collator.unique(collator, ["Café", "tea", "cafe", "TEA", "juice"])
+      ["Café", "tea", "juice"]
+  """
+  @spec unique(t(), [String.t()]) :: [String.t()]
+  def unique(%__MODULE__{resource: resource}, strings) when is_list(strings) do
+    {:ok, unique} = Nif.collator_unique(resource, strings)
+    unique
+  end
+
+  @doc """
+  Returns `true` when no two strings are equal under the collator.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "en", strength: :secondary)
+      iex> Icu.Collator.unique?(collator, ["Tag", "tag"])
+      false
+  """
+  @spec unique?(t(), [String.t()]) :: boolean()
+  def unique?(%__MODULE__{} = collator, strings) when is_list(strings) do
+    length(unique(collator, strings)) == length(strings)
+  end
+
+  @doc """
+  Sorts strings in the locale's order, comparing digit sequences numerically.
+
+  A shorthand for sorting with a `numeric: true` collator, taking the
+  options of `new/1`. Raises when the options are invalid.
+
+  ## Examples
+
+      iex> Icu.Collator.natural_sort(["item10", "Item2", "item1"], locale: "en")
+      ["item1", "Item2", "item10"]
+  """
+  @spec natural_sort([String.t()], options_input()) :: [String.t()]
+  def natural_sort(strings, options \\ []) when is_list(strings) do
+    collator = new!(Enum.to_list(options) ++ [numeric: true])
+    sort(collator, strings)
+  end
+
+  @doc """
+  Finds the occurrences of `needle` in `haystack` that collate equal to it.
+
+  Matches are non-overlapping and start and end on grapheme cluster
+  boundaries. Use a `:primary` strength collator to match regardless of
+  accents and case.
+
+  ## Examples
+
+      iex> collator = Icu.Collator.new!(locale: "en", strength: :primary)
+      iex> haystack = "Café or cafe?"
+      iex> for {offset, length} <- Icu.Collator.find(collator, haystack, "CAFE"),
+      ...>   do: binary_part(haystack, offset, length)
+      ["Café", "cafe"]
+  """
+  @spec find(t(), String.t(), String.t()) :: [match()]
+  def find(%__MODULE__{resource: resource}, haystack, needle)
+      when is_binary(haystack) and is_binary(needle) do
+    {:ok, matches} = Nif.collator_find(resource, haystack, needle)
+    matches
+  end
+
+  defimpl Inspect do
+    def inspect(_collator, _opts) do
+      "#Icu.Collator<>"
+    end
+  end
+
+  @doc false
+  @spec normalize_options(options_input()) :: {:ok, map()} | {:error, term()}
+  def normalize_options(options) do
+    Options.normalize_options(:collator, options, &(&1 in @option_keys))
+  end
+end
@@ -0,0 +1,133 @@
+defmodule Icu.Decimal do
+  @moduledoc """
+  Decimals held natively by ICU4X.
+
+  Every formatter converts the number it is given to an ICU4X decimal. An
+  `Icu.Decimal` is that conversion done once: `Icu.Number`, the number
+  formatter, and the currency and unit formatters accept it wherever they
+  accept a number, and use it without converting it again. This pays off for
+  numbers formatted several times, such as an amount shown in a list, a
+  total and a chart label.
+
+  ## Examples
+
+      iex> {:ok, decimal} = Icu.Decimal.new("1234.5678")
+      iex> {:ok, rounded} = Icu.Decimal.round(decimal, 2)
+      iex> Icu.Decimal.to_string(rounded)
+      "1234.57"
+      iex> Icu.Number.format(rounded, maximum_fraction_digits: 2)
+      {:ok, "1,234.57"}
+
+  Digits are kept exactly as given, trailing zeros included:
+
+      iex> Icu.Decimal.new!("1.50") |> Icu.Decimal.to_string()
+      "1.50"
+
+  """
+
+  import Kernel, except: [round: 1, to_string: 1]
+
+  alias Icu.Nif
+
+  defstruct [:resource]
+
+  @opaque t :: %__MODULE__{}
+
+  @typedoc "Values a decimal can be created from."
+  @type input :: number() | Decimal.t() | String.t() | t()
+
+  @typedoc """
+  How `round/3` treats the dropped digits, named as in the `Decimal`
+  library. `:down` truncates towards zero and `:up` rounds away from zero;
+  `:ceiling` and `:floor` round towards positive and negative infinity. The
+  `:half_*` modes round to the nearest value and differ only on ties.
+  """
+  @type rounding_mode ::
+          :down | :up | :ceiling | :floor | :half_up | :half_down | :half_even
+
+  @rounding_modes [:down, :up, :ceiling, :floor, :half_up, :half_down, :half_even]
+
+  @doc """
+  Creates a decimal from an integer, float, `Decimal` or decimal string.
+
+  Floats keep the shortest digits that read back as the same float, so
+  `0.1` is `0.1`.
+  """
+  @spec new(input()) :: {:ok, t()} | {:error, :invalid_number}
+  def new(%__MODULE__{} = decimal), do: {:ok, decimal}
+
+  def new(number) when is_number(number) or is_struct(number, Decimal) or is_binary(number) do
+    with {:ok, resource} <- Nif.decimal_new(number) do
+      {:ok, %__MODULE__{resource: resource}}
+    end
+  end
+
+  def new(_other), do: {:error, :invalid_number}
+
+  @spec new!(input()) :: t()
+  def new!(number) do
+    case new(number) do
+      {:ok, decimal} -> decimal
+      {:error, reason} -> raise ArgumentError, "invalid decimal: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Rounds to `places` fraction digits with `mode`, `:half_even` by default.
+
+  A negative `places` rounds to tens, hundreds and so on.
+
+  ## Examples
+
+      iex> decimal = Icu.Decimal.new!("2.5")
+      iex> decimal |> Icu.Decimal.round!() |> Icu.Decimal.to_string()
+      "2"
+      iex> decimal |> Icu.Decimal.round!(0, :half_up) |> Icu.Decimal.to_string()
+      "3"
+
+      iex> Icu.Decimal.new!(1234) |> Icu.Decimal.round!(-2) |> Icu.Decimal.to_string()
+      "1200"
+
+  """
+  @spec round(t(), integer(), rounding_mode()) ::
+          {:ok, t()} | {:error, {:invalid_option_value, :places | :rounding_mode}}
+  def round(decimal, places \\ 0, mode \\ :half_even)
+
+  def round(%__MODULE__{resource: resource}, places, mode)
+      when is_integer(places) and places in -32_767..32_767 and mode in @rounding_modes do
+    with {:ok, resource} <- Nif.decimal_round(resource, -places, mode) do
+      {:ok, %__MODULE__{resource: resource}}
+    end
+  end
+
+  def round(%__MODULE__{}, _places, mode) when mode in @rounding_modes,
+    do: {:error, {:invalid_option_value, :places}}
+
+  def round(%__MODULE__{}, _places, _mode),
+    do: {:error, {:invalid_option_value, :rounding_mode}}
+
+  @spec round!(t(), integer(), rounding_mode()) :: t()
+  def round!(decimal, places \\ 0, mode \\ :half_even) do
+    case round(decimal, places, mode) do
+      {:ok, rounded} -> rounded
+      {:error, reason} -> raise ArgumentError, "decimal rounding failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the digits of the decimal as a plain decimal string, such as
+  `"-1234.50"`, with no grouping or locale symbols.
+  """
+  @spec to_string(t()) :: String.t()
+  def to_string(%__MODULE__{resource: resource}), do: Nif.decimal_to_string(resource)
+
+  defimpl String.Chars do
+    def to_string(decimal), do: Icu.Decimal.to_string(decimal)
+  end
+
+  defimpl Inspect do
+    def inspect(decimal, _opts) do
+      "#Icu.Decimal<#{Icu.Decimal.to_string(decimal)}>"
+    end
+  end
+end
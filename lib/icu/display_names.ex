@@ -17,35 +17,90 @@ defmodule Icu.DisplayNames do
       iex> Icu.DisplayNames.format(:region, "GB")
       {:ok, "United Kingdom"}
 
+      iex> Icu.DisplayNames.format(:unit, "kilometer-per-hour", locale: "de")
+      {:ok, "Kilometer pro Stunde"}
+
+  ## Units
+
+  Measurement units are named by their CLDR core unit identifier, such as
+  `"kilometer-per-hour"`. The `:style` option selects the unit width: `:short` and
+  `:menu` give the short form, `:narrow` the narrow form and anything else the long form.
+  The name is the locale's plural pattern for the unit without its number. Compounds
+  that CLDR does not name, such as `"liter-per-100-kilometer"`, are composed with the
+  locale's per pattern.
+
+  ## Locale extensions
+
+  The `-u-` keywords of a locale are named after its region and variants, following the
+  CLDR locale display pattern, e.g. "English (United States, Buddhist Calendar)". ICU4X
+  has no names for them, so the bundled English names are used. Naming a locale with
+  keywords in another language returns `{:error, :unsupported_locale}`; pass
+  `extensions: false` to name it without them.
+
+  ## Numbering systems
+
+  Numbering systems are named by their CLDR identifier, such as `"deva"`; see
+  `Icu.Number.numbering_systems/0`. ICU4X has no numbering system names, so only the
+  bundled English names are available and other locales resolve through `:fallback`.
+
+  ## Styles
+
+  CLDR has names in other styles than the long one for only a few languages, regions
+  and scripts: short names such as "UK" for `"GB"`, and menu names that put the word a
+  list is sorted by first, such as "Chinese, Cantonese" for `"yue"`. A value without a
+  name in the requested style gets its long name, never `nil`. `format_with_style/3`
+  returns the style the name was actually found in.
+
+      iex> Icu.DisplayNames.format(:language, "yue", style: :menu)
+      {:ok, "Chinese, Cantonese"}
+
+      iex> Icu.DisplayNames.format(:language, "de", style: :menu)
+      {:ok, "German"}
+
   ## Options
 
   - `:style` – choose between `:narrow`, `:short`, `:long`, or `:menu`. Defaults to the ICU long form.
   - `:fallback` – specify `:code` to fall back to the original value or `:none` to return `nil` when missing.
-  - `:language_display` – toggle between `:dialect` and `:standard` language names.
+  - `:language_display` – how `:locale` names name a language that has a region or
+    script. `:dialect` (the default) uses the CLDR name of the combination where there
+    is one ("British English"), `:standard` always composes the language with the rest
+    ("English (United Kingdom)"). A single lookup on a formatter can also be given it,
+    overriding the one the formatter was built with.
+  - `:extensions` – set to `false` to leave the `-u-` keywords out of `:locale` names.
+    Defaults to `true`.
   - `:locale` – override the lookup locale (accepts `Icu.LanguageTag.t()` or a locale string).
   """
 
   alias Icu.DisplayNames.Formatter
+  alias Icu.Nif
+
+  @type kind :: :locale | :language | :region | :script | :variant | :unit | :numbering_system
+
+  @typedoc "The form of a display name, see the `:style` option."
+  @type style :: :narrow | :short | :long | :menu
 
-  @type kind :: :locale | :language | :region | :script | :variant
+  @typedoc "How `:locale` names name a language with a region or script."
+  @type language_display :: :dialect | :standard
 
   @typedoc """
   Keyword form of the supported options.
   """
   @type options_list ::
           [
-            {:style, :narrow | :short | :long | :menu | nil}
+            {:style, style() | nil}
             | {:fallback, :code | :none | nil}
-            | {:language_display, :dialect | :standard | nil}
+            | {:language_display, language_display() | nil}
+            | {:extensions, boolean() | nil}
             | {:locale, Icu.LanguageTag.t() | String.t() | nil}
           ]
 
   @typedoc "Map form of the supported options."
   @type options ::
           %{
-            optional(:style) => :narrow | :short | :long | :menu | nil,
+            optional(:style) => style() | nil,
             optional(:fallback) => :code | :none | nil,
-            optional(:language_display) => :dialect | :standard | nil,
+            optional(:language_display) => language_display() | nil,
+            optional(:extensions) => boolean() | nil,
             optional(:locale) => Icu.LanguageTag.t() | String.t() | nil
           }
 
@@ -59,11 +114,13 @@ defmodule Icu.DisplayNames do
           | {:error, :invalid_locale}
           | {:error, :invalid_formatter}
           | {:error, :invalid_options}
+          | {:error, :unsupported_locale}
 
   @doc """
   Formats the provided `value` for the given `kind`.
 
-  The `kind` must be one of `:locale`, `:language`, `:region`, `:script`, or `:variant`.
+  The `kind` must be one of `:locale`, `:language`, `:region`, `:script`, `:variant`,
+  `:unit`, or `:numbering_system`.
   Returns `{:ok, String.t()}` or `{:ok, nil}` when the display name cannot be resolved
   and the fallback strategy allows it.
 
@@ -94,6 +151,9 @@ defmodule Icu.DisplayNames do
 
       iex> Icu.DisplayNames.format_locale("en-GB")
       {:ok, "British English"}
+
+      iex> Icu.DisplayNames.format_locale("en-US-u-ca-buddhist", language_display: :standard)
+      {:ok, "English (United States, Buddhist Calendar)"}
   """
   @spec format_locale(term(), options_input()) :: {:ok, String.t() | nil} | error()
   def format_locale(value, options \\ []) do
@@ -132,6 +192,111 @@ defmodule Icu.DisplayNames do
     format(:variant, value, options)
   end
 
+  @doc """
+  Formats a measurement unit display name.
+
+  ## Examples
+
+      iex> Icu.DisplayNames.format_unit("kilometer-per-hour")
+      {:ok, "kilometers per hour"}
+  """
+  @spec format_unit(term(), options_input()) :: {:ok, String.t() | nil} | error()
+  def format_unit(value, options \\ []) do
+    format(:unit, value, options)
+  end
+
+  @doc """
+  Formats a numbering system display name.
+
+  ## Examples
+
+      iex> Icu.DisplayNames.format_numbering_system("deva")
+      {:ok, "Devanagari Digits"}
+  """
+  @spec format_numbering_system(term(), options_input()) :: {:ok, String.t() | nil} | error()
+  def format_numbering_system(value, options \\ []) do
+    format(:numbering_system, value, options)
+  end
+
+  @doc """
+  Formats a language, region or script name like `format/3`, returning the style the
+  name was found in along with it.
+
+  The style is the requested one when the data has a name in that style, and `:long`
+  when the long name was used in its place.
+
+  ## Examples
+
+      iex> Icu.DisplayNames.format_with_style(:region, "GB", style: :short)
+      {:ok, {"UK", :short}}
+
+      iex> Icu.DisplayNames.format_with_style(:region, "DE", style: :short)
+      {:ok, {"Germany", :long}}
+  """
+  @spec format_with_style(:language | :region | :script, term(), options_input()) ::
+          {:ok, {String.t(), style()} | nil} | error()
+  def format_with_style(kind, value, options \\ [])
+
+  def format_with_style(kind, value, options)
+      when kind in [:language, :region, :script] and (is_binary(value) or is_atom(value)) do
+    with {:ok, opts} <- Formatter.normalize_options(options) do
+      Nif.display_names_of_styled(
+        Map.fetch!(opts, :locale),
+        kind,
+        value,
+        Map.delete(opts, :locale)
+      )
+    end
+  end
+
+  def format_with_style(kind, _value, _options) when kind in [:language, :region, :script],
+    do: {:error, :invalid_options}
+
+  def format_with_style(kind, _value, _options), do: {:invalid_kind, kind}
+
+  @doc """
+  Returns the flag emoji of a region subtag.
+
+  The flag is the pair of regional indicator symbols for the region code. Regions
+  without an emoji flag, such as `"EZ"` or the numeric `"419"`, return `{:ok, nil}`.
+
+  ## Examples
+
+      iex> Icu.DisplayNames.region_flag_emoji("DE")
+      {:ok, "🇩🇪"}
+
+      iex> Icu.DisplayNames.region_flag_emoji("EZ")
+      {:ok, nil}
+
+      iex> Icu.DisplayNames.region_flag_emoji("Germany")
+      {:error, :invalid_region}
+  """
+  @spec region_flag_emoji(String.t() | atom()) ::
+          {:ok, String.t() | nil} | {:error, :invalid_region}
+  def region_flag_emoji(region) when is_atom(region) and not is_nil(region) do
+    region_flag_emoji(Atom.to_string(region))
+  end
+
+  def region_flag_emoji(region) when is_binary(region) do
+    Nif.display_names_region_flag(region)
+  end
+
+  @doc """
+  Returns the flag emoji of a region subtag and raises on error.
+
+  ## Examples
+
+      iex> Icu.DisplayNames.region_flag_emoji!("jp")
+      "🇯🇵"
+  """
+  @spec region_flag_emoji!(String.t() | atom()) :: String.t() | nil
+  def region_flag_emoji!(region) do
+    case region_flag_emoji(region) do
+      {:ok, flag} -> flag
+      {:error, reason} -> raise "region flag lookup failed: #{inspect(reason)}"
+    end
+  end
+
   @doc """
   Formats a value and raises on error.
   """
@@ -149,6 +314,24 @@ defmodule Icu.DisplayNames do
     end
   end
 
+  @doc """
+  Formats a name with its style and raises on error.
+  """
+  @spec format_with_style!(:language | :region | :script, term(), options_input()) ::
+          {String.t(), style()} | nil
+  def format_with_style!(kind, value, options \\ []) do
+    case format_with_style(kind, value, options) do
+      {:ok, result} ->
+        result
+
+      {:invalid_kind, reason} ->
+        raise ArgumentError, "invalid display name kind: #{inspect(reason)}"
+
+      {:error, reason} ->
+        raise "display names formatting failed: #{inspect(reason)}"
+    end
+  end
+
   @doc """
   Formats a locale display name and raises on error.
   """
@@ -193,4 +376,20 @@ defmodule Icu.DisplayNames do
   def format_variant!(value, options \\ []) do
     format!(:variant, value, options)
   end
+
+  @doc """
+  Formats a measurement unit display name and raises on error.
+  """
+  @spec format_unit!(term(), options_input()) :: String.t() | nil
+  def format_unit!(value, options \\ []) do
+    format!(:unit, value, options)
+  end
+
+  @doc """
+  Formats a numbering system display name and raises on error.
+  """
+  @spec format_numbering_system!(term(), options_input()) :: String.t() | nil
+  def format_numbering_system!(value, options \\ []) do
+    format!(:numbering_system, value, options)
+  end
 end
@@ -2,17 +2,20 @@ defmodule Icu.DisplayNames.Formatter do
   @moduledoc false
 
   alias Icu.DisplayNames
+  alias Icu.Formatter.Config
   alias Icu.Formatter.Options
   alias Icu.LanguageTag
   alias Icu.Nif
 
-  @valid_kinds [:locale, :language, :region, :script, :variant]
+  @valid_kinds [:locale, :language, :region, :script, :variant, :unit, :numbering_system]
+  @option_keys [:style, :fallback, :language_display, :extensions, :locale]
 
-  defstruct [:resource, :kind]
+  defstruct [:resource, :kind, :config]
 
   @opaque t :: %__MODULE__{
             resource: reference(),
-            kind: DisplayNames.kind()
+            kind: DisplayNames.kind(),
+            config: map()
           }
 
   @doc """
@@ -38,29 +41,38 @@ defmodule Icu.DisplayNames.Formatter do
              kind,
              Map.delete(opts, :locale)
            ) do
-      {:ok, %__MODULE__{resource: resource, kind: kind}}
+      warnings = Options.warnings(:display_names, options, opts, &(&1 in @option_keys))
+      args = [kind, Config.put_locale(options, opts.locale)]
+      config = Config.new(__MODULE__, :new, args, warnings)
+      {:ok, %__MODULE__{resource: resource, kind: kind, config: config}}
     end
   end
 
   @doc """
   Returns the display name of the provided `value`.
+
+  `:language_display` overrides the one the formatter was built with for
+  this lookup. It only changes `:locale` names.
   """
-  @spec display_name(t(), term()) ::
+  @spec display_name(t(), term(), [{:language_display, DisplayNames.language_display()}]) ::
           {:ok, String.t() | nil}
+          | Options.error()
           | {:error, :invalid_locale}
           | {:error, :invalid_options}
-  def display_name(%__MODULE__{kind: kind, resource: resource}, value) do
-    with {:ok, normalized} <- normalize_value(kind, value) do
-      Nif.display_names_of(resource, normalized)
+  def display_name(%__MODULE__{kind: kind, resource: resource}, value, options \\ []) do
+    with {:ok, opts} <- normalize_call_options(options),
+         {:ok, normalized} <- normalize_value(kind, value) do
+      Nif.display_names_of(resource, normalized, opts)
     end
   end
 
   @doc """
   Returns the display name, raising on error.
   """
-  @spec display_name!(t(), term()) :: String.t() | nil
-  def display_name!(%__MODULE__{} = formatter, value) do
-    case display_name(formatter, value) do
+  @spec display_name!(t(), term(), [{:language_display, DisplayNames.language_display()}]) ::
+          String.t() | nil
+  def display_name!(%__MODULE__{} = formatter, value, options \\ []) do
+    case display_name(formatter, value, options) do
       {:ok, result} ->
         result
 
@@ -81,15 +93,18 @@ defmodule Icu.DisplayNames.Formatter do
   def normalize_options(nil), do: normalize_options(%{})
 
   def normalize_options(options) when is_list(options) or is_map(options) do
-    Options.normalize_options(
-      :display_names,
-      options,
-      &(&1 in [:style, :fallback, :language_display, :locale])
-    )
+    Options.normalize_options(:display_names, options, &(&1 in @option_keys))
   end
 
   def normalize_options(_other), do: {:error, :invalid_options}
 
+  defp normalize_call_options(options) do
+    with {:ok, opts} <-
+           Options.normalize_options(:display_names, options, &(&1 == :language_display)) do
+      {:ok, Map.delete(opts, :locale)}
+    end
+  end
+
   defp normalize_value(:locale, %LanguageTag{resource: resource}) do
     {:ok, resource}
   end
@@ -19,11 +19,33 @@ defmodule Icu.Experimental.Currency do
 
   - `:currency` – **required** – ISO 4217 currency code (e.g. `"USD"`, `"EUR"`, `"JPY"`).
   - `:width` – display width (`:short`, `:narrow`, `:long`). Defaults to `:short`.
+  - `:notation` – `:standard` (default) or `:compact_short` for compact
+    amounts such as `"$1.2M"`. Compact notation supports the `:short` and
+    `:narrow` widths only.
+  - `:compact_rounding` – `:auto` (default) lets the compact formatter round
+    the significand (`"$1.2M"`), `{:significant, n}` keeps `n` significant
+    digits (`"$1.23M"` for `n = 3`). Only applies to compact notation.
+  - `:compact_threshold` – amounts whose absolute value is below this are
+    formatted in standard notation, e.g. `10_000` keeps `"$9,500.00"` but
+    abbreviates `"$12K"`. Only applies to compact notation.
+  - `:approximately` – when `true`, prefix the amount with the approximately
+    sign of the locale, as in `"~$5.00"`. See `Icu.Number`.
   - `:locale` – override the locale for this invocation.
+  - `:output` – `:binary` (default) or `:iodata`, as for `Icu.List.format/2`. The
+    formatted amount is a single short binary, which is already iodata, so both
+    return the same binary.
+
+  ## Region currencies
+
+  ICU4X does not ship the CLDR supplemental currency data that maps regions to
+  their legal tenders, so that table is bundled. `region_currencies/2` looks a
+  region up in it, e.g. to default the currency of a checkout to the one of
+  the user's region.
   """
 
   alias Icu.LanguageTag
   alias Icu.Experimental.Currency.Formatter
+  alias Icu.Formatter.Options
 
   @typedoc "Opaque reference to an ICU4X currency formatter."
   @type formatter :: Formatter.t()
@@ -34,6 +56,24 @@ defmodule Icu.Experimental.Currency do
   @typedoc "Controls the display width of the currency."
   @type width :: :short | :narrow | :long
 
+  @typedoc "Selects between plain and compact amounts."
+  @type notation :: :standard | :compact_short
+
+  @typedoc "Controls how compact amounts are rounded."
+  @type compact_rounding :: :auto | {:significant, pos_integer()}
+
+  @typedoc """
+  A formatted part.
+
+  `:currency` is the currency symbol or name, `:number` the digits with their
+  separators and sign, and `:compact` the compact affix (e.g. `"Mio."`).
+  Everything else is a `:literal`.
+  """
+  @type part :: %{
+          part_type: :currency | :number | :compact | :literal,
+          value: String.t()
+        }
+
   @type fractions :: %{
           digits: non_neg_integer(),
           rounding: non_neg_integer(),
@@ -58,9 +98,15 @@ defmodule Icu.Experimental.Currency do
           [
             {:currency, currency()}
             | {:width, width()}
+            | {:notation, notation()}
+            | {:compact_rounding, compact_rounding()}
+            | {:compact_threshold, number()}
+            | {:approximately, boolean()}
             | {:locale, LanguageTag.t() | String.t() | nil}
             | {:currency_digits, currency_digits()}
             | {:rounding_mode, rounding_mode()}
+            | {:index_mode, Icu.index_mode()}
+            | {:output, Icu.List.output()}
           ]
 
   @typedoc "Map form of the supported options."
@@ -68,9 +114,15 @@ defmodule Icu.Experimental.Currency do
           %{
             required(:currency) => currency(),
             optional(:width) => width(),
+            optional(:notation) => notation(),
+            optional(:compact_rounding) => compact_rounding(),
+            optional(:compact_threshold) => number(),
+            optional(:approximately) => boolean(),
             optional(:locale) => LanguageTag.t() | String.t() | nil,
             optional(:currency_digits) => currency_digits(),
-            optional(:rounding_mode) => rounding_mode()
+            optional(:rounding_mode) => rounding_mode(),
+            optional(:index_mode) => Icu.index_mode(),
+            optional(:output) => Icu.List.output()
           }
 
   @type options_input :: options() | options_list()
@@ -82,6 +134,7 @@ defmodule Icu.Experimental.Currency do
           | :invalid_options
           | :invalid_currency
           | {:missing_option, :currency}
+          | {:invalid_option_value, :output}
 
   @doc """
   Formats a number as currency.
@@ -101,7 +154,10 @@ defmodule Icu.Experimental.Currency do
   @spec format(number() | Decimal.t(), options_input()) ::
           {:ok, String.t()} | {:error, format_error()}
   def format(number, options) do
-    with {:ok, formatter} <- Formatter.new(options) do
+    {output, options} = Options.pop_output(options)
+
+    with :ok <- Options.output(output),
+         {:ok, formatter} <- Formatter.new(options) do
       Formatter.format(formatter, number)
     end
   end
@@ -122,6 +178,119 @@ defmodule Icu.Experimental.Currency do
     end
   end
 
+  @doc """
+  Formats a number as currency and returns the formatted parts.
+
+  ICU4X does not annotate currency output, so the currency text and the number
+  are located in the formatted string. This separates the compact affix from
+  the currency symbol when `notation: :compact_short` is used.
+
+  ## Examples
+
+      iex> Icu.Experimental.Currency.format_to_parts(1234.5, currency: "USD")
+      {:ok,
+       [
+         %{part_type: :currency, value: "$"},
+         %{part_type: :number, value: "1,234.50"}
+       ]}
+  """
+  @spec format_to_parts(number() | Decimal.t(), options_input()) ::
+          {:ok, [part()]} | {:error, format_error()}
+  def format_to_parts(number, options) do
+    {index_mode, options} = Options.pop_index_mode(options)
+
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_to_parts(formatter, number, index_mode: index_mode)
+    end
+  end
+
+  @doc """
+  Like `format_to_parts/2`, but raises on error.
+  """
+  @spec format_to_parts!(number() | Decimal.t(), options_input()) :: [part()]
+  def format_to_parts!(number, options) do
+    case format_to_parts(number, options) do
+      {:ok, parts} -> parts
+      {:error, reason} -> raise "currency formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats the range between two amounts of a currency.
+
+  Both amounts are rounded like `format/2`. A currency written after the
+  number is shown once for the whole range ("10,00–20,00 €"), while a symbol
+  in front of it is repeated on both ends ("$10.00–$20.00"). With a
+  `:compact_threshold`, both ends use the notation of the larger amount.
+
+  ## Examples
+
+      iex> Icu.Experimental.Currency.format_range(10, 20, currency: "USD")
+      {:ok, "$10.00–$20.00"}
+
+      iex> Icu.Experimental.Currency.format_range(10, 20, currency: "EUR", locale: "de")
+      {:ok, "10,00–20,00 €"}
+  """
+  @spec format_range(number() | Decimal.t(), number() | Decimal.t(), options_input()) ::
+          {:ok, String.t()} | {:error, format_error()}
+  def format_range(start, finish, options) do
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_range(formatter, start, finish)
+    end
+  end
+
+  @doc """
+  Like `format_range/3`, but raises on error.
+  """
+  @spec format_range!(number() | Decimal.t(), number() | Decimal.t(), options_input()) ::
+          String.t()
+  def format_range!(start, finish, options) do
+    case format_range(start, finish, options) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "currency range formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats the range between two amounts to parts.
+
+  Each part carries a `:source` of `:start` or `:end`, or `:shared` for the
+  separator and for a currency written once for both ends.
+
+  ## Examples
+
+      iex> {:ok, parts} = Icu.Experimental.Currency.format_range_to_parts(10, 20, currency: "USD")
+      iex> Enum.map(parts, &{&1.part_type, &1.value, &1.source})
+      [
+        {:currency, "$", :start},
+        {:number, "10.00", :start},
+        {:literal, "–", :shared},
+        {:currency, "$", :end},
+        {:number, "20.00", :end}
+      ]
+  """
+  @spec format_range_to_parts(number() | Decimal.t(), number() | Decimal.t(), options_input()) ::
+          {:ok, [map()]} | {:error, format_error()}
+  def format_range_to_parts(start, finish, options) do
+    {index_mode, options} = Options.pop_index_mode(options)
+
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_range_to_parts(formatter, start, finish, index_mode: index_mode)
+    end
+  end
+
+  @doc """
+  Like `format_range_to_parts/3`, but raises on error.
+  """
+  @spec format_range_to_parts!(number() | Decimal.t(), number() | Decimal.t(), options_input()) ::
+          [map()]
+  def format_range_to_parts!(start, finish, options) do
+    case format_range_to_parts(start, finish, options) do
+      {:ok, parts} -> parts
+      {:error, reason} -> raise "currency formatting failed: #{inspect(reason)}"
+    end
+  end
+
   @typedoc "Options for `round/2`."
   @type round_options :: %{
           required(:currency) => currency(),
@@ -171,6 +340,92 @@ defmodule Icu.Experimental.Currency do
   @spec round!(number() | Decimal.t(), round_options() | round_options_list()) :: Decimal.t()
   def round!(number, options), do: Formatter.round!(number, options)
 
+  @typedoc "Selects between the standard and the cash rounding rules of a currency."
+  @type rounding_kind :: :standard | :cash
+
+  @doc """
+  Rounds `amount` with the standard or cash rules of `currency`.
+
+  Shorthand for `round/2` with `currency_digits: :iso` (`:standard`) or
+  `currency_digits: :cash`, using `:half_even` rounding. Useful to share the
+  rounding rules applied by `format/2` with business logic that never formats.
+
+  ## Examples
+
+      iex> Icu.Experimental.Currency.round(0.125, "USD", :standard)
+      {:ok, Decimal.new("0.12")}
+
+      iex> Icu.Experimental.Currency.round(Decimal.new("1.03"), "CHF", :cash)
+      {:ok, Decimal.new("1.05")}
+  """
+  @spec round(number() | Decimal.t(), currency(), rounding_kind()) ::
+          {:ok, Decimal.t()} | {:error, term()}
+  def round(amount, currency, :standard),
+    do: Formatter.round(amount, currency: currency, currency_digits: :iso)
+
+  def round(amount, currency, :cash),
+    do: Formatter.round(amount, currency: currency, currency_digits: :cash)
+
+  def round(_amount, _currency, _kind), do: {:error, :invalid_currency_digits}
+
+  @doc """
+  Like `round/3`, but raises on error.
+  """
+  @spec round!(number() | Decimal.t(), currency(), rounding_kind()) :: Decimal.t()
+  def round!(amount, currency, kind) do
+    case __MODULE__.round(amount, currency, kind) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "currency rounding failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the ISO 4217 codes of the legal tenders of `region`, the main one
+  first, from the bundled CLDR supplemental currency data.
+
+  Regions without a currency of their own, such as `"AQ"`, return
+  `{:error, :no_data}`.
+
+  ## Options
+
+  - `:historical` – when `true`, also return the currencies the region used
+    before, most recent first. Only currencies replaced since 2000 are
+    bundled, such as those the euro replaced. Defaults to `false`.
+
+  ## Examples
+
+      iex> Icu.Experimental.Currency.region_currencies("DE")
+      {:ok, ["EUR"]}
+
+      iex> Icu.Experimental.Currency.region_currencies("hr", historical: true)
+      {:ok, ["EUR", "HRK"]}
+
+      iex> Icu.Experimental.Currency.region_currencies("PA")
+      {:ok, ["PAB", "USD"]}
+  """
+  @spec region_currencies(String.t(), [{:historical, boolean()}]) ::
+          {:ok, [currency()]} | {:error, :invalid_region | :no_data | term()}
+  def region_currencies(region, options \\ [])
+
+  def region_currencies(region, options) when is_binary(region) do
+    with {:ok, opts} <- Options.normalize_options(:currency, options, &(&1 == :historical)) do
+      Icu.Nif.currency_region_currencies(region, Map.get(opts, :historical, false))
+    end
+  end
+
+  def region_currencies(_region, _options), do: {:error, :invalid_region}
+
+  @doc """
+  Like `region_currencies/2`, but raises on error.
+  """
+  @spec region_currencies!(String.t(), [{:historical, boolean()}]) :: [currency()]
+  def region_currencies!(region, options \\ []) do
+    case region_currencies(region, options) do
+      {:ok, currencies} -> currencies
+      {:error, reason} -> raise "region currency lookup failed: #{inspect(reason)}"
+    end
+  end
+
   @doc """
   Returns currency fraction data for a given ISO 4217 currency code.
   """
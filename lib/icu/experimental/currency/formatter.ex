@@ -2,40 +2,84 @@ defmodule Icu.Experimental.Currency.Formatter do
   @moduledoc false
 
   alias Icu.Nif
+  alias Icu.Formatter.Config
   alias Icu.Formatter.Options
 
   # Rounding options are stored in the struct because ICU4X doesn't currently
   # accept rounding config at formatter creation or format time. We apply
   # Elixir-side rounding before passing the value to the NIF. If ICU4X gains
   # rounding support, these fields can be forwarded to the NIF instead.
-  defstruct [:resource, :currency, :currency_digits, :rounding_mode]
+  defstruct [
+    :resource,
+    :currency,
+    :currency_digits,
+    :rounding_mode,
+    :config,
+    compact_threshold: nil,
+    standard_resource: nil
+  ]
 
   @opaque t :: %__MODULE__{}
 
+  @option_keys [
+    :locale,
+    :currency,
+    :width,
+    :notation,
+    :compact_rounding,
+    :compact_threshold,
+    :approximately,
+    :currency_digits,
+    :rounding_mode
+  ]
+
   @spec new(keyword()) :: {:ok, t()} | {:error, term()}
   def new(options \\ []) do
     with {:ok, opts} <- normalize_options(options) do
+      warnings = Options.warnings(:currency, options, opts, &(&1 in @option_keys))
       {nif_opts, rounding_opts} = split_rounding_opts(opts)
+      {threshold, nif_opts} = Map.pop(nif_opts, :compact_threshold)
       currency = Map.fetch!(nif_opts, :currency)
       locale = Map.fetch!(nif_opts, :locale)
       rest = nif_opts |> Map.delete(:currency) |> Map.delete(:locale)
 
-      case Nif.currency_formatter_new(locale, currency, rest) do
-        {:ok, resource} ->
-          {:ok,
-           %__MODULE__{
-             resource: resource,
-             currency: currency,
-             currency_digits: Map.get(rounding_opts, :currency_digits, :iso),
-             rounding_mode: Map.get(rounding_opts, :rounding_mode, :half_even)
-           }}
-
-        {:error, _} = error ->
-          error
+      with {:ok, resource} <- Nif.currency_formatter_new(locale, currency, rest),
+           {:ok, standard_resource} <- standard_resource(locale, currency, rest, threshold) do
+        {:ok,
+         %__MODULE__{
+           resource: resource,
+           currency: currency,
+           currency_digits: Map.get(rounding_opts, :currency_digits, :iso),
+           rounding_mode: Map.get(rounding_opts, :rounding_mode, :half_even),
+           compact_threshold: standard_resource && to_decimal(threshold),
+           standard_resource: standard_resource,
+           config: Config.new(__MODULE__, :new, [Config.put_locale(options, locale)], warnings)
+         }}
       end
     end
   end
 
+  # Amounts below the compact threshold are formatted by a second formatter
+  # in standard notation.
+  defp standard_resource(locale, currency, %{notation: :compact_short} = opts, threshold)
+       when not is_nil(threshold) do
+    opts = opts |> Map.put(:notation, :standard) |> Map.delete(:compact_rounding)
+    Nif.currency_formatter_new(locale, currency, opts)
+  end
+
+  defp standard_resource(_locale, _currency, _opts, _threshold), do: {:ok, nil}
+
+  defp resource_for(%__MODULE__{standard_resource: nil, resource: resource}, _amount),
+    do: resource
+
+  defp resource_for(%__MODULE__{} = formatter, amount) do
+    if Decimal.lt?(Decimal.abs(amount), formatter.compact_threshold) do
+      formatter.standard_resource
+    else
+      formatter.resource
+    end
+  end
+
   @spec new!(keyword()) :: t()
   def new!(options \\ []) do
     case new(options) do
@@ -52,12 +96,68 @@ defmodule Icu.Experimental.Currency.Formatter do
              currency_digits: formatter.currency_digits,
              rounding_mode: formatter.rounding_mode
            ) do
-      Nif.currency_format(formatter.resource, rounded)
+      Nif.currency_format(resource_for(formatter, rounded), rounded)
     end
   end
 
   def format(%__MODULE__{}, _other), do: {:error, :invalid_number}
 
+  @spec format_to_parts(t(), number() | struct(), Options.options_input()) ::
+          {:ok, [map()]} | {:error, term()}
+  def format_to_parts(formatter, number, options \\ [])
+
+  def format_to_parts(%__MODULE__{} = formatter, number, options)
+      when is_number(number) or is_struct(number) do
+    with {:ok, index_mode} <- Options.index_mode(options),
+         {:ok, rounded} <-
+           round(number,
+             currency: formatter.currency,
+             currency_digits: formatter.currency_digits,
+             rounding_mode: formatter.rounding_mode
+           ) do
+      Nif.currency_format_to_parts(resource_for(formatter, rounded), rounded, index_mode)
+    end
+  end
+
+  def format_to_parts(%__MODULE__{}, _other, _options), do: {:error, :invalid_number}
+
+  @spec format_range(t(), number() | struct(), number() | struct()) ::
+          {:ok, String.t()} | {:error, term()}
+  def format_range(%__MODULE__{} = formatter, start, finish) do
+    with {:ok, resource, start, finish} <- round_range(formatter, start, finish) do
+      Nif.currency_format_range(resource, start, finish)
+    end
+  end
+
+  @spec format_range_to_parts(
+          t(),
+          number() | struct(),
+          number() | struct(),
+          Options.options_input()
+        ) :: {:ok, [map()]} | {:error, term()}
+  def format_range_to_parts(%__MODULE__{} = formatter, start, finish, options \\ []) do
+    with {:ok, index_mode} <- Options.index_mode(options),
+         {:ok, resource, start, finish} <- round_range(formatter, start, finish) do
+      Nif.currency_format_range_to_parts(resource, start, finish, index_mode)
+    end
+  end
+
+  # Both ends share one notation, chosen by the larger amount, so that a
+  # range crossing the compact threshold reads "$900–$1.2K" consistently.
+  defp round_range(formatter, start, finish) do
+    rounding = [
+      currency: formatter.currency,
+      currency_digits: formatter.currency_digits,
+      rounding_mode: formatter.rounding_mode
+    ]
+
+    with {:ok, start} <- round(start, rounding),
+         {:ok, finish} <- round(finish, rounding) do
+      larger = Decimal.max(Decimal.abs(start), Decimal.abs(finish))
+      {:ok, resource_for(formatter, larger), start, finish}
+    end
+  end
+
   @spec format!(t(), number() | Decimal.t()) :: String.t()
   def format!(%__MODULE__{} = formatter, number) do
     case format(formatter, number) do
@@ -101,11 +201,7 @@ defmodule Icu.Experimental.Currency.Formatter do
   @doc false
   @spec normalize_options(keyword() | map()) :: {:ok, map()} | {:error, term()}
   def normalize_options(options) do
-    Options.normalize_options(
-      :currency,
-      options,
-      &(&1 in [:locale, :currency, :width, :currency_digits, :rounding_mode])
-    )
+    Options.normalize_options(:currency, options, &(&1 in @option_keys))
     |> validate_currency_required()
   end
 
@@ -124,10 +220,14 @@ defmodule Icu.Experimental.Currency.Formatter do
     do: {:error, :invalid_number}
 
   defp validate_number(%Decimal{}), do: {:ok, :valid}
+  defp validate_number(%Icu.Decimal{}), do: {:ok, :valid}
   defp validate_number(n) when is_number(n), do: {:ok, :valid}
   defp validate_number(_), do: {:error, :invalid_number}
 
   defp to_decimal(%Decimal{} = d), do: d
+  # Currency rounding is done with `Decimal`, so an `Icu.Decimal` is read
+  # back from its digits.
+  defp to_decimal(%Icu.Decimal{} = d), do: Decimal.new(Icu.Decimal.to_string(d))
   # Since we strictly do formatting in this API, we allow floats
   # which are not normally fit for purpose for currencies.
   defp to_decimal(n) when is_float(n), do: Decimal.from_float(n)
@@ -0,0 +1,466 @@
+defmodule Icu.Experimental.Units do
+  @moduledoc """
+  Measurement unit conversion.
+
+  Units are identified by their CLDR core unit identifiers such as
+  `"kilometer"`, `"mile"`, `"celsius"` or compound identifiers like
+  `"kilometer-per-hour"`. Conversion is delegated to the ICU4X experimental
+  units converter.
+
+  ## Examples
+
+      iex> {:ok, miles} = Icu.Experimental.Units.convert(10, "kilometer", "mile")
+      iex> Float.round(miles, 3)
+      6.214
+
+      iex> Icu.Experimental.Units.convert(100, "celsius", "fahrenheit")
+      {:ok, 212.0}
+
+  ## Formatting
+
+  `format/2` formats an amount of a single unit. Compound units such as
+  `"kilometer-per-hour"` use CLDR's precomposed patterns when the locale has
+  them. Other `-per-` compounds (e.g. `"liter-per-100-kilometer"`) are
+  composed from their numerator and denominator with the locale's "per"
+  pattern, such as `"{0}/{1}"` for the short width and `"{0} per {1}"` for
+  the long width in English.
+
+      iex> Icu.Experimental.Units.format(100, unit: "kilometer-per-hour", locale: "en")
+      {:ok, "100 km/h"}
+
+      iex> Icu.Experimental.Units.format(6.5, unit: "liter-per-100-kilometer", locale: "en")
+      {:ok, "6.5 L/100 km"}
+
+  ## Locale-preferred units
+
+  `convert_for_locale/3` converts a value into the unit used by the
+  measurement system of a locale. The system is taken from the `-u-ms-`
+  keyword, the `-u-rg-` region override or the locale's (likely) region.
+
+  ICU4X does not ship the CLDR unit preference data, so preferences are
+  resolved from a built-in table of everyday units (length, area, mass,
+  volume, temperature and speed). Units without a customary counterpart, such
+  as time units, are returned unchanged.
+
+  `preferred_units/3` additionally takes a CLDR usage. The supported usages
+  are `"person-height"`, `"person"` (body weight), `"road"` and `"weather"`;
+  any other usage falls back to `"default"`, as in CLDR.
+
+  ## Mixed units
+
+  `format_mixed/2` formats a sequence of related measures as a single string
+  joined with the locale's unit list patterns, e.g. `"5 ft, 11 in"`. The
+  output of `preferred_units/3` can be passed to it directly.
+
+  ## Unit patterns
+
+  `patterns/2` returns the pattern the unit formatter uses for each plural
+  category of the locale, with `{0}` standing for the number. They are
+  useful to compose messages that the formatter cannot produce on its own
+  while keeping the unit wording consistent with it.
+  """
+
+  alias Icu.Experimental.Units.Formatter
+  alias Icu.Formatter.Options
+  alias Icu.LanguageTag
+  alias Icu.Nif
+
+  @typedoc "Opaque reference to an ICU4X unit formatter."
+  @type formatter :: Formatter.t()
+
+  @typedoc "CLDR unit identifier, e.g. `\"kilometer\"` or `\"mile-per-hour\"`."
+  @type unit :: String.t()
+
+  @typedoc "CLDR unit usage, e.g. `\"person-height\"` or `\"road\"`."
+  @type usage :: String.t()
+
+  @typedoc "An amount expressed in a unit."
+  @type measure :: {number(), unit()}
+
+  @type error :: :invalid_number | :invalid_unit | :incompatible_units | :invalid_locale
+
+  @type locale_options :: [{:locale, LanguageTag.t() | String.t() | nil}]
+
+  @type preference_options :: [
+          {:maximum_fraction_digits, non_neg_integer()}
+          | {:locale, LanguageTag.t() | String.t() | nil}
+        ]
+
+  @typedoc "Controls the display width of the unit names."
+  @type width :: :short | :narrow | :long
+
+  @type format_options :: [
+          {:width, width()}
+          | {:locale, LanguageTag.t() | String.t() | nil}
+        ]
+
+  @typedoc "A plural category of the locale's cardinal plural rules."
+  @type plural_category :: :zero | :one | :two | :few | :many | :other
+
+  @typedoc "Options accepted by `format/2`."
+  @type options_list :: [
+          {:unit, unit()}
+          | {:width, width()}
+          | {:approximately, boolean()}
+          | {:locale, LanguageTag.t() | String.t() | nil}
+          | {:index_mode, Icu.index_mode()}
+          | {:output, Icu.List.output()}
+        ]
+
+  @doc """
+  Formats an amount of a unit.
+
+  ## Options
+
+  - `:unit` – **required** – CLDR unit identifier, including compound units.
+  - `:width` – display width (`:short`, `:narrow`, `:long`). Defaults to `:short`.
+  - `:approximately` – when `true`, prefix the amount with the approximately
+    sign of the locale, as in `"~42 km"`. See `Icu.Number`.
+  - `:locale` – override the locale for this invocation.
+  - `:output` – `:binary` (default) or `:iodata`, as for `Icu.List.format/2`. The
+    formatted amount is a single short binary, which is already iodata, so both
+    return the same binary.
+
+  ## Examples
+
+      iex> Icu.Experimental.Units.format(21, unit: "celsius", locale: "en")
+      {:ok, "21°C"}
+
+      iex> Icu.Experimental.Units.format(3, unit: "meter", locale: "en", width: :long)
+      {:ok, "3 meters"}
+
+      iex> Icu.Experimental.Units.format(42, unit: "kilometer", locale: "de", approximately: true)
+      {:ok, "≈42 km"}
+  """
+  @spec format(number() | Decimal.t(), options_list()) ::
+          {:ok, String.t()} | {:error, term()}
+  def format(number, options) do
+    {output, options} = Options.pop_output(options)
+
+    with :ok <- Options.output(output),
+         {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format(formatter, number)
+    end
+  end
+
+  @doc """
+  Like `format/2`, but raises on error.
+  """
+  @spec format!(number() | Decimal.t(), options_list()) :: String.t()
+  def format!(number, options) do
+    case format(number, options) do
+      {:ok, formatted} -> formatted
+      {:error, reason} -> raise "unit formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats an amount of a unit to parts.
+
+  ICU4X does not annotate unit output, so the number is located in the
+  formatted string and the text around it is tagged as `:unit`, apart from
+  the spaces in between. Takes the same options as `format/2`, plus
+  `:index_mode` (see `t:Icu.index_mode/0`).
+
+  ## Examples
+
+      iex> Icu.Experimental.Units.format_to_parts(100, unit: "kilometer-per-hour", locale: "en")
+      {:ok,
+       [
+         %{part_type: :number, value: "100"},
+         %{part_type: :literal, value: " "},
+         %{part_type: :unit, value: "km/h"}
+       ]}
+  """
+  @spec format_to_parts(number() | Decimal.t(), options_list()) ::
+          {:ok, [map()]} | {:error, term()}
+  def format_to_parts(number, options) do
+    {index_mode, options} = Options.pop_index_mode(options)
+
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_to_parts(formatter, number, index_mode: index_mode)
+    end
+  end
+
+  @doc """
+  Like `format_to_parts/2`, but raises on error.
+  """
+  @spec format_to_parts!(number() | Decimal.t(), options_list()) :: [map()]
+  def format_to_parts!(number, options) do
+    case format_to_parts(number, options) do
+      {:ok, parts} -> parts
+      {:error, reason} -> raise "unit formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the options in effect for a unit formatter.
+
+  ## Examples
+
+      iex> formatter = Icu.Experimental.Units.Formatter.new!(unit: "meter", locale: "en")
+      iex> Icu.Experimental.Units.resolved_options(formatter)
+      %{unit: "meter", width: :short, approximately: false}
+  """
+  @spec resolved_options(formatter()) :: %{
+          unit: unit(),
+          width: width(),
+          approximately: boolean()
+        }
+  def resolved_options(formatter), do: Formatter.resolved_options(formatter)
+
+  @doc """
+  Formats the range between two amounts of a unit.
+
+  A unit written after the number is shown once for the whole range, as in
+  "3–5 km". Takes the same options as `format/2`.
+
+  ## Examples
+
+      iex> Icu.Experimental.Units.format_range(3, 5, unit: "kilometer", locale: "en")
+      {:ok, "3–5 km"}
+
+      iex> Icu.Experimental.Units.format_range(3, 5, unit: "day", locale: "en", width: :long)
+      {:ok, "3–5 days"}
+  """
+  @spec format_range(number() | Decimal.t(), number() | Decimal.t(), options_list()) ::
+          {:ok, String.t()} | {:error, term()}
+  def format_range(start, finish, options) do
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_range(formatter, start, finish)
+    end
+  end
+
+  @doc """
+  Like `format_range/3`, but raises on error.
+  """
+  @spec format_range!(number() | Decimal.t(), number() | Decimal.t(), options_list()) ::
+          String.t()
+  def format_range!(start, finish, options) do
+    case format_range(start, finish, options) do
+      {:ok, formatted} -> formatted
+      {:error, reason} -> raise "unit range formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Converts `value` from the `from` unit to the `to` unit.
+
+  ## Examples
+
+      iex> {:ok, pounds} = Icu.Experimental.Units.convert(2, "kilogram", "pound")
+      iex> Float.round(pounds, 2)
+      4.41
+
+      iex> Icu.Experimental.Units.convert(1, "kilogram", "meter")
+      {:error, :incompatible_units}
+  """
+  @spec convert(number(), unit(), unit()) :: {:ok, float()} | {:error, error()}
+  def convert(value, from, to)
+      when is_number(value) and is_binary(from) and is_binary(to) do
+    Nif.unit_convert(value, from, to)
+  end
+
+  def convert(_value, _from, _to), do: {:error, :invalid_number}
+
+  @doc """
+  Like `convert/3`, but raises on error.
+  """
+  @spec convert!(number(), unit(), unit()) :: float()
+  def convert!(value, from, to) do
+    case convert(value, from, to) do
+      {:ok, converted} -> converted
+      {:error, reason} -> raise "unit conversion failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Converts `value` into the unit preferred by the locale's measurement system.
+
+  Returns the converted value together with the unit it is expressed in.
+
+  ## Options
+
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> {:ok, {miles, unit}} =
+      ...>   Icu.Experimental.Units.convert_for_locale(10, "kilometer", locale: "en-US")
+      iex> {Float.round(miles, 3), unit}
+      {6.214, "mile"}
+
+      iex> Icu.Experimental.Units.convert_for_locale(10, "kilometer", locale: "de")
+      {:ok, {10.0, "kilometer"}}
+  """
+  @spec convert_for_locale(number(), unit(), locale_options()) ::
+          {:ok, {float(), unit()}} | {:error, error() | term()}
+  def convert_for_locale(value, from, options \\ [])
+
+  def convert_for_locale(value, from, options) when is_number(value) and is_binary(from) do
+    with {:ok, %{locale: locale}} <-
+           Options.normalize_options(:units, options, &(&1 == :locale)) do
+      Nif.unit_convert_for_locale(value, from, locale)
+    end
+  end
+
+  def convert_for_locale(_value, _from, _options), do: {:error, :invalid_number}
+
+  @doc """
+  Like `convert_for_locale/3`, but raises on error.
+  """
+  @spec convert_for_locale!(number(), unit(), locale_options()) :: {float(), unit()}
+  def convert_for_locale!(value, from, options \\ []) do
+    case convert_for_locale(value, from, options) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "unit conversion failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Expresses a measure in the units preferred by the locale for `usage`.
+
+  Returns the mixed-unit breakdown, largest unit first, ready to be passed to
+  a unit formatter. All amounts but the last are whole numbers; the last one
+  carries the remainder, rounded before the breakdown is made final so that
+  a remainder rounding up to a whole larger unit carries over (`6 ft 0 in`
+  rather than `5 ft 12 in`).
+
+  ## Options
+
+  - `:locale` – override the locale for this invocation.
+  - `:maximum_fraction_digits` – the fraction digits the last amount is
+    rounded to. Defaults to `3`.
+
+  ## Examples
+
+      iex> {:ok, [{5, "foot"}, {inches, "inch"}]} =
+      ...>   Icu.Experimental.Units.preferred_units({180, "centimeter"}, "person-height",
+      ...>     locale: "en-US"
+      ...>   )
+      iex> inches
+      10.866
+
+      iex> Icu.Experimental.Units.preferred_units({180, "centimeter"}, "person-height",
+      ...>   locale: "de"
+      ...> )
+      {:ok, [{180.0, "centimeter"}]}
+  """
+  @spec preferred_units(measure(), usage(), preference_options()) ::
+          {:ok, [measure()]} | {:error, error() | term()}
+  def preferred_units(measure, usage, options \\ [])
+
+  def preferred_units({value, unit}, usage, options)
+      when is_number(value) and is_binary(unit) and is_binary(usage) do
+    accepts_key = &(&1 in [:locale, :maximum_fraction_digits])
+
+    with {:ok, %{locale: locale} = opts} <-
+           Options.normalize_options(:units, options, accepts_key) do
+      fraction_digits = Map.get(opts, :maximum_fraction_digits, 3)
+      Nif.unit_preferred_units(locale, usage, value, unit, fraction_digits)
+    end
+  end
+
+  def preferred_units(_measure, _usage, _options), do: {:error, :invalid_number}
+
+  @doc """
+  Like `preferred_units/3`, but raises on error.
+  """
+  @spec preferred_units!(measure(), usage(), preference_options()) :: [measure()]
+  def preferred_units!(measure, usage, options \\ []) do
+    case preferred_units(measure, usage, options) do
+      {:ok, measures} -> measures
+      {:error, reason} -> raise "unit conversion failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats a sequence of measures as a single mixed-unit string.
+
+  ## Options
+
+  - `:width` – display width of the units (`:short`, `:narrow`, `:long`).
+    Defaults to `:short`.
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> Icu.Experimental.Units.format_mixed([{5, "foot"}, {11, "inch"}], locale: "en")
+      {:ok, "5 ft, 11 in"}
+
+      iex> Icu.Experimental.Units.format_mixed([{1, "hour"}, {30, "minute"}],
+      ...>   locale: "en",
+      ...>   width: :long
+      ...> )
+      {:ok, "1 hour, 30 minutes"}
+  """
+  @spec format_mixed([measure()], format_options()) ::
+          {:ok, String.t()} | {:error, error() | :invalid_items | term()}
+  def format_mixed(measures, options \\ [])
+
+  def format_mixed([_ | _] = measures, options) do
+    with {:ok, opts} <-
+           Options.normalize_options(:units, options, &(&1 in [:locale, :width])) do
+      {locale, rest} = Map.pop(opts, :locale)
+      Nif.unit_format_mixed(locale, measures, rest)
+    end
+  end
+
+  def format_mixed(_measures, _options), do: {:error, :invalid_items}
+
+  @doc """
+  Like `format_mixed/2`, but raises on error.
+  """
+  @spec format_mixed!([measure()], format_options()) :: String.t()
+  def format_mixed!(measures, options \\ []) do
+    case format_mixed(measures, options) do
+      {:ok, formatted} -> formatted
+      {:error, reason} -> raise "unit formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the unit's patterns keyed by the locale's plural categories.
+
+  Each pattern contains `{0}` where the formatted number goes. Categories
+  only appear in the result if the locale's cardinal plural rules use them.
+
+  ## Options
+
+  - `:width` – display width of the unit (`:short`, `:narrow`, `:long`).
+    Defaults to `:short`.
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> Icu.Experimental.Units.patterns("day", locale: "de", width: :long)
+      {:ok, %{one: "{0} Tag", other: "{0} Tage"}}
+
+      iex> Icu.Experimental.Units.patterns("kilometer", locale: "en")
+      {:ok, %{one: "{0} km", other: "{0} km"}}
+  """
+  @spec patterns(unit(), format_options()) ::
+          {:ok, %{plural_category() => String.t()}} | {:error, error() | term()}
+  def patterns(unit, options \\ [])
+
+  def patterns(unit, options) when is_binary(unit) do
+    with {:ok, opts} <-
+           Options.normalize_options(:units, options, &(&1 in [:locale, :width])) do
+      {locale, rest} = Map.pop(opts, :locale)
+      Nif.unit_patterns(locale, unit, rest)
+    end
+  end
+
+  def patterns(_unit, _options), do: {:error, :invalid_unit}
+
+  @doc """
+  Like `patterns/2`, but raises on error.
+  """
+  @spec patterns!(unit(), format_options()) :: %{plural_category() => String.t()}
+  def patterns!(unit, options \\ []) do
+    case patterns(unit, options) do
+      {:ok, patterns} -> patterns
+      {:error, reason} -> raise "unit pattern lookup failed: #{inspect(reason)}"
+    end
+  end
+end
@@ -0,0 +1,102 @@
+defmodule Icu.Experimental.Units.Formatter do
+  @moduledoc false
+
+  alias Icu.Nif
+  alias Icu.Formatter.Config
+  alias Icu.Formatter.Options
+
+  defstruct [:resource, :unit, :config]
+
+  @opaque t :: %__MODULE__{}
+
+  @option_keys [:locale, :unit, :width, :approximately]
+
+  defguardp is_number_input(term)
+            when is_number(term) or is_struct(term, Decimal) or is_struct(term, Icu.Decimal)
+
+  @spec new(keyword() | map()) :: {:ok, t()} | {:error, term()}
+  def new(options \\ []) do
+    with {:ok, opts} <- normalize_options(options) do
+      warnings = Options.warnings(:units, options, opts, &(&1 in @option_keys))
+      {unit, opts} = Map.pop(opts, :unit)
+      {locale, rest} = Map.pop(opts, :locale)
+
+      case Nif.unit_formatter_new(locale, unit, rest) do
+        {:ok, resource} ->
+          config = Config.new(__MODULE__, :new, [Config.put_locale(options, locale)], warnings)
+          {:ok, %__MODULE__{resource: resource, unit: unit, config: config}}
+
+        {:error, _} = error ->
+          error
+      end
+    end
+  end
+
+  @spec new!(keyword() | map()) :: t()
+  def new!(options \\ []) do
+    case new(options) do
+      {:ok, formatter} -> formatter
+      {:error, reason} -> raise "unit formatter creation failed: #{inspect(reason)}"
+    end
+  end
+
+  @spec format(t(), number() | Decimal.t()) :: {:ok, String.t()} | {:error, term()}
+  def format(%__MODULE__{resource: resource}, number) when is_number_input(number) do
+    Nif.unit_format(resource, number)
+  end
+
+  def format(%__MODULE__{}, _other), do: {:error, :invalid_number}
+
+  @spec format_to_parts(t(), number() | Decimal.t(), Options.options_input()) ::
+          {:ok, [map()]} | {:error, term()}
+  def format_to_parts(formatter, number, options \\ [])
+
+  def format_to_parts(%__MODULE__{resource: resource}, number, options)
+      when is_number_input(number) do
+    with {:ok, index_mode} <- Options.index_mode(options) do
+      Nif.unit_format_to_parts(resource, number, index_mode)
+    end
+  end
+
+  def format_to_parts(%__MODULE__{}, _other, _options), do: {:error, :invalid_number}
+
+  @spec format!(t(), number() | Decimal.t()) :: String.t()
+  def format!(%__MODULE__{} = formatter, number) do
+    case format(formatter, number) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "unit formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @spec format_range(t(), number() | Decimal.t(), number() | Decimal.t()) ::
+          {:ok, String.t()} | {:error, term()}
+  def format_range(%__MODULE__{resource: resource}, start, finish)
+      when is_number_input(start) and is_number_input(finish) do
+    Nif.unit_format_range(resource, start, finish)
+  end
+
+  def format_range(%__MODULE__{}, _start, _finish), do: {:error, :invalid_number}
+
+  @spec resolved_options(t()) :: %{unit: String.t(), width: atom(), approximately: boolean()}
+  def resolved_options(%__MODULE__{resource: resource}) do
+    {:ok, options} = Nif.unit_resolved_options(resource)
+    options
+  end
+
+  defimpl Inspect do
+    def inspect(formatter, _opts) do
+      "#Icu.Experimental.Units.Formatter<#{formatter.unit}>"
+    end
+  end
+
+  @doc false
+  @spec normalize_options(keyword() | map()) :: {:ok, map()} | {:error, term()}
+  def normalize_options(options) do
+    Options.normalize_options(:units, options, &(&1 in @option_keys))
+    |> validate_unit_required()
+  end
+
+  defp validate_unit_required({:ok, %{unit: _} = opts}), do: {:ok, opts}
+  defp validate_unit_required({:ok, _}), do: {:error, {:missing_option, :unit}}
+  defp validate_unit_required(error), do: error
+end
@@ -0,0 +1,62 @@
+defmodule Icu.Formatter.Config do
+  @moduledoc false
+
+  # Formatters wrap NIF resources, which cannot be sent to another node or
+  # survive a code reload. Each formatter therefore records the constructor
+  # that built it and its arguments, with the locale resolved to a string, so
+  # that an equivalent formatter can be rebuilt from plain terms. The options
+  # the constructor ignored are kept alongside as warnings; they play no part
+  # in rebuilding.
+
+  @constructors %{
+    Icu.Collator => [:new],
+    Icu.DisplayNames.Formatter => [:new],
+    Icu.Experimental.Currency.Formatter => [:new],
+    Icu.Experimental.Units.Formatter => [:new],
+    Icu.List.Formatter => [:new],
+    Icu.Number.Formatter => [:new],
+    Icu.RelativeTime.Formatter => [:new],
+    Icu.Temporal.Formatter => [:new, :new_date, :new_time, :new_zoned_datetime, :from_pattern]
+  }
+
+  @type t :: %{
+          formatter: module(),
+          constructor: atom(),
+          args: [term()],
+          warnings: [Icu.warning()]
+        }
+
+  @spec new(module(), atom(), [term()], [Icu.warning()]) :: t()
+  def new(formatter, constructor, args, warnings \\ []) do
+    %{formatter: formatter, constructor: constructor, args: args, warnings: warnings}
+  end
+
+  @doc """
+  Returns `options` as a map with `:locale` replaced by the string form of
+  the locale the formatter was actually built with.
+  """
+  @spec put_locale(map() | keyword(), reference()) :: map()
+  def put_locale(options, locale_resource) do
+    Map.put(Map.new(options), :locale, locale_string(locale_resource))
+  end
+
+  @spec locale_string(reference()) :: String.t()
+  def locale_string(locale_resource) do
+    {:ok, locale} = Icu.Nif.locale_to_string(locale_resource)
+    locale
+  end
+
+  @spec rebuild(term()) :: {:ok, struct()} | {:error, term()}
+  def rebuild(%{formatter: formatter, constructor: constructor, args: args})
+      when is_list(args) do
+    if constructor in Map.get(@constructors, formatter, []) and
+         Code.ensure_loaded?(formatter) and
+         function_exported?(formatter, constructor, length(args)) do
+      apply(formatter, constructor, args)
+    else
+      {:error, :invalid_config}
+    end
+  end
+
+  def rebuild(_config), do: {:error, :invalid_config}
+end
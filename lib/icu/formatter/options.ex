@@ -5,12 +5,27 @@ defmodule Icu.Formatter.Options do
   alias Icu.HourCycle
   alias Icu.LanguageTag
 
-  @type area :: :temporal | :number | :list | :display_names
+  require Logger
+
+  @type area ::
+          :temporal
+          | :number
+          | :list
+          | :display_names
+          | :calendar
+          | :gettext
+          | :collator
+          | :currency
+          | :units
+          | :relative_time
+          | :grammar
+          | :text
   @type accept_fun :: (atom() -> boolean())
   @type options_input :: map() | keyword()
   @type error ::
           {:error, :invalid_options}
           | {:error, {:bad_option, atom()}}
+          | {:error, {:invalid_option_value, atom()}}
           | {:error, {:invalid_option_value, atom(), term()}}
 
   @spec normalize_options(atom(), options_input(), accept_fun()) ::
@@ -34,14 +49,98 @@ defmodule Icu.Formatter.Options do
               {:halt, {:error, {:invalid_option_value, key}}}
           end
         else
-          {:halt, {:error, {:bad_option, key}}}
+          unknown_option(area, key, acc)
         end
     end)
     |> ensure_locale_option()
   end
 
+  # Applies the `:icu, :unknown_options` policy to a key the area does not
+  # accept. The NIFs reject unknown keys, so they are never forwarded.
+  defp unknown_option(area, key, acc) do
+    case Application.get_env(:icu, :unknown_options, :error) do
+      :ignore ->
+        {:cont, {:ok, acc}}
+
+      :warn ->
+        Logger.warning("ignoring unknown #{area} option #{inspect(key)}")
+        {:cont, {:ok, acc}}
+
+      :error ->
+        {:halt, {:error, {:bad_option, key}}}
+    end
+  end
+
   def normalize_options(_area, _other, _accepts_key), do: {:error, :invalid_options}
 
+  @doc """
+  Returns the options in `options` that have no effect on a formatter built
+  from their normalized form `opts`: unknown keys dropped by the
+  `:unknown_options` policy or set to `nil`, and options that only apply in
+  combination with another one.
+  """
+  @spec warnings(atom(), options_input() | nil, map(), accept_fun()) :: [Icu.warning()]
+  def warnings(area, options, opts, accepts_key) do
+    dropped =
+      for {key, value} <- options || [],
+          not Map.has_key?(opts, key),
+          not (is_nil(value) and accepts_key.(key)),
+          do: {:ignored_option, key}
+
+    dropped ++ ineffective_options(area, opts)
+  end
+
+  defp ineffective_options(:currency, %{notation: :compact_short}), do: []
+
+  defp ineffective_options(:currency, opts) do
+    for key <- [:compact_rounding, :compact_threshold],
+        Map.has_key?(opts, key),
+        do: {:ignored_option, key}
+  end
+
+  defp ineffective_options(_area, _opts), do: []
+
+  @index_modes [:byte, :grapheme, :utf16]
+
+  @doc """
+  Splits the `:index_mode` of the `format_to_parts` functions off `options`,
+  since it applies to a single call rather than to the formatter.
+  """
+  @spec pop_index_mode(options_input() | nil) :: {term(), options_input() | nil}
+  def pop_index_mode(options) when is_map(options), do: Map.pop(options, :index_mode)
+  def pop_index_mode(options) when is_list(options), do: Keyword.pop(options, :index_mode)
+  def pop_index_mode(options), do: {nil, options}
+
+  @doc """
+  Returns the `:index_mode` in the `format_to_parts` options, or `nil` to
+  leave the parts without offsets.
+  """
+  @spec index_mode(options_input()) :: {:ok, atom() | nil} | error()
+  def index_mode(options) when is_map(options) or is_list(options) do
+    case options[:index_mode] do
+      mode when is_nil(mode) or mode in @index_modes -> {:ok, mode}
+      _other -> {:error, {:invalid_option_value, :index_mode}}
+    end
+  end
+
+  def index_mode(_other), do: {:error, :invalid_options}
+
+  @doc """
+  Splits the `:output` of the `format` functions off `options`, since it
+  shapes the result of a single call rather than the formatter.
+  """
+  @spec pop_output(options_input() | nil) :: {term(), options_input() | nil}
+  def pop_output(options) when is_map(options), do: Map.pop(options, :output, :binary)
+  def pop_output(options) when is_list(options), do: Keyword.pop(options, :output, :binary)
+  def pop_output(options), do: {:binary, options}
+
+  @doc """
+  Checks the `:output` popped by `pop_output/1`.
+  """
+  @spec output(term()) :: :ok | error()
+  def output(output) when output in [:binary, :iodata], do: :ok
+  def output(_other), do: {:error, {:invalid_option_value, :output}}
+
   def ensure_locale_option({:ok, %{locale: locale} = options}) do
     {:ok, Map.put(options, :locale, locale.resource)}
   end
@@ -64,6 +163,9 @@ defmodule Icu.Formatter.Options do
     Icu.get_locale()
   end
 
+  def normalize_option(:temporal, :calendar, value) when is_atom(value) or is_binary(value),
+    do: {:ok, Calendar.calendar_id(value)}
+
   def normalize_option(_areal, :calendar, value) do
     case Calendar.normalize_identifier(value) do
       {:ok, value} -> {:ok, value}
@@ -78,6 +180,23 @@ defmodule Icu.Formatter.Options do
     end
   end
 
+  def normalize_option(area, :preferences, value)
+      when area in [:number, :temporal] and (is_list(value) or is_map(value)) do
+    Enum.reduce_while(value, {:ok, %{}}, fn
+      {_key, nil}, acc ->
+        {:cont, acc}
+
+      {key, preference}, {:ok, acc} ->
+        case normalize_preference(key, preference) do
+          {:ok, normalized} -> {:cont, {:ok, Map.put(acc, key, normalized)}}
+          :error -> {:halt, :error}
+        end
+
+      _other, _acc ->
+        {:halt, :error}
+    end)
+  end
+
   def normalize_option(_area, :date_length, value) when value in [:short, :medium, :long, :full],
     do: {:ok, value}
 
@@ -101,7 +220,7 @@ defmodule Icu.Formatter.Options do
     do: {:ok, value}
 
   def normalize_option(_area, :date_fields, value)
-      when value in [:d, :md, :ymd, :de, :mde, :ymde, :e, :m, :ym, :y],
+      when value in [:d, :md, :ymd, :de, :mde, :ymde, :e, :m, :ym, :y, :yq, :q],
       do: {:ok, value}
 
   def normalize_option(_area, :time_precision, {:subsecond, digits})
@@ -131,7 +250,52 @@ defmodule Icu.Formatter.Options do
   def normalize_option(:temporal, :year_style, value) when value in [:auto, :full, :with_era],
     do: {:ok, value}
 
+  def normalize_option(:temporal, :month, value)
+      when value in [:numeric, :two_digit, :short, :long, :narrow],
+      do: {:ok, value}
+
+  def normalize_option(:temporal, :year, value) when value in [:numeric, :two_digit],
+    do: {:ok, value}
+
+  def normalize_option(:temporal, :width, value) when value in [:wide, :short, :narrow],
+    do: {:ok, value}
+
+  def normalize_option(:temporal, :skeleton, value) when is_binary(value) and value != "",
+    do: {:ok, value}
+
+  # The reference date is handed to the NIF as an ISO 8601 date string.
+  def normalize_option(:temporal, :relative_to, %Date{} = date) do
+    case Date.convert(date, Elixir.Calendar.ISO) do
+      {:ok, date} -> {:ok, Date.to_iso8601(date)}
+      {:error, _} -> :error
+    end
+  end
+
+  def normalize_option(:temporal, :relative_to, %struct{} = datetime)
+      when struct in [NaiveDateTime, DateTime],
+      do: normalize_option(:temporal, :relative_to, struct.to_date(datetime))
+
+  def normalize_option(:temporal, :relative_to, value) when is_binary(value), do: {:ok, value}
+
+  def normalize_option(:temporal, :relative_to, {unix_seconds, _utc_offset} = now)
+      when is_integer(unix_seconds),
+      do: {:ok, now}
+
+  def normalize_option(:temporal, :relative_days, value)
+      when is_integer(value) and value >= 0 and value <= 255,
+      do: {:ok, value}
+
+  def normalize_option(:temporal, :leap_second, value) when value in [:clamp, :error],
+    do: {:ok, value}
+
   # Number
+  def normalize_option(:number, :ruleset, value)
+      when value in ["spellout-cardinal", "spellout-numbering"],
+      do: {:ok, value}
+
+  def normalize_option(:number, :numbering_system, value) when is_binary(value),
+    do: {:ok, value}
+
   def normalize_option(:number, :grouping, value)
       when value in [:auto, :always, :min2, :never] do
     {:ok, value}
@@ -162,13 +326,96 @@ defmodule Icu.Formatter.Options do
     {:ok, value}
   end
 
+  def normalize_option(:number, :significant_digits, value)
+      when is_integer(value) and value >= 1 and value <= 255,
+      do: {:ok, value}
+
+  def normalize_option(:number, :float_precision, value) when value in [:round_trip, :integer],
+    do: {:ok, value}
+
+  def normalize_option(:number, :absolute_value, value) when is_boolean(value),
+    do: {:ok, value}
+
+  def normalize_option(:number, :scale, value) when is_integer(value) and value > 0 do
+    if power_of_ten?(value), do: {:ok, value}, else: :error
+  end
+
+  def normalize_option(:number, :float_precision, {:magnitude, magnitude} = value)
+      when is_integer(magnitude) and magnitude >= -32_768 and magnitude <= 32_767,
+      do: {:ok, value}
+
+  def normalize_option(area, :approximately, value)
+      when area in [:number, :units, :currency] and is_boolean(value),
+      do: {:ok, value}
+
+  # Collator
+  def normalize_option(:collator, :strength, value)
+      when value in [:primary, :secondary, :tertiary, :quaternary, :identical],
+      do: {:ok, value}
+
+  def normalize_option(:collator, :numeric, value) when is_boolean(value),
+    do: {:ok, value}
+
+  def normalize_option(:collator, :case_first, value) when value in [:upper, :lower, :off],
+    do: {:ok, value}
+
+  # Text
+  def normalize_option(:text, :ellipsis, value) when is_binary(value),
+    do: {:ok, value}
+
+  def normalize_option(:text, :word_boundary, value) when is_boolean(value),
+    do: {:ok, value}
+
+  def normalize_option(:text, :ambiguous, value) when value in [:narrow, :wide],
+    do: {:ok, value}
+
+  def normalize_option(:text, :leading_adjustment, value) when value in [:auto, :none, :to_cased],
+    do: {:ok, value}
+
+  def normalize_option(:text, :trailing_case, value) when value in [:lower, :unchanged],
+    do: {:ok, value}
+
+  def normalize_option(:text, :stop_words, value) when is_list(value) do
+    if Enum.all?(value, &is_binary/1), do: {:ok, value}, else: :error
+  end
+
+  def normalize_option(:text, :marker, value) when is_binary(value) and value != "",
+    do: {:ok, value}
+
+  def normalize_option(:text, :top, value) when is_integer(value) and value >= 0,
+    do: {:ok, value}
+
+  # Units
+  def normalize_option(:units, :unit, value) when is_binary(value) and value != "",
+    do: {:ok, value}
+
+  def normalize_option(:units, :width, value) when value in [:short, :narrow, :long],
+    do: {:ok, value}
+
+  def normalize_option(:units, :maximum_fraction_digits, value)
+      when is_integer(value) and value >= 0 and value <= 255,
+      do: {:ok, value}
+
   # Currency
   def normalize_option(:currency, :width, value) when value in [:short, :narrow, :long],
     do: {:ok, value}
 
+  def normalize_option(:currency, :notation, value) when value in [:standard, :compact_short],
+    do: {:ok, value}
+
   def normalize_option(:currency, :currency, value) when is_binary(value),
     do: {:ok, value}
 
+  def normalize_option(:currency, :compact_rounding, :auto), do: {:ok, :auto}
+
+  def normalize_option(:currency, :compact_rounding, {:significant, digits} = value)
+      when is_integer(digits) and digits >= 1 and digits <= 255,
+      do: {:ok, value}
+
+  def normalize_option(:currency, :compact_threshold, value)
+      when is_number(value) and value >= 0,
+      do: {:ok, value}
+
   def normalize_option(:currency, :currency_digits, value) when value in [:iso, :cash],
     do: {:ok, value}
 
@@ -189,6 +436,8 @@ defmodule Icu.Formatter.Options do
   def normalize_option(:currency, :rounding_mode, value) when value in @decimal_rounding_modes,
     do: {:ok, value}
 
+  def normalize_option(:currency, :historical, value) when is_boolean(value), do: {:ok, value}
+
   # List
   def normalize_option(:list, :type, value) when value in [:and, :or, :unit], do: {:ok, value}
 
@@ -207,5 +456,33 @@ defmodule Icu.Formatter.Options do
       when value in [:dialect, :standard],
       do: {:ok, value}
 
+  def normalize_option(:display_names, :extensions, value) when is_boolean(value),
+    do: {:ok, value}
+
+  # Gettext
+  def normalize_option(:gettext, key, value)
+      when key in [:case_insensitive, :strip_script] and is_boolean(value),
+      do: {:ok, value}
+
+  def normalize_option(:gettext, :output, value) when value in [:original, :canonical],
+    do: {:ok, value}
+
   def normalize_option(_area, _key, _value), do: :error
+
+  # Preferences are checked against the CLDR keyword data by the NIF.
+  defp normalize_preference(:calendar, value) when is_atom(value) or is_binary(value),
+    do: {:ok, Calendar.calendar_id(value)}
+
+  defp normalize_preference(:hour_cycle, value),
+    do: normalize_option(:temporal, :hour_cycle, value)
+
+  defp normalize_preference(key, value)
+       when key == :numbering_system and is_binary(value),
+       do: {:ok, value}
+
+  defp normalize_preference(_key, _value), do: :error
+
+  defp power_of_ten?(1), do: true
+  defp power_of_ten?(value) when rem(value, 10) == 0, do: power_of_ten?(div(value, 10))
+  defp power_of_ten?(_value), do: false
 end
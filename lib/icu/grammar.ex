@@ -0,0 +1,66 @@
+defmodule Icu.Grammar do
+  @moduledoc """
+  Grammatical features of the words formatters produce.
+
+  Message templates in gendered languages need the gender of a formatted
+  unit or date field to pick the right article or adjective ending, as in
+  German "die Stunde" but "der Tag".
+
+  ICU4X does not ship CLDR's grammatical features, so this module bundles
+  the genders of the common units (lengths, masses, durations, `"liter"`,
+  `"byte"` and `"percent"`) and of weekday and month names for German,
+  French, Spanish, Italian and Portuguese. Other languages and units report
+  no gender.
+
+  ## Examples
+
+      iex> Icu.Grammar.info({:unit, "hour"}, locale: "de")
+      {:ok, %{gender: :feminine}}
+
+      iex> Icu.Grammar.info({:weekday, 7}, locale: "it")
+      {:ok, %{gender: :feminine}}
+
+      iex> Icu.Grammar.info({:month, 1}, locale: "en")
+      {:ok, %{gender: nil}}
+  """
+
+  alias Icu.Formatter.Options
+  alias Icu.LanguageTag
+  alias Icu.Nif
+
+  @typedoc """
+  The thing to describe: a CLDR unit identifier such as `"meter"`, an ISO
+  weekday number (Monday is 1) or a month number.
+  """
+  @type thing :: {:unit, String.t()} | {:weekday, 1..7} | {:month, 1..12}
+
+  @type gender :: :masculine | :feminine | :neuter
+
+  @type info :: %{gender: gender() | nil}
+
+  @type options :: [{:locale, LanguageTag.t() | String.t() | nil}]
+
+  @doc """
+  Returns the grammatical features of `thing` in the locale's language.
+
+  Accepts a `:locale` option; otherwise `Icu.get_locale/0` is used.
+  """
+  @spec info(thing(), options()) ::
+          {:ok, info()} | {:error, :invalid_options | :invalid_locale | term()}
+  def info(thing, options \\ []) do
+    with {:ok, opts} <- Options.normalize_options(:grammar, options, &(&1 == :locale)) do
+      Nif.grammar_info(opts.locale, thing)
+    end
+  end
+
+  @doc """
+  Same as `info/2` but raises on failure.
+  """
+  @spec info!(thing(), options()) :: info()
+  def info!(thing, options \\ []) do
+    case info(thing, options) do
+      {:ok, info} -> info
+      {:error, reason} -> raise "grammar lookup failed: #{inspect(reason)}"
+    end
+  end
+end
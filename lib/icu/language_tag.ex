@@ -6,6 +6,7 @@ defmodule Icu.LanguageTag do
   construct values and avoid manipulating the resource directly.
   """
 
+  alias Icu.Formatter.Options
   alias Icu.LanguageTag.Components
   alias Icu.Nif
   import Kernel, except: [to_string: 1]
@@ -18,6 +19,17 @@ defmodule Icu.LanguageTag do
 
   @type parse_error :: {:error, :invalid_locale}
 
+  @typedoc "Why a `-u-` keyword was rejected."
+  @type extension_error :: {:error, :unknown_extension_key | :invalid_extension_value}
+
+  @type gettext_options :: [
+          {:case_insensitive, boolean()}
+          | {:strip_script, boolean()}
+          | {:output, :original | :canonical}
+        ]
+
+  @gettext_keys [:case_insensitive, :strip_script, :output]
+
   @doc """
   Parses a locale string and returns a language tag resource.
   """
@@ -225,6 +237,127 @@ defmodule Icu.LanguageTag do
     end
   end
 
+  @doc """
+  Checks a Unicode extension (`-u-`) keyword against the CLDR keyword data.
+
+  Returns `{:error, :unknown_extension_key}` for a key CLDR does not define
+  and `{:error, :invalid_extension_value}` for a value the key does not
+  take. Formatters silently ignore such keywords, so checking them up front
+  catches mistakes such as `"nu"` set to `"yes"`.
+
+  Keys with a closed set of values, such as `"ca"`, `"co"`, `"hc"` or
+  `"nu"`, are checked against that set. Keys with open-ended values are
+  checked for the shape of their values only: `"cu"` takes a three-letter
+  currency code, `"rg"` and `"sd"` a region with a subdivision suffix, and
+  `"tz"` a short time zone identifier.
+
+  ## Examples
+
+      iex> Icu.LanguageTag.validate_extension_value("ca", "buddhist")
+      :ok
+
+      iex> Icu.LanguageTag.validate_extension_value("nu", "yes")
+      {:error, :invalid_extension_value}
+
+      iex> Icu.LanguageTag.validate_extension_value("zz", "yes")
+      {:error, :unknown_extension_key}
+
+  """
+  @spec validate_extension_value(String.t(), String.t()) :: :ok | extension_error()
+  def validate_extension_value(key, value) when is_binary(key) and is_binary(value) do
+    Nif.locale_validate_extension_value(key, value)
+  end
+
+  @doc """
+  Sets a Unicode extension (`-u-`) keyword on a language tag.
+
+  The keyword is checked as by `validate_extension_value/2` and replaces
+  any value the key already has.
+
+  ## Examples
+
+      iex> tag = Icu.LanguageTag.parse!("th-TH")
+      iex> {:ok, tag} = Icu.LanguageTag.set_extension(tag, "ca", "buddhist")
+      iex> Icu.LanguageTag.to_string!(tag)
+      "th-TH-u-ca-buddhist"
+
+      iex> Icu.LanguageTag.set_extension(Icu.LanguageTag.parse!("en"), "nu", "yes")
+      {:error, :invalid_extension_value}
+
+  """
+  @spec set_extension(t(), String.t(), String.t()) ::
+          {:ok, t()} | extension_error() | {:error, :invalid_resource}
+  def set_extension(%__MODULE__{resource: resource}, key, value)
+      when is_binary(key) and is_binary(value) do
+    case Nif.locale_set_extension(resource, key, value) do
+      {:ok, new_resource} -> {:ok, %__MODULE__{resource: new_resource}}
+      {:error, _} = error -> error
+    end
+  end
+
+  @doc """
+  Sets a Unicode extension keyword and raises on error.
+
+  ## Examples
+
+      iex> tag = Icu.LanguageTag.set_extension!(Icu.LanguageTag.parse!("ar"), "nu", "latn")
+      iex> Icu.LanguageTag.to_string!(tag)
+      "ar-u-nu-latn"
+
+  """
+  @spec set_extension!(t(), String.t(), String.t()) :: t()
+  def set_extension!(%__MODULE__{} = tag, key, value) do
+    case set_extension(tag, key, value) do
+      {:ok, new_tag} -> new_tag
+      {:error, reason} -> raise ArgumentError, "failed to set extension: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Resolves the hour cycle the locale's time formats use.
+
+  Unlike `get_hour_cycle/1`, which only reads the `-u-hc-` extension, this
+  consults the locale data, so it always returns a cycle. An `-u-hc-`
+  extension still takes precedence.
+
+  ## Examples
+
+      iex> Icu.LanguageTag.hour_cycle(Icu.LanguageTag.parse!("en-US"))
+      {:ok, :h12}
+
+      iex> Icu.LanguageTag.hour_cycle(Icu.LanguageTag.parse!("de"))
+      {:ok, :h23}
+
+      iex> Icu.LanguageTag.hour_cycle(Icu.LanguageTag.parse!("en-US-u-hc-h23"))
+      {:ok, :h23}
+
+  """
+  @spec hour_cycle(t()) :: {:ok, hour_cycle() | :h24} | {:error, :invalid_locale}
+  def hour_cycle(%__MODULE__{resource: resource}) do
+    Nif.locale_hour_cycle(resource)
+  end
+
+  @doc """
+  Returns whether the locale shows times on a 24-hour clock, for example to
+  decide whether a time picker needs an AM/PM selector.
+
+  ## Examples
+
+      iex> Icu.LanguageTag.uses_24h?(Icu.LanguageTag.parse!("fr"))
+      true
+
+      iex> Icu.LanguageTag.uses_24h?(Icu.LanguageTag.parse!("en-US"))
+      false
+
+  """
+  @spec uses_24h?(t()) :: boolean()
+  def uses_24h?(%__MODULE__{} = tag) do
+    case hour_cycle(tag) do
+      {:ok, cycle} -> cycle in [:h23, :h24]
+      {:error, reason} -> raise "failed to resolve hour cycle: #{inspect(reason)}"
+    end
+  end
+
   @doc """
   Returns the full list of fallback locales for the given locale.
   "lookup" according to RFC4647.
@@ -235,6 +368,190 @@ defmodule Icu.LanguageTag do
     {:ok, Enum.map(fallbacks, &%__MODULE__{resource: &1})}
   end
 
+  @doc """
+  Returns the CLDR inheritance chain of a locale.
+
+  The chain starts with the locale itself, without its extensions, and
+  follows the CLDR `parentLocales` data, removing the last subtag where
+  there is no explicit parent. The root locale is not included. Unlike
+  `fallbacks/1`, no likely subtags are added, so the chain matches the
+  order in which CLDR-structured translations inherit from each other.
+
+  ## Examples
+
+      iex> {:ok, parents} = Icu.LanguageTag.parents(Icu.LanguageTag.parse!("es-AR"))
+      iex> Enum.map(parents, &Icu.LanguageTag.to_string!/1)
+      ["es-AR", "es-419", "es"]
+
+  """
+  @spec parents(t()) :: {:ok, [t()]}
+  def parents(%__MODULE__{resource: resource}) do
+    {:ok, parents} = Nif.locale_parents(resource)
+    {:ok, Enum.map(parents, &%__MODULE__{resource: &1})}
+  end
+
+  @doc """
+  Compares two language tags by their canonical BCP-47 form.
+
+  Tags are canonicalized when parsed, so differences in case do not matter.
+  The order is total and stable, which lets tags be sorted with
+  `Enum.sort(tags, Icu.LanguageTag)`.
+
+  ## Examples
+
+      iex> en_us = Icu.LanguageTag.parse!("en-US")
+      iex> Icu.LanguageTag.compare(en_us, Icu.LanguageTag.parse!("EN-us"))
+      :eq
+      iex> Icu.LanguageTag.compare(Icu.LanguageTag.parse!("de"), en_us)
+      :lt
+
+  """
+  @spec compare(t(), t()) :: :lt | :eq | :gt
+  def compare(%__MODULE__{resource: left}, %__MODULE__{resource: right}) do
+    case Nif.locale_compare(left, right) do
+      {:ok, order} -> order
+      {:error, reason} -> raise ArgumentError, "locale comparison failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns whether two language tags have the same canonical form.
+
+  Two tags parsed from the same string are separate resources, so `==`
+  does not consider them equal. Use this function instead.
+
+  ## Examples
+
+      iex> tag = Icu.LanguageTag.parse!("zh-hant-tw")
+      iex> Icu.LanguageTag.equal?(tag, Icu.LanguageTag.parse!("zh-Hant-TW"))
+      true
+
+  """
+  @spec equal?(t(), t()) :: boolean()
+  def equal?(%__MODULE__{} = left, %__MODULE__{} = right) do
+    compare(left, right) == :eq
+  end
+
+  @doc """
+  Returns a hash of the canonical form of a language tag.
+
+  Tags that are `equal?/2` hash the same, so the hash can key a map or
+  deduplicate tags without converting them to strings. Hashes are only
+  stable within one build of the NIF and should not be persisted.
+  """
+  @spec hash(t()) :: non_neg_integer()
+  def hash(%__MODULE__{resource: resource}) do
+    case Nif.locale_hash(resource) do
+      {:ok, hash} -> hash
+      {:error, reason} -> raise ArgumentError, "locale hashing failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the distance from a desired locale to a supported one.
+
+  This is a simplified distance, not the CLDR language matching data. Both
+  locales are maximized first, so `"en"` and `"en-Latn-US"` are 0 apart.
+  Otherwise the distance adds up what differs:
+
+    * a different region adds 4, or 5 when CLDR parent locales put the
+      regions in different groups, like `"es-ES"` and `"es-419"`
+    * a different script adds 50
+    * a different language is 80, except for a few closely related ones
+      such as Norwegian Bokmål and Norwegian (1)
+
+  Distances below 50 mean the locales share their script and their language
+  or a closely related one. Applications can compare the distance against
+  their own threshold, e.g. only switching locales automatically below 10.
+
+  ## Examples
+
+      iex> Icu.LanguageTag.distance("es-MX", "es-AR")
+      {:ok, 4}
+
+      iex> Icu.LanguageTag.distance("es-MX", "es-ES")
+      {:ok, 5}
+
+      iex> Icu.LanguageTag.distance("sr-Latn", "sr")
+      {:ok, 50}
+
+      iex> Icu.LanguageTag.distance("de", "fr")
+      {:ok, 80}
+
+  """
+  @spec distance(parsable(), parsable()) :: {:ok, non_neg_integer()} | parse_error()
+  def distance(desired, supported) do
+    Nif.locale_distance(nif_locale(desired), nif_locale(supported))
+  end
+
+  @doc """
+  Returns the region codes to offer for the language of `locale`.
+
+  The regions are those whose most likely language it is, the language's
+  own likely region, and those CLDR has locale data for in the language.
+  This suits the second step of a language → region picker. Other subtags
+  of `locale` are ignored, and the codes are sorted alphabetically.
+
+  ## Examples
+
+      iex> {:ok, regions} = Icu.LanguageTag.language_regions("es")
+      iex> Enum.all?(["AR", "ES", "MX"], &(&1 in regions))
+      true
+
+  """
+  @spec language_regions(parsable()) :: {:ok, [String.t()]} | parse_error()
+  def language_regions(locale) do
+    Nif.locale_language_regions(nif_locale(locale))
+  end
+
+  @doc """
+  Returns the region codes for the language of `locale` and raises on error.
+  """
+  @spec language_regions!(parsable()) :: [String.t()]
+  def language_regions!(locale) do
+    case language_regions(locale) do
+      {:ok, regions} ->
+        regions
+
+      {:error, reason} ->
+        raise ArgumentError, "invalid locale #{inspect(locale)}: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the script codes the language of `locale` is written in.
+
+  The scripts are the likely scripts of the language on its own and in each
+  of the regions returned by `language_regions/1`, sorted alphabetically.
+
+  ## Examples
+
+      iex> Icu.LanguageTag.language_scripts("zh")
+      {:ok, ["Hans", "Hant"]}
+
+  """
+  @spec language_scripts(parsable()) :: {:ok, [String.t()]} | parse_error()
+  def language_scripts(locale) do
+    Nif.locale_language_scripts(nif_locale(locale))
+  end
+
+  @doc """
+  Returns the script codes for the language of `locale` and raises on error.
+  """
+  @spec language_scripts!(parsable()) :: [String.t()]
+  def language_scripts!(locale) do
+    case language_scripts(locale) do
+      {:ok, scripts} ->
+        scripts
+
+      {:error, reason} ->
+        raise ArgumentError, "invalid locale #{inspect(locale)}: #{inspect(reason)}"
+    end
+  end
+
+  defp nif_locale(%__MODULE__{resource: resource}), do: resource
+  defp nif_locale(locale), do: locale
+
   @doc """
   Attempts to match the given `LanguageTag.t()` against a list of
   gettext locales.
@@ -242,10 +559,101 @@ defmodule Icu.LanguageTag do
   Uses "lookup" according to RFC4647.
 
   Accepts both `_` and `-` as separators in `gettext_locales`.
+
+  ## Options
+
+    * `:case_insensitive` – match `"pt_br"` as well as `"pt_BR"`. Defaults
+      to `false`.
+    * `:strip_script` – ignore script subtags on both sides, so that a
+      `"zh_TW"` catalog serves `"zh-Hant-TW"`. Defaults to `false`.
+    * `:output` – `:original` (the default) returns the gettext locale as
+      given, `:canonical` returns its canonical BCP-47 form, e.g. `"pt-BR"`.
+
+  ## Examples
+
+      iex> tag = Icu.LanguageTag.parse!("pt-BR")
+      iex> Icu.LanguageTag.match_gettext(tag, ["en", "pt_br"], case_insensitive: true)
+      {:ok, "pt_br"}
+      iex> Icu.LanguageTag.match_gettext(tag, ["en", "pt_BR"], output: :canonical)
+      {:ok, "pt-BR"}
+
+  """
+  @spec match_gettext(t(), [String.t()], gettext_options()) ::
+          {:ok, String.t()} | {:error, :no_match} | Options.error()
+  def match_gettext(%__MODULE__{} = tag, gettext_locales, options \\ []) do
+    case match_gettext_with_distance(tag, gettext_locales, options) do
+      {:ok, gettext_locale, _distance} -> {:ok, gettext_locale}
+      {:error, _} = error -> error
+    end
+  end
+
+  @doc """
+  Like `match_gettext/3`, but also returns the distance of the match.
+
+  The distance is the number of fallback steps from the locale to the
+  matched gettext locale: 0 for an exact match, more the further the locale
+  had to fall back. Callers can use it to warn about weak matches.
+
+  ## Examples
+
+      iex> tag = Icu.LanguageTag.parse!("de-AT")
+      iex> Icu.LanguageTag.match_gettext_with_distance(tag, ["de_AT", "de"])
+      {:ok, "de_AT", 0}
+      iex> {:ok, "de", distance} = Icu.LanguageTag.match_gettext_with_distance(tag, ["de"])
+      iex> distance > 0
+      true
+
+  """
+  @spec match_gettext_with_distance(t(), [String.t()], gettext_options()) ::
+          {:ok, String.t(), non_neg_integer()} | {:error, :no_match} | Options.error()
+  def match_gettext_with_distance(
+        %__MODULE__{resource: resource},
+        gettext_locales,
+        options \\ []
+      ) do
+    with {:ok, opts} <- Options.normalize_options(:gettext, options, &(&1 in @gettext_keys)) do
+      Nif.locale_match_gettext(resource, gettext_locales, Map.delete(opts, :locale))
+    end
+  end
+
+  @doc """
+  Returns the requested locales that the available locales can serve.
+
+  This mirrors `Intl.supportedLocalesOf`: each requested locale is kept,
+  in its canonical form, when an available locale matches it, and
+  duplicates are dropped. Extensions such as `-u-nu-latn` are kept in the
+  result but ignored while matching. Suited to content negotiation, e.g.
+  filtering an `Accept-Language` list against the locales an app ships.
+
+  The matcher is one of:
+
+    * `:lookup` – the RFC 4647 lookup of `Intl`, removing subtags from the
+      end until a locale matches, so `"de-CH"` is served by `"de"`.
+    * `:best_fit` (the default) – also follows the locale fallback chain
+      with its likely scripts and CLDR parent locales, so `"zh-TW"` is
+      served by `"zh-Hant"`.
+
+  ## Examples
+
+      iex> Icu.LanguageTag.filter_supported(["fr-CA", "de-CH", "ja"], ["en", "de"])
+      {:ok, ["de-CH"]}
+
+      iex> Icu.LanguageTag.filter_supported(["zh-TW"], ["zh-Hant"], :lookup)
+      {:ok, []}
+
+      iex> Icu.LanguageTag.filter_supported(["zh-TW"], ["zh-Hant"], :best_fit)
+      {:ok, ["zh-TW"]}
+
   """
-  @spec match_gettext(t(), [String.t()]) :: {:ok, String.t()} | {:error, :no_match}
-  def match_gettext(%__MODULE__{resource: resource}, gettext_locales) do
-    Nif.locale_match_gettext(resource, gettext_locales)
+  @spec filter_supported([parsable()], [parsable()], :lookup | :best_fit) ::
+          {:ok, [String.t()]} | parse_error() | {:error, :invalid_options}
+  def filter_supported(requested, available, matcher \\ :best_fit)
+      when is_list(requested) and is_list(available) do
+    Nif.locale_filter_supported(
+      Enum.map(requested, &nif_locale/1),
+      Enum.map(available, &nif_locale/1),
+      matcher
+    )
   end
 
   defimpl Inspect do
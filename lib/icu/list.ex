@@ -20,8 +20,18 @@ defmodule Icu.List do
   - `:type` – conjunction style to use (`:and`, `:or`, or `:unit`).
   - `:width` – textual width (`:wide`, `:short`, or `:narrow`) that trades context for brevity.
   - `:locale` – override the locale used for formatting; defaults to the application locale.
+  - `:output` – `:binary` (default) or `:iodata`. With `:iodata` the result is an iolist
+    that reuses the item binaries instead of copying them into a new string, which is
+    cheaper when the result is written straight to a socket or a Phoenix response.
+
+  ## Large inputs
+
+  Lists whose items add up to more than 64 KiB are formatted on a dirty CPU
+  scheduler so they do not hold up other processes. The threshold, in bytes,
+  can be changed with the `:icu, :list_dirty_threshold` application setting.
   """
 
+  alias Icu.Formatter.Options
   alias Icu.LanguageTag
   alias Icu.List.Formatter
 
@@ -34,12 +44,17 @@ defmodule Icu.List do
   @typedoc "Controls the stylistic width of conjunctions."
   @type width :: :wide | :short | :narrow
 
+  @typedoc "Shape of the formatted result: one binary, or an iolist of the items and literals."
+  @type output :: :binary | :iodata
+
   @typedoc "Keyword form of the supported options."
   @type options_list ::
           [
             {:type, type()}
             | {:width, width()}
             | {:locale, LanguageTag.t() | nil}
+            | {:output, output()}
+            | {:index_mode, Icu.index_mode()}
           ]
 
   @typedoc "Map form of the supported options."
@@ -47,7 +62,9 @@ defmodule Icu.List do
           %{
             optional(:type) => type(),
             optional(:width) => width(),
-            optional(:locale) => LanguageTag.t() | nil
+            optional(:locale) => LanguageTag.t() | nil,
+            optional(:output) => output(),
+            optional(:index_mode) => Icu.index_mode()
           }
 
   @type options_input :: options() | options_list() | nil
@@ -71,12 +88,17 @@ defmodule Icu.List do
 
       iex> Icu.List.format(1..3, type: :or)
       {:ok, "1, 2, or 3"}
+
+      iex> Icu.List.format(["Foo", "Bar"], output: :iodata)
+      {:ok, ["Foo", " and ", "Bar"]}
   """
   @spec format(Enumerable.t(), options_input()) ::
-          {:ok, String.t()} | {:error, format_error()}
+          {:ok, iodata()} | {:error, format_error()}
   def format(values, options \\ []) do
+    {output, options} = Options.pop_output(options)
+
     with {:ok, formatter} <- Formatter.new(options) do
-      Formatter.format(formatter, values)
+      Formatter.format(formatter, values, output: output)
     end
   end
 
@@ -88,7 +110,7 @@ defmodule Icu.List do
       iex> Icu.List.format!(["Foo", "Bar", "Baz"])
       "Foo, Bar, and Baz"
   """
-  @spec format!(Enumerable.t(), options_input()) :: String.t()
+  @spec format!(Enumerable.t(), options_input()) :: iodata()
   def format!(values, options \\ []) do
     case format(values, options) do
       {:ok, result} -> result
@@ -100,19 +122,27 @@ defmodule Icu.List do
   Formats values into discrete parts.
 
   Returns each literal and element as a tagged map so the caller can apply
-  custom rendering (for example when interleaving HTML tags).
+  custom rendering (for example when interleaving HTML tags). Pass
+  `:index_mode` to also get the `:start` and `:end` offset of each part, for
+  example in UTF-16 code units to highlight the parts in a browser.
 
   ## Examples
 
       iex> {:ok, parts} = Icu.List.format_to_parts(["A", "B", "C"])
       iex> Enum.map(parts, & &1.part_type)
       [:element, :literal, :element, :literal, :element]
+
+      iex> {:ok, parts} = Icu.List.format_to_parts(["😀", "B"], index_mode: :utf16)
+      iex> Enum.map(parts, &{&1.value, &1.start, &1.end})
+      [{"😀", 0, 2}, {" and ", 2, 7}, {"B", 7, 8}]
   """
   @spec format_to_parts(Enumerable.t(), options_input()) ::
           {:ok, [map()]} | {:error, format_error()}
   def format_to_parts(values, options \\ []) do
+    {index_mode, options} = Options.pop_index_mode(options)
+
     with {:ok, formatter} <- Formatter.new(options) do
-      Formatter.format_to_parts(formatter, values)
+      Formatter.format_to_parts(formatter, values, index_mode: index_mode)
     end
   end
 
@@ -3,19 +3,26 @@ defmodule Icu.List.Formatter do
 
   alias Icu.List
   alias Icu.Nif
+  alias Icu.Formatter.Config
   alias Icu.Formatter.Options
 
-  defstruct [:resource]
+  defstruct [:resource, :config]
+
+  @default_dirty_threshold 65_536
 
   @opaque t :: %__MODULE__{}
 
+  @option_keys [:type, :width, :locale]
+
   @spec new(List.options_input()) ::
           {:ok, t()} | {:error, List.format_error()}
   def new(options \\ []) do
     with {:ok, opts} <- normalize_options(options),
          {:ok, resource} <-
            Nif.list_formatter_new(Map.fetch!(opts, :locale), Map.delete(opts, :locale)) do
-      {:ok, %__MODULE__{resource: resource}}
+      warnings = Options.warnings(:list, options, opts, &(&1 in @option_keys))
+      config = Config.new(__MODULE__, :new, [Config.put_locale(options, opts.locale)], warnings)
+      {:ok, %__MODULE__{resource: resource, config: config}}
     end
   end
 
@@ -27,14 +34,23 @@ defmodule Icu.List.Formatter do
     end
   end
 
-  @spec format(t(), Enumerable.t()) :: {:ok, String.t()} | {:error, List.format_error()}
-  def format(%__MODULE__{resource: resource}, values) do
-    with {:ok, items} <- collect_items(values) do
-      Nif.list_format(resource, items)
+  @spec format(t(), Enumerable.t(), [{:output, List.output()}]) ::
+          {:ok, iodata()} | {:error, List.format_error()}
+  def format(formatter, values, opts \\ [])
+
+  def format(%__MODULE__{resource: resource}, values, opts) do
+    with {:ok, output} <- fetch_output(opts),
+         {:ok, items} <- collect_items(values) do
+      case {output, dirty?(items)} do
+        {:binary, false} -> Nif.list_format(resource, items)
+        {:binary, true} -> Nif.list_format_dirty(resource, items)
+        {:iodata, false} -> Nif.list_format_iodata(resource, items)
+        {:iodata, true} -> Nif.list_format_iodata_dirty(resource, items)
+      end
     end
   end
 
-  def format(%__MODULE__{}, _other), do: {:error, :invalid_items}
+  def format(%__MODULE__{}, _other, _opts), do: {:error, :invalid_items}
 
   @spec format!(t(), Enumerable.t()) :: String.t()
   def format!(%__MODULE__{} = formatter, values) do
@@ -44,15 +60,22 @@ defmodule Icu.List.Formatter do
     end
   end
 
-  @spec format_to_parts(t(), Enumerable.t()) ::
+  @spec format_to_parts(t(), Enumerable.t(), Options.options_input()) ::
           {:ok, [map()]} | {:error, List.format_error()}
-  def format_to_parts(%__MODULE__{resource: resource}, values) do
-    with {:ok, items} <- collect_items(values) do
-      Nif.list_format_to_parts(resource, items)
+  def format_to_parts(formatter, values, options \\ [])
+
+  def format_to_parts(%__MODULE__{resource: resource}, values, options) do
+    with {:ok, index_mode} <- Options.index_mode(options),
+         {:ok, items} <- collect_items(values) do
+      if dirty?(items) do
+        Nif.list_format_to_parts_dirty(resource, items, index_mode)
+      else
+        Nif.list_format_to_parts(resource, items, index_mode)
+      end
     end
   end
 
-  def format_to_parts(%__MODULE__{}, _other), do: {:error, :invalid_items}
+  def format_to_parts(%__MODULE__{}, _other, _options), do: {:error, :invalid_items}
 
   @spec format_to_parts!(t(), Enumerable.t()) :: [map()]
   def format_to_parts!(%__MODULE__{} = formatter, values) do
@@ -71,15 +94,22 @@ defmodule Icu.List.Formatter do
   def normalize_options(nil), do: %{type: :and, width: :wide}
 
   def normalize_options(options) when is_list(options) or is_map(options) do
-    Options.normalize_options(
-      :list,
-      options,
-      &(&1 in [
-          :type,
-          :width,
-          :locale
-        ])
-    )
+    Options.normalize_options(:list, options, &(&1 in @option_keys))
+  end
+
+  defp fetch_output(opts) do
+    case Keyword.get(opts, :output, :binary) do
+      output when output in [:binary, :iodata] -> {:ok, output}
+      _other -> {:error, :invalid_options}
+    end
+  end
+
+  # Formatting runs in one NIF call, so inputs above the configured size (in
+  # bytes, `:icu, :list_dirty_threshold`) go to a dirty CPU scheduler instead
+  # of blocking a normal one.
+  defp dirty?(items) do
+    threshold = Application.get_env(:icu, :list_dirty_threshold, @default_dirty_threshold)
+    Enum.reduce(items, 0, &(byte_size(&1) + &2)) > threshold
   end
 
   defp collect_items(values) when is_list(values), do: normalize_items(values)
@@ -44,9 +44,36 @@ defmodule Icu.Nif do
   def locale_minimize(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def locale_minimize_favor_script(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def locale_fallbacks(_resource), do: :erlang.nif_error(:nif_not_loaded)
-  def locale_match_gettext(_resource, _available), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_parents(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_compare(_left, _right), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_hash(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_distance(_desired, _supported), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_language_regions(_locale), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_language_scripts(_locale), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_match_gettext(_resource, _available, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def locale_filter_supported(_requested, _available, _matcher),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def locale_set_hour_cycle(_resource, _hour_cycle), do: :erlang.nif_error(:nif_not_loaded)
   def locale_get_hour_cycle(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_set_extension(_resource, _key, _value), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_validate_extension_value(_key, _value), do: :erlang.nif_error(:nif_not_loaded)
+  def locale_hour_cycle(_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Constructors taking a `_locale_resource` also accept a BCP-47 string,
+  # which is parsed inline.
+
+  # Formatters
+  def formatter_data_locale(_formatter_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def formatter_memory(_formatter_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def formatter_warmup(_locales, _kinds), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Stats
+  def icu_stats_enable(_enabled), do: :erlang.nif_error(:nif_not_loaded)
+  def icu_stats, do: :erlang.nif_error(:nif_not_loaded)
+  def icu_stats_reset, do: :erlang.nif_error(:nif_not_loaded)
 
   # Numbers
   def number_formatter_new(_locale_resource, _options),
@@ -54,33 +81,99 @@ defmodule Icu.Nif do
 
   def number_format(_formatter_resource, _number), do: :erlang.nif_error(:nif_not_loaded)
 
-  def number_format_to_parts(_formatter_resource, _number),
+  def number_format_to_parts(_formatter_resource, _number, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_format_range(_formatter_resource, _start, _end),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_format_range_to_parts(_formatter_resource, _start, _end, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_ordinal_format(_locale_resource, _number), do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_spellout_format(_locale_resource, _number, _ruleset),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_digits_to_latin(_string, _numbering_system),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_digits_from_latin(_string, _numbering_system),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_symbols(_locale_resource, _numbering_system),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_numbering_systems, do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_grouping_sizes(_locale_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  def number_percent_format(_locale_resource, _numerator, _denominator, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
   # Lists
   def list_formatter_new(_locale_resource, _options), do: :erlang.nif_error(:nif_not_loaded)
   def list_format(_formatter_resource, _items), do: :erlang.nif_error(:nif_not_loaded)
 
-  def list_format_to_parts(_formatter_resource, _items),
+  def list_format_to_parts(_formatter_resource, _items, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  # Dirty CPU variants for large inputs.
+  def list_format_dirty(_formatter_resource, _items), do: :erlang.nif_error(:nif_not_loaded)
+
+  def list_format_to_parts_dirty(_formatter_resource, _items, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def list_format_iodata(_formatter_resource, _items), do: :erlang.nif_error(:nif_not_loaded)
+
+  def list_format_iodata_dirty(_formatter_resource, _items),
     do: :erlang.nif_error(:nif_not_loaded)
 
   # Display names
   def display_names_formatter_new(_locale_resource, _kind, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def display_names_of(_formatter_resource, _value),
+  def display_names_of(_formatter_resource, _value, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def display_names_of_styled(_locale_resource, _kind, _value, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def display_names_region_flag(_region), do: :erlang.nif_error(:nif_not_loaded)
+
   # Temporals
   def temporal_formatter_new(_locale_resource, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def temporal_date_formatter_new(_locale_resource, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def temporal_time_formatter_new(_locale_resource, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def temporal_zoned_datetime_formatter_new(_locale_resource, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def temporal_formatter_from_pattern(_locale_resource, _pattern),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def temporal_formatter_pattern(_formatter_resource, _sample),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def temporal_day_periods(_locale_resource, _width), do: :erlang.nif_error(:nif_not_loaded)
+
+  def temporal_relative_day_name(_locale_resource, _offset, _width),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def temporal_format(_formatter_resource, _datetime_map),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def temporal_format_to_parts(_formatter_resource, _datetime_map),
+  def temporal_format_to_parts(_formatter_resource, _datetime_map, _index_mode),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def temporal_format_rfc(_datetime_map, _format), do: :erlang.nif_error(:nif_not_loaded)
+  def temporal_validate(_datetime_map, _calendar), do: :erlang.nif_error(:nif_not_loaded)
+
   def time_zone_from_string(_identifier), do: :erlang.nif_error(:nif_not_loaded)
   def time_zone_from_offset(_offset_minutes), do: :erlang.nif_error(:nif_not_loaded)
 
@@ -93,12 +186,145 @@ defmodule Icu.Nif do
   def relative_time_format_to_parts(_formatter_resource, _value, _unit),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  # Calendars
+  def calendar_date_to_rata_die(_date, _calendar), do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_date_from_rata_die(_rata_die, _calendar),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_date_add(_date, _duration, _calendar), do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_date_diff(_date, _other, _unit, _calendar),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_japanese_eras(_locale), do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_japanese_era_for_date(_date, _locale), do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_weekend(_locale), do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_year_info(_year, _calendar), do: :erlang.nif_error(:nif_not_loaded)
+
+  def calendar_months(_year, _calendar, _locale), do: :erlang.nif_error(:nif_not_loaded)
+
   # Currency
   def currency_fractions(_currency), do: :erlang.nif_error(:nif_not_loaded)
+  def currency_region_currencies(_region, _historical), do: :erlang.nif_error(:nif_not_loaded)
 
   def currency_formatter_new(_locale_resource, _currency_code, _options),
     do: :erlang.nif_error(:nif_not_loaded)
 
   def currency_format(_formatter_resource, _number),
     do: :erlang.nif_error(:nif_not_loaded)
+
+  def currency_format_to_parts(_formatter_resource, _number, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def currency_format_range(_formatter_resource, _start, _end),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def currency_format_range_to_parts(_formatter_resource, _start, _end, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  # Units
+  def unit_formatter_new(_locale_resource, _unit, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_format(_formatter_resource, _number), do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_format_to_parts(_formatter_resource, _number, _index_mode),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_resolved_options(_formatter_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_format_range(_formatter_resource, _start, _end),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_convert(_value, _from, _to), do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_convert_for_locale(_value, _from, _locale_resource),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_preferred_units(_locale_resource, _usage, _value, _unit, _fraction_digits),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_format_mixed(_locale_resource, _measures, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def unit_patterns(_locale_resource, _unit, _options), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Properties
+  def properties_is_emoji(_string), do: :erlang.nif_error(:nif_not_loaded)
+
+  def properties_contains_emoji(_string), do: :erlang.nif_error(:nif_not_loaded)
+
+  def properties_emoji_segments(_string), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Normalizer
+  def normalizer_nfc_concat(_left, _right), do: :erlang.nif_error(:nif_not_loaded)
+
+  def normalizer_canonical_equal(_left, _right), do: :erlang.nif_error(:nif_not_loaded)
+
+  def normalizer_normalized_up_to(_text, _form), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Grammar
+  def grammar_info(_locale_resource, _thing), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Spoof
+  def spoof_skeleton(_string), do: :erlang.nif_error(:nif_not_loaded)
+
+  def spoof_confusable(_left, _right), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Collator
+  def collator_new(_locale_resource, _options), do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_compare(_collator_resource, _left, _right),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_find(_collator_resource, _haystack, _needle),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_equal(_collator_resource, _left, _right), do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_equal_ignoring(_locale_resource, _left, _right, _ignoring),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_resolved_options(_collator_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_sort(_collator_resource, _strings), do: :erlang.nif_error(:nif_not_loaded)
+
+  def collator_unique(_collator_resource, _strings), do: :erlang.nif_error(:nif_not_loaded)
+
+
+  # Decimal
+  def decimal_new(_number), do: :erlang.nif_error(:nif_not_loaded)
+
+  def decimal_round(_decimal_resource, _position, _mode), do: :erlang.nif_error(:nif_not_loaded)
+
+  def decimal_to_string(_decimal_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Text
+  def text_truncate(_text, _max, _options), do: :erlang.nif_error(:nif_not_loaded)
+
+  def text_display_width(_text, _options), do: :erlang.nif_error(:nif_not_loaded)
+
+  def text_titlecase_words(_text, _locale_resource, _options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def text_word_stats(_text, _top), do: :erlang.nif_error(:nif_not_loaded)
+
+  def text_insert_soft_breaks(_text, _locale_resource, _marker),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def common_terms(_locale_resource, _term), do: :erlang.nif_error(:nif_not_loaded)
+
+  def text_quote(_text, _locale_resource, _level), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Segmenter
+  def segmenter_new(_kind, _text), do: :erlang.nif_error(:nif_not_loaded)
+
+  def segments_next(_iterator_resource, _count), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Snapshot
+  def icu_snapshot(_kind, _locales, _options, _samples), do: :erlang.nif_error(:nif_not_loaded)
 end
@@ -0,0 +1,90 @@
+defmodule Icu.Normalizer do
+  @moduledoc """
+  Unicode normalization for joining and comparing strings.
+
+  The same text can be encoded in more than one way: `"é"` is either the
+  single code point U+00E9 or `"e"` followed by the combining acute accent
+  U+0301. Such strings are canonically equivalent and render the same, but
+  compare unequal byte for byte. These functions work on their canonical
+  composition (NFC).
+
+  ## Examples
+
+      iex> Icu.Normalizer.nfc_concat("cafe", "\\u0301")
+      "café"
+
+      iex> Icu.Normalizer.canonical_equal?("café", "cafe\\u0301")
+      true
+  """
+
+  alias Icu.Nif
+
+  @typedoc """
+  A Unicode normalization form: canonical composition (`:nfc`) or
+  decomposition (`:nfd`), or their compatibility variants, which also fold
+  characters such as `"ﬁ"` to `"fi"`.
+  """
+  @type form :: :nfc | :nfd | :nfkc | :nfkd
+
+  @forms [:nfc, :nfd, :nfkc, :nfkd]
+
+  @doc """
+  Joins `left` and `right` and returns the result in NFC.
+
+  Characters at the end of `left` compose with combining marks at the start
+  of `right`, which `NFC(left) <> NFC(right)` would leave apart. Neither
+  input needs to be normalized.
+
+  ## Examples
+
+      iex> Icu.Normalizer.nfc_concat("\\u1100", "\\u1161")
+      "가"
+
+      iex> Icu.Normalizer.nfc_concat("a", "b")
+      "ab"
+  """
+  @spec nfc_concat(String.t(), String.t()) :: String.t()
+  def nfc_concat(left, right) when is_binary(left) and is_binary(right),
+    do: Nif.normalizer_nfc_concat(left, right)
+
+  @doc """
+  Returns `true` when `left` and `right` are canonically equivalent, that
+  is when their NFC forms are equal.
+
+  Inputs already in NFC are compared without being copied.
+  """
+  @spec canonical_equal?(String.t(), String.t()) :: boolean()
+  def canonical_equal?(left, right) when is_binary(left) and is_binary(right),
+    do: Nif.normalizer_canonical_equal(left, right)
+
+  @doc """
+  Returns the length in bytes of the longest prefix of `text` that is known
+  to be in `form`.
+
+  This is a quick check that does not normalize anything. A result equal to
+  `byte_size(text)` means the whole text is normalized, so an ingestion
+  pipeline can skip normalizing it. Otherwise only the rest needs to be
+  normalized: appending its normalization to the prefix gives the
+  normalization of the whole text. The prefix may stop a little before the
+  first character that actually changes, at the start of the sequence it
+  could combine with.
+
+  ## Examples
+
+      iex> Icu.Normalizer.normalized_up_to("café", :nfc)
+      5
+
+      iex> Icu.Normalizer.normalized_up_to("ﬁle", :nfc)
+      5
+
+      iex> Icu.Normalizer.normalized_up_to("ﬁle", :nfkc)
+      0
+  """
+  @spec normalized_up_to(String.t(), form()) :: non_neg_integer()
+  def normalized_up_to(text, form) when is_binary(text) and form in @forms do
+    case Nif.normalizer_normalized_up_to(text, form) do
+      {:ok, length} -> length
+      {:error, reason} -> raise ArgumentError, "normalization check failed: #{inspect(reason)}"
+    end
+  end
+end
@@ -20,12 +20,43 @@ defmodule Icu.Number do
   - `:grouping` – toggle locale-driven grouping rules (`:auto`, `:always`, `:min2`, `:never`).
   - `:sign_display` – control sign rendering (`:auto`, `:always`, `:never`, `:except_zero`, `:negative`).
   - `:minimum_integer_digits` – left-pad with zeros to hit a minimum integer width.
+  - `:maximum_integer_digits` – drop the integer digits above this width, so
+    `12_345` with a maximum of 3 keeps `345`. The digits are truncated, not
+    rounded, which suits fixed-width counters.
   - `:minimum_fraction_digits` – right-pad with zeros to ensure fractional precision.
   - `:maximum_fraction_digits` – clamp or round fractional precision.
+  - `:float_precision` – how float input is converted before any rounding.
+    `:round_trip` (the default) keeps the shortest digits that read back as
+    the same float, so `0.1 + 0.2` becomes `0.30000000000000004`.
+    `{:magnitude, n}` rounds the float to the power of ten `n` first, like
+    ECMA-402 does (`{:magnitude, -2}` keeps hundredths). `:integer` accepts
+    only integral floats and returns `{:error, :invalid_number}` otherwise.
+  - `:scale` – a power of ten to multiply the number by before rounding, such
+    as `100` for percentages stored as fractions or `10_000` for basis points.
+    The digits are shifted exactly, so `0.0125` with a scale of `10_000` is
+    `125` without float error. `:float_precision` applies to the number as
+    given, before scaling.
+  - `:absolute_value` – when `true`, format the number without its sign, for
+    interfaces that render the sign as an element of its own. Parts output
+    still marks a negative number with a leading `:minus_sign` part whose
+    value is empty, so the sign can be drawn from the parts alone.
+    `:sign_display` then applies to the absolute value.
+  - `:approximately` – when `true`, prefix the output with the approximately
+    sign of the locale to mark an estimate: `"~42"` in English, `"≈42"` in
+    German, taken from the CLDR data of the locale. Parts output starts with
+    an `:approximately_sign` part.
+  - `:preferences` – locale preferences that replace the `-u-` keywords of
+    the locale, see `t:Icu.preferences/0`. `numbering_system: "arab"` formats
+    with Arabic-Indic digits whatever the locale says.
   - `:locale` – override the locale for this invocation.
+  - `:output` – `:binary` (default) or `:iodata`, as for `Icu.List.format/2`. The
+    formatted number is a single short binary, which is already iodata, so both
+    return the same binary.
   """
 
+  alias Icu.Formatter.Options
   alias Icu.LanguageTag
+  alias Icu.Nif
   alias Icu.Number.Formatter
 
   @typedoc "Opaque reference to an ICU4X number formatter."
@@ -37,15 +68,26 @@ defmodule Icu.Number do
   @typedoc "Controls how positive/negative signs are displayed."
   @type sign_display :: :auto | :always | :never | :except_zero | :negative
 
+  @typedoc "Controls how floats are converted to decimals."
+  @type float_precision :: :round_trip | :integer | {:magnitude, integer()}
+
   @typedoc "Keyword form of the supported options."
   @type options_list ::
           [
             {:grouping, grouping()}
             | {:sign_display, sign_display()}
             | {:minimum_integer_digits, pos_integer()}
+            | {:maximum_integer_digits, pos_integer()}
             | {:minimum_fraction_digits, non_neg_integer()}
             | {:maximum_fraction_digits, non_neg_integer() | nil}
+            | {:float_precision, float_precision()}
+            | {:scale, pos_integer()}
+            | {:absolute_value, boolean()}
+            | {:approximately, boolean()}
+            | {:preferences, Icu.preferences()}
             | {:locale, LanguageTag.t() | String.t() | nil}
+            | {:index_mode, Icu.index_mode()}
+            | {:output, Icu.List.output()}
           ]
 
   @typedoc "Map form of the supported options."
@@ -54,15 +96,33 @@ defmodule Icu.Number do
             optional(:grouping) => grouping(),
             optional(:sign_display) => sign_display(),
             optional(:minimum_integer_digits) => pos_integer(),
+            optional(:maximum_integer_digits) => pos_integer(),
             optional(:minimum_fraction_digits) => non_neg_integer(),
             optional(:maximum_fraction_digits) => non_neg_integer() | nil,
-            optional(:locale) => LanguageTag.t() | String.t() | nil
+            optional(:float_precision) => float_precision(),
+            optional(:scale) => pos_integer(),
+            optional(:absolute_value) => boolean(),
+            optional(:approximately) => boolean(),
+            optional(:preferences) => Icu.preferences(),
+            optional(:locale) => LanguageTag.t() | String.t() | nil,
+            optional(:index_mode) => Icu.index_mode(),
+            optional(:output) => Icu.List.output()
           }
 
   @type options_input :: options() | options_list() | nil
 
+  @typedoc """
+  A number to format: an integer, a float, a `Decimal` or a decimal string
+  such as `"-1234.5"` or `"1.5E3"`.
+  """
+  @type number_input :: number() | Decimal.t() | String.t()
+
   @type format_error ::
-          :invalid_formatter | :invalid_number | :invalid_locale | :invalid_options
+          :invalid_formatter
+          | :invalid_number
+          | :invalid_locale
+          | :invalid_options
+          | {:invalid_option_value, :output}
 
   @doc """
   Formats a number.
@@ -71,6 +131,14 @@ defmodule Icu.Number do
   implements the required protocol). Returns `{:ok, String.t()}` or an error tuple
   when the input or options are invalid.
 
+  Decimal strings are parsed exactly, so every digit survives without a
+  detour through a float:
+
+      iex> Icu.Number.format("0.1000000000000000055511151231257827",
+      ...>   maximum_fraction_digits: 34
+      ...> )
+      {:ok, "0.1000000000000000055511151231257827"}
+
   ## Examples
 
       iex> Icu.Number.format(-123.45)
@@ -79,10 +147,13 @@ defmodule Icu.Number do
       iex> Icu.Number.format(42, sign_display: :always)
       {:ok, "+42.000"}
   """
-  @spec format(number(), options_input()) ::
+  @spec format(number_input(), options_input()) ::
           {:ok, String.t()} | {:error, format_error()}
   def format(number, options \\ []) do
-    with {:ok, formatter} <- Formatter.new(options),
+    {output, options} = Options.pop_output(options)
+
+    with :ok <- Options.output(output),
+         {:ok, formatter} <- Formatter.new(options),
          {:ok, formatted} <- Formatter.format(formatter, number) do
       {:ok, formatted}
     end
@@ -96,7 +167,7 @@ defmodule Icu.Number do
       iex> Icu.Number.format!(42, sign_display: :always)
       "+42.000"
   """
-  @spec format!(number(), options_input()) :: String.t()
+  @spec format!(number_input(), options_input()) :: String.t()
   def format!(number, options \\ []) do
     case format(number, options) do
       {:ok, formatted} -> formatted
@@ -110,17 +181,26 @@ defmodule Icu.Number do
   Returns tagged pieces (integer, decimal separator, fraction, etc.) so callers
   can add markup around specific components.
 
+  With `:index_mode` (see `t:Icu.index_mode/0`), each part also carries its
+  `:start` and `:end` offsets in the formatted string.
+
   ## Examples
 
       iex> {:ok, parts} = Icu.Number.format_to_parts(123.5)
       iex> Enum.map(parts, & &1.part_type)
       [:integer, :decimal, :fraction]
+
+      iex> {:ok, parts} = Icu.Number.format_to_parts(1234.5, index_mode: :byte)
+      iex> Enum.map(parts, &{&1.part_type, &1.start, &1.end})
+      [{:integer, 0, 1}, {:group, 1, 2}, {:integer, 2, 5}, {:decimal, 5, 6}, {:fraction, 6, 9}]
   """
-  @spec format_to_parts(number(), options_input()) ::
+  @spec format_to_parts(number_input(), options_input()) ::
           {:ok, [map()]} | {:error, format_error()}
   def format_to_parts(number, options \\ []) do
+    {index_mode, options} = Options.pop_index_mode(options)
+
     with {:ok, formatter} <- Formatter.new(options),
-         {:ok, parts} <- Formatter.format_to_parts(formatter, number) do
+         {:ok, parts} <- Formatter.format_to_parts(formatter, number, index_mode: index_mode) do
       {:ok, parts}
     end
   end
@@ -134,11 +214,457 @@ defmodule Icu.Number do
       iex> Enum.count(parts)
       3
   """
-  @spec format_to_parts!(number(), options_input()) :: [map()]
+  @spec format_to_parts!(number_input(), options_input()) :: [map()]
   def format_to_parts!(number, options \\ []) do
     case format_to_parts(number, options) do
       {:ok, parts} -> parts
       {:error, reason} -> raise "number format to parts failed: #{inspect(reason)}"
     end
   end
+
+  @doc """
+  Formats the range between two numbers, e.g. "3–5".
+
+  Both ends are formatted with the same options and joined with the range
+  separator of the locale (an en dash in most languages, a hyphen in Spanish
+  or Chinese). Ends that format identically collapse into a single number.
+
+  ICU4X has no range formatter yet, so the separators are bundled with the
+  library rather than loaded from CLDR data.
+
+  ## Examples
+
+      iex> Icu.Number.format_range(3, 5, maximum_fraction_digits: 0)
+      {:ok, "3–5"}
+
+      iex> Icu.Number.format_range(1000, 2500, locale: "es", maximum_fraction_digits: 0)
+      {:ok, "1000-2500"}
+  """
+  @spec format_range(number_input(), number_input(), options_input()) ::
+          {:ok, String.t()} | {:error, format_error()}
+  def format_range(start, finish, options \\ []) do
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_range(formatter, start, finish)
+    end
+  end
+
+  @doc """
+  Formats the range between two numbers and raises on error.
+
+  ## Examples
+
+      iex> Icu.Number.format_range!(3, 5, maximum_fraction_digits: 0)
+      "3–5"
+  """
+  @spec format_range!(number_input(), number_input(), options_input()) :: String.t()
+  def format_range!(start, finish, options \\ []) do
+    case format_range(start, finish, options) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "number range formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats the range between two numbers to parts.
+
+  Each part carries a `:source` of `:start` or `:end` for the end it was
+  formatted from, or `:shared` for the separator and for the single number
+  left when both ends are equal.
+
+  ## Examples
+
+      iex> {:ok, parts} = Icu.Number.format_range_to_parts(3, 5, maximum_fraction_digits: 0)
+      iex> Enum.map(parts, &{&1.part_type, &1.source})
+      [{:integer, :start}, {:literal, :shared}, {:integer, :end}]
+  """
+  @spec format_range_to_parts(number_input(), number_input(), options_input()) ::
+          {:ok, [map()]} | {:error, format_error()}
+  def format_range_to_parts(start, finish, options \\ []) do
+    {index_mode, options} = Options.pop_index_mode(options)
+
+    with {:ok, formatter} <- Formatter.new(options) do
+      Formatter.format_range_to_parts(formatter, start, finish, index_mode: index_mode)
+    end
+  end
+
+  @doc """
+  Formats the range between two numbers to parts and raises on error.
+  """
+  @spec format_range_to_parts!(number_input(), number_input(), options_input()) :: [map()]
+  def format_range_to_parts!(start, finish, options \\ []) do
+    case format_range_to_parts(start, finish, options) do
+      {:ok, parts} -> parts
+      {:error, reason} -> raise "number range format to parts failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats an integral number as an ordinal ("1st", "2.", "1er").
+
+  Accepts the same inputs as `format/2`, such as big integers and `Decimal`
+  structs, as long as their value has no fraction.
+
+  The locale's ordinal plural rules select the CLDR ordinal suffix. ICU4X does
+  not provide rule-based number formatting, so only languages with a known
+  suffix pattern are supported; others return `{:error, :unsupported_locale}`.
+
+  ## Options
+
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> Icu.Number.format_ordinal(22)
+      {:ok, "22nd"}
+
+      iex> Icu.Number.format_ordinal(2, locale: "de")
+      {:ok, "2."}
+
+      iex> Icu.Number.format_ordinal(1, locale: "fr")
+      {:ok, "1er"}
+  """
+  @spec format_ordinal(number_input(), [{:locale, LanguageTag.t() | String.t() | nil}]) ::
+          {:ok, String.t()} | {:error, format_error() | :unsupported_locale | term()}
+  def format_ordinal(number, options \\ []) do
+    with {:ok, %{locale: locale}} <-
+           Options.normalize_options(:number, options, &(&1 == :locale)) do
+      Nif.number_ordinal_format(locale, number)
+    end
+  end
+
+  @doc """
+  Formats an integral number as an ordinal and raises on error.
+
+  ## Examples
+
+      iex> Icu.Number.format_ordinal!(13)
+      "13th"
+  """
+  @spec format_ordinal!(number_input(), [{:locale, LanguageTag.t() | String.t() | nil}]) ::
+          String.t()
+  def format_ordinal!(number, options \\ []) do
+    case format_ordinal(number, options) do
+      {:ok, formatted} -> formatted
+      {:error, reason} -> raise "ordinal formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Formats the ratio `numerator / denominator` as a percentage.
+
+  Computes, rounds and formats the percentage in one call, using the
+  locale's percent pattern.
+
+  ## Options
+
+  - `:significant_digits` – round the percentage to this many significant
+    digits.
+  - `:maximum_fraction_digits` – round the percentage to this many fraction
+    digits. Defaults to 0 unless `:significant_digits` is given.
+  - `:minimum_fraction_digits` – keep at least this many fraction digits.
+  - `:locale` – override the locale for this invocation.
+
+  A `denominator` of zero returns `{:error, :invalid_number}`.
+
+  ## Examples
+
+      iex> Icu.Number.format_percent(1, 3)
+      {:ok, "33%"}
+
+      iex> Icu.Number.format_percent(1, 3, maximum_fraction_digits: 1)
+      {:ok, "33.3%"}
+
+      iex> Icu.Number.format_percent(1234, 1000, significant_digits: 2)
+      {:ok, "120%"}
+  """
+  @spec format_percent(number(), number(), [
+          {:significant_digits, pos_integer()}
+          | {:minimum_fraction_digits, non_neg_integer()}
+          | {:maximum_fraction_digits, non_neg_integer()}
+          | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: {:ok, String.t()} | {:error, format_error() | term()}
+  def format_percent(numerator, denominator, options \\ [])
+
+  def format_percent(numerator, denominator, options)
+      when is_number(numerator) and is_number(denominator) do
+    with {:ok, opts} <-
+           Options.normalize_options(
+             :number,
+             options,
+             &(&1 in [
+                 :locale,
+                 :significant_digits,
+                 :minimum_fraction_digits,
+                 :maximum_fraction_digits
+               ])
+           ) do
+      {locale, rest} = Map.pop(opts, :locale)
+      Nif.number_percent_format(locale, numerator, denominator, rest)
+    end
+  end
+
+  def format_percent(_numerator, _denominator, _options), do: {:error, :invalid_number}
+
+  @doc """
+  Formats a ratio as a percentage and raises on error.
+
+  ## Examples
+
+      iex> Icu.Number.format_percent!(1, 8, maximum_fraction_digits: 2)
+      "12.5%"
+  """
+  @spec format_percent!(number(), number(), [
+          {:significant_digits, pos_integer()}
+          | {:minimum_fraction_digits, non_neg_integer()}
+          | {:maximum_fraction_digits, non_neg_integer()}
+          | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: String.t()
+  def format_percent!(numerator, denominator, options \\ []) do
+    case format_percent(numerator, denominator, options) do
+      {:ok, formatted} -> formatted
+      {:error, reason} -> raise "percent formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Spells out an integral number in words ("forty-two", "zweiundvierzig").
+
+  Accepts the same inputs as `format/2`, such as big integers and `Decimal`
+  structs, as long as their value has no fraction. Numbers of a sextillion
+  (10^21) and more are written in digits, as the CLDR rules do past their
+  largest scale.
+
+  ICU4X does not implement rule-based number formatting, so the CLDR
+  `spellout-cardinal` rules are bundled for English and German only. Other
+  languages return `{:error, :unsupported_locale}`.
+
+  ## Options
+
+  - `:ruleset` – `"spellout-cardinal"` (default) or `"spellout-numbering"`.
+    CLDR defines the numbering rules of both languages as their cardinal
+    rules for integers, so both give the same words.
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> Icu.Number.format_spellout(42)
+      {:ok, "forty-two"}
+
+      iex> Icu.Number.format_spellout(42, locale: "de")
+      {:ok, "zweiundvierzig"}
+  """
+  @spec format_spellout(number_input(), [
+          {:ruleset, String.t()} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: {:ok, String.t()} | {:error, format_error() | :unsupported_locale | term()}
+  def format_spellout(number, options \\ []) do
+    with {:ok, opts} <-
+           Options.normalize_options(:number, options, &(&1 in [:locale, :ruleset])) do
+      ruleset = Map.get(opts, :ruleset, "spellout-cardinal")
+      Nif.number_spellout_format(opts.locale, number, ruleset)
+    end
+  end
+
+  @doc """
+  Spells out an integral number in words and raises on error.
+
+  ## Examples
+
+      iex> Icu.Number.format_spellout!(1_000_001)
+      "one million one"
+  """
+  @spec format_spellout!(number_input(), [
+          {:ruleset, String.t()} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: String.t()
+  def format_spellout!(number, options \\ []) do
+    case format_spellout(number, options) do
+      {:ok, formatted} -> formatted
+      {:error, reason} -> raise "spellout formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @typedoc "CLDR numbering system identifier, e.g. `\"arab\"` or `\"deva\"`."
+  @type numbering_system :: String.t()
+
+  @doc """
+  Replaces native digits in `string` with ASCII digits.
+
+  Without a numbering system, every Unicode decimal digit is converted, which
+  is useful to normalise user input before parsing. With a numbering system,
+  only that system's digits are converted.
+
+  ## Examples
+
+      iex> Icu.Number.digits_to_latin("١٢٣٫٥")
+      {:ok, "123٫5"}
+
+      iex> Icu.Number.digits_to_latin("४२ and ٤٢", "deva")
+      {:ok, "42 and ٤٢"}
+  """
+  @spec digits_to_latin(String.t(), numbering_system() | nil) ::
+          {:ok, String.t()} | {:error, :invalid_numbering_system}
+  def digits_to_latin(string, numbering_system \\ nil)
+      when is_binary(string) and (is_binary(numbering_system) or is_nil(numbering_system)) do
+    Nif.number_digits_to_latin(string, numbering_system)
+  end
+
+  @doc """
+  Replaces ASCII digits in `string` with the digits of `numbering_system`.
+
+  ## Examples
+
+      iex> Icu.Number.digits_from_latin("2024-05", "arab")
+      {:ok, "٢٠٢٤-٠٥"}
+
+      iex> Icu.Number.digits_from_latin("42", "klingon")
+      {:error, :invalid_numbering_system}
+  """
+  @spec digits_from_latin(String.t(), numbering_system()) ::
+          {:ok, String.t()} | {:error, :invalid_numbering_system}
+  def digits_from_latin(string, numbering_system)
+      when is_binary(string) and is_binary(numbering_system) do
+    Nif.number_digits_from_latin(string, numbering_system)
+  end
+
+  @typedoc """
+  Number symbols of a locale, as returned by `symbols/1`.
+
+  `:digits` lists the digits zero through nine.
+  """
+  @type symbols :: %{
+          decimal: String.t(),
+          group: String.t(),
+          plus_sign: String.t(),
+          minus_sign: String.t(),
+          percent: String.t(),
+          digits: [String.t()]
+        }
+
+  @doc """
+  Returns the symbols the locale uses when formatting numbers.
+
+  The CLDR `nan` and `infinity` symbols are not available from ICU4X and are
+  therefore not included.
+
+  ## Options
+
+  - `:numbering_system` – use the symbols of another numbering system, e.g.
+    `"arab"`. Defaults to the locale's own (or its `-u-nu-` keyword).
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> {:ok, symbols} = Icu.Number.symbols(locale: "de")
+      iex> {symbols.decimal, symbols.group}
+      {",", "."}
+
+      iex> {:ok, symbols} = Icu.Number.symbols(locale: "ar", numbering_system: "arab")
+      iex> Enum.take(symbols.digits, 3)
+      ["٠", "١", "٢"]
+  """
+  @spec symbols([
+          {:numbering_system, numbering_system()} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: {:ok, symbols()} | {:error, :invalid_numbering_system | term()}
+  def symbols(options \\ []) do
+    with {:ok, opts} <-
+           Options.normalize_options(:number, options, &(&1 in [:locale, :numbering_system])) do
+      Nif.number_symbols(opts.locale, Map.get(opts, :numbering_system))
+    end
+  end
+
+  @doc """
+  Returns the locale's number symbols and raises on error.
+
+  ## Examples
+
+      iex> Icu.Number.symbols!().decimal
+      "."
+  """
+  @spec symbols!([
+          {:numbering_system, numbering_system()} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: symbols()
+  def symbols!(options \\ []) do
+    case symbols(options) do
+      {:ok, symbols} -> symbols
+      {:error, reason} -> raise "number symbols lookup failed: #{inspect(reason)}"
+    end
+  end
+
+  @typedoc """
+  A numbering system, as returned by `numbering_systems/0`.
+
+  `:digits` lists the digits zero through nine.
+  """
+  @type numbering_system_info :: %{id: numbering_system(), digits: [String.t()]}
+
+  @doc """
+  Lists the numeric numbering systems with their digits.
+
+  Every identifier can be passed as `:numbering_system` to `symbols/1` and
+  `digits_from_latin/2`, or used as the `-u-nu-` keyword of a locale. Use
+  `Icu.DisplayNames.format(:numbering_system, id)` for their names.
+
+  ## Examples
+
+      iex> deva = Enum.find(Icu.Number.numbering_systems(), &(&1.id == "deva"))
+      iex> Enum.join(deva.digits)
+      "०१२३४५६७८९"
+  """
+  @spec numbering_systems() :: [numbering_system_info()]
+  def numbering_systems do
+    Nif.number_numbering_systems()
+  end
+
+  @typedoc """
+  Digit grouping sizes of a locale, as returned by `grouping_sizes/1`.
+
+  `:primary` is the size of the group nearest the decimal separator and
+  `:secondary` the size of every group above it. `:min_grouping` is the
+  number of digits required in front of the first group before it is
+  separated at all under `grouping: :auto`.
+  """
+  @type grouping_sizes :: %{
+          primary: non_neg_integer(),
+          secondary: non_neg_integer(),
+          min_grouping: non_neg_integer()
+        }
+
+  @doc """
+  Returns the locale's digit grouping sizes.
+
+  ## Options
+
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> Icu.Number.grouping_sizes(locale: "en")
+      {:ok, %{primary: 3, secondary: 3, min_grouping: 1}}
+
+      iex> Icu.Number.grouping_sizes(locale: "hi-IN")
+      {:ok, %{primary: 3, secondary: 2, min_grouping: 1}}
+  """
+  @spec grouping_sizes([{:locale, LanguageTag.t() | String.t() | nil}]) ::
+          {:ok, grouping_sizes()} | {:error, term()}
+  def grouping_sizes(options \\ []) do
+    with {:ok, %{locale: locale}} <-
+           Options.normalize_options(:number, options, &(&1 == :locale)) do
+      Nif.number_grouping_sizes(locale)
+    end
+  end
+
+  @doc """
+  Returns the locale's digit grouping sizes and raises on error.
+
+  ## Examples
+
+      iex> Icu.Number.grouping_sizes!(locale: "hi-IN").secondary
+      2
+  """
+  @spec grouping_sizes!([{:locale, LanguageTag.t() | String.t() | nil}]) :: grouping_sizes()
+  def grouping_sizes!(options \\ []) do
+    case grouping_sizes(options) do
+      {:ok, sizes} -> sizes
+      {:error, reason} -> raise "grouping sizes lookup failed: #{inspect(reason)}"
+    end
+  end
 end
@@ -3,19 +3,37 @@ defmodule Icu.Number.Formatter do
 
   alias Icu.Nif
   alias Icu.Number
+  alias Icu.Formatter.Config
   alias Icu.Formatter.Options
 
-  defstruct [:resource]
+  defstruct [:resource, :config]
 
   @opaque t :: %__MODULE__{}
 
+  @option_keys [
+    :grouping,
+    :sign_display,
+    :minimum_integer_digits,
+    :minimum_fraction_digits,
+    :maximum_integer_digits,
+    :maximum_fraction_digits,
+    :float_precision,
+    :scale,
+    :absolute_value,
+    :approximately,
+    :preferences,
+    :locale
+  ]
+
   @spec new(Number.options_input()) ::
           {:ok, t()} | {:error, Number.format_error()}
   def new(options \\ []) do
     with {:ok, opts} <- normalize_options(options),
          {:ok, resource} <-
            Nif.number_formatter_new(Map.fetch!(opts, :locale), Map.delete(opts, :locale)) do
-      {:ok, %__MODULE__{resource: resource}}
+      warnings = Options.warnings(:number, options, opts, &(&1 in @option_keys))
+      config = Config.new(__MODULE__, :new, [Config.put_locale(options, opts.locale)], warnings)
+      {:ok, %__MODULE__{resource: resource, config: config}}
     end
   end
 
@@ -27,14 +45,16 @@ defmodule Icu.Number.Formatter do
     end
   end
 
-  @spec format(t(), number() | struct()) :: {:ok, String.t()} | {:error, Number.format_error()}
-  def format(%__MODULE__{resource: resource}, number) when is_number(number) or is_struct(number) do
+  @spec format(t(), Number.number_input()) ::
+          {:ok, String.t()} | {:error, Number.format_error()}
+  def format(%__MODULE__{resource: resource}, number)
+      when is_number(number) or is_struct(number) or is_binary(number) do
     Nif.number_format(resource, number)
   end
 
   def format(%__MODULE__{}, _other), do: {:error, :invalid_number}
 
-  @spec format!(t(), number()) :: String.t()
+  @spec format!(t(), Number.number_input()) :: String.t()
   def format!(%__MODULE__{} = formatter, number) do
     case format(formatter, number) do
       {:ok, result} -> result
@@ -42,16 +62,20 @@ defmodule Icu.Number.Formatter do
     end
   end
 
-  @spec format_to_parts(t(), number() | struct()) ::
+  @spec format_to_parts(t(), Number.number_input(), Options.options_input()) ::
           {:ok, [map()]} | {:error, Number.format_error()}
-  def format_to_parts(%__MODULE__{resource: resource}, number)
-      when is_number(number) or is_struct(number) do
-    Nif.number_format_to_parts(resource, number)
+  def format_to_parts(formatter, number, options \\ [])
+
+  def format_to_parts(%__MODULE__{resource: resource}, number, options)
+      when is_number(number) or is_struct(number) or is_binary(number) do
+    with {:ok, index_mode} <- Options.index_mode(options) do
+      Nif.number_format_to_parts(resource, number, index_mode)
+    end
   end
 
-  def format_to_parts(%__MODULE__{}, _other), do: {:error, :invalid_number}
+  def format_to_parts(%__MODULE__{}, _other, _options), do: {:error, :invalid_number}
 
-  @spec format_to_parts!(t(), number()) :: [map()]
+  @spec format_to_parts!(t(), Number.number_input()) :: [map()]
   def format_to_parts!(%__MODULE__{} = formatter, number) do
     case format_to_parts(formatter, number) do
       {:ok, parts} -> parts
@@ -59,6 +83,51 @@ defmodule Icu.Number.Formatter do
     end
   end
 
+  @spec format_range(t(), Number.number_input(), Number.number_input()) ::
+          {:ok, String.t()} | {:error, Number.format_error()}
+  def format_range(%__MODULE__{resource: resource}, start, finish)
+      when (is_number(start) or is_struct(start) or is_binary(start)) and
+             (is_number(finish) or is_struct(finish) or is_binary(finish)) do
+    Nif.number_format_range(resource, start, finish)
+  end
+
+  def format_range(%__MODULE__{}, _start, _finish), do: {:error, :invalid_number}
+
+  @spec format_range!(t(), Number.number_input(), Number.number_input()) :: String.t()
+  def format_range!(%__MODULE__{} = formatter, start, finish) do
+    case format_range(formatter, start, finish) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "number range formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @spec format_range_to_parts(
+          t(),
+          Number.number_input(),
+          Number.number_input(),
+          Options.options_input()
+        ) :: {:ok, [map()]} | {:error, Number.format_error()}
+  def format_range_to_parts(formatter, start, finish, options \\ [])
+
+  def format_range_to_parts(%__MODULE__{resource: resource}, start, finish, options)
+      when (is_number(start) or is_struct(start) or is_binary(start)) and
+             (is_number(finish) or is_struct(finish) or is_binary(finish)) do
+    with {:ok, index_mode} <- Options.index_mode(options) do
+      Nif.number_format_range_to_parts(resource, start, finish, index_mode)
+    end
+  end
+
+  def format_range_to_parts(%__MODULE__{}, _start, _finish, _options),
+    do: {:error, :invalid_number}
+
+  @spec format_range_to_parts!(t(), Number.number_input(), Number.number_input()) :: [map()]
+  def format_range_to_parts!(%__MODULE__{} = formatter, start, finish) do
+    case format_range_to_parts(formatter, start, finish) do
+      {:ok, parts} -> parts
+      {:error, reason} -> raise "number range format to parts failed: #{inspect(reason)}"
+    end
+  end
+
   defimpl Inspect do
     def inspect(_formatter, _opts) do
       "#Icu.Number.Formatter<>"
@@ -68,18 +137,6 @@ defmodule Icu.Number.Formatter do
   @doc false
   @spec normalize_options(Number.options_input()) :: {:ok, map()} | {:error, term()}
   def normalize_options(options) do
-    Options.normalize_options(
-      :number,
-      options,
-      &(&1 in [
-          :grouping,
-          :sign_display,
-          :minimum_integer_digits,
-          :minimum_fraction_digits,
-          :maximum_integer_digits,
-          :maximum_fraction_digits,
-          :locale
-        ])
-    )
+    Options.normalize_options(:number, options, &(&1 in @option_keys))
   end
 end
@@ -0,0 +1,67 @@
+defmodule Icu.Properties do
+  @moduledoc """
+  Unicode character property helpers.
+
+  Emoji detection works on grapheme clusters, so multi-codepoint emoji such as
+  flags, keycaps, skin-tone modifiers and ZWJ sequences count as a single
+  emoji. A grapheme is an emoji when it has default emoji presentation, or
+  when an emoji character is combined with U+FE0F or a keycap. Characters
+  that default to text presentation (such as `"©"` or `"#"`) on their own are
+  treated as text.
+
+  ## Examples
+
+      iex> Icu.Properties.emoji?("👍🏽")
+      true
+
+      iex> Icu.Properties.emoji_segments("hi 👋🏻!")
+      [text: "hi ", emoji: "👋🏻", text: "!"]
+  """
+
+  alias Icu.Nif
+
+  @typedoc "A run of consecutive emoji or non-emoji graphemes."
+  @type segment :: {:emoji | :text, String.t()}
+
+  @doc """
+  Returns `true` when the string is non-empty and consists only of emoji.
+
+  ## Examples
+
+      iex> Icu.Properties.emoji?("🇨🇭🎉")
+      true
+
+      iex> Icu.Properties.emoji?("ok 🎉")
+      false
+  """
+  @spec emoji?(String.t()) :: boolean()
+  def emoji?(string) when is_binary(string), do: Nif.properties_is_emoji(string)
+
+  @doc """
+  Returns `true` when the string contains at least one emoji.
+
+  ## Examples
+
+      iex> Icu.Properties.contains_emoji?("ok 🎉")
+      true
+
+      iex> Icu.Properties.contains_emoji?("© 2024")
+      false
+  """
+  @spec contains_emoji?(String.t()) :: boolean()
+  def contains_emoji?(string) when is_binary(string), do: Nif.properties_contains_emoji(string)
+
+  @doc """
+  Splits the string into alternating runs of emoji and text.
+
+  ## Examples
+
+      iex> Icu.Properties.emoji_segments("1️⃣ done")
+      [emoji: "1️⃣", text: " done"]
+
+      iex> Icu.Properties.emoji_segments("")
+      []
+  """
+  @spec emoji_segments(String.t()) :: [segment()]
+  def emoji_segments(string) when is_binary(string), do: Nif.properties_emoji_segments(string)
+end
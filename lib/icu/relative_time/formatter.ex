@@ -1,23 +1,32 @@
 defmodule Icu.RelativeTime.Formatter do
   @moduledoc false
 
+  alias Icu.Formatter.Config
   alias Icu.Formatter.Options
   alias Icu.LanguageTag
   alias Icu.Nif
   alias Icu.RelativeTime
 
-  defstruct [:resource]
+  defstruct [:resource, :config]
 
   @opaque t :: %__MODULE__{}
 
+  @option_keys [:locale, :format, :numeric]
+
   @spec new(LanguageTag.t() | String.t(), RelativeTime.options_input()) ::
           {:ok, t()} | {:error, RelativeTime.format_error()}
   def new(locale, options \\ []) do
     with {:ok, locale_tag} <- LanguageTag.parse(locale),
          {:ok, opts} <- normalize_options(options) do
       case Nif.relative_time_formatter_new(locale_tag.resource, Map.delete(opts, :locale)) do
-        {:ok, formatter} -> {:ok, %__MODULE__{resource: formatter}}
-        {:error, _} = error -> error
+        {:ok, formatter} ->
+          locale = Config.locale_string(locale_tag.resource)
+          warnings = Options.warnings(:relative_time, options, opts, &(&1 in @option_keys))
+          config = Config.new(__MODULE__, :new, [locale, Map.new(options)], warnings)
+          {:ok, %__MODULE__{resource: formatter, config: config}}
+
+        {:error, _} = error ->
+          error
       end
     else
       {:error, {:bad_option, _} = reason} ->
@@ -83,10 +92,6 @@ defmodule Icu.RelativeTime.Formatter do
   @doc false
   @spec normalize_options(RelativeTime.options_input()) :: {:ok, map()} | Options.error()
   def normalize_options(options) do
-    Options.normalize_options(
-      :relative_time,
-      options,
-      &(&1 in [:locale, :format, :numeric])
-    )
+    Options.normalize_options(:relative_time, options, &(&1 in @option_keys))
   end
 end
@@ -0,0 +1,117 @@
+defmodule Icu.Segmenter do
+  @moduledoc """
+  Unicode text segmentation into grapheme clusters, words, sentences and
+  line break opportunities, taken incrementally.
+
+  A segmenter walks a text and hands out its breaks a chunk at a time, so
+  a large document never has all of its breaks in memory at once.
+  `stream/3` wraps this in a `Stream` of the segments themselves.
+
+  ## Kinds
+
+  - `:grapheme` – user-perceived characters.
+  - `:word` – words, and the spaces and punctuation between them, including
+    dictionary-based segmentation of Chinese, Japanese, Thai and others.
+  - `:sentence` – sentences with their trailing spaces.
+  - `:line` – the stretches between line break opportunities, as text
+    wrapping would use them.
+
+  ## Examples
+
+      iex> Icu.Segmenter.stream("Hello world.", :word) |> Enum.to_list()
+      ["Hello", " ", "world", "."]
+
+      iex> {:ok, segmenter} = Icu.Segmenter.new("One. Two.", :sentence)
+      iex> Icu.Segmenter.next(segmenter, 2)
+      {:ok, [0, 5]}
+      iex> Icu.Segmenter.next(segmenter, 2)
+      {:ok, [9]}
+      iex> Icu.Segmenter.next(segmenter, 2)
+      :done
+
+  """
+
+  alias Icu.Nif
+
+  defstruct [:resource, :text]
+
+  @opaque t :: %__MODULE__{}
+
+  @typedoc "What a segmenter breaks the text into."
+  @type kind :: :grapheme | :word | :sentence | :line
+
+  @kinds [:grapheme, :word, :sentence, :line]
+
+  @default_chunk_size 1000
+
+  @doc """
+  Creates a segmenter over `text`.
+  """
+  @spec new(String.t(), kind()) :: {:ok, t()} | {:error, :invalid_options}
+  def new(text, kind) when is_binary(text) and kind in @kinds do
+    with {:ok, resource} <- Nif.segmenter_new(kind, text) do
+      {:ok, %__MODULE__{resource: resource, text: text}}
+    end
+  end
+
+  def new(_text, _kind), do: {:error, :invalid_options}
+
+  @doc """
+  Takes the next `count` breaks, as byte offsets into the text.
+
+  The first chunk starts with `0` and the last one ends with the byte size
+  of the text, so consecutive breaks delimit the segments. Returns `:done`
+  once every break has been taken. A segmenter has a single position
+  shared by every process that holds it.
+  """
+  @spec next(t(), pos_integer()) :: {:ok, [non_neg_integer()]} | :done
+  def next(%__MODULE__{resource: resource}, count \\ @default_chunk_size)
+      when is_integer(count) and count > 0 do
+    Nif.segments_next(resource, count)
+  end
+
+  @doc """
+  Returns a stream of the segments of `text`.
+
+  Segments are sub-binaries of `text`. Breaks are taken `:chunk_size` at a
+  time (#{@default_chunk_size} by default) as the stream is consumed.
+
+  ## Examples
+
+      iex> Icu.Segmenter.stream("👍🏽!", :grapheme) |> Enum.to_list()
+      ["👍🏽", "!"]
+
+  """
+  @spec stream(String.t(), kind(), [{:chunk_size, pos_integer()}]) :: Enumerable.t()
+  def stream(text, kind, options \\ []) when is_binary(text) and kind in @kinds do
+    chunk_size = Keyword.get(options, :chunk_size, @default_chunk_size)
+
+    unless is_integer(chunk_size) and chunk_size > 0 do
+      raise ArgumentError, "invalid chunk size: #{inspect(chunk_size)}"
+    end
+
+    Stream.resource(
+      fn ->
+        {:ok, segmenter} = new(text, kind)
+        {segmenter, nil}
+      end,
+      fn {segmenter, previous} ->
+        case next(segmenter, chunk_size) do
+          {:ok, breaks} -> segments(text, previous, breaks, segmenter)
+          :done -> {:halt, {segmenter, previous}}
+        end
+      end,
+      fn _acc -> :ok end
+    )
+  end
+
+  defp segments(text, previous, breaks, segmenter) do
+    {segments, last} =
+      Enum.flat_map_reduce(breaks, previous, fn
+        offset, nil -> {[], offset}
+        offset, start -> {[binary_part(text, start, offset - start)], offset}
+      end)
+
+    {segments, {segmenter, last}}
+  end
+end
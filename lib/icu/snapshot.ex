@@ -0,0 +1,80 @@
+defmodule Icu.Snapshot do
+  @moduledoc """
+  Snapshots of formatter output across many locales, for test suites.
+
+  `take/4` formats a set of samples in every given locale in a single NIF
+  call and returns the outputs together with digests of them. Checking the
+  digest into a test makes an ICU data update that changes user-visible
+  output fail that test, and the outputs of the locales whose digest moved
+  show what changed.
+
+  ## Examples
+
+      iex> {:ok, snapshot} = Icu.Snapshot.take(:list, ["en"], [], [["A", "B"]])
+      iex> snapshot.locales["en"].outputs
+      ["A and B"]
+      iex> snapshot.digest
+      "28178cbf26bf0d3f"
+
+  ## Samples
+
+  Without samples, each kind formats a built-in set chosen to exercise the
+  data: numbers with signs, grouping, rounded fractions and more than 64
+  bits of digits, and lists of one to four items. Samples are what the
+  formatter of the kind accepts: numbers for `:number`, lists of strings for
+  `:list`.
+
+  ## Digests
+
+  Digests are 64-bit FNV-1a hashes in hex. Each locale has the digest of its
+  outputs, and the snapshot has one over every locale's digest. Locales are
+  keyed by their canonical form and hashed in sorted order, so the order
+  they are given in does not change the digest. The hash does not depend on
+  the Rust or OTP release, only on the outputs.
+  """
+
+  alias Icu.LanguageTag
+  alias Icu.Nif
+
+  @typedoc "Formatter whose output is snapshotted."
+  @type kind :: :number | :list
+
+  @typedoc "Outputs of one locale, in sample order, and their digest."
+  @type locale_snapshot :: %{digest: String.t(), outputs: [String.t()]}
+
+  @type t :: %{digest: String.t(), locales: %{String.t() => locale_snapshot()}}
+
+  @doc """
+  Formats `samples` in each of `locales` with the formatter options of
+  `kind`, such as `maximum_fraction_digits: 2` for `:number` or
+  `type: :or` for `:list`. `nil` samples use the built-in set of the kind.
+  """
+  @spec take(kind(), [LanguageTag.t() | String.t()], keyword() | map(), [term()] | nil) ::
+          {:ok, t()} | {:error, term()}
+  def take(kind, locales, options \\ [], samples \\ nil)
+
+  def take(kind, locales, options, samples)
+      when is_list(locales) and (is_list(samples) or is_nil(samples)) do
+    with {:ok, opts} <- normalize_options(kind, options) do
+      locales = Enum.map(locales, &nif_locale/1)
+      Nif.icu_snapshot(kind, locales, Map.delete(opts, :locale), samples)
+    end
+  end
+
+  def take(_kind, _locales, _options, _samples), do: {:error, :invalid_options}
+
+  @spec take!(kind(), [LanguageTag.t() | String.t()], keyword() | map(), [term()] | nil) :: t()
+  def take!(kind, locales, options \\ [], samples \\ nil) do
+    case take(kind, locales, options, samples) do
+      {:ok, snapshot} -> snapshot
+      {:error, reason} -> raise "snapshot failed: #{inspect(reason)}"
+    end
+  end
+
+  defp normalize_options(:number, options), do: Icu.Number.Formatter.normalize_options(options)
+  defp normalize_options(:list, options), do: Icu.List.Formatter.normalize_options(options)
+  defp normalize_options(_kind, _options), do: {:error, :invalid_options}
+
+  defp nif_locale(%LanguageTag{resource: resource}), do: resource
+  defp nif_locale(locale), do: locale
+end
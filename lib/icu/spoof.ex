@@ -0,0 +1,51 @@
+defmodule Icu.Spoof do
+  @moduledoc """
+  Confusable detection for identifiers (UTS #39).
+
+  `skeleton/1` maps a string onto a canonical "skeleton" in which visually
+  confusable characters share the same prototype. Two strings whose
+  skeletons are equal are likely to be mistaken for one another, which is
+  useful to flag look-alike usernames or domain labels.
+
+  ICU4X does not ship the Unicode confusables data, so this module bundles
+  the prototypes for the common cases: Latin look-alikes from Cyrillic and
+  Greek, fullwidth forms, and ASCII confusables such as `"l"`/`"1"`/`"I"`,
+  `"O"`/`"0"` and `"rn"`/`"m"`.
+
+  ## Examples
+
+      iex> Icu.Spoof.confusable?("paypal", "раypal")
+      true
+
+      iex> Icu.Spoof.confusable?("paypal", "paypa1")
+      true
+
+      iex> Icu.Spoof.confusable?("paypal", "paypai")
+      false
+  """
+
+  alias Icu.Nif
+
+  @doc """
+  Returns the UTS #39 skeleton of `string`.
+
+  Skeletons are meant for comparison only and should not be displayed.
+
+  ## Examples
+
+      iex> Icu.Spoof.skeleton("Ηello")
+      "Hello"
+
+      iex> Icu.Spoof.skeleton("modern")
+      "rnodern"
+  """
+  @spec skeleton(String.t()) :: String.t()
+  def skeleton(string) when is_binary(string), do: Nif.spoof_skeleton(string)
+
+  @doc """
+  Returns `true` when `left` and `right` have the same skeleton.
+  """
+  @spec confusable?(String.t(), String.t()) :: boolean()
+  def confusable?(left, right) when is_binary(left) and is_binary(right),
+    do: Nif.spoof_confusable(left, right)
+end
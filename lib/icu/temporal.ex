@@ -18,6 +18,21 @@ defmodule Icu.Temporal do
   - `DateTime` – Contains date, time, and timezone components.
 
   Timezone information is only formatted when a `:zone_style` option is explicitly provided.
+  The offset of a `DateTime` includes its daylight saving offset (`std_offset`).
+
+  Plain maps with the same fields (`:year`, `:month`, `:day`, `:hour`, `:minute`,
+  `:second`, `:time_zone`) are accepted as well. Their `:utc_offset` may be given in
+  seconds, as an `{hours, minutes}` tuple such as `{5, 45}` or `{-3, 30}`, or as a string
+  such as `"+05:45"` or `"−03:30"`.
+
+      iex> input = %{year: 2024, month: 1, day: 15, hour: 12, minute: 0, second: 0}
+      iex> {:ok, output} =
+      ...>   Icu.Temporal.format(Map.put(input, :utc_offset, "+05:45"),
+      ...>     locale: "en",
+      ...>     zone_style: :localized_offset_long
+      ...>   )
+      iex> String.ends_with?(output, "GMT+05:45")
+      true
 
   If you need to format only part of a `DateTime` (e.g., just the date), use Elixir's
   conversion functions like `DateTime.to_date/1` or `DateTime.to_time/1` before formatting.
@@ -60,6 +75,35 @@ defmodule Icu.Temporal do
   - `:m` – A standalone month, as in "January".
   - `:ym` – A month and year, as in "January 2000".
   - `:y` – A year, as in "2000".
+  - `:yq` – A quarter and year, as in "Q1 2000".
+  - `:q` – A quarter alone, as in "Q1".
+
+  ICU4X has no quarter fields, so `:yq` and `:q` format the year through the
+  regular field set and add the quarter name from a small bundled table
+  (English, German, French, Spanish, Italian, Portuguese, Dutch, Japanese and
+  Chinese; other languages use "Q1"–"Q4"). `length: :long` selects the wide
+  names, as in "1st quarter 2000". Quarters follow the Gregorian months, and
+  these fields cannot be combined with a time or zone. They appear as
+  `:quarter` parts in `format_to_parts/2`.
+
+  ### `:month` and `:year`
+
+  Exact widths for the month and year fields, following the ECMA-402
+  component model. They are layered on top of `:date_fields` and `:length`:
+
+  - `:month` – `:numeric` ("1"), `:two_digit` ("01"), `:short` ("Jan"),
+    `:long` ("January") or `:narrow` ("J"). Unless `:length` is given, the
+    month width also selects it (`:long` for long months, `:medium` for short
+    and narrow ones, `:short` for numeric ones), so the separators suit the
+    month form.
+  - `:year` – `:numeric` ("2024") or `:two_digit` ("24").
+
+  The widths rewrite the pattern the other options resolve to, which is then
+  formatted in the Gregorian calendar. They cannot be combined with
+  `:zone_style`.
+
+      iex> Icu.Temporal.format(~D[2024-01-05], month: :two_digit, locale: "en")
+      {:ok, "01/5/24"}
 
   ### `:time_precision`
 
@@ -84,6 +128,12 @@ defmodule Icu.Temporal do
   - `:location` – The location format, as in "Los Angeles time".
   - `:exemplar_city` – The exemplar city format, as in "Los Angeles".
 
+  `:exemplar_city` and `:location` need the input's time zone. When they cannot
+  format it, such as for input carrying only a UTC offset, formatting falls
+  back from the exemplar city to the location format and then to the long
+  localized offset. The time zone part returned by `format_to_parts` carries a
+  `:zone_style` key naming the style that was used.
+
   ### `:alignment`
 
   Alignment behavior for formatted output:
@@ -99,12 +149,138 @@ defmodule Icu.Temporal do
   - `:full` – Always display the century, and display the era when needed to disambiguate the year, based on locale preferences. Examples: "1000 BC", "77 AD", "1900", "2024".
   - `:with_era` – Always display the century and era. Examples: "1000 BC", "77 AD", "1900 AD", "2024 AD".
 
+  ### `:calendar`
+
+  The calendar to format dates in, overriding the `-u-ca` keyword of the locale. It
+  takes the calendar identifiers of `Icu.Calendar`, such as `:hebrew`, `:japanese` or
+  one of the Islamic calendars:
+
+  - `:islamic` – The observational calendar, approximated by a simulation of sightings
+    in Mecca (also `:islamic_rgsa`).
+  - `:islamic_umalqura` – The Umm al-Qura calendar of Saudi Arabia.
+  - `:islamic_civil` – The tabular calendar counted from Friday, 16 July 622 (Julian).
+  - `:islamic_tbla` – The tabular calendar counted from Thursday, 15 July 622 (Julian).
+
+  The same day can differ by one between these calendars. An unknown calendar is
+  rejected with `:invalid_calendar`. The `:month` and `:year` widths always format in
+  the Gregorian calendar and cannot be combined with another `:calendar`.
+
+      iex> for calendar <- [:islamic_umalqura, :islamic_civil, :islamic_tbla] do
+      ...>   Icu.Temporal.format!(~D[2024-03-20], date_fields: :d, calendar: calendar)
+      ...> end
+      ["10", "10", "11"]
+
+  ### Combining fields
+
+  `:date_fields`, `:time_precision` and `:zone_style` can be combined as follows:
+
+  - The day-based date fields (`:d`, `:md`, `:ymd`, `:de`, `:mde`, `:ymde` and `:e`) with
+    a time, a zone, or both. A weekday alone with a time gives output such as "Tue 14:30".
+  - A time with a zone.
+  - `:m`, `:ym` and `:y` only on their own, and `:yq` and `:q` only with a year.
+
+  Other combinations are rejected with `:invalid_field_combination`.
+
+      iex> Icu.Temporal.format(~N[2024-01-16 14:30:00],
+      ...>   locale: "en-GB",
+      ...>   date_fields: :e,
+      ...>   time_precision: :minute
+      ...> )
+      {:ok, "Tue 14:30"}
+
+  ### `:skeleton`
+
+  A CLDR skeleton string such as `"yMMMEd Hm"`, as used by ICU4C and `ex_cldr`.
+  The skeleton is translated into the options above: the date symbols (`y`, `M`,
+  `d`, `E`) select `:date_fields`, the month or weekday width selects `:length`,
+  `h`/`H`/`m`/`s`/`S` select `:time_precision`, `G` selects `year_style: :with_era`
+  and the zone symbols (`z`, `v`, `O`, `V`, `Z`, `X`, `x`) select `:zone_style`.
+  An explicit `H` or `h` also forces a 24-hour or 12-hour clock.
+
+  Options given alongside the skeleton take precedence over the values derived
+  from it. Skeletons that cannot be expressed as an ICU4X field set (for example
+  `"yd"`) are rejected with `:invalid_options`.
+
+      iex> Icu.Temporal.format(~N[2024-01-15 14:05:00], skeleton: "yMMMd Hm")
+      {:ok, "Jan 15, 2024, 14:05"}
+
+  ### `:relative_to` and `:relative_days`
+
+  A reference date, usually today, as a `Date`, `NaiveDateTime`, `DateTime`, ISO 8601
+  string or `t:now/0` clock reading. Dates within `:relative_days` days of it (one by
+  default) are written with their CLDR relative day name instead of the date fields,
+  keeping any time and zone. Days the locale has no name for are written numerically,
+  as in "in 2 days". The substituted name is a `:relative_day` part in
+  `format_to_parts/2`. The options cannot be combined with a time-only formatter or the
+  quarter fields.
+
+  The name takes the place of the date inside the locale's date-time pattern, so the
+  text joining it to the time is the one used for dates. CLDR's separate pattern for
+  a relative day with a time is not applied, and weekdays are not named relatively
+  ("last Friday"): ICU4X carries neither in its data.
+
+      iex> Icu.Temporal.format(~D[2024-01-14], locale: "en", relative_to: ~D[2024-01-15])
+      {:ok, "yesterday"}
+
+      iex> Icu.Temporal.format(~D[2024-01-10], locale: "en", relative_to: ~D[2024-01-15])
+      {:ok, "Jan 10, 2024"}
+
+  The NIFs never read the system clock. Pass `now/1` to compare against the current
+  date, or a fixed `{unix_seconds, utc_offset}` reading to freeze it in tests:
+
+      iex> Icu.Temporal.format(~D[2023-11-15], locale: "en", relative_to: {1_700_000_000, 0})
+      {:ok, "tomorrow"}
+
+  See `relative_day_name/2` to look up a name directly.
+
+  ### `:leap_second`
+
+  How a `second` of 60, as written for a leap second, is treated:
+
+  - `:clamp` – Format it as second 59 of the minute, as ICU does. **Default.**
+  - `:error` – Reject the input with `{:out_of_range, :second, 0, 59}`.
+
+      iex> Icu.Temporal.format("23:59:60", locale: "en-GB")
+      {:ok, "23:59:59"}
+
+      iex> Icu.Temporal.format("23:59:60", locale: "en-GB", leap_second: :error)
+      {:error, {:out_of_range, :second, 0, 59}}
+
+  ### `:preferences`
+
+  Locale preferences that replace the `-u-` keywords of the locale, so they need not be
+  spliced into the locale string: `:calendar`, `:hour_cycle` and `:numbering_system`,
+  see `t:Icu.preferences/0`. Values are checked against the CLDR keyword data, and an
+  unknown key or value is rejected with `{:invalid_option_value, :preferences}`. The
+  `:calendar` option takes precedence over the calendar preference.
+
+      iex> Icu.Temporal.format(~T[14:30:00],
+      ...>   locale: "en-US",
+      ...>   time_precision: :minute,
+      ...>   preferences: [hour_cycle: :h23]
+      ...> )
+      {:ok, "14:30"}
+
+  ### `:output`
+
+  `:binary` (default) or `:iodata`, as for `Icu.List.format/2`. The formatted date or
+  time is a single short binary, which is already iodata, so both return the same binary.
+
   ### `:locale`
 
   Override the lookup locale; otherwise defaults to `Icu.get_locale()` which sources from the environment.
+
+  ## Fixed patterns
+
+  When an exact legacy format has to be reproduced, build a formatter from a
+  CLDR pattern with `Icu.Temporal.Formatter.from_pattern/2`. Month and weekday
+  names are still localized, but the field order and widths are taken verbatim
+  from the pattern.
   """
 
+  alias Icu.Formatter.Options
   alias Icu.LanguageTag
+  alias Icu.Nif
   alias Icu.Temporal.Formatter
 
   @typedoc "Opaque reference to an ICU4X temporal formatter."
@@ -115,7 +291,13 @@ defmodule Icu.Temporal do
 
   @typedoc "Field combinations used when rendering the date component."
   @type date_fields ::
-          :d | :md | :ymd | :de | :mde | :ymde | :e | :m | :ym | :y
+          :d | :md | :ymd | :de | :mde | :ymde | :e | :m | :ym | :y | :yq | :q
+
+  @typedoc "Explicit width of the month field."
+  @type month :: :numeric | :two_digit | :short | :long | :narrow
+
+  @typedoc "Explicit width of the year field."
+  @type year :: :numeric | :two_digit
 
   @typedoc "Precision control for the time component."
   @type time_precision :: :hour | :minute | :second | {:subsecond, 1..9} | :minute_optional
@@ -137,20 +319,50 @@ defmodule Icu.Temporal do
   @typedoc "Controls which year form is preferred."
   @type year_style :: :auto | :full | :with_era
 
+  @typedoc "Treatment of the 60th second of a leap second."
+  @type leap_second :: :clamp | :error
+
+  @typedoc "CLDR skeleton string, such as `\"yMMMEd Hm\"`."
+  @type skeleton :: String.t()
+
+  @typedoc """
+  A clock reading: Unix seconds and the UTC offset of the zone to read them in,
+  given in seconds, as an `{hours, minutes}` tuple or as a string such as `"+05:45"`.
+  """
+  @type now :: {integer(), integer() | {integer(), integer()} | String.t()}
+
+  @typedoc "Reference date for relative day names."
+  @type relative_to :: Elixir.Date.t() | NaiveDateTime.t() | DateTime.t() | String.t() | now()
+
   @typedoc "Inputs that can be coerced into the temporal map accepted by the NIF."
   @type native_input ::
-          Elixir.Date.t() | Elixir.Time.t() | NaiveDateTime.t() | DateTime.t() | map()
+          Elixir.Date.t()
+          | Elixir.Time.t()
+          | NaiveDateTime.t()
+          | DateTime.t()
+          | map()
+          | String.t()
 
   @typedoc "Keyword form of the supported options."
   @type options_list ::
           [
             {:length, length()}
             | {:date_fields, date_fields()}
+            | {:month, month()}
+            | {:year, year()}
             | {:time_precision, time_precision()}
             | {:zone_style, zone_style()}
             | {:alignment, alignment()}
             | {:year_style, year_style()}
+            | {:calendar, Icu.Calendar.calendar()}
+            | {:skeleton, skeleton()}
+            | {:relative_to, relative_to()}
+            | {:relative_days, non_neg_integer()}
+            | {:leap_second, leap_second()}
+            | {:preferences, Icu.preferences()}
             | {:locale, LanguageTag.t() | String.t() | nil}
+            | {:index_mode, Icu.index_mode()}
+            | {:output, Icu.List.output()}
           ]
 
   @typedoc "Map form of the supported options."
@@ -158,51 +370,96 @@ defmodule Icu.Temporal do
           %{
             optional(:length) => length(),
             optional(:date_fields) => date_fields(),
+            optional(:month) => month(),
+            optional(:year) => year(),
             optional(:time_precision) => time_precision(),
             optional(:zone_style) => zone_style(),
             optional(:alignment) => alignment(),
             optional(:year_style) => year_style(),
-            optional(:locale) => LanguageTag.t() | String.t() | nil
+            optional(:calendar) => Icu.Calendar.calendar(),
+            optional(:skeleton) => skeleton(),
+            optional(:relative_to) => relative_to(),
+            optional(:relative_days) => non_neg_integer(),
+            optional(:leap_second) => leap_second(),
+            optional(:preferences) => Icu.preferences(),
+            optional(:locale) => LanguageTag.t() | String.t() | nil,
+            optional(:index_mode) => Icu.index_mode(),
+            optional(:output) => Icu.List.output()
           }
 
   @type options_input :: options() | options_list() | nil
 
+  @typedoc "A temporal field rejected by `validate/2`, with the reason."
+  @type validation_error ::
+          {atom(), :missing | :invalid | {:out_of_range, integer(), integer()}}
+
   @type format_error ::
           :invalid_formatter
           | :invalid_locale
           | :invalid_options
+          | :invalid_field_combination
+          | :invalid_calendar
           | :invalid_datetime
+          | {:out_of_range, atom(), integer(), integer()}
+          | :invalid_pattern
+          | {:missing_field, atom()}
           | :invalid_time_zone
           | :unsupported_calendar
+          | {:invalid_option_value, :output}
 
   @doc """
   Formats a temporal input.
 
-  Accepts Elixir `Date`, `Time`, `NaiveDateTime`, `DateTime`, or a pre-normalized
-  temporal map. Returns `{:ok, formatted}` on success or an error tuple when the
-  input or options cannot be processed.
+  Accepts Elixir `Date`, `Time`, `NaiveDateTime`, `DateTime`, a pre-normalized
+  temporal map, or an ISO 8601 string. Returns `{:ok, formatted}` on success or
+  an error tuple when the input or options cannot be processed.
+
+  ISO 8601 and RFC 3339 strings are parsed by the NIF, which saves building
+  a map for each call. A string holds a date (`"2025-06-01"`), a time
+  (`"14:30:00.250"`) or both (`"2025-06-01T14:30:00"`), optionally followed
+  by a UTC offset (`"Z"`, `"+02:00"`) and an RFC 9557 time zone annotation
+  (`"[Europe/Berlin]"`). A string that does not parse, or whose annotation
+  names an unknown time zone, returns `{:error, :invalid_datetime}`. The fields
+  of a string that parses are checked like those of a map.
+
+  Years run from -999999 to 999999, the expanded years of ISO 8601 (written
+  `"+275760-09-13"` in strings). A field outside the bounds it can take returns
+  `{:error, {:out_of_range, field, min, max}}`, so callers can clamp it:
+
+      iex> Icu.Temporal.format(%{year: 2024, month: 2, day: 30})
+      {:error, {:out_of_range, :day, 1, 29}}
+
+      iex> Icu.Temporal.format("2024-02-30")
+      {:error, {:out_of_range, :day, 1, 29}}
 
   This function automatically applies sensible defaults based on the input type:
   - For `Date`: defaults to `date_fields: :ymd, length: :medium`
-  - For `Time`: defaults to `time_precision: :second`
+  - For `Time`: defaults to `time_precision: :second`, or `{:subsecond, n}` when
+    the input carries `n` digits of microsecond precision
   - For `NaiveDateTime` or `DateTime`: defaults to both date and time defaults
 
-  You can override any defaults by passing explicit options.
+  You can override any defaults by passing explicit options. No defaults are
+  applied when a `:skeleton` is given.
 
   ## Examples
 
       iex> Icu.Temporal.format(~D[2024-01-15], date_fields: :ymd)
       {:ok, "Jan 15, 2024"}
 
+      iex> Icu.Temporal.format("2025-06-01")
+      {:ok, "Jun 1, 2025"}
+
       iex> Icu.Temporal.format(:invalid, date_fields: :ymd)
       {:error, :invalid_temporal}
   """
   @spec format(native_input(), options_input()) ::
           {:ok, String.t()} | {:error, format_error()}
   def format(input, options \\ []) do
+    {output, options} = Options.pop_output(options)
     options_with_defaults = apply_defaults(input, options)
 
-    with {:ok, formatter} <- Formatter.new(options_with_defaults) do
+    with :ok <- Options.output(output),
+         {:ok, formatter} <- Formatter.new(options_with_defaults) do
       Formatter.format(formatter, input)
     end
   end
@@ -227,7 +484,8 @@ defmodule Icu.Temporal do
   Formats a temporal input to parts using a formatter.
 
   Returns the string pieces tagged with their semantic part types, making it
-  easier to post-process the output when building custom markup.
+  easier to post-process the output when building custom markup. Parts
+  without a known type are tagged as described in `t:Icu.part_type/0`.
 
   Like `format/2`, this function automatically applies sensible defaults based
   on the input type.
@@ -241,10 +499,11 @@ defmodule Icu.Temporal do
   @spec format_to_parts(native_input(), options_input()) ::
           {:ok, [map()]} | {:error, format_error()}
   def format_to_parts(input, options \\ []) do
+    {index_mode, options} = Options.pop_index_mode(options)
     options_with_defaults = apply_defaults(input, options)
 
     with {:ok, formatter} <- Formatter.new(options_with_defaults) do
-      Formatter.format_to_parts(formatter, input)
+      Formatter.format_to_parts(formatter, input, index_mode: index_mode)
     end
   end
 
@@ -265,17 +524,243 @@ defmodule Icu.Temporal do
     end
   end
 
+  @doc """
+  Formats a date and time in one of the fixed English formats used by email and
+  HTTP headers. The output does not depend on any locale.
+
+  - `:rfc2822` – `"Sun, 01 Jun 2025 14:30:00 +0200"`, in the input's own offset. An
+    input without an offset is written with `-0000`, marking its zone as unknown.
+  - `:http` – the HTTP-date, `"Sun, 01 Jun 2025 12:30:00 GMT"`, converted to UTC. An
+    input without an offset is taken to be in UTC.
+
+  The input needs both a date and a time. Fractions of a second are dropped.
+
+  ## Examples
+
+      iex> Icu.Temporal.format_rfc(~U[2025-06-01 14:30:00Z], :rfc2822)
+      {:ok, "Sun, 01 Jun 2025 14:30:00 +0000"}
+
+      iex> Icu.Temporal.format_rfc("2025-06-01T01:30:00+02:00", :http)
+      {:ok, "Sat, 31 May 2025 23:30:00 GMT"}
+
+      iex> Icu.Temporal.format_rfc(~D[2025-06-01], :http)
+      {:error, {:missing_field, :hour}}
+  """
+  @spec format_rfc(native_input(), :rfc2822 | :http) ::
+          {:ok, String.t()} | {:error, format_error()}
+  def format_rfc(input, format) do
+    with {:ok, native} <- Formatter.normalize_input(input) do
+      Nif.temporal_format_rfc(native, format)
+    end
+  end
+
+  @doc """
+  Formats a date and time for email or HTTP headers and raises on error.
+
+  ## Examples
+
+      iex> Icu.Temporal.format_rfc!(~N[2025-06-01 14:30:00], :rfc2822)
+      "Sun, 01 Jun 2025 14:30:00 -0000"
+  """
+  @spec format_rfc!(native_input(), :rfc2822 | :http) :: String.t()
+  def format_rfc!(input, format) do
+    case format_rfc(input, format) do
+      {:ok, result} -> result
+      {:error, reason} -> raise "temporal formatting failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Checks that a temporal input names a date and time that exist.
+
+  The date fields are counted in `calendar` (ISO by default), so the month and
+  day limits and the leap years are those of that calendar. The input is read
+  exactly as the formatters read it, so an input passing validation formats
+  without an `:invalid_datetime` error. A leap second is accepted, as with the
+  default `leap_second: :clamp`. The fields of an ISO 8601 string are checked
+  like those of a map, and a string that does not parse returns
+  `{:error, :invalid_datetime}`.
+
+  Returns `:ok`, or a keyword list with the reason each rejected field failed:
+
+  - `:missing` – the field is needed by the fields given alongside it, such as
+    a `:day` next to a `:year` and `:month`.
+  - `:invalid` – the value has the wrong type, or names a month the year does
+    not have.
+  - `{:out_of_range, min, max}` – the value lies outside the given bounds.
+
+  ## Examples
+
+      iex> Icu.Temporal.validate(%{year: 2024, month: 2, day: 29})
+      :ok
+
+      iex> Icu.Temporal.validate(%{year: 2023, month: 2, day: 29})
+      {:error, [day: {:out_of_range, 1, 28}]}
+
+      iex> Icu.Temporal.validate(%{year: 2024, month: 13, day: 1, hour: 24, minute: 0, second: 0})
+      {:error, [hour: {:out_of_range, 0, 23}, month: {:out_of_range, 1, 12}]}
+
+      iex> Icu.Temporal.validate(%{year: 1739, month: 13, day: 6}, :coptic)
+      :ok
+  """
+  @spec validate(native_input(), Icu.Calendar.calendar()) ::
+          :ok | {:error, [validation_error()] | :invalid_datetime | :invalid_calendar}
+  def validate(input, calendar \\ :iso8601) do
+    with {:ok, native} <- Formatter.normalize_input(input) do
+      Nif.temporal_validate(native, Icu.Calendar.calendar_id(calendar))
+    end
+  end
+
+  @typedoc """
+  A day period name with the hours it covers.
+
+  `:end_hour` is exclusive. Noon and midnight cover a single instant, so their
+  start and end hours are equal.
+  """
+  @type day_period :: %{
+          period: :am | :pm | :midnight | :noon,
+          name: String.t(),
+          start_hour: 0..24,
+          end_hour: 0..24
+        }
+
+  @doc """
+  Returns the locale's day period names, as used by formatted 12-hour times.
+
+  AM and PM are always listed. Midnight and noon are listed only when the
+  locale has names for them that differ from AM and PM. ICU4X does not
+  support the CLDR flexible day periods ("in the morning"), so those are not
+  available.
+
+  ## Options
+
+  - `:width` – `:short` (default), `:wide` or `:narrow`.
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> {:ok, periods} = Icu.Temporal.day_periods()
+      iex> Enum.map(periods, &{&1.period, &1.name})
+      [am: "AM", pm: "PM", midnight: "midnight", noon: "noon"]
+  """
+  @spec day_periods([
+          {:width, :wide | :short | :narrow} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: {:ok, [day_period()]} | {:error, format_error()}
+  def day_periods(options \\ []) do
+    with {:ok, opts} <-
+           Options.normalize_options(:temporal, options, &(&1 in [:locale, :width])) do
+      Nif.temporal_day_periods(opts.locale, Map.get(opts, :width, :short))
+    end
+  end
+
+  @doc """
+  Returns the locale's day period names and raises on error.
+
+  ## Examples
+
+      iex> Icu.Temporal.day_periods!(width: :narrow) |> Enum.map(& &1.name) |> Enum.take(2)
+      ["a", "p"]
+  """
+  @spec day_periods!([
+          {:width, :wide | :short | :narrow} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: [day_period()]
+  def day_periods!(options \\ []) do
+    case day_periods(options) do
+      {:ok, periods} -> periods
+      {:error, reason} -> raise "day period lookup failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Reads the system clock as a `t:now/0` reading in the given UTC offset.
+
+  Functions comparing against the current date take the reading as an argument
+  or option rather than reading the clock themselves, so tests can pass a fixed one.
+
+  ## Examples
+
+      iex> {unix_seconds, "+02:00"} = Icu.Temporal.now("+02:00")
+      iex> is_integer(unix_seconds)
+      true
+  """
+  @spec now(integer() | {integer(), integer()} | String.t()) :: now()
+  def now(utc_offset \\ 0), do: {System.os_time(:second), utc_offset}
+
+  @doc """
+  Returns the locale's name for the day `offset` days from today, such as
+  "yesterday" for `-1` or "tomorrow" for `1`.
+
+  The names come from the CLDR relative day fields. Offsets the locale has no
+  name for are formatted numerically, as in "in 3 days". Relative weekday
+  names such as "last Friday" are not available, as ICU4X has no data for
+  them.
+
+  ## Options
+
+  - `:width` – `:wide` (default), `:short` or `:narrow`.
+  - `:locale` – override the locale for this invocation.
+
+  ## Examples
+
+      iex> Icu.Temporal.relative_day_name(-1, locale: "en")
+      {:ok, "yesterday"}
+
+      iex> Icu.Temporal.relative_day_name(0, locale: "en")
+      {:ok, "today"}
+
+      iex> Icu.Temporal.relative_day_name(-2, locale: "de")
+      {:ok, "vorgestern"}
+  """
+  @spec relative_day_name(integer(), [
+          {:width, :wide | :short | :narrow} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: {:ok, String.t()} | {:error, format_error()}
+  def relative_day_name(offset, options \\ [])
+
+  def relative_day_name(offset, options) when is_integer(offset) do
+    with {:ok, opts} <-
+           Options.normalize_options(:temporal, options, &(&1 in [:locale, :width])) do
+      Nif.temporal_relative_day_name(opts.locale, offset, Map.get(opts, :width, :wide))
+    end
+  end
+
+  def relative_day_name(_offset, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Returns the relative day name and raises on error.
+
+  ## Examples
+
+      iex> Icu.Temporal.relative_day_name!(1, locale: "en")
+      "tomorrow"
+  """
+  @spec relative_day_name!(integer(), [
+          {:width, :wide | :short | :narrow} | {:locale, LanguageTag.t() | String.t() | nil}
+        ]) :: String.t()
+  def relative_day_name!(offset, options \\ []) do
+    case relative_day_name(offset, options) do
+      {:ok, name} -> name
+      {:error, reason} -> raise "relative day name lookup failed: #{inspect(reason)}"
+    end
+  end
+
   # Private functions
 
   defp apply_defaults(input, options) do
     options = normalize_options(options)
+    apply_defaults_unless_skeleton(input, options)
+  end
+
+  defp apply_defaults_unless_skeleton(_input, %{skeleton: skeleton} = options)
+       when not is_nil(skeleton),
+       do: options
 
+  defp apply_defaults_unless_skeleton(input, options) do
     has_date = has_date_component?(input)
     has_time = has_time_component?(input)
 
     options
     |> maybe_add_date_defaults(has_date)
-    |> maybe_add_time_defaults(has_time)
+    |> maybe_add_time_defaults(has_time, subsecond_precision(input))
   end
 
   defp normalize_options(options) when is_list(options), do: Map.new(options)
@@ -283,11 +768,20 @@ defmodule Icu.Temporal do
   defp normalize_options(nil), do: %{}
 
   defp has_date_component?(%{year: _, month: _, day: _}), do: true
+
+  defp has_date_component?(string) when is_binary(string),
+    do: Regex.match?(~r/^(?:[+-]\d{6}|\d{4})-\d{2}-\d{2}/, string)
+
   defp has_date_component?(_), do: false
 
   defp has_time_component?(%{hour: _, minute: _, second: _}), do: true
+  defp has_time_component?(string) when is_binary(string), do: iso_time(string) != nil
   defp has_time_component?(_), do: false
 
+  defp maybe_add_date_defaults(%{month: _} = options, true) do
+    Map.put_new(options, :date_fields, :ymd)
+  end
+
   defp maybe_add_date_defaults(options, true) do
     options
     |> Map.put_new(:date_fields, :ymd)
@@ -296,9 +790,29 @@ defmodule Icu.Temporal do
 
   defp maybe_add_date_defaults(options, false), do: options
 
-  defp maybe_add_time_defaults(options, true) do
+  defp maybe_add_time_defaults(options, true, precision) when precision in 1..9 do
+    Map.put_new(options, :time_precision, {:subsecond, precision})
+  end
+
+  defp maybe_add_time_defaults(options, true, _precision) do
     Map.put_new(options, :time_precision, :second)
   end
 
-  defp maybe_add_time_defaults(options, false), do: options
+  defp maybe_add_time_defaults(options, false, _precision), do: options
+
+  defp subsecond_precision(%{microsecond: {_value, precision}}), do: precision
+
+  defp subsecond_precision(string) when is_binary(string) do
+    case iso_time(string) do
+      [_time, fraction] -> byte_size(fraction)
+      _ -> 0
+    end
+  end
+
+  defp subsecond_precision(_), do: 0
+
+  # The time of an ISO 8601 string, with its fraction digits if any.
+  defp iso_time(string) do
+    Regex.run(~r/(?:^|[Tt ])\d{2}:\d{2}(?::\d{2}(?:[.,](\d{1,9}))?)?/, string)
+  end
 end
@@ -25,21 +25,47 @@ defmodule Icu.Temporal.Formatter do
   """
 
   alias Icu.Calendar
+  alias Icu.Formatter.Config
   alias Icu.Formatter.Options
+  alias Icu.LanguageTag
   alias Icu.Nif
   alias Icu.Temporal
 
-  defstruct [:resource]
+  defstruct [:resource, :config]
 
   @opaque t :: %__MODULE__{}
 
+  @option_keys [
+    :length,
+    :date_fields,
+    :month,
+    :year,
+    :time_precision,
+    :zone_style,
+    :alignment,
+    :year_style,
+    :calendar,
+    :skeleton,
+    :relative_to,
+    :relative_days,
+    :leap_second,
+    :preferences,
+    :locale
+  ]
+
   @spec new(Temporal.options_input()) ::
           {:ok, t()} | {:error, Temporal.format_error()}
   def new(options \\ []) do
     with {:ok, opts} <- normalize_options(options) do
       case Nif.temporal_formatter_new(Map.fetch!(opts, :locale), Map.delete(opts, :locale)) do
-        {:ok, formatter} -> {:ok, %__MODULE__{resource: formatter}}
-        {:error, _} = error -> error
+        {:ok, formatter} ->
+          warnings = Options.warnings(:temporal, options, opts, &(&1 in @option_keys))
+          args = [Config.put_locale(options, opts.locale)]
+          config = Config.new(__MODULE__, :new, args, warnings)
+          {:ok, %__MODULE__{resource: formatter, config: config}}
+
+        {:error, _} = error ->
+          error
       end
     else
       {:error, {:bad_option, _} = reason} ->
@@ -64,6 +90,204 @@ defmodule Icu.Temporal.Formatter do
     end
   end
 
+  @date_option_keys [
+    :length,
+    :date_fields,
+    :month,
+    :year,
+    :alignment,
+    :year_style,
+    :calendar,
+    :relative_to,
+    :relative_days,
+    :preferences,
+    :locale
+  ]
+  @time_option_keys [:length, :time_precision, :alignment, :leap_second, :preferences, :locale]
+  @zoned_option_keys [
+    :length,
+    :date_fields,
+    :time_precision,
+    :zone_style,
+    :alignment,
+    :year_style,
+    :calendar,
+    :relative_to,
+    :relative_days,
+    :leap_second,
+    :preferences,
+    :locale
+  ]
+
+  @doc """
+  Builds a formatter that only renders dates.
+
+  Accepts `:length`, `:date_fields`, `:month`, `:year`, `:alignment`,
+  `:year_style`, `:calendar`, `:relative_to`, `:relative_days`, `:preferences`
+  and `:locale`.
+  `:date_fields` defaults to `:ymd` and `:length` to `:medium`.
+
+  Unlike `new/1`, formatting validates the input up front and reports the
+  first missing field as `{:error, {:missing_field, field}}`.
+
+  ## Examples
+
+      iex> formatter = Icu.Temporal.Formatter.new_date!(locale: "en")
+      iex> Icu.Temporal.Formatter.format(formatter, ~T[10:00:00])
+      {:error, {:missing_field, :year}}
+  """
+  @spec new_date(Temporal.options_input()) :: {:ok, t()} | {:error, Temporal.format_error()}
+  def new_date(options \\ []) do
+    new_validated(
+      :new_date,
+      &Nif.temporal_date_formatter_new/2,
+      @date_option_keys,
+      %{date_fields: :ymd, length: :medium},
+      options
+    )
+  end
+
+  @spec new_date!(Temporal.options_input()) :: t()
+  def new_date!(options \\ []), do: unwrap_new!(new_date(options))
+
+  @doc """
+  Builds a formatter that only renders times of day.
+
+  Accepts `:length`, `:time_precision`, `:alignment`, `:leap_second`, `:preferences`
+  and `:locale`.
+  `:time_precision` defaults to `:second`. Inputs lacking `:hour`, `:minute`
+  or `:second` are rejected with `{:error, {:missing_field, field}}`.
+  """
+  @spec new_time(Temporal.options_input()) :: {:ok, t()} | {:error, Temporal.format_error()}
+  def new_time(options \\ []) do
+    new_validated(
+      :new_time,
+      &Nif.temporal_time_formatter_new/2,
+      @time_option_keys,
+      %{time_precision: :second},
+      options
+    )
+  end
+
+  @spec new_time!(Temporal.options_input()) :: t()
+  def new_time!(options \\ []), do: unwrap_new!(new_time(options))
+
+  @doc """
+  Builds a formatter for datetimes carrying a time zone.
+
+  Accepts the same options as `new/1` except `:skeleton`. Defaults to
+  `date_fields: :ymd`, `length: :medium`, `time_precision: :second` and
+  `zone_style: :localized_offset_short`.
+
+  Inputs must carry a full date and time plus either a `:time_zone` or a
+  `:utc_offset`; naive datetimes are rejected with
+  `{:error, {:missing_field, :time_zone}}`.
+  """
+  @spec new_zoned_datetime(Temporal.options_input()) ::
+          {:ok, t()} | {:error, Temporal.format_error()}
+  def new_zoned_datetime(options \\ []) do
+    new_validated(
+      :new_zoned_datetime,
+      &Nif.temporal_zoned_datetime_formatter_new/2,
+      @zoned_option_keys,
+      %{
+        date_fields: :ymd,
+        length: :medium,
+        time_precision: :second,
+        zone_style: :localized_offset_short
+      },
+      options
+    )
+  end
+
+  @spec new_zoned_datetime!(Temporal.options_input()) :: t()
+  def new_zoned_datetime!(options \\ []), do: unwrap_new!(new_zoned_datetime(options))
+
+  # A month width picks its own length unless one is given explicitly.
+  defp length_default(defaults, %{month: _}), do: Map.delete(defaults, :length)
+  defp length_default(defaults, _opts), do: defaults
+
+  defp new_validated(constructor, nif_fun, accepted_keys, defaults, options) do
+    with {:ok, opts} <- Options.normalize_options(:temporal, options, &(&1 in accepted_keys)),
+         opts = Map.merge(length_default(defaults, opts), opts),
+         {:ok, resource} <- nif_fun.(Map.fetch!(opts, :locale), Map.delete(opts, :locale)) do
+      warnings = Options.warnings(:temporal, options, opts, &(&1 in accepted_keys))
+      args = [Config.put_locale(options, opts.locale)]
+      config = Config.new(__MODULE__, constructor, args, warnings)
+      {:ok, %__MODULE__{resource: resource, config: config}}
+    else
+      {:error, {:bad_option, _} = reason} ->
+        {:error, {:invalid_options, reason}}
+
+      {:error, {:invalid_option_value, _} = reason} ->
+        {:error, {:invalid_options, reason}}
+
+      {:error, _} = error ->
+        error
+    end
+  end
+
+  defp unwrap_new!({:ok, formatter}), do: formatter
+
+  defp unwrap_new!({:error, reason}),
+    do: raise("temporal formatter creation failed: #{inspect(reason)}")
+
+  @doc """
+  Builds a formatter from a raw CLDR date/time pattern, such as
+  `"EEE, dd.MM.yyyy HH:mm"`.
+
+  Field symbols are rendered exactly as written while month, weekday and day
+  period names are localized. Values are formatted in the Gregorian calendar.
+  Time zone symbols are not supported.
+
+  The input must carry the fields the pattern refers to: a pattern with date
+  fields fails with `{:error, {:missing_field, :year}}` for a time alone, and one
+  with time fields with `{:error, {:missing_field, :hour}}` for a date alone. A
+  `Time` can be formatted with a time-only pattern.
+
+  Accepts a `:locale` option; otherwise `Icu.get_locale/0` is used.
+
+  ## Examples
+
+      iex> {:ok, formatter} = Icu.Temporal.Formatter.from_pattern("EEE, dd.MM.yyyy HH:mm", locale: "de")
+      iex> Icu.Temporal.Formatter.format(formatter, ~N[2024-01-15 09:05:00])
+      {:ok, "Mo., 15.01.2024 09:05"}
+  """
+  @spec from_pattern(String.t(), [{:locale, LanguageTag.t() | String.t() | nil}] | map()) ::
+          {:ok, t()} | {:error, Temporal.format_error()}
+  def from_pattern(pattern, options \\ [])
+
+  def from_pattern(pattern, options) when is_binary(pattern) do
+    with {:ok, opts} <- Options.normalize_options(:temporal, options, &(&1 == :locale)),
+         {:ok, resource} <-
+           Nif.temporal_formatter_from_pattern(Map.fetch!(opts, :locale), pattern) do
+      warnings = Options.warnings(:temporal, options, opts, &(&1 == :locale))
+      args = [pattern, Config.put_locale(options, opts.locale)]
+      config = Config.new(__MODULE__, :from_pattern, args, warnings)
+
+      {:ok, %__MODULE__{resource: resource, config: config}}
+    else
+      {:error, {:bad_option, _} = reason} ->
+        {:error, {:invalid_options, reason}}
+
+      {:error, {:invalid_option_value, _} = reason} ->
+        {:error, {:invalid_options, reason}}
+
+      {:error, _} = error ->
+        error
+    end
+  end
+
+  def from_pattern(_pattern, _options), do: {:error, :invalid_pattern}
+
+  @spec from_pattern!(String.t(), [{:locale, LanguageTag.t() | String.t() | nil}] | map()) :: t()
+  def from_pattern!(pattern, options \\ []) do
+    case from_pattern(pattern, options) do
+      {:ok, formatter} -> formatter
+      {:error, reason} -> raise "temporal formatter creation failed: #{inspect(reason)}"
+    end
+  end
+
   @spec format(t(), Temporal.native_input()) ::
           {:ok, String.t()} | {:error, Temporal.format_error()}
   def format(%__MODULE__{resource: resource}, input) do
@@ -80,11 +304,12 @@ defmodule Icu.Temporal.Formatter do
     end
   end
 
-  @spec format_to_parts(t(), Temporal.native_input()) ::
+  @spec format_to_parts(t(), Temporal.native_input(), Options.options_input()) ::
           {:ok, [map()]} | {:error, Temporal.format_error()}
-  def format_to_parts(%__MODULE__{resource: resource}, input) do
-    with {:ok, temporal_map} <- normalize_input(input) do
-      Nif.temporal_format_to_parts(resource, temporal_map)
+  def format_to_parts(%__MODULE__{resource: resource}, input, options \\ []) do
+    with {:ok, index_mode} <- Options.index_mode(options),
+         {:ok, temporal_map} <- normalize_input(input) do
+      Nif.temporal_format_to_parts(resource, temporal_map, index_mode)
     end
   end
 
@@ -96,15 +321,63 @@ defmodule Icu.Temporal.Formatter do
     end
   end
 
+  @doc """
+  Returns the CLDR pattern the formatter resolved, such as
+  `"d. MMMM y 'um' HH:mm"`.
+
+  Some locales pick a different pattern depending on the value being
+  formatted, so an optional `sample` can be given to resolve the pattern used
+  for that value. Without a sample the pattern is resolved from the formatter
+  configuration alone.
+
+  ## Examples
+
+      iex> formatter = Icu.Temporal.Formatter.new!(date_fields: :ymd, length: :long, locale: "en")
+      iex> Icu.Temporal.Formatter.pattern(formatter)
+      {:ok, "MMMM d, y"}
+  """
+  @spec pattern(t(), Temporal.native_input() | nil) ::
+          {:ok, String.t()} | {:error, Temporal.format_error()}
+  def pattern(formatter, sample \\ nil)
+
+  def pattern(%__MODULE__{resource: resource}, nil) do
+    Nif.temporal_formatter_pattern(resource, nil)
+  end
+
+  def pattern(%__MODULE__{resource: resource}, sample) do
+    with {:ok, temporal_map} <- normalize_input(sample) do
+      Nif.temporal_formatter_pattern(resource, temporal_map)
+    end
+  end
+
+  @spec pattern!(t(), Temporal.native_input() | nil) :: String.t()
+  def pattern!(%__MODULE__{} = formatter, sample \\ nil) do
+    case pattern(formatter, sample) do
+      {:ok, pattern} -> pattern
+      {:error, reason} -> raise "temporal pattern resolution failed: #{inspect(reason)}"
+    end
+  end
+
   defimpl Inspect do
     def inspect(_formatter, _opts) do
       "#Icu.Temporal.Formatter<>"
     end
   end
 
+  # Fields of `t:Icu.Calendar.date/0` that are not part of a temporal map.
+  @calendar_date_keys [
+    :calendar,
+    :era,
+    :era_year,
+    :cyclic_year,
+    :related_iso,
+    :month_code,
+    :leap_month
+  ]
+
   @doc false
   @spec normalize_input(Temporal.native_input()) ::
-          {:ok, map()} | {:error, Temporal.format_error()}
+          {:ok, map() | String.t()} | {:error, Temporal.format_error()}
   def normalize_input(%Date{} = date) do
     %Elixir.Date{year: year, month: month, day: day, calendar: calendar} = date
 
@@ -144,10 +417,12 @@ defmodule Icu.Temporal.Formatter do
 
   def normalize_input(%DateTime{} = datetime) do
     with {:ok, calendar_identifier} <- Calendar.normalize_identifier(datetime.calendar) do
+      # The offset from UTC includes the daylight saving offset.
       value =
         datetime
         |> Map.delete(:__struct__)
         |> Map.put(:calendar_identifier, calendar_identifier)
+        |> Map.put(:utc_offset, datetime.utc_offset + datetime.std_offset)
 
       {:ok, value}
     else
@@ -157,23 +432,34 @@ defmodule Icu.Temporal.Formatter do
     _ -> {:error, :unsupported_calendar}
   end
 
+  # Dates from `Icu.Calendar` are moved to ISO, keeping any time fields.
+  def normalize_input(%{calendar: _, month_code: _} = fields) when not is_struct(fields) do
+    with {:ok, date} <- Calendar.convert(fields, :iso8601) do
+      value =
+        fields
+        |> Map.drop(@calendar_date_keys)
+        |> Map.merge(Map.take(date, [:year, :month, :day]))
+
+      {:ok, value}
+    else
+      {:error, :invalid_calendar} -> {:error, :unsupported_calendar}
+      {:error, _reason} -> {:error, :invalid_temporal}
+    end
+  end
+
+  def normalize_input(%{} = fields)
+      when not is_struct(fields) and (is_map_key(fields, :year) or is_map_key(fields, :hour)) do
+    {:ok, fields}
+  end
+
+  # ISO 8601 strings are parsed by the NIF.
+  def normalize_input(string) when is_binary(string), do: {:ok, string}
+
   def normalize_input(_), do: {:error, :invalid_temporal}
 
   @doc false
   @spec normalize_options(Temporal.options_input()) :: {:ok, map()} | Options.error()
   def normalize_options(options) do
-    Options.normalize_options(
-      :temporal,
-      options,
-      &(&1 in [
-          :length,
-          :date_fields,
-          :time_precision,
-          :zone_style,
-          :alignment,
-          :year_style,
-          :locale
-        ])
-    )
+    Options.normalize_options(:temporal, options, &(&1 in @option_keys))
   end
 end
@@ -0,0 +1,440 @@
+defmodule Icu.Text do
+  @moduledoc """
+  Grapheme-aware text helpers.
+
+  These functions operate on grapheme clusters (user-perceived characters)
+  as defined by Unicode text segmentation, so emoji sequences, flags and
+  combining marks are never split.
+  """
+
+  alias Icu.Formatter.Options
+  alias Icu.Nif
+
+  @titlecase_keys [:leading_adjustment, :trailing_case, :stop_words, :locale]
+
+  @typedoc "Where `titlecase_words/2` starts casing a word."
+  @type leading_adjustment :: :auto | :none | :to_cased
+
+  @typedoc "Options accepted by `titlecase_words/2`."
+  @type titlecase_options :: [
+          {:leading_adjustment, leading_adjustment()}
+          | {:trailing_case, :lower | :unchanged}
+          | {:stop_words, [String.t()]}
+          | {:locale, Icu.LanguageTag.t() | String.t()}
+        ]
+
+  @typedoc "Word counts returned by `word_stats/2`."
+  @type word_stats :: %{
+          words: non_neg_integer(),
+          unique: non_neg_integer(),
+          top: [{String.t(), pos_integer()}]
+        }
+
+  @default_top 10
+
+  @soft_break_keys [:marker, :locale]
+
+  @typedoc "A string `common_term/2` returns."
+  @type common_term ::
+          :and
+          | :or
+          | :quotation_start
+          | :quotation_end
+          | :alternate_quotation_start
+          | :alternate_quotation_end
+          | :ellipsis
+
+  @typedoc "Which quotation marks `quote_text/3` uses."
+  @type quote_level :: :primary | :secondary
+
+  @common_terms [
+    :and,
+    :or,
+    :quotation_start,
+    :quotation_end,
+    :alternate_quotation_start,
+    :alternate_quotation_end,
+    :ellipsis
+  ]
+
+  @typedoc "Options accepted by `insert_soft_breaks/2`."
+  @type soft_break_options :: [
+          {:marker, String.t()}
+          | {:locale, Icu.LanguageTag.t() | String.t()}
+        ]
+
+  @typedoc "Options accepted by `truncate/3`."
+  @type truncate_options :: [
+          {:ellipsis, String.t()}
+          | {:word_boundary, boolean()}
+        ]
+
+  @doc """
+  Truncates `text` to at most `max` grapheme clusters.
+
+  When the text is longer than `max`, it is cut on a grapheme boundary and
+  the ellipsis is appended; the ellipsis counts towards `max`. Text that
+  already fits is returned unchanged.
+
+  `max` counts grapheme clusters, not columns: an East Asian wide character
+  such as `"漢"` counts as one, like `"a"`. Use `display_width/2` to measure
+  how many monospace columns the result occupies.
+
+  ## Options
+
+  - `:ellipsis` – string appended to truncated text. Defaults to `"…"`, the
+    CLDR root ellipsis. ICU4X does not ship the CLDR `characters` data, so
+    the default does not follow the locale; pass the locale's ellipsis here
+    where it differs.
+  - `:word_boundary` – when `true`, cut at the last word boundary that fits
+    instead of mid-word. A single word longer than the limit is still cut.
+    Defaults to `false`.
+
+  ## Examples
+
+      iex> Icu.Text.truncate("Hello world", 8)
+      {:ok, "Hello w…"}
+
+      iex> Icu.Text.truncate("Hello world", 8, word_boundary: true)
+      {:ok, "Hello…"}
+
+      iex> Icu.Text.truncate("👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧", 2)
+      {:ok, "👨‍👩‍👧…"}
+
+      iex> Icu.Text.truncate("short", 10)
+      {:ok, "short"}
+  """
+  @spec truncate(String.t(), non_neg_integer(), truncate_options()) ::
+          {:ok, String.t()} | {:error, term()}
+  def truncate(text, max, options \\ [])
+
+  def truncate(text, max, options) when is_binary(text) and is_integer(max) and max >= 0 do
+    with {:ok, opts} <-
+           Options.normalize_options(:text, options, &(&1 in [:ellipsis, :word_boundary])) do
+      Nif.text_truncate(text, max, Map.delete(opts, :locale))
+    end
+  end
+
+  def truncate(_text, _max, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Like `truncate/3`, but raises on error.
+  """
+  @spec truncate!(String.t(), non_neg_integer(), truncate_options()) :: String.t()
+  def truncate!(text, max, options \\ []) do
+    case truncate(text, max, options) do
+      {:ok, truncated} -> truncated
+      {:error, reason} -> raise "truncation failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns the number of monospace columns `text` occupies.
+
+  Widths come from the Unicode East Asian Width property and are measured
+  per grapheme cluster: wide and fullwidth characters (CJK ideographs, kana,
+  Hangul, emoji) take two columns, combining marks and joiners add nothing to
+  their cluster, and control characters take none.
+
+  ## Options
+
+  - `:ambiguous` – width of East Asian ambiguous characters such as `"±"` or
+    Greek letters: `:narrow` (1 column, the default) or `:wide` (2 columns,
+    as rendered by CJK legacy terminals).
+
+  ## Examples
+
+      iex> Icu.Text.display_width("abc")
+      {:ok, 3}
+
+      iex> Icu.Text.display_width("日本語")
+      {:ok, 6}
+
+      iex> Icu.Text.display_width("±1", ambiguous: :wide)
+      {:ok, 3}
+  """
+  @spec display_width(String.t(), [{:ambiguous, :narrow | :wide}]) ::
+          {:ok, non_neg_integer()} | {:error, term()}
+  def display_width(text, options \\ [])
+
+  def display_width(text, options) when is_binary(text) do
+    with {:ok, opts} <- Options.normalize_options(:text, options, &(&1 == :ambiguous)) do
+      Nif.text_display_width(text, Map.delete(opts, :locale))
+    end
+  end
+
+  @doc """
+  Like `display_width/2`, but raises on error.
+  """
+  @spec display_width!(String.t(), [{:ambiguous, :narrow | :wide}]) :: non_neg_integer()
+  def display_width!(text, options \\ []) do
+    case display_width(text, options) do
+      {:ok, width} -> width
+      {:error, reason} -> raise "display width calculation failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Titlecases every word of `text`, as for a heading.
+
+  Casing follows the rules of the locale rather than upcasing the first
+  letter: Dutch capitalizes the "ij" digraph as a whole, and Turkish and
+  Azerbaijani uppercase "i" to "İ". Words are found by Unicode word
+  segmentation, so an apostrophe inside a word such as "o'clock" does not
+  start a new one.
+
+  ## Options
+
+  - `:leading_adjustment` – where casing starts in a word that begins with
+    something other than a letter. `:auto` (the default) skips to the first
+    letter, digit or symbol, `:to_cased` skips to the first character that
+    has case, and `:none` cases the first character whatever it is.
+  - `:trailing_case` – `:lower` (the default) lowercases the rest of each
+    word, `:unchanged` keeps it as written, which preserves acronyms.
+  - `:stop_words` – words lowercased instead of titlecased, such as
+    `["a", "of", "the"]`, unless they start or end the text. They are
+    matched regardless of case.
+  - `:locale` – the locale whose casing rules apply; defaults to the
+    application locale.
+
+  ## Examples
+
+      iex> Icu.Text.titlecase_words("the lord of the rings", stop_words: ["of", "the"])
+      {:ok, "The Lord of the Rings"}
+
+      iex> Icu.Text.titlecase_words("ijsselmeer en ijmuiden", locale: "nl")
+      {:ok, "IJsselmeer En IJmuiden"}
+
+      iex> Icu.Text.titlecase_words("NASA launches", trailing_case: :unchanged)
+      {:ok, "NASA Launches"}
+  """
+  @spec titlecase_words(String.t(), titlecase_options()) :: {:ok, String.t()} | {:error, term()}
+  def titlecase_words(text, options \\ [])
+
+  def titlecase_words(text, options) when is_binary(text) do
+    with {:ok, opts} <- Options.normalize_options(:text, options, &(&1 in @titlecase_keys)) do
+      Nif.text_titlecase_words(text, Map.fetch!(opts, :locale), Map.delete(opts, :locale))
+    end
+  end
+
+  def titlecase_words(_text, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Like `titlecase_words/2`, but raises on error.
+  """
+  @spec titlecase_words!(String.t(), titlecase_options()) :: String.t()
+  def titlecase_words!(text, options \\ []) do
+    case titlecase_words(text, options) do
+      {:ok, titlecased} -> titlecased
+      {:error, reason} -> raise "titlecasing failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Counts the words of `text`.
+
+  Words are found by Unicode word segmentation, including dictionary-based
+  segmentation of Chinese, Japanese, Thai and others, and compared after
+  case folding. Spaces, punctuation and emoji between words are not counted.
+
+  Returns the number of words, the number of distinct words, and the most
+  frequent ones in their case-folded form with their counts. Words that
+  occur equally often are ordered by code point.
+
+  ## Options
+
+  - `:top` – how many of the most frequent words to return, #{@default_top}
+    by default.
+
+  ## Examples
+
+      iex> Icu.Text.word_stats("The cat and the hat.", top: 2)
+      {:ok, %{words: 5, unique: 4, top: [{"the", 2}, {"and", 1}]}}
+  """
+  @spec word_stats(String.t(), [{:top, non_neg_integer()}]) ::
+          {:ok, word_stats()} | {:error, term()}
+  def word_stats(text, options \\ [])
+
+  def word_stats(text, options) when is_binary(text) do
+    with {:ok, opts} <- Options.normalize_options(:text, options, &(&1 == :top)) do
+      Nif.text_word_stats(text, Map.get(opts, :top, @default_top))
+    end
+  end
+
+  def word_stats(_text, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Like `word_stats/2`, but raises on error.
+  """
+  @spec word_stats!(String.t(), [{:top, non_neg_integer()}]) :: word_stats()
+  def word_stats!(text, options \\ []) do
+    case word_stats(text, options) do
+      {:ok, stats} -> stats
+      {:error, reason} -> raise "word statistics failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Inserts a soft hyphen (U+00AD) at the line break opportunities inside
+  runs of text, so that HTML emails and narrow columns can wrap them.
+
+  Break opportunities are those of Unicode line breaking tailored to the
+  locale: between words of Thai, Lao, Khmer and Burmese found by dictionary,
+  and after characters such as slashes. Opportunities after spaces and
+  dashes, and next to Chinese, Japanese and Korean characters, wrap without
+  help and get no marker. ICU has no hyphenation data, so words of languages
+  written with spaces are not broken into syllables.
+
+  Calling this again on its output inserts nothing more.
+
+  ## Options
+
+  - `:marker` – what to insert instead of a soft hyphen, such as a zero
+    width space (`"\\u200B"`) for scripts that are not hyphenated or
+    `"<wbr>"` for HTML.
+  - `:locale` – the locale whose line breaking applies; defaults to the
+    application locale.
+
+  ## Examples
+
+      iex> Icu.Text.insert_soft_breaks("read/write", marker: "|")
+      {:ok, "read/|write"}
+
+      iex> Icu.Text.insert_soft_breaks("日本語です", marker: "|", locale: "ja")
+      {:ok, "日本語です"}
+
+      iex> Icu.Text.insert_soft_breaks("well-known words", marker: "|")
+      {:ok, "well-known words"}
+  """
+  @spec insert_soft_breaks(String.t(), soft_break_options()) ::
+          {:ok, String.t()} | {:error, term()}
+  def insert_soft_breaks(text, options \\ [])
+
+  def insert_soft_breaks(text, options) when is_binary(text) do
+    with {:ok, opts} <- Options.normalize_options(:text, options, &(&1 in @soft_break_keys)) do
+      marker = Map.get(opts, :marker, "\u00AD")
+      Nif.text_insert_soft_breaks(text, Map.fetch!(opts, :locale), marker)
+    end
+  end
+
+  def insert_soft_breaks(_text, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Like `insert_soft_breaks/2`, but raises on error.
+  """
+  @spec insert_soft_breaks!(String.t(), soft_break_options()) :: String.t()
+  def insert_soft_breaks!(text, options \\ []) do
+    case insert_soft_breaks(text, options) do
+      {:ok, broken} -> broken
+      {:error, reason} -> raise "soft break insertion failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Returns a short localized string that UI code would otherwise hard-code
+  for each language.
+
+  ## Terms
+
+  - `:and`, `:or` – the word joining the two items of a list, as
+    `Icu.List` uses it, without the spaces around it.
+  - `:quotation_start`, `:quotation_end` – the marks around a quotation.
+  - `:alternate_quotation_start`, `:alternate_quotation_end` – the marks
+    around a quotation inside another one.
+  - `:ellipsis` – the character that stands for left out text.
+
+  ICU4X does not carry the CLDR quotation mark and ellipsis data, so the
+  marks of the common languages are bundled. Other languages return
+  `{:error, :no_data}` for the marks rather than another language's. The
+  ellipsis is the CLDR root `"…"` in every language. CLDR has
+  no localized "more" patterns such as "+3 more"; those belong in the
+  application's messages.
+
+  ## Options
+
+  - `:locale` – the locale of the string; defaults to the application
+    locale.
+
+  ## Examples
+
+      iex> Icu.Text.common_term(:or, locale: "de")
+      {:ok, "oder"}
+
+      iex> Icu.Text.common_term(:quotation_start, locale: "de-AT")
+      {:ok, "„"}
+
+      iex> Icu.Text.common_term(:alternate_quotation_end, locale: "ja")
+      {:ok, "』"}
+  """
+  @spec common_term(common_term(), [{:locale, Icu.LanguageTag.t() | String.t()}]) ::
+          {:ok, String.t()} | {:error, term()}
+  def common_term(term, options \\ [])
+
+  def common_term(term, options) when term in @common_terms do
+    with {:ok, opts} <- Options.normalize_options(:text, options, &(&1 == :locale)) do
+      Nif.common_terms(Map.fetch!(opts, :locale), term)
+    end
+  end
+
+  def common_term(_term, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Like `common_term/2`, but raises on error.
+  """
+  @spec common_term!(common_term(), [{:locale, Icu.LanguageTag.t() | String.t()}]) ::
+          String.t()
+  def common_term!(term, options \\ []) do
+    case common_term(term, options) do
+      {:ok, string} -> string
+      {:error, reason} -> raise "common term lookup failed: #{inspect(reason)}"
+    end
+  end
+
+  @doc """
+  Wraps `text` in the quotation marks of the locale, such as „…“, «…» or
+  “…”. Named so because `quote` is taken by Elixir itself.
+
+  `:primary` quotes use the marks of a quotation, and `:secondary` ones the
+  marks of a quotation inside another, as `common_term/2` returns them. A
+  locale whose marks are not bundled returns `{:error, :no_data}`.
+
+  ## Options
+
+  - `:locale` – the locale whose quotation marks apply; defaults to the
+    application locale.
+
+  ## Examples
+
+      iex> Icu.Text.quote_text("Hallo", :primary, locale: "de")
+      {:ok, "„Hallo“"}
+
+      iex> Icu.Text.quote_text("Bonjour", :primary, locale: "fr")
+      {:ok, "«Bonjour»"}
+
+      iex> Icu.Text.quote_text("Hello", :secondary, locale: "en")
+      {:ok, "‘Hello’"}
+  """
+  @spec quote_text(String.t(), quote_level(), [{:locale, Icu.LanguageTag.t() | String.t()}]) ::
+          {:ok, String.t()} | {:error, term()}
+  def quote_text(text, level, options \\ [])
+
+  def quote_text(text, level, options) when is_binary(text) and level in [:primary, :secondary] do
+    with {:ok, opts} <- Options.normalize_options(:text, options, &(&1 == :locale)) do
+      Nif.text_quote(text, Map.fetch!(opts, :locale), level)
+    end
+  end
+
+  def quote_text(_text, _level, _options), do: {:error, :invalid_options}
+
+  @doc """
+  Like `quote_text/3`, but raises on error.
+  """
+  @spec quote_text!(String.t(), quote_level(), [{:locale, Icu.LanguageTag.t() | String.t()}]) ::
+          String.t()
+  def quote_text!(text, level, options \\ []) do
+    case quote_text(text, level, options) do
+      {:ok, quoted} -> quoted
+      {:error, reason} -> raise "quoting failed: #{inspect(reason)}"
+    end
+  end
+end
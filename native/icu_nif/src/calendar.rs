@@ -0,0 +1,635 @@
+//! Conversion of dates between the calendars supported by ICU4X.
+//!
+//! Dates cross the NIF boundary either as an ISO `{year, month, day}` tuple or
+//! as a map of calendar fields (`year`, `era`, `month_code` or `month`,
+//! `day`) interpreted in the calendar passed alongside.
+
+use std::ops::RangeInclusive;
+
+use icu::calendar::provider::{Baked as CalendarBaked, CalendarJapaneseModernV1};
+use icu::calendar::types::{DateDuration, DateDurationUnit, MonthCode, RataDie};
+use icu::calendar::week::WeekInformation;
+use icu::calendar::{AnyCalendar, AnyCalendarKind, Date, Iso, Ref};
+use icu::datetime::fieldsets::enums::CompositeFieldSet;
+use icu::datetime::DateTimeFormatter;
+use icu::locale::preferences::extensions::unicode::keywords::{
+    CalendarAlgorithm, HijriCalendarAlgorithm,
+};
+use icu_provider::{DataProvider as _, DataRequest, DataResponse};
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, NifTaggedEnum, Term, TermType};
+use tinystr::TinyAsciiStr;
+
+use crate::atoms;
+use crate::datetime::{self, NamedField};
+use crate::locale::LocaleArg;
+use crate::stats::{self, Counted};
+
+/// Days between the start of the rata die count (0001-01-01 ISO is day 1)
+/// and the Unix epoch.
+pub(crate) const UNIX_EPOCH_RATA_DIE: i64 = 719_163;
+
+/// Formatter reading the localized name of a single date field.
+type NameFormatter = DateTimeFormatter<CompositeFieldSet>;
+
+/// A date in a specific calendar, as returned to Elixir.
+#[derive(NifMap)]
+pub(crate) struct CalendarDate {
+    calendar: String,
+    year: i32,
+    era: Option<String>,
+    era_year: Option<i32>,
+    cyclic_year: Option<u8>,
+    related_iso: Option<i32>,
+    month: u8,
+    month_code: String,
+    leap_month: bool,
+    day: u8,
+}
+
+impl CalendarDate {
+    fn new(id: &str, date: &Date<Ref<AnyCalendar>>) -> Self {
+        let year = date.year();
+        let era = year.era();
+        let cyclic = year.cyclic();
+        let month = date.month();
+        let month_code = month.standard_code.0.to_string();
+        CalendarDate {
+            calendar: id.to_string(),
+            year: date.extended_year(),
+            era: era.as_ref().map(|era| era.era.to_string()),
+            era_year: era.map(|era| era.year),
+            cyclic_year: cyclic.as_ref().map(|cyclic| cyclic.year),
+            related_iso: cyclic.map(|cyclic| cyclic.related_iso),
+            month: month.ordinal,
+            leap_month: month_code.ends_with('L'),
+            month_code,
+            day: date.day_of_month().0,
+        }
+    }
+}
+
+/// Returns the calendar for a BCP-47 calendar identifier such as `"hebrew"`.
+pub(crate) fn calendar_kind(id: &str) -> Option<AnyCalendarKind> {
+    let kind = match id {
+        "buddhist" => AnyCalendarKind::Buddhist,
+        "chinese" => AnyCalendarKind::Chinese,
+        "coptic" => AnyCalendarKind::Coptic,
+        "dangi" => AnyCalendarKind::Dangi,
+        "ethioaa" => AnyCalendarKind::EthiopianAmeteAlem,
+        "ethiopic" => AnyCalendarKind::Ethiopian,
+        "gregory" => AnyCalendarKind::Gregorian,
+        "hebrew" => AnyCalendarKind::Hebrew,
+        "indian" => AnyCalendarKind::Indian,
+        "islamic" | "islamic-rgsa" => AnyCalendarKind::HijriSimulatedMecca,
+        "islamic-civil" => AnyCalendarKind::HijriTabularTypeIIFriday,
+        "islamic-tbla" => AnyCalendarKind::HijriTabularTypeIIThursday,
+        "islamic-umalqura" => AnyCalendarKind::HijriUmmAlQura,
+        "iso8601" => AnyCalendarKind::Iso,
+        "japanese" => AnyCalendarKind::Japanese,
+        "japanext" => AnyCalendarKind::JapaneseExtended,
+        "persian" => AnyCalendarKind::Persian,
+        "roc" => AnyCalendarKind::Roc,
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
+/// Returns the formatting calendar for a BCP-47 calendar identifier. Unlike
+/// `calendar_kind`, the extended Japanese calendar has no formatting data.
+pub(crate) fn calendar_algorithm(id: &str) -> Option<CalendarAlgorithm> {
+    let algorithm = match id {
+        "buddhist" => CalendarAlgorithm::Buddhist,
+        "chinese" => CalendarAlgorithm::Chinese,
+        "coptic" => CalendarAlgorithm::Coptic,
+        "dangi" => CalendarAlgorithm::Dangi,
+        "ethioaa" => CalendarAlgorithm::Ethioaa,
+        "ethiopic" => CalendarAlgorithm::Ethiopic,
+        "gregory" => CalendarAlgorithm::Gregory,
+        "hebrew" => CalendarAlgorithm::Hebrew,
+        "indian" => CalendarAlgorithm::Indian,
+        "islamic" => CalendarAlgorithm::Hijri(None),
+        "islamic-civil" => CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Civil)),
+        "islamic-rgsa" => CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Rgsa)),
+        "islamic-tbla" => CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Tbla)),
+        "islamic-umalqura" => CalendarAlgorithm::Hijri(Some(HijriCalendarAlgorithm::Umalqura)),
+        "iso8601" => CalendarAlgorithm::Iso8601,
+        "japanese" => CalendarAlgorithm::Japanese,
+        "persian" => CalendarAlgorithm::Persian,
+        "roc" => CalendarAlgorithm::Roc,
+        _ => return None,
+    };
+
+    Some(algorithm)
+}
+
+/// Returns the rata die of a date, counting 0001-01-01 (ISO) as day 1.
+#[rustler::nif]
+pub(crate) fn calendar_date_to_rata_die<'a>(
+    env: Env<'a>,
+    date_term: Term<'a>,
+    calendar: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarDateToRataDie, || {
+        calendar_date_to_rata_die_inner(env, date_term, calendar)
+    })
+}
+
+fn calendar_date_to_rata_die_inner<'a>(
+    env: Env<'a>,
+    date_term: Term<'a>,
+    calendar: &str,
+) -> NifResult<Term<'a>> {
+    let calendar = match calendar_kind(calendar) {
+        Some(kind) => AnyCalendar::new(kind),
+        None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+    };
+
+    let date = match decode_date(date_term, &calendar) {
+        Ok(date) => date,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    Ok((atoms::ok(), date.to_rata_die().to_i64_date()).encode(env))
+}
+
+/// Returns the date of a rata die in `calendar`.
+#[rustler::nif]
+pub(crate) fn calendar_date_from_rata_die<'a>(
+    env: Env<'a>,
+    rata_die: i64,
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarDateFromRataDie, || {
+        calendar_date_from_rata_die_inner(env, rata_die, calendar_id)
+    })
+}
+
+fn calendar_date_from_rata_die_inner<'a>(
+    env: Env<'a>,
+    rata_die: i64,
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    let calendar = match calendar_kind(calendar_id) {
+        Some(kind) => AnyCalendar::new(kind),
+        None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+    };
+
+    let date = match iso_from_rata_die(rata_die) {
+        Some(date) => date.to_calendar(Ref(&calendar)),
+        None => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    Ok((atoms::ok(), CalendarDate::new(calendar_id, &date)).encode(env))
+}
+
+/// An era of the modern Japanese calendar, as returned to Elixir.
+#[derive(NifMap)]
+struct JapaneseEra {
+    code: String,
+    name: Option<String>,
+    start: (i32, u8, u8),
+}
+
+/// Returns the era codes and ISO start dates of the Japanese calendar from
+/// Meiji onwards, oldest first.
+fn japanese_era_starts() -> Vec<(String, Date<Iso>)> {
+    let response: DataResponse<CalendarJapaneseModernV1> =
+        match CalendarBaked.load(DataRequest::default()) {
+            Ok(response) => response,
+            Err(_) => return Vec::new(),
+        };
+
+    response
+        .payload
+        .get()
+        .dates_to_eras
+        .iter()
+        .filter_map(|(start, code)| {
+            let date = Date::try_new_iso(start.year, start.month, start.day).ok()?;
+            Some((code.to_string(), date))
+        })
+        .collect()
+}
+
+fn japanese_era(formatter: Option<&NameFormatter>, code: String, start: Date<Iso>) -> JapaneseEra {
+    JapaneseEra {
+        name: formatter
+            .and_then(|formatter| datetime::field_name(formatter, start, NamedField::Era)),
+        start: (
+            start.extended_year(),
+            start.month().ordinal,
+            start.day_of_month().0,
+        ),
+        code,
+    }
+}
+
+fn japanese_era_formatter(locale: &LocaleArg) -> Option<NameFormatter> {
+    datetime::field_name_formatter(&locale.0, CalendarAlgorithm::Japanese, NamedField::Era)
+}
+
+/// Returns the eras of the Japanese calendar with their names in `locale`.
+#[rustler::nif]
+pub(crate) fn calendar_japanese_eras<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarJapaneseEras, || {
+        calendar_japanese_eras_inner(env, locale_term)
+    })
+}
+
+fn calendar_japanese_eras_inner<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let formatter = japanese_era_formatter(&locale_arg);
+    let eras: Vec<JapaneseEra> = japanese_era_starts()
+        .into_iter()
+        .map(|(code, start)| japanese_era(formatter.as_ref(), code, start))
+        .collect();
+
+    Ok((atoms::ok(), eras).encode(env))
+}
+
+/// Returns the Japanese era a date falls in, or `nil` before Meiji.
+#[rustler::nif]
+pub(crate) fn calendar_japanese_era_for_date<'a>(
+    env: Env<'a>,
+    date_term: Term<'a>,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarJapaneseEraForDate, || {
+        calendar_japanese_era_for_date_inner(env, date_term, locale_term)
+    })
+}
+
+fn calendar_japanese_era_for_date_inner<'a>(
+    env: Env<'a>,
+    date_term: Term<'a>,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let calendar = AnyCalendar::new(AnyCalendarKind::Gregorian);
+    let date = match decode_date(date_term, &calendar) {
+        Ok(date) => date.to_rata_die().to_i64_date(),
+        Err(_) => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    let era = japanese_era_starts()
+        .into_iter()
+        .take_while(|(_, start)| rata_die(start) <= date)
+        .last()
+        .map(|(code, start)| {
+            let formatter = japanese_era_formatter(&locale_arg);
+            japanese_era(formatter.as_ref(), code, start)
+        });
+
+    Ok((atoms::ok(), era).encode(env))
+}
+
+/// Returns the weekend days of a locale's region as ISO weekday numbers,
+/// Monday being 1.
+#[rustler::nif]
+pub(crate) fn calendar_weekend<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarWeekend, || {
+        calendar_weekend_inner(env, locale_term)
+    })
+}
+
+fn calendar_weekend_inner<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let week = match WeekInformation::try_new((&locale_arg.0).into()) {
+        Ok(week) => week,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let mut days: Vec<u8> = week.weekend().map(|day| day as u8).collect();
+    days.sort_unstable();
+    Ok((atoms::ok(), days).encode(env))
+}
+
+/// A year in a calendar, as returned to Elixir.
+#[derive(NifMap)]
+struct CalendarYear {
+    leap_year: bool,
+    months_in_year: u8,
+    days_in_year: u16,
+}
+
+/// A month of a year in a calendar, as returned to Elixir.
+#[derive(NifMap)]
+struct CalendarMonth {
+    ordinal: u8,
+    month_code: String,
+    leap_month: bool,
+    name: Option<String>,
+}
+
+fn first_day_of_year(year: i32, calendar: &AnyCalendar) -> Option<Date<Ref<'_, AnyCalendar>>> {
+    let month_code = MonthCode(TinyAsciiStr::try_from_str("M01").ok()?);
+    Date::try_new_from_codes(None, year, month_code, 1, Ref(calendar)).ok()
+}
+
+/// Returns whether an extended year of a calendar is a leap year, with its
+/// number of months and days.
+#[rustler::nif]
+pub(crate) fn calendar_year_info<'a>(
+    env: Env<'a>,
+    year: i32,
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarYearInfo, || {
+        calendar_year_info_inner(env, year, calendar_id)
+    })
+}
+
+fn calendar_year_info_inner<'a>(env: Env<'a>, year: i32, calendar_id: &str) -> NifResult<Term<'a>> {
+    let calendar = match calendar_kind(calendar_id) {
+        Some(kind) => AnyCalendar::new(kind),
+        None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+    };
+
+    let first = match first_day_of_year(year, &calendar) {
+        Some(date) => date,
+        None => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    let info = CalendarYear {
+        leap_year: first.is_in_leap_year(),
+        months_in_year: first.months_in_year(),
+        days_in_year: first.days_in_year(),
+    };
+    Ok((atoms::ok(), info).encode(env))
+}
+
+/// Returns the months of an extended year of a calendar in order, named as
+/// standalone months in `locale`.
+#[rustler::nif]
+pub(crate) fn calendar_months<'a>(
+    env: Env<'a>,
+    year: i32,
+    calendar_id: &str,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarMonths, || {
+        calendar_months_inner(env, year, calendar_id, locale_term)
+    })
+}
+
+fn calendar_months_inner<'a>(
+    env: Env<'a>,
+    year: i32,
+    calendar_id: &str,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let calendar = match calendar_kind(calendar_id) {
+        Some(kind) => AnyCalendar::new(kind),
+        None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+    };
+
+    let months_in_year = match first_day_of_year(year, &calendar) {
+        Some(date) => date.months_in_year(),
+        None => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    let formatter = calendar_algorithm(calendar_id).and_then(|algorithm| {
+        datetime::field_name_formatter(&locale_arg.0, algorithm, NamedField::Month)
+    });
+
+    let months: Vec<CalendarMonth> = (0..months_in_year)
+        .filter_map(|offset| {
+            let duration = DateDuration::new(0, i32::from(offset), 0, 0);
+            let date = first_day_of_year(year, &calendar)?.added(duration);
+            let month = date.month();
+            let month_code = month.standard_code.0.to_string();
+            let name = formatter.as_ref().and_then(|formatter| {
+                datetime::field_name(formatter, date.to_iso(), NamedField::Month)
+            });
+            Some(CalendarMonth {
+                ordinal: month.ordinal,
+                leap_month: month_code.ends_with('L'),
+                month_code,
+                name,
+            })
+        })
+        .collect();
+
+    Ok((atoms::ok(), months).encode(env))
+}
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum DurationUnit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+}
+
+/// Adds `{years, months, weeks, days}` to a date. Each unit is added in turn
+/// from years down to days.
+#[rustler::nif]
+pub(crate) fn calendar_date_add<'a>(
+    env: Env<'a>,
+    date_term: Term<'a>,
+    duration: (i32, i32, i32, i32),
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarDateAdd, || {
+        calendar_date_add_inner(env, date_term, duration, calendar_id)
+    })
+}
+
+fn calendar_date_add_inner<'a>(
+    env: Env<'a>,
+    date_term: Term<'a>,
+    duration: (i32, i32, i32, i32),
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    let calendar = match calendar_kind(calendar_id) {
+        Some(kind) => AnyCalendar::new(kind),
+        None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+    };
+
+    let date = match decode_date(date_term, &calendar) {
+        Ok(date) => date,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    let (years, months, weeks, days) = duration;
+    let date = date.added(DateDuration::new(years, months, weeks, days));
+    Ok((atoms::ok(), CalendarDate::new(calendar_id, &date)).encode(env))
+}
+
+/// Returns the number of whole `unit`s from `to` to `from`, negative when
+/// `from` is the earlier date.
+///
+/// Months and years are counted with `Date::until`, so they follow the month
+/// lengths and leap months of the calendar.
+#[rustler::nif]
+pub(crate) fn calendar_date_diff<'a>(
+    env: Env<'a>,
+    from_term: Term<'a>,
+    to_term: Term<'a>,
+    unit: DurationUnit,
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CalendarDateDiff, || {
+        calendar_date_diff_inner(env, from_term, to_term, unit, calendar_id)
+    })
+}
+
+fn calendar_date_diff_inner<'a>(
+    env: Env<'a>,
+    from_term: Term<'a>,
+    to_term: Term<'a>,
+    unit: DurationUnit,
+    calendar_id: &str,
+) -> NifResult<Term<'a>> {
+    let calendar = match calendar_kind(calendar_id) {
+        Some(kind) => AnyCalendar::new(kind),
+        None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+    };
+
+    let (from, to) = match (
+        decode_date(from_term, &calendar),
+        decode_date(to_term, &calendar),
+    ) {
+        (Ok(from), Ok(to)) => (from, to),
+        _ => return Ok((atoms::error(), atoms::invalid_date()).encode(env)),
+    };
+
+    let days = from.to_rata_die().to_i64_date() - to.to_rata_die().to_i64_date();
+
+    let count = match unit {
+        DurationUnit::Days => days,
+        DurationUnit::Weeks => days / 7,
+        DurationUnit::Months => {
+            let duration = to.until(&from, DateDurationUnit::Months, DateDurationUnit::Days);
+            i64::from(duration.months)
+        }
+        DurationUnit::Years => {
+            let duration = to.until(&from, DateDurationUnit::Years, DateDurationUnit::Days);
+            i64::from(duration.years)
+        }
+    };
+
+    Ok((atoms::ok(), count).encode(env))
+}
+
+/// Decodes an ISO `{year, month, day}` tuple or a map of calendar fields.
+pub(crate) fn decode_date<'a, 'c>(
+    term: Term<'a>,
+    calendar: &'c AnyCalendar,
+) -> Result<Date<Ref<'c, AnyCalendar>>, ()> {
+    if term.get_type() == TermType::Tuple {
+        let (year, month, day): (i32, u8, u8) = term.decode().map_err(|_| ())?;
+        let date = iso_date(year, month, day).ok_or(())?;
+        return Ok(date.to_calendar(Ref(calendar)));
+    }
+
+    let day: u8 = term
+        .map_get(atoms::day())
+        .and_then(|t| t.decode())
+        .map_err(|_| ())?;
+
+    // The year is counted in the era when both are given, and is the extended
+    // year otherwise.
+    let era: Option<String> = optional_field(term, atoms::era())?;
+    let era_year: Option<i32> = optional_field(term, atoms::era_year())?;
+    let (era, year) = match (era, era_year) {
+        (Some(era), Some(era_year)) => (Some(era), era_year),
+        _ => {
+            let year: i32 = term
+                .map_get(atoms::year())
+                .and_then(|t| t.decode())
+                .map_err(|_| ())?;
+            (None, year)
+        }
+    };
+
+    // `month` is the number of a non-leap month; leap months need a code.
+    let code = match term.map_get(atoms::month_code()) {
+        Ok(value) => value.decode::<String>().map_err(|_| ())?,
+        Err(_) => {
+            let month: u8 = term
+                .map_get(atoms::month())
+                .and_then(|t| t.decode())
+                .map_err(|_| ())?;
+            format!("M{month:02}")
+        }
+    };
+    let month_code = MonthCode(TinyAsciiStr::try_from_str(&code).map_err(|_| ())?);
+
+    Date::try_new_from_codes(era.as_deref(), year, month_code, day, Ref(calendar)).map_err(|_| ())
+}
+
+fn optional_field<'a, T: rustler::Decoder<'a>>(term: Term<'a>, key: Atom) -> Result<Option<T>, ()> {
+    match term.map_get(key) {
+        Ok(value) => value.decode().map_err(|_| ()),
+        Err(_) => Ok(None),
+    }
+}
+
+pub(crate) fn rata_die(date: &Date<Iso>) -> i64 {
+    date.to_rata_die().to_i64_date()
+}
+
+/// The ISO date of a rata die, or `None` outside `ISO_YEARS`.
+pub(crate) fn iso_from_rata_die(rata_die: i64) -> Option<Date<Iso>> {
+    let first = iso_date(*ISO_YEARS.start(), 1, 1)?;
+    let last = iso_date(*ISO_YEARS.end(), 12, 31)?;
+    if !(self::rata_die(&first)..=self::rata_die(&last)).contains(&rata_die) {
+        return None;
+    }
+    Some(Date::from_rata_die(RataDie::new(rata_die), Iso))
+}
+
+/// The years of the ISO dates this crate handles: the expanded years of ISO
+/// 8601, which ICU4X represents although `Date::try_new_iso` stops at ±9999.
+pub(crate) const ISO_YEARS: RangeInclusive<i32> = -999_999..=999_999;
+
+/// The number of days in an ISO month.
+pub(crate) fn iso_days_in_month(year: i32, month: u8) -> u8 {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days in 400 Gregorian years, after which the calendar repeats.
+const GREGORIAN_CYCLE_DAYS: i64 = 146_097;
+
+/// Builds an ISO date, reaching the expanded years `Date::try_new_iso`
+/// rejects from the year at the same place in the 400-year cycle.
+pub(crate) fn iso_date(year: i32, month: u8, day: u8) -> Option<Date<Iso>> {
+    if let Ok(date) = Date::try_new_iso(year, month, day) {
+        return Some(date);
+    }
+    if !ISO_YEARS.contains(&year) {
+        return None;
+    }
+    let date = Date::try_new_iso(year.rem_euclid(400), month, day).ok()?;
+    let cycles = i64::from(year.div_euclid(400));
+    let rata_die = rata_die(&date) + cycles * GREGORIAN_CYCLE_DAYS;
+    Some(Date::from_rata_die(RataDie::new(rata_die), Iso))
+}
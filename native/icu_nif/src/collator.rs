@@ -0,0 +1,470 @@
+use std::cmp::Ordering;
+
+use icu::collator::options::{CaseLevel, CollatorOptions, Strength};
+use icu::collator::preferences::{CollationCaseFirst, CollationNumericOrdering};
+use icu::collator::provider::{Baked as CollatorBaked, CollationTailoringV1};
+use icu::collator::{Collator, CollatorBorrowed, CollatorPreferences};
+use icu::locale::Locale;
+use icu::segmenter::GraphemeClusterSegmenter;
+use icu_provider::prelude::*;
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, NifTaggedEnum, ResourceArc, Term, TermType};
+
+use crate::atoms;
+use crate::locale::{self, resolve_data_locale, LocaleArg};
+use crate::options::{decode_map, UnknownKeys};
+use crate::stats::{self, Counted};
+
+pub(crate) struct CollatorResource {
+    collator: CollatorBorrowed<'static>,
+    locale: Locale,
+}
+
+impl rustler::Resource for CollatorResource {}
+
+impl CollatorResource {
+    /// The locale of the collation tailoring the collator loaded.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        resolve_data_locale::<CollationTailoringV1, _>(
+            &CollatorBaked,
+            &self.locale,
+            DataMarkerAttributes::empty(),
+        )
+    }
+
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        std::mem::size_of::<Self>() + locale::heap_size(&self.locale)
+    }
+}
+
+#[derive(NifMap)]
+struct ResolvedOptions {
+    strength: Atom,
+    case_first: Atom,
+    numeric: bool,
+}
+
+pub(crate) fn load(env: Env) -> bool {
+    env.register::<CollatorResource>().is_ok()
+}
+
+#[rustler::nif]
+pub(crate) fn collator_new<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorNew, || {
+        collator_new_inner(env, locale_term, options_term)
+    })
+}
+
+fn collator_new_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let config = match decode_config(options_term) {
+        Ok(config) => config,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let mut prefs: CollatorPreferences = locale_arg.0.clone().into();
+    if let Some(numeric) = config.numeric {
+        prefs.numeric_ordering = Some(if numeric {
+            CollationNumericOrdering::True
+        } else {
+            CollationNumericOrdering::False
+        });
+    }
+    if let Some(case_first) = config.case_first {
+        prefs.case_first = Some(case_first);
+    }
+
+    let collator = match Collator::try_new(prefs, config.options) {
+        Ok(collator) => collator,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let resource = CollatorResource {
+        collator,
+        locale: locale_arg.0,
+    };
+    Ok((atoms::ok(), ResourceArc::new(resource)).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn collator_compare<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorCompare, || {
+        collator_compare_inner(env, collator_term, left, right)
+    })
+}
+
+fn collator_compare_inner<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CollatorResource> = match collator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let ordering = match resource.collator.compare(left, right) {
+        Ordering::Less => atoms::lt(),
+        Ordering::Equal => atoms::eq(),
+        Ordering::Greater => atoms::gt(),
+    };
+
+    Ok((atoms::ok(), ordering).encode(env))
+}
+
+/// Whether two strings collate equal.
+#[rustler::nif]
+pub(crate) fn collator_equal<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorEqual, || {
+        collator_equal_inner(env, collator_term, left, right)
+    })
+}
+
+fn collator_equal_inner<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CollatorResource> = match collator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let equal = resource.collator.compare(left, right) == Ordering::Equal;
+    Ok((atoms::ok(), equal).encode(env))
+}
+
+/// The differences `collator_equal_ignoring` disregards.
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum Ignoring {
+    Case,
+    Accents,
+    Both,
+}
+
+impl Ignoring {
+    /// Case is a tertiary difference and accents a secondary one, so
+    /// ignoring case alone compares at secondary strength, while ignoring
+    /// accents alone compares base letters with the case level turned on.
+    fn options(self) -> CollatorOptions {
+        let mut options = CollatorOptions::default();
+        match self {
+            Ignoring::Case => options.strength = Some(Strength::Secondary),
+            Ignoring::Accents => {
+                options.strength = Some(Strength::Primary);
+                options.case_level = Some(CaseLevel::On);
+            }
+            Ignoring::Both => options.strength = Some(Strength::Primary),
+        }
+        options
+    }
+}
+
+/// Whether two strings are equal in the locale once case, accents or both
+/// are disregarded.
+#[rustler::nif]
+pub(crate) fn collator_equal_ignoring<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    left: &str,
+    right: &str,
+    ignoring_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorEqualIgnoring, || {
+        collator_equal_ignoring_inner(env, locale_term, left, right, ignoring_term)
+    })
+}
+
+fn collator_equal_ignoring_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    left: &str,
+    right: &str,
+    ignoring_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let ignoring: Ignoring = match ignoring_term.decode() {
+        Ok(ignoring) => ignoring,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let collator = match Collator::try_new(locale_arg.0.into(), ignoring.options()) {
+        Ok(collator) => collator,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let equal = collator.compare(left, right) == Ordering::Equal;
+    Ok((atoms::ok(), equal).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn collator_resolved_options<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorResolvedOptions, || {
+        collator_resolved_options_inner(env, collator_term)
+    })
+}
+
+fn collator_resolved_options_inner<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CollatorResource> = match collator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let resolved = resource.collator.resolved_options();
+
+    let strength = match resolved.strength {
+        Strength::Primary => atoms::primary(),
+        Strength::Secondary => atoms::secondary(),
+        Strength::Quaternary => atoms::quaternary(),
+        Strength::Identical => atoms::identical(),
+        _ => atoms::tertiary(),
+    };
+
+    let case_first = match resolved.case_first {
+        CollationCaseFirst::Upper => atoms::upper(),
+        CollationCaseFirst::Lower => atoms::lower(),
+        _ => atoms::off(),
+    };
+
+    let options = ResolvedOptions {
+        strength,
+        case_first,
+        numeric: resolved.numeric == CollationNumericOrdering::True,
+    };
+
+    Ok((atoms::ok(), options).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn collator_sort<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    strings: Vec<String>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorSort, || {
+        collator_sort_inner(env, collator_term, strings)
+    })
+}
+
+fn collator_sort_inner<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    strings: Vec<String>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CollatorResource> = match collator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let mut strings = strings;
+    strings.sort_by(|left, right| resource.collator.compare(left, right));
+
+    Ok((atoms::ok(), strings).encode(env))
+}
+
+/// Removes strings that collate equal to an earlier string, keeping the
+/// first occurrence of each and the original order.
+#[rustler::nif]
+pub(crate) fn collator_unique<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    strings: Vec<String>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorUnique, || {
+        collator_unique_inner(env, collator_term, strings)
+    })
+}
+
+fn collator_unique_inner<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    strings: Vec<String>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CollatorResource> = match collator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    // A stable sort keeps equal strings in input order, so the first index
+    // of each run of equal strings is the occurrence to keep.
+    let mut order: Vec<usize> = (0..strings.len()).collect();
+    order.sort_by(|&left, &right| resource.collator.compare(&strings[left], &strings[right]));
+
+    let mut keep = vec![false; strings.len()];
+    let mut previous: Option<usize> = None;
+    for index in order {
+        let duplicate = previous.is_some_and(|previous| {
+            resource
+                .collator
+                .compare(&strings[previous], &strings[index])
+                == Ordering::Equal
+        });
+        if !duplicate {
+            keep[index] = true;
+            previous = Some(index);
+        }
+    }
+
+    let unique: Vec<String> = strings
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(string, keep)| keep.then_some(string))
+        .collect();
+
+    Ok((atoms::ok(), unique).encode(env))
+}
+
+/// Finds the non-overlapping substrings of `haystack` that collate equal to
+/// `needle`, returned as `{byte_offset, byte_length}` pairs.
+///
+/// Candidates start and end on grapheme cluster boundaries. Since ignorable
+/// characters and contractions can make an equal substring shorter or longer
+/// than the needle, windows of up to twice the needle's grapheme count are
+/// tried, and the shortest match at each position wins. The number of
+/// comparisons grows with the haystack, so it runs on a dirty CPU scheduler.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub(crate) fn collator_find<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    haystack: &str,
+    needle: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CollatorFind, || {
+        collator_find_inner(env, collator_term, haystack, needle)
+    })
+}
+
+fn collator_find_inner<'a>(
+    env: Env<'a>,
+    collator_term: Term<'a>,
+    haystack: &str,
+    needle: &str,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CollatorResource> = match collator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    if needle.is_empty() {
+        return Ok((atoms::ok(), Vec::<(usize, usize)>::new()).encode(env));
+    }
+
+    let segmenter = GraphemeClusterSegmenter::new();
+    let boundaries: Vec<usize> = segmenter.segment_str(haystack).collect();
+    let max_window = segmenter.segment_str(needle).count().saturating_sub(1) * 2;
+
+    let mut matches = Vec::new();
+    let mut start_index = 0;
+
+    while start_index + 1 < boundaries.len() {
+        let start = boundaries[start_index];
+        let window_end = (start_index + max_window).min(boundaries.len() - 1);
+
+        let found = (start_index + 1..=window_end).find(|&end_index| {
+            let candidate = &haystack[start..boundaries[end_index]];
+            resource.collator.compare(candidate, needle) == Ordering::Equal
+        });
+
+        match found {
+            Some(end_index) => {
+                matches.push((start, boundaries[end_index] - start));
+                start_index = end_index;
+            }
+            None => start_index += 1,
+        }
+    }
+
+    Ok((atoms::ok(), matches).encode(env))
+}
+
+#[derive(Default)]
+struct CollatorConfig {
+    options: CollatorOptions,
+    /// Overrides the locale's `-u-kn-` keyword when set.
+    numeric: Option<bool>,
+    /// Overrides the locale's `-u-kf-` keyword when set.
+    case_first: Option<CollationCaseFirst>,
+}
+
+fn decode_config(term: Term) -> Result<CollatorConfig, ()> {
+    let mut config = CollatorConfig::default();
+
+    if term.get_type() != TermType::Map {
+        return Ok(config);
+    }
+
+    decode_map(term, UnknownKeys::Error, |key, value_term| {
+        if key == atoms::strength() {
+            let value: Atom = value_term.decode().map_err(|_| ())?;
+            config.options.strength = Some(decode_strength(value)?);
+        } else if key == atoms::numeric() {
+            config.numeric = Some(value_term.decode().map_err(|_| ())?);
+        } else if key == atoms::case_first() {
+            let value: Atom = value_term.decode().map_err(|_| ())?;
+            config.case_first = Some(if value == atoms::upper() {
+                CollationCaseFirst::Upper
+            } else if value == atoms::lower() {
+                CollationCaseFirst::Lower
+            } else if value == atoms::off() {
+                CollationCaseFirst::False
+            } else {
+                return Err(());
+            });
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+
+    Ok(config)
+}
+
+fn decode_strength(value: Atom) -> Result<Strength, ()> {
+    if value == atoms::primary() {
+        Ok(Strength::Primary)
+    } else if value == atoms::secondary() {
+        Ok(Strength::Secondary)
+    } else if value == atoms::tertiary() {
+        Ok(Strength::Tertiary)
+    } else if value == atoms::quaternary() {
+        Ok(Strength::Quaternary)
+    } else if value == atoms::identical() {
+        Ok(Strength::Identical)
+    } else {
+        Err(())
+    }
+}
@@ -1,3 +1,14 @@
+use std::ops::Range;
+use std::str::FromStr;
+
+use fixed_decimal::Decimal as FixedDecimal;
+use icu::decimal::options::DecimalFormatterOptions;
+use icu::decimal::parts as decimal_parts;
+use icu::decimal::DecimalFormatter;
+use icu::experimental::dimension::currency::compact_formatter::CompactCurrencyFormatter;
+use icu::experimental::dimension::currency::compact_options::{
+    CompactCurrencyFormatterOptions, Width as CompactWidth,
+};
 use icu::experimental::dimension::currency::formatter::CurrencyFormatter;
 use icu::experimental::dimension::currency::long_formatter::LongCurrencyFormatter;
 use icu::experimental::dimension::currency::options::{CurrencyFormatterOptions, Width};
@@ -5,14 +16,21 @@ use icu::experimental::dimension::currency::CurrencyCode;
 use icu::experimental::dimension::provider::currency::fractions::{
     CurrencyFractionsV1, FractionInfo,
 };
-use icu_provider::{DataProvider as _, DataRequest, DataResponse};
-use rustler::types::map::MapIterator;
+use icu::locale::subtags::Region;
+use icu::locale::Locale;
+use icu_provider::{DataLocale, DataProvider as _, DataRequest, DataResponse};
 use rustler::{Atom, Encoder, Env, NifResult, ResourceArc, Term, TermType};
 use tinystr::{TinyAsciiStr, UnvalidatedTinyAsciiStr};
+use writeable::Writeable;
 
 use crate::atoms;
-use crate::locale::LocaleResource;
+use crate::index;
+use crate::locale::{self, LocaleArg};
 use crate::number;
+use crate::options::{decode_map, UnknownKeys};
+use crate::range::{self, RangeParts};
+use crate::stats::{self, Counted};
+use crate::writeable_parts::{self, CollectedPart, FormattedPart, PartsCollector};
 
 #[derive(rustler::NifMap)]
 struct CurrencyFractionData {
@@ -24,6 +42,12 @@ struct CurrencyFractionData {
 
 #[rustler::nif]
 pub(crate) fn currency_fractions<'a>(env: Env<'a>, currency: &str) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CurrencyFractions, || {
+        currency_fractions_inner(env, currency)
+    })
+}
+
+fn currency_fractions_inner<'a>(env: Env<'a>, currency: &str) -> NifResult<Term<'a>> {
     match get_currency_fractions_inner(currency) {
         Some(fractions) => Ok((
             atoms::ok(),
@@ -56,20 +80,442 @@ fn get_currency_fractions_inner(currency: &str) -> Option<FractionInfo> {
     )
 }
 
+/// Returns the legal tenders of `region`, most common first, followed by
+/// the currencies it used before them when `historical` is set, most recent
+/// first.
+#[rustler::nif]
+pub(crate) fn currency_region_currencies<'a>(
+    env: Env<'a>,
+    region: &str,
+    historical: bool,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CurrencyRegionCurrencies, || {
+        currency_region_currencies_inner(env, region, historical)
+    })
+}
+
+fn currency_region_currencies_inner<'a>(
+    env: Env<'a>,
+    region: &str,
+    historical: bool,
+) -> NifResult<Term<'a>> {
+    let region = match Region::from_str(region) {
+        Ok(region) => region,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_region()).encode(env)),
+    };
+
+    match region_currencies(region, historical) {
+        Some(currencies) => Ok((atoms::ok(), currencies).encode(env)),
+        None => Ok((atoms::error(), atoms::no_data()).encode(env)),
+    }
+}
+
+fn region_currencies(region: Region, historical: bool) -> Option<Vec<&'static str>> {
+    let (_, current, previous) = REGION_CURRENCIES
+        .iter()
+        .find(|(name, _, _)| *name == region.as_str())?;
+
+    let mut currencies = current.to_vec();
+    if historical {
+        currencies.extend_from_slice(previous);
+    }
+    Some(currencies)
+}
+
+/// The CLDR supplemental `currencyData` of each region: its legal tenders,
+/// then the tenders it replaced since 2000, most recent first. ICU4X does not
+/// ship this data. Earlier currencies and non-tender funds codes such as
+/// `CHE` are left out, as are regions without a currency such as `AQ`.
+const REGION_CURRENCIES: &[(&str, &[&str], &[&str])] = &[
+    ("AC", &["SHP"], &[]),
+    ("AD", &["EUR"], &[]),
+    ("AE", &["AED"], &[]),
+    ("AF", &["AFN"], &["AFA"]),
+    ("AG", &["XCD"], &[]),
+    ("AI", &["XCD"], &[]),
+    ("AL", &["ALL"], &[]),
+    ("AM", &["AMD"], &[]),
+    ("AO", &["AOA"], &[]),
+    ("AR", &["ARS"], &[]),
+    ("AS", &["USD"], &[]),
+    ("AT", &["EUR"], &["ATS"]),
+    ("AU", &["AUD"], &[]),
+    ("AW", &["AWG"], &[]),
+    ("AX", &["EUR"], &[]),
+    ("AZ", &["AZN"], &["AZM"]),
+    ("BA", &["BAM"], &[]),
+    ("BB", &["BBD"], &[]),
+    ("BD", &["BDT"], &[]),
+    ("BE", &["EUR"], &["BEF"]),
+    ("BF", &["XOF"], &[]),
+    ("BG", &["EUR"], &["BGN"]),
+    ("BH", &["BHD"], &[]),
+    ("BI", &["BIF"], &[]),
+    ("BJ", &["XOF"], &[]),
+    ("BL", &["EUR"], &[]),
+    ("BM", &["BMD"], &[]),
+    ("BN", &["BND"], &[]),
+    ("BO", &["BOB"], &[]),
+    ("BQ", &["USD"], &["ANG"]),
+    ("BR", &["BRL"], &[]),
+    ("BS", &["BSD"], &[]),
+    ("BT", &["BTN", "INR"], &[]),
+    ("BV", &["NOK"], &[]),
+    ("BW", &["BWP"], &[]),
+    ("BY", &["BYN"], &["BYR"]),
+    ("BZ", &["BZD"], &[]),
+    ("CA", &["CAD"], &[]),
+    ("CC", &["AUD"], &[]),
+    ("CD", &["CDF"], &[]),
+    ("CF", &["XAF"], &[]),
+    ("CG", &["XAF"], &[]),
+    ("CH", &["CHF"], &[]),
+    ("CI", &["XOF"], &[]),
+    ("CK", &["NZD"], &[]),
+    ("CL", &["CLP"], &[]),
+    ("CM", &["XAF"], &[]),
+    ("CN", &["CNY"], &[]),
+    ("CO", &["COP"], &[]),
+    ("CR", &["CRC"], &[]),
+    ("CU", &["CUP"], &["CUC"]),
+    ("CV", &["CVE"], &[]),
+    ("CW", &["XCG"], &["ANG"]),
+    ("CX", &["AUD"], &[]),
+    ("CY", &["EUR"], &["CYP"]),
+    ("CZ", &["CZK"], &[]),
+    ("DE", &["EUR"], &["DEM"]),
+    ("DG", &["USD"], &[]),
+    ("DJ", &["DJF"], &[]),
+    ("DK", &["DKK"], &[]),
+    ("DM", &["XCD"], &[]),
+    ("DO", &["DOP"], &[]),
+    ("DZ", &["DZD"], &[]),
+    ("EA", &["EUR"], &[]),
+    ("EC", &["USD"], &["ECS"]),
+    ("EE", &["EUR"], &["EEK"]),
+    ("EG", &["EGP"], &[]),
+    ("EH", &["MAD"], &[]),
+    ("ER", &["ERN"], &[]),
+    ("ES", &["EUR"], &["ESP"]),
+    ("ET", &["ETB"], &[]),
+    ("FI", &["EUR"], &["FIM"]),
+    ("FJ", &["FJD"], &[]),
+    ("FK", &["FKP"], &[]),
+    ("FM", &["USD"], &[]),
+    ("FO", &["DKK"], &[]),
+    ("FR", &["EUR"], &["FRF"]),
+    ("GA", &["XAF"], &[]),
+    ("GB", &["GBP"], &[]),
+    ("GD", &["XCD"], &[]),
+    ("GE", &["GEL"], &[]),
+    ("GF", &["EUR"], &[]),
+    ("GG", &["GBP"], &[]),
+    ("GH", &["GHS"], &["GHC"]),
+    ("GI", &["GIP"], &[]),
+    ("GL", &["DKK"], &[]),
+    ("GM", &["GMD"], &[]),
+    ("GN", &["GNF"], &[]),
+    ("GP", &["EUR"], &[]),
+    ("GQ", &["XAF"], &[]),
+    ("GR", &["EUR"], &["GRD"]),
+    ("GS", &["GBP"], &[]),
+    ("GT", &["GTQ"], &[]),
+    ("GU", &["USD"], &[]),
+    ("GW", &["XOF"], &[]),
+    ("GY", &["GYD"], &[]),
+    ("HK", &["HKD"], &[]),
+    ("HM", &["AUD"], &[]),
+    ("HN", &["HNL"], &[]),
+    ("HR", &["EUR"], &["HRK"]),
+    ("HT", &["HTG", "USD"], &[]),
+    ("HU", &["HUF"], &[]),
+    ("IC", &["EUR"], &[]),
+    ("ID", &["IDR"], &[]),
+    ("IE", &["EUR"], &["IEP"]),
+    ("IL", &["ILS"], &[]),
+    ("IM", &["GBP"], &[]),
+    ("IN", &["INR"], &[]),
+    ("IO", &["USD"], &[]),
+    ("IQ", &["IQD"], &[]),
+    ("IR", &["IRR"], &[]),
+    ("IS", &["ISK"], &[]),
+    ("IT", &["EUR"], &["ITL"]),
+    ("JE", &["GBP"], &[]),
+    ("JM", &["JMD"], &[]),
+    ("JO", &["JOD"], &[]),
+    ("JP", &["JPY"], &[]),
+    ("KE", &["KES"], &[]),
+    ("KG", &["KGS"], &[]),
+    ("KH", &["KHR"], &[]),
+    ("KI", &["AUD"], &[]),
+    ("KM", &["KMF"], &[]),
+    ("KN", &["XCD"], &[]),
+    ("KP", &["KPW"], &[]),
+    ("KR", &["KRW"], &[]),
+    ("KW", &["KWD"], &[]),
+    ("KY", &["KYD"], &[]),
+    ("KZ", &["KZT"], &[]),
+    ("LA", &["LAK"], &[]),
+    ("LB", &["LBP"], &[]),
+    ("LC", &["XCD"], &[]),
+    ("LI", &["CHF"], &[]),
+    ("LK", &["LKR"], &[]),
+    ("LR", &["LRD"], &[]),
+    ("LS", &["ZAR", "LSL"], &[]),
+    ("LT", &["EUR"], &["LTL"]),
+    ("LU", &["EUR"], &["LUF"]),
+    ("LV", &["EUR"], &["LVL"]),
+    ("LY", &["LYD"], &[]),
+    ("MA", &["MAD"], &[]),
+    ("MC", &["EUR"], &[]),
+    ("MD", &["MDL"], &[]),
+    ("ME", &["EUR"], &["DEM"]),
+    ("MF", &["EUR"], &[]),
+    ("MG", &["MGA"], &["MGF"]),
+    ("MH", &["USD"], &[]),
+    ("MK", &["MKD"], &[]),
+    ("ML", &["XOF"], &[]),
+    ("MM", &["MMK"], &[]),
+    ("MN", &["MNT"], &[]),
+    ("MO", &["MOP"], &[]),
+    ("MP", &["USD"], &[]),
+    ("MQ", &["EUR"], &[]),
+    ("MR", &["MRU"], &["MRO"]),
+    ("MS", &["XCD"], &[]),
+    ("MT", &["EUR"], &["MTL"]),
+    ("MU", &["MUR"], &[]),
+    ("MV", &["MVR"], &[]),
+    ("MW", &["MWK"], &[]),
+    ("MX", &["MXN"], &[]),
+    ("MY", &["MYR"], &[]),
+    ("MZ", &["MZN"], &["MZM"]),
+    ("NA", &["NAD", "ZAR"], &[]),
+    ("NC", &["XPF"], &[]),
+    ("NE", &["XOF"], &[]),
+    ("NF", &["AUD"], &[]),
+    ("NG", &["NGN"], &[]),
+    ("NI", &["NIO"], &[]),
+    ("NL", &["EUR"], &["NLG"]),
+    ("NO", &["NOK"], &[]),
+    ("NP", &["NPR"], &[]),
+    ("NR", &["AUD"], &[]),
+    ("NU", &["NZD"], &[]),
+    ("NZ", &["NZD"], &[]),
+    ("OM", &["OMR"], &[]),
+    ("PA", &["PAB", "USD"], &[]),
+    ("PE", &["PEN"], &[]),
+    ("PF", &["XPF"], &[]),
+    ("PG", &["PGK"], &[]),
+    ("PH", &["PHP"], &[]),
+    ("PK", &["PKR"], &[]),
+    ("PL", &["PLN"], &[]),
+    ("PM", &["EUR"], &[]),
+    ("PN", &["NZD"], &[]),
+    ("PR", &["USD"], &[]),
+    ("PS", &["ILS", "JOD"], &[]),
+    ("PT", &["EUR"], &["PTE"]),
+    ("PW", &["USD"], &[]),
+    ("PY", &["PYG"], &[]),
+    ("QA", &["QAR"], &[]),
+    ("RE", &["EUR"], &[]),
+    ("RO", &["RON"], &["ROL"]),
+    ("RS", &["RSD"], &["CSD"]),
+    ("RU", &["RUB"], &[]),
+    ("RW", &["RWF"], &[]),
+    ("SA", &["SAR"], &[]),
+    ("SB", &["SBD"], &[]),
+    ("SC", &["SCR"], &[]),
+    ("SD", &["SDG"], &["SDD"]),
+    ("SE", &["SEK"], &[]),
+    ("SG", &["SGD"], &[]),
+    ("SH", &["SHP"], &[]),
+    ("SI", &["EUR"], &["SIT"]),
+    ("SJ", &["NOK"], &[]),
+    ("SK", &["EUR"], &["SKK"]),
+    ("SL", &["SLE"], &["SLL"]),
+    ("SM", &["EUR"], &[]),
+    ("SN", &["XOF"], &[]),
+    ("SO", &["SOS"], &[]),
+    ("SR", &["SRD"], &["SRG"]),
+    ("SS", &["SSP"], &["SDG"]),
+    ("ST", &["STN"], &["STD"]),
+    ("SV", &["USD"], &["SVC"]),
+    ("SX", &["XCG"], &["ANG"]),
+    ("SY", &["SYP"], &[]),
+    ("SZ", &["SZL"], &[]),
+    ("TA", &["GBP"], &[]),
+    ("TC", &["USD"], &[]),
+    ("TD", &["XAF"], &[]),
+    ("TF", &["EUR"], &[]),
+    ("TG", &["XOF"], &[]),
+    ("TH", &["THB"], &[]),
+    ("TJ", &["TJS"], &["TJR"]),
+    ("TK", &["NZD"], &[]),
+    ("TL", &["USD"], &[]),
+    ("TM", &["TMT"], &["TMM"]),
+    ("TN", &["TND"], &[]),
+    ("TO", &["TOP"], &[]),
+    ("TR", &["TRY"], &["TRL"]),
+    ("TT", &["TTD"], &[]),
+    ("TV", &["AUD"], &[]),
+    ("TW", &["TWD"], &[]),
+    ("TZ", &["TZS"], &[]),
+    ("UA", &["UAH"], &[]),
+    ("UG", &["UGX"], &[]),
+    ("UM", &["USD"], &[]),
+    ("US", &["USD"], &[]),
+    ("UY", &["UYU"], &[]),
+    ("UZ", &["UZS"], &[]),
+    ("VA", &["EUR"], &[]),
+    ("VC", &["XCD"], &[]),
+    ("VE", &["VES"], &["VEF", "VEB"]),
+    ("VG", &["USD"], &[]),
+    ("VI", &["USD"], &[]),
+    ("VN", &["VND"], &[]),
+    ("VU", &["VUV"], &[]),
+    ("WF", &["XPF"], &[]),
+    ("WS", &["WST"], &[]),
+    ("XK", &["EUR"], &[]),
+    ("YE", &["YER"], &[]),
+    ("YT", &["EUR"], &[]),
+    ("ZA", &["ZAR"], &[]),
+    ("ZM", &["ZMW"], &["ZMK"]),
+    ("ZW", &["ZWG", "USD"], &["ZWL", "ZWR", "ZWN", "ZWD"]),
+];
+
 // Currency formatter
 
 enum CurrencyFormatterKind {
     Standard(CurrencyFormatter),
     Long(LongCurrencyFormatter),
+    CompactShort(CompactCurrencyFormatter),
+}
+
+impl CurrencyFormatterKind {
+    /// The formatted amount and the byte range of the number in it.
+    fn format(
+        &self,
+        decimal: &FixedDecimal,
+        currency_code: CurrencyCode,
+    ) -> (String, Option<Range<usize>>) {
+        let (output, parts) = self.format_to_parts(decimal, currency_code);
+        let number = writeable_parts::number_range(&parts);
+        (output, number)
+    }
+
+    /// The formatted amount and the flattened parts of the number in it.
+    fn format_to_parts(
+        &self,
+        decimal: &FixedDecimal,
+        currency_code: CurrencyCode,
+    ) -> (String, Vec<CollectedPart>) {
+        let mut collector = PartsCollector::new();
+        // Writing into a `String` cannot fail.
+        let _ = match self {
+            CurrencyFormatterKind::Standard(f) => f
+                .format_fixed_decimal(decimal, currency_code)
+                .write_to_parts(&mut collector),
+            CurrencyFormatterKind::Long(f) => f
+                .format_fixed_decimal(decimal, currency_code)
+                .write_to_parts(&mut collector),
+            CurrencyFormatterKind::CompactShort(f) => f
+                .format_fixed_decimal(decimal, currency_code)
+                .write_to_parts(&mut collector),
+        };
+        collector.finish_flat()
+    }
+}
+
+/// Rounds compact amounts to a fixed number of significant digits.
+struct CompactRounding {
+    significant_digits: u8,
+    decimal_formatter: DecimalFormatter,
+}
+
+impl CompactRounding {
+    /// ICU4X rounds the compact significand itself and shows at most two
+    /// significant digits for small significands. The amount is rounded here
+    /// first, so the formatter picks the magnitude of the rounded value, and
+    /// the number in its output is then replaced with the significand
+    /// formatted with every requested digit. The integer parts of the output
+    /// give the number of digits before the magnitude suffix.
+    fn format(
+        &self,
+        formatter: &CurrencyFormatterKind,
+        decimal: &FixedDecimal,
+        currency_code: CurrencyCode,
+    ) -> (String, Option<Range<usize>>) {
+        let mut rounded = decimal.clone();
+        let position = rounded.nonzero_magnitude_start() - i16::from(self.significant_digits) + 1;
+        rounded.round(position);
+
+        let (output, parts) = formatter.format_to_parts(&rounded, currency_code);
+        let Some(range) = writeable_parts::number_range(&parts) else {
+            return (output, None);
+        };
+
+        let integer_digits = parts
+            .iter()
+            .filter(|collected| collected.part == decimal_parts::INTEGER)
+            .map(|collected| output[collected.range()].chars().count())
+            .sum::<usize>() as i16;
+        let exponent = (rounded.nonzero_magnitude_start() + 1 - integer_digits).max(0);
+
+        let mut significand = rounded;
+        significand.multiply_pow10(-exponent);
+        significand.trim_end();
+
+        let number = self
+            .decimal_formatter
+            .format(&significand)
+            .write_to_string();
+        let mut formatted = output.clone();
+        formatted.replace_range(range.clone(), &number);
+        (formatted, Some(range.start..range.start + number.len()))
+    }
 }
 
 pub(crate) struct CurrencyFormatterResource {
     formatter: CurrencyFormatterKind,
     currency_code: CurrencyCode,
+    /// Text the formatter renders for the currency itself (symbol or name),
+    /// used to tell it from the other text around the number.
+    symbol: String,
+    /// Set for compact notation with `compact_rounding: {:significant, n}`.
+    compact_rounding: Option<CompactRounding>,
+    locale: Locale,
+    approximately: bool,
 }
 
 impl rustler::Resource for CurrencyFormatterResource {}
 
+impl CurrencyFormatterResource {
+    /// The formatted amount and the byte range of the number in it.
+    fn format(&self, decimal: &FixedDecimal) -> (String, Option<Range<usize>>) {
+        match &self.compact_rounding {
+            Some(rounding) => rounding.format(&self.formatter, decimal, self.currency_code),
+            None => self.formatter.format(decimal, self.currency_code),
+        }
+    }
+
+    /// The locale of the number symbols the formatter loaded.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        number::data_locale(&self.locale)
+    }
+
+    /// The parts of the formatted amount.
+    fn format_to_parts(&self, decimal: &FixedDecimal) -> range::EndParts {
+        let (output, number) = self.format(decimal);
+        let compact = matches!(self.formatter, CurrencyFormatterKind::CompactShort(_));
+        amount_parts(&output, number, &self.symbol, compact)
+    }
+
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        std::mem::size_of::<Self>() + self.symbol.capacity() + locale::heap_size(&self.locale)
+    }
+}
+
 pub(crate) fn load(env: Env) -> bool {
     env.register::<CurrencyFormatterResource>().is_ok()
 }
@@ -81,8 +527,19 @@ pub(crate) fn currency_formatter_new<'a>(
     currency_code_str: &str,
     options_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
-    let locale_resource: ResourceArc<LocaleResource> = match locale_term.decode() {
-        Ok(resource) => resource,
+    stats::measure(Counted::CurrencyFormatterNew, || {
+        currency_formatter_new_inner(env, locale_term, currency_code_str, options_term)
+    })
+}
+
+fn currency_formatter_new_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    currency_code_str: &str,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
         Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
     };
 
@@ -92,36 +549,81 @@ pub(crate) fn currency_formatter_new<'a>(
     };
     let currency_code = CurrencyCode(tiny_str);
 
-    let width = decode_width(options_term)?;
+    let (width, notation, significant_digits) = decode_options(options_term)?;
+    let approximately =
+        number::decode_approximately(options_term).map_err(|_| rustler::Error::BadArg)?;
 
-    let formatter = match width {
-        WidthOption::Short => {
+    let formatter = match (notation, width) {
+        (NotationOption::Standard, WidthOption::Short) => {
             let mut opts = CurrencyFormatterOptions::default();
             opts.width = Width::Short;
-            match CurrencyFormatter::try_new(locale_resource.0.clone().into(), opts) {
+            match CurrencyFormatter::try_new(locale_arg.0.clone().into(), opts) {
                 Ok(f) => CurrencyFormatterKind::Standard(f),
                 Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
             }
         }
-        WidthOption::Narrow => {
+        (NotationOption::Standard, WidthOption::Narrow) => {
             let mut opts = CurrencyFormatterOptions::default();
             opts.width = Width::Narrow;
-            match CurrencyFormatter::try_new(locale_resource.0.clone().into(), opts) {
+            match CurrencyFormatter::try_new(locale_arg.0.clone().into(), opts) {
                 Ok(f) => CurrencyFormatterKind::Standard(f),
                 Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
             }
         }
-        WidthOption::Long => {
-            match LongCurrencyFormatter::try_new(locale_resource.0.clone().into(), &currency_code) {
+        (NotationOption::Standard, WidthOption::Long) => {
+            match LongCurrencyFormatter::try_new(locale_arg.0.clone().into(), &currency_code) {
                 Ok(f) => CurrencyFormatterKind::Long(f),
                 Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
             }
         }
+        (NotationOption::CompactShort, WidthOption::Short | WidthOption::Narrow) => {
+            let mut opts = CompactCurrencyFormatterOptions::default();
+            opts.width = match width {
+                WidthOption::Narrow => CompactWidth::Narrow,
+                _ => CompactWidth::Short,
+            };
+            match CompactCurrencyFormatter::try_new(locale_arg.0.clone().into(), opts) {
+                Ok(f) => CurrencyFormatterKind::CompactShort(f),
+                Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+            }
+        }
+        (NotationOption::CompactShort, WidthOption::Long) => {
+            return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+        }
+    };
+
+    // The text around the number in a formatted zero is the currency.
+    let (zero, number) = formatter.format(&FixedDecimal::from(0), currency_code);
+    let symbol = match number {
+        Some(number) => [&zero[..number.start], &zero[number.end..]].concat(),
+        None => zero,
+    }
+    .trim()
+    .to_string();
+
+    let compact_rounding = match (&formatter, significant_digits) {
+        (CurrencyFormatterKind::CompactShort(_), Some(significant_digits)) => {
+            match DecimalFormatter::try_new(
+                locale_arg.0.clone().into(),
+                DecimalFormatterOptions::default(),
+            ) {
+                Ok(decimal_formatter) => Some(CompactRounding {
+                    significant_digits,
+                    decimal_formatter,
+                }),
+                Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+            }
+        }
+        _ => None,
     };
 
     let resource = CurrencyFormatterResource {
         formatter,
         currency_code,
+        symbol,
+        compact_rounding,
+        locale: locale_arg.0,
+        approximately,
     };
     Ok((atoms::ok(), ResourceArc::new(resource)).encode(env))
 }
@@ -131,6 +633,16 @@ pub(crate) fn currency_format<'a>(
     env: Env<'a>,
     formatter_term: Term<'a>,
     number_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CurrencyFormat, || {
+        currency_format_inner(env, formatter_term, number_term)
+    })
+}
+
+fn currency_format_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
     let resource: ResourceArc<CurrencyFormatterResource> = match formatter_term.decode() {
         Ok(resource) => resource,
@@ -142,43 +654,253 @@ pub(crate) fn currency_format<'a>(
         Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
     };
 
-    let formatted = match &resource.formatter {
-        CurrencyFormatterKind::Standard(f) => {
-            f.format_fixed_decimal(&decimal, resource.currency_code)
-                .to_string()
-        }
-        CurrencyFormatterKind::Long(f) => {
-            f.format_fixed_decimal(&decimal, resource.currency_code)
-                .to_string()
-        }
+    let (formatted, _) = resource.format(&decimal);
+    let formatted = number::approximated(&resource.locale, resource.approximately, formatted);
+
+    Ok((atoms::ok(), formatted).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn currency_format_to_parts<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CurrencyFormatToParts, || {
+        currency_format_to_parts_inner(env, formatter_term, number_term, index_mode_term)
+    })
+}
+
+fn currency_format_to_parts_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CurrencyFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+
+    let decimal = match number::term_to_decimal(number_term) {
+        Ok(decimal) => decimal,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let mut parts = resource.format_to_parts(&decimal);
+    if resource.approximately {
+        let sign = number::approximately_sign(&resource.locale);
+        parts.insert(0, (atoms::approximately_sign().into(), sign));
+    }
+
+    // The parts cover the output in order, so their ranges follow from
+    // their lengths.
+    let mut output = String::new();
+    let parts: Vec<(FormattedPart, Range<usize>)> = parts
+        .into_iter()
+        .map(|(part_type, value)| {
+            let start = output.len();
+            output.push_str(&value);
+            (FormattedPart { part_type, value }, start..output.len())
+        })
+        .collect();
+
+    let parts = index::encode_parts(env, &output, parts, index_mode);
+    Ok((atoms::ok(), parts).encode(env))
+}
+
+/// Formats the range from `start` to `end`, e.g. "$10–$20" or "10–20 €".
+#[rustler::nif]
+pub(crate) fn currency_format_range<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CurrencyFormatRange, || {
+        currency_format_range_inner(env, formatter_term, start_term, end_term)
+    })
+}
+
+fn currency_format_range_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CurrencyFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
     };
 
+    let (start, end) = match format_ends(&resource, start_term, end_term) {
+        Some(ends) => ends,
+        None => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    let formatted: String = range::segments(&resource.locale, start, end)
+        .into_iter()
+        .map(|(_, _, text)| text)
+        .collect();
+    let formatted = number::approximated(&resource.locale, resource.approximately, formatted);
+
     Ok((atoms::ok(), formatted).encode(env))
 }
 
+/// Like `currency_format_range`, returning the parts of each end tagged with
+/// the end they belong to.
+#[rustler::nif]
+pub(crate) fn currency_format_range_to_parts<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CurrencyFormatRangeToParts, || {
+        currency_format_range_to_parts_inner(
+            env,
+            formatter_term,
+            start_term,
+            end_term,
+            index_mode_term,
+        )
+    })
+}
+
+fn currency_format_range_to_parts_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<CurrencyFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+
+    let (start, end) = match format_ends(&resource, start_term, end_term) {
+        Some(ends) => ends,
+        None => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let mut parts = RangeParts::default();
+    if resource.approximately {
+        let sign = number::approximately_sign(&resource.locale);
+        parts.push(atoms::approximately_sign(), &sign, atoms::shared());
+    }
+    for (source, part_type, value) in range::segments(&resource.locale, start, end) {
+        parts.push(part_type, &value, source);
+    }
+
+    let parts = index::encode_parts(env, &parts.output, parts.parts, index_mode);
+    Ok((atoms::ok(), parts).encode(env))
+}
+
+fn format_ends(
+    resource: &CurrencyFormatterResource,
+    start_term: Term,
+    end_term: Term,
+) -> Option<(range::EndParts, range::EndParts)> {
+    let start = number::term_to_decimal(start_term).ok()?;
+    let end = number::term_to_decimal(end_term).ok()?;
+    Some((
+        resource.format_to_parts(&start),
+        resource.format_to_parts(&end),
+    ))
+}
+
+/// The parts of a formatted amount: the number, as the decimal parts the
+/// formatter wrote mark it, and the currency text and literals around it.
+/// In compact notation any other non-whitespace text is the compact affix.
+fn amount_parts(
+    output: &str,
+    number: Option<Range<usize>>,
+    symbol: &str,
+    compact: bool,
+) -> range::EndParts {
+    let Some(number) = number else {
+        return text_parts(output, symbol, compact);
+    };
+
+    let mut parts = text_parts(&output[..number.start], symbol, compact);
+    parts.push((atoms::number().into(), output[number.clone()].to_string()));
+    parts.extend(text_parts(&output[number.end..], symbol, compact));
+    parts
+}
+
+/// The parts of the text on one side of the number. Only this text is
+/// searched for the currency, so digits in the currency text cannot be
+/// mistaken for the number and the other way round.
+fn text_parts(text: &str, symbol: &str, compact: bool) -> range::EndParts {
+    let Some(start) = text.find(symbol).filter(|_| !symbol.is_empty()) else {
+        return affix_parts(text, compact);
+    };
+
+    let end = start + symbol.len();
+    let mut parts = affix_parts(&text[..start], compact);
+    parts.push((atoms::currency().into(), symbol.to_string()));
+    parts.extend(affix_parts(&text[end..], compact));
+    parts
+}
+
+fn affix_parts(text: &str, compact: bool) -> range::EndParts {
+    let trimmed = text.trim();
+    if !compact || trimmed.is_empty() {
+        if text.is_empty() {
+            return Vec::new();
+        }
+        return vec![(atoms::literal().into(), text.to_string())];
+    }
+
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    [
+        (leading, atoms::literal()),
+        (trimmed, atoms::compact()),
+        (trailing, atoms::literal()),
+    ]
+    .into_iter()
+    .filter(|(value, _)| !value.is_empty())
+    .map(|(value, part_type)| (part_type.into(), value.to_string()))
+    .collect()
+}
+
 enum WidthOption {
     Short,
     Narrow,
     Long,
 }
 
-fn decode_width<'a>(term: Term<'a>) -> NifResult<WidthOption> {
+enum NotationOption {
+    Standard,
+    CompactShort,
+}
+
+/// Decodes the width, the notation and the significant digits requested by
+/// `compact_rounding` (`None` for `:auto`).
+fn decode_options<'a>(term: Term<'a>) -> NifResult<(WidthOption, NotationOption, Option<u8>)> {
     if term.get_type() != TermType::Map {
-        if let Ok(atom_name) = term.atom_to_string() {
-            if atom_name == "nil" {
-                return Ok(WidthOption::Short);
-            }
-        }
-        return Ok(WidthOption::Short);
+        return Ok((WidthOption::Short, NotationOption::Standard, None));
     }
 
     let mut width = WidthOption::Short;
-    let iter = MapIterator::new(term).ok_or(rustler::Error::BadArg)?;
-
-    for (key_term, value_term) in iter {
-        let key: Atom = key_term.decode().map_err(|_| rustler::Error::BadArg)?;
+    let mut notation = NotationOption::Standard;
+    let mut significant_digits = None;
+    decode_map(term, UnknownKeys::Ignore, |key, value_term| {
         if key == atoms::width() {
-            let value: Atom = value_term.decode().map_err(|_| rustler::Error::BadArg)?;
+            let value: Atom = value_term.decode().map_err(|_| ())?;
             width = if value == atoms::short() {
                 WidthOption::Short
             } else if value == atoms::narrow() {
@@ -186,10 +908,35 @@ fn decode_width<'a>(term: Term<'a>) -> NifResult<WidthOption> {
             } else if value == atoms::long() {
                 WidthOption::Long
             } else {
-                return Err(rustler::Error::BadArg);
+                return Err(());
+            };
+        } else if key == atoms::notation() {
+            let value: Atom = value_term.decode().map_err(|_| ())?;
+            notation = if value == atoms::standard() {
+                NotationOption::Standard
+            } else if value == atoms::compact_short() {
+                NotationOption::CompactShort
+            } else {
+                return Err(());
             };
+        } else if key == atoms::compact_rounding() {
+            significant_digits = match value_term.decode::<Atom>() {
+                Ok(value) if value == atoms::auto() => None,
+                Ok(_) => return Err(()),
+                Err(_) => {
+                    let (tag, digits): (Atom, u8) = value_term.decode().map_err(|_| ())?;
+                    if tag != atoms::significant() || digits == 0 {
+                        return Err(());
+                    }
+                    Some(digits)
+                }
+            };
+        } else {
+            return Ok(false);
         }
-    }
+        Ok(true)
+    })
+    .map_err(|_| rustler::Error::BadArg)?;
 
-    Ok(width)
+    Ok((width, notation, significant_digits))
 }
@@ -1,305 +1,1940 @@
-use std::fmt;
+use std::ops::Range;
 
-use icu::calendar::{AnyCalendar, Date};
+use fixed_decimal::Decimal as FixedDecimal;
+use icu::calendar::types::MonthCode;
+use icu::calendar::{
+    AnyCalendar, AnyCalendarKind, Date, DateError, Gregorian, Iso, RangeError, Ref,
+};
 use icu::datetime::fieldsets::builder::FieldSetBuilder;
-use icu::datetime::fieldsets::enums::CompositeFieldSet;
-use icu::datetime::input::Time;
+use icu::datetime::fieldsets::enums::{CompositeDateTimeFieldSet, CompositeFieldSet};
+use icu::datetime::input::{DateTime, Time};
 use icu::datetime::options;
+use icu::datetime::pattern::{DateTimePattern, FixedCalendarDateTimeNames};
+use icu::datetime::provider::neo::{marker_attrs, DatetimeNamesWeekdayV1};
+use icu::datetime::provider::Baked as DateTimeBaked;
 use icu::datetime::unchecked::DateTimeInputUnchecked;
 use icu::datetime::{parts as datetime_parts, DateTimeFormatter, DateTimeFormatterPreferences};
-use icu::decimal::parts as decimal_parts;
+use icu::experimental::relativetime::options::Numeric;
+use icu::experimental::relativetime::{RelativeTimeFormatter, RelativeTimeFormatterOptions};
+use icu::locale::preferences::extensions::unicode::keywords::{CalendarAlgorithm, HourCycle};
+use icu::locale::Locale;
 use icu::time::zone::{IanaParser, UtcOffset};
+use icu::time::TimeZone;
+use icu_provider::DataLocale;
 use rustler::types::map::MapIterator;
 use rustler::{Atom, Encoder, Env, NifMap, NifResult, NifTaggedEnum, ResourceArc, Term, TermType};
-use writeable::{Part as WriteablePart, PartsWrite, TryWriteable};
+use writeable::{Part as WriteablePart, TryWriteable, Writeable};
 
 use crate::atoms;
-use crate::locale::LocaleResource;
+use crate::calendar;
+use crate::index;
+use crate::locale::{self, resolve_data_locale, LocaleArg};
+use crate::options::{decode_map, decode_option, get_option, UnknownKeys};
+use crate::preferences;
+use crate::stats::{self, Counted};
+use crate::writeable_parts::{CollectedPart, PartType, PartsCollector};
 
-pub(crate) struct DateTimeFormatterResource(DateTimeFormatter<CompositeFieldSet>);
+pub(crate) struct DateTimeFormatterResource {
+    formatter: TemporalFormatter,
+    kind: TemporalKind,
+    locale: Locale,
+    zone_fallback: Option<ZoneFallback>,
+    relative_days: Option<RelativeDays>,
+    leap_second: LeapSecond,
+}
+
+/// How a formatter treats the 60th second a leap second is written with.
+#[derive(NifTaggedEnum, Clone, Copy, Default)]
+enum LeapSecond {
+    /// Formats it as the last second of the minute, as ICU4X does.
+    #[default]
+    Clamp,
+    /// Rejects the input as an invalid datetime.
+    Error,
+}
+
+impl LeapSecond {
+    fn max_second(self) -> u8 {
+        match self {
+            Self::Clamp => 60,
+            Self::Error => 59,
+        }
+    }
+}
+
+/// The zone styles tried, in order, when a field set formatter's location
+/// based zone style cannot format the input, such as an input carrying only
+/// a UTC offset.
+struct ZoneFallback {
+    style: TemporalZoneStyle,
+    formatters: Vec<(TemporalZoneStyle, DateTimeFormatter<CompositeFieldSet>)>,
+}
+
+/// Names the dates within `window` days of `reference` by their relative day
+/// name, such as "yesterday", in place of the formatted date fields.
+struct RelativeDays {
+    reference: Date<Iso>,
+    window: u8,
+    formatter: RelativeTimeFormatter,
+}
+
+impl RelativeDays {
+    fn name(&self, date: Option<Date<Iso>>) -> Option<String> {
+        let offset = calendar::rata_die(&date?) - calendar::rata_die(&self.reference);
+        if offset.unsigned_abs() > u64::from(self.window) {
+            return None;
+        }
+        Some(relative_day_name(&self.formatter, offset))
+    }
+}
+
+/// The part reported for a relative day name that replaced the date fields.
+pub(crate) const RELATIVE_DAY_PART: WriteablePart = WriteablePart {
+    category: "icu_nif",
+    value: "relative_day",
+};
+
+enum TemporalFormatter {
+    FieldSet(DateTimeFormatter<CompositeFieldSet>),
+    /// Formats an explicit CLDR pattern in the Gregorian calendar.
+    Pattern {
+        names: FixedCalendarDateTimeNames<Gregorian, CompositeDateTimeFieldSet>,
+        pattern: DateTimePattern,
+    },
+    /// Formats the quarter of the year, which the ICU4X field set builder
+    /// does not support. The year, if any, goes through a regular year
+    /// field set and the quarter label is added from `quarter_labels`.
+    Quarter {
+        year: Option<DateTimeFormatter<CompositeFieldSet>>,
+        labels: [&'static str; 4],
+        pattern: &'static str,
+        wide: bool,
+    },
+}
 
 impl rustler::Resource for DateTimeFormatterResource {}
 
+impl DateTimeFormatterResource {
+    /// The locale of the date symbols the formatter loaded, probed through
+    /// the abbreviated weekday names.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        resolve_data_locale::<DatetimeNamesWeekdayV1, _>(
+            &DateTimeBaked,
+            &self.locale,
+            marker_attrs::ABBR,
+        )
+    }
+
+    /// The relative day name replacing the date of the input, if the
+    /// formatter names days near its reference date.
+    fn relative_day(&self, fields: &TemporalFields) -> Option<String> {
+        self.relative_days.as_ref()?.name(fields.date)
+    }
+
+    /// Estimated bytes held by the resource. A custom pattern is counted at
+    /// roughly one byte per pattern character.
+    pub(crate) fn memory(&self) -> usize {
+        let pattern = match &self.formatter {
+            TemporalFormatter::Pattern { pattern, .. } => pattern.to_string().len(),
+            _ => 0,
+        };
+        std::mem::size_of::<Self>() + pattern + locale::heap_size(&self.locale)
+    }
+}
+
+/// Which fields a formatter was built for. Dedicated date, time and zoned
+/// formatters validate both their options and their inputs against it.
+#[derive(Clone, Copy, PartialEq)]
+enum TemporalKind {
+    Any,
+    Date,
+    Time,
+    ZonedDateTime,
+}
+
+impl TemporalKind {
+    /// Returns the first field required by this kind that the input map lacks.
+    /// ISO 8601 strings are checked once parsed, by `missing_parsed_field`.
+    fn missing_field(self, term: Term) -> Option<Atom> {
+        if term.get_type() == TermType::Binary {
+            return None;
+        }
+        self.first_missing(|key| term.map_get(key).is_ok())
+    }
+
+    /// Returns the first field required by this kind that parsed fields lack.
+    fn missing_parsed_field(self, fields: &TemporalFields) -> Option<Atom> {
+        self.first_missing(|key| {
+            if key == atoms::time_zone() {
+                fields.time_zone.is_some()
+            } else if key == atoms::utc_offset() {
+                fields.utc_offset.is_some()
+            } else if [atoms::hour(), atoms::minute(), atoms::second()].contains(&key) {
+                fields.time.is_some()
+            } else {
+                fields.date.is_some()
+            }
+        })
+    }
+
+    fn first_missing(self, has: impl Fn(Atom) -> bool) -> Option<Atom> {
+        let date_keys = [atoms::year(), atoms::month(), atoms::day()];
+        let time_keys = [atoms::hour(), atoms::minute(), atoms::second()];
+
+        let required: &[Atom] = match self {
+            TemporalKind::Any => &[],
+            TemporalKind::Date => &date_keys,
+            TemporalKind::Time => &time_keys,
+            TemporalKind::ZonedDateTime => &[
+                atoms::year(),
+                atoms::month(),
+                atoms::day(),
+                atoms::hour(),
+                atoms::minute(),
+                atoms::second(),
+            ],
+        };
+
+        if let Some(key) = required.iter().find(|key| !has(**key)) {
+            return Some(*key);
+        }
+
+        if self == TemporalKind::ZonedDateTime
+            && !has(atoms::time_zone())
+            && !has(atoms::utc_offset())
+        {
+            return Some(atoms::time_zone());
+        }
+
+        None
+    }
+}
+
 #[derive(NifMap)]
 struct DateTimeFormatPart {
     #[rustler(map = "type")]
-    part_type: Atom,
+    part_type: PartType,
     value: String,
 }
 
-struct CollectedPart {
-    start: usize,
-    end: usize,
-    part: WriteablePart,
+#[derive(NifMap)]
+struct ZoneFormatPart {
+    #[rustler(map = "type")]
+    part_type: PartType,
+    value: String,
+    zone_style: TemporalZoneStyle,
 }
 
-struct PartsCollector {
-    output: String,
-    parts: Vec<CollectedPart>,
+#[derive(NifMap)]
+struct DayPeriod {
+    period: Atom,
+    name: String,
+    start_hour: u8,
+    end_hour: u8,
 }
 
-impl PartsCollector {
-    fn new() -> Self {
-        Self {
-            output: String::new(),
-            parts: Vec::new(),
+pub(crate) fn load(env: Env) -> bool {
+    env.register::<DateTimeFormatterResource>().is_ok()
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_formatter_new<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalFormatterNew, || {
+        new_field_set_formatter(env, locale_term, options_term, TemporalKind::Any)
+    })
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_date_formatter_new<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalDateFormatterNew, || {
+        new_field_set_formatter(env, locale_term, options_term, TemporalKind::Date)
+    })
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_time_formatter_new<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalTimeFormatterNew, || {
+        new_field_set_formatter(env, locale_term, options_term, TemporalKind::Time)
+    })
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_zoned_datetime_formatter_new<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalZonedDatetimeFormatterNew, || {
+        new_field_set_formatter(env, locale_term, options_term, TemporalKind::ZonedDateTime)
+    })
+}
+
+fn new_field_set_formatter<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+    kind: TemporalKind,
+) -> NifResult<Term<'a>> {
+    let mut locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+    if let Err(reason) = preferences::apply(&mut locale_arg.0, options_term) {
+        return Ok((atoms::error(), reason).encode(env));
+    }
+
+    let quarter = match decode_option(options_term, atoms::date_fields()) {
+        Ok(Some(TemporalDateFields::YQ)) => Some(true),
+        Ok(Some(TemporalDateFields::Q)) => Some(false),
+        _ => None,
+    };
+
+    // Relative day names replace the date, so they need one to replace. The
+    // quarter formatters have no day to name.
+    let relative_days = match decode_relative_days(options_term, &locale_arg.0) {
+        Ok(relative_days) => relative_days,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+    if relative_days.is_some() && (kind == TemporalKind::Time || quarter.is_some()) {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    }
+
+    if let Some(with_year) = quarter {
+        return new_quarter_formatter(env, &locale_arg.0, options_term, kind, with_year);
+    }
+
+    let leap_second = match decode_option(options_term, atoms::leap_second()) {
+        Ok(leap_second) => leap_second.unwrap_or_default(),
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let widths = match decode_field_widths(options_term) {
+        Ok(widths) => widths,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    // A month width implies the length unless one is given, so the locale's
+    // separators suit the month form ("Jan 15, 2024" rather than "1 15, 2024").
+    let options_term = match widths.month {
+        Some(month) if get_option(options_term, atoms::length()).is_none() => {
+            let length = match month {
+                MonthWidth::Long => TemporalLength::Long,
+                MonthWidth::Short | MonthWidth::Narrow => TemporalLength::Medium,
+                MonthWidth::Numeric | MonthWidth::TwoDigit => TemporalLength::Short,
+            };
+            match options_term.map_put(atoms::length(), length) {
+                Ok(term) => term,
+                Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+            }
+        }
+        _ => options_term,
+    };
+
+    let (field_set, hour_cycle) = match build_field_set(options_term, kind) {
+        Ok(result) => result,
+        Err(error) => return Ok((atoms::error(), error).encode(env)),
+    };
+
+    let mut prefs: DateTimeFormatterPreferences = locale_arg.0.clone().into();
+    if hour_cycle.is_some() {
+        prefs.hour_cycle = hour_cycle;
+    }
+    // An explicit calendar overrides the locale's `-u-ca` keyword.
+    let explicit_calendar = match get_option(options_term, atoms::calendar()) {
+        Some(calendar_term) => {
+            match calendar_term
+                .decode::<&str>()
+                .ok()
+                .and_then(calendar::calendar_algorithm)
+            {
+                Some(algorithm) => Some(algorithm),
+                None => return Ok((atoms::error(), atoms::invalid_calendar()).encode(env)),
+            }
+        }
+        None => None,
+    };
+    if explicit_calendar.is_some() {
+        prefs.calendar_algorithm = explicit_calendar;
+    }
+
+    let formatter = match DateTimeFormatter::try_new(prefs.clone(), field_set) {
+        Ok(formatter) => formatter,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    if widths.month.is_none() && widths.year.is_none() {
+        let zone_fallback = match build_zone_fallback(options_term, kind, &prefs) {
+            Ok(zone_fallback) => zone_fallback,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+        };
+        let resource = ResourceArc::new(DateTimeFormatterResource {
+            formatter: TemporalFormatter::FieldSet(formatter),
+            kind,
+            locale: locale_arg.0,
+            zone_fallback,
+            relative_days,
+            leap_second,
+        });
+        return Ok((atoms::ok(), resource).encode(env));
+    }
+
+    // Field widths are applied to the pattern the field set resolves to,
+    // which is then formatted like an explicit pattern. Pattern names carry
+    // no time zone data and are always Gregorian.
+    if get_option(options_term, atoms::zone_style()).is_some()
+        || explicit_calendar.is_some_and(|algorithm| algorithm != CalendarAlgorithm::Gregory)
+    {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    }
+
+    let resolved = formatter
+        .format_unchecked(TemporalFields::default().to_unchecked(formatter.calendar().0))
+        .pattern()
+        .to_string();
+
+    let pattern =
+        match DateTimePattern::try_from_pattern_str(&apply_field_widths(&resolved, &widths)) {
+            Ok(pattern) => pattern,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_pattern()).encode(env)),
+        };
+
+    let mut names: FixedCalendarDateTimeNames<Gregorian, CompositeDateTimeFieldSet> =
+        match FixedCalendarDateTimeNames::try_new(prefs) {
+            Ok(names) => names,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        };
+
+    if names.include_for_pattern(&pattern).is_err() {
+        return Ok((atoms::error(), atoms::invalid_pattern()).encode(env));
+    }
+
+    let resource = ResourceArc::new(DateTimeFormatterResource {
+        formatter: TemporalFormatter::Pattern { names, pattern },
+        kind,
+        locale: locale_arg.0,
+        zone_fallback: None,
+        relative_days,
+        leap_second,
+    });
+    Ok((atoms::ok(), resource).encode(env))
+}
+
+/// ECMA-402 style per-field widths layered over the length-based field set.
+#[derive(Default)]
+struct FieldWidths {
+    month: Option<MonthWidth>,
+    year: Option<YearWidth>,
+}
+
+fn decode_field_widths(term: Term) -> Result<FieldWidths, ()> {
+    Ok(FieldWidths {
+        month: decode_option(term, atoms::month())?,
+        year: decode_option(term, atoms::year())?,
+    })
+}
+
+/// Rewrites the month (`M`/`L`) and year (`y`) fields of a CLDR pattern to
+/// the requested widths, leaving quoted literals untouched.
+fn apply_field_widths(pattern: &str, widths: &FieldWidths) -> String {
+    let mut output = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            quoted = !quoted;
+            output.push(c);
+            continue;
+        }
+
+        let width = match c {
+            _ if quoted => None,
+            'M' | 'L' => widths.month.map(|month| match month {
+                MonthWidth::Numeric => 1,
+                MonthWidth::TwoDigit => 2,
+                MonthWidth::Short => 3,
+                MonthWidth::Long => 4,
+                MonthWidth::Narrow => 5,
+            }),
+            'y' => widths.year.map(|year| match year {
+                YearWidth::Numeric => 1,
+                YearWidth::TwoDigit => 2,
+            }),
+            _ => None,
+        };
+
+        match width {
+            Some(width) => {
+                while chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                output.extend(std::iter::repeat(c).take(width));
+            }
+            None => output.push(c),
+        }
+    }
+
+    output
+}
+
+fn new_quarter_formatter<'a>(
+    env: Env<'a>,
+    locale: &Locale,
+    options_term: Term<'a>,
+    kind: TemporalKind,
+    with_year: bool,
+) -> NifResult<Term<'a>> {
+    let has_time = [
+        atoms::time_precision(),
+        atoms::zone_style(),
+        atoms::skeleton(),
+    ]
+    .into_iter()
+    .any(|key| get_option(options_term, key).is_some());
+    if has_time || !matches!(kind, TemporalKind::Any | TemporalKind::Date) {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    }
+
+    let wide = match decode_option(options_term, atoms::length()) {
+        Ok(length) => matches!(length, Some(TemporalLength::Long)),
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let year = if with_year {
+        let year_options = match options_term.map_put(atoms::date_fields(), TemporalDateFields::Y) {
+            Ok(options) => options,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+        };
+        let (field_set, _) = match build_field_set(year_options, kind) {
+            Ok(result) => result,
+            Err(error) => return Ok((atoms::error(), error).encode(env)),
+        };
+        match DateTimeFormatter::try_new(locale.clone().into(), field_set) {
+            Ok(formatter) => Some(formatter),
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        }
+    } else {
+        None
+    };
+
+    let language = locale.id.language.as_str();
+    let resource = ResourceArc::new(DateTimeFormatterResource {
+        formatter: TemporalFormatter::Quarter {
+            year,
+            labels: quarter_labels(language, wide),
+            pattern: if with_year {
+                quarter_pattern(language)
+            } else {
+                "{quarter}"
+            },
+            wide,
+        },
+        kind,
+        locale: locale.clone(),
+        zone_fallback: None,
+        relative_days: None,
+        leap_second: LeapSecond::default(),
+    });
+    Ok((atoms::ok(), resource).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_formatter_from_pattern<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    pattern: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalFormatterFromPattern, || {
+        temporal_formatter_from_pattern_inner(env, locale_term, pattern)
+    })
+}
+
+fn temporal_formatter_from_pattern_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    pattern: &str,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let pattern = match DateTimePattern::try_from_pattern_str(pattern) {
+        Ok(pattern) => pattern,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_pattern()).encode(env)),
+    };
+
+    let prefs: DateTimeFormatterPreferences = locale_arg.0.clone().into();
+    let mut names: FixedCalendarDateTimeNames<Gregorian, CompositeDateTimeFieldSet> =
+        match FixedCalendarDateTimeNames::try_new(prefs) {
+            Ok(names) => names,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        };
+
+    // Loads every name and the decimal formatter the pattern refers to, so
+    // formatting can later borrow the names immutably.
+    if names.include_for_pattern(&pattern).is_err() {
+        return Ok((atoms::error(), atoms::invalid_pattern()).encode(env));
+    }
+
+    let resource = ResourceArc::new(DateTimeFormatterResource {
+        formatter: TemporalFormatter::Pattern { names, pattern },
+        kind: TemporalKind::Any,
+        locale: locale_arg.0,
+        zone_fallback: None,
+        relative_days: None,
+        leap_second: LeapSecond::default(),
+    });
+    Ok((atoms::ok(), resource).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_format<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    datetime_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalFormat, || {
+        temporal_format_inner(env, formatter_term, datetime_term)
+    })
+}
+
+fn temporal_format_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    datetime_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let formatter_resource: ResourceArc<DateTimeFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+
+    if let Some(field) = formatter_resource.kind.missing_field(datetime_term) {
+        return Ok((atoms::error(), (atoms::missing_field(), field)).encode(env));
+    }
+
+    let fields = match decode_temporal(datetime_term, formatter_resource.leap_second) {
+        Ok(fields) => fields,
+        Err(error) => return Ok((atoms::error(), error).encode(env)),
+    };
+    if let Some(field) = formatter_resource.kind.missing_parsed_field(&fields) {
+        return Ok((atoms::error(), (atoms::missing_field(), field)).encode(env));
+    }
+    let relative_day = formatter_resource.relative_day(&fields);
+
+    let formatted_result = match &formatter_resource.formatter {
+        TemporalFormatter::FieldSet(formatter) => {
+            with_zone_fallback(&formatter_resource, formatter, |formatter| {
+                write_string(
+                    &formatter.format_unchecked(fields.to_unchecked(formatter.calendar().0)),
+                    relative_day.as_deref(),
+                )
+            })
+            .map(|(output, _)| output)
+        }
+        TemporalFormatter::Pattern { names, pattern } => {
+            match fields.to_gregorian_datetime(pattern) {
+                Ok(datetime) => write_string(
+                    &names.with_pattern_unchecked(pattern).format(&datetime),
+                    relative_day.as_deref(),
+                ),
+                Err(field) => {
+                    return Ok((atoms::error(), (atoms::missing_field(), field)).encode(env))
+                }
+            }
+        }
+        TemporalFormatter::Quarter { .. } => {
+            format_quarter(&formatter_resource.formatter, &fields).map(|(output, _)| output)
+        }
+    };
+
+    match formatted_result {
+        Ok(str) => Ok((atoms::ok(), str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::invalid_datetime()).encode(env)),
+    }
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_format_to_parts<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    datetime_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalFormatToParts, || {
+        temporal_format_to_parts_inner(env, formatter_term, datetime_term, index_mode_term)
+    })
+}
+
+fn temporal_format_to_parts_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    datetime_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let formatter_resource: ResourceArc<DateTimeFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    if let Some(field) = formatter_resource.kind.missing_field(datetime_term) {
+        return Ok((atoms::error(), (atoms::missing_field(), field)).encode(env));
+    }
+
+    let fields = match decode_temporal(datetime_term, formatter_resource.leap_second) {
+        Ok(fields) => fields,
+        Err(error) => return Ok((atoms::error(), error).encode(env)),
+    };
+    if let Some(field) = formatter_resource.kind.missing_parsed_field(&fields) {
+        return Ok((atoms::error(), (atoms::missing_field(), field)).encode(env));
+    }
+    let relative_day = formatter_resource.relative_day(&fields);
+
+    let parts = match &formatter_resource.formatter {
+        TemporalFormatter::FieldSet(formatter) => {
+            with_zone_fallback(&formatter_resource, formatter, |formatter| {
+                collect_parts(
+                    &formatter.format_unchecked(fields.to_unchecked(formatter.calendar().0)),
+                    relative_day.as_deref(),
+                )
+            })
         }
+        TemporalFormatter::Pattern { names, pattern } => {
+            match fields.to_gregorian_datetime(pattern) {
+                Ok(datetime) => collect_parts(
+                    &names.with_pattern_unchecked(pattern).format(&datetime),
+                    relative_day.as_deref(),
+                )
+                .map(|parts| (parts, None)),
+                Err(field) => {
+                    return Ok((atoms::error(), (atoms::missing_field(), field)).encode(env))
+                }
+            }
+        }
+        TemporalFormatter::Quarter { .. } => {
+            format_quarter(&formatter_resource.formatter, &fields).map(|parts| (parts, None))
+        }
+    };
+
+    let ((output, parts), zone_style) = match parts {
+        Ok(result) => result,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_datetime()).encode(env)),
+    };
+
+    // The time zone part reports which level of the zone fallback chain
+    // produced it.
+    let parts: Vec<(Term<'a>, Range<usize>)> = parts
+        .into_iter()
+        .map(|(part, range)| match zone_style {
+            Some(zone_style) if part.part_type == atoms::time_zone_name() => {
+                let part = ZoneFormatPart {
+                    part_type: part.part_type,
+                    value: part.value,
+                    zone_style,
+                };
+                (part.encode(env), range)
+            }
+            _ => (part.encode(env), range),
+        })
+        .collect();
+
+    let parts = index::encode_parts(env, &output, parts, index_mode);
+    Ok((atoms::ok(), parts).encode(env))
+}
+
+const RFC_WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const RFC_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The fixed English formats of email and HTTP headers.
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum RfcFormat {
+    /// `Sun, 01 Jun 2025 14:30:00 +0200`, in the input's own offset.
+    Rfc2822,
+    /// `Sun, 01 Jun 2025 12:30:00 GMT`, always in UTC.
+    Http,
+}
+
+/// Formats a date and time as an RFC 2822 date or an HTTP-date. Neither
+/// depends on a locale, so no formatter is involved. RFC 2822 writes an input
+/// without an offset as `-0000`, marking its zone as unknown, and the
+/// HTTP-date treats it as UTC.
+#[rustler::nif]
+pub(crate) fn temporal_format_rfc<'a>(
+    env: Env<'a>,
+    datetime_term: Term<'a>,
+    format_term: Term<'a>,
+) -> Term<'a> {
+    stats::measure(Counted::TemporalFormatRfc, || {
+        temporal_format_rfc_inner(env, datetime_term, format_term)
+    })
+}
+
+fn temporal_format_rfc_inner<'a>(
+    env: Env<'a>,
+    datetime_term: Term<'a>,
+    format_term: Term<'a>,
+) -> Term<'a> {
+    let format: RfcFormat = match format_term.decode() {
+        Ok(format) => format,
+        Err(_) => return (atoms::error(), atoms::invalid_options()).encode(env),
+    };
+
+    // Both formats need a full date and time.
+    let missing = |kind: TemporalKind| kind.missing_field(datetime_term);
+    if let Some(field) = missing(TemporalKind::Date).or_else(|| missing(TemporalKind::Time)) {
+        return (atoms::error(), (atoms::missing_field(), field)).encode(env);
+    }
+    let fields = match decode_temporal(datetime_term, LeapSecond::Clamp) {
+        Ok(fields) => fields,
+        Err(error) => return (atoms::error(), error).encode(env),
+    };
+    let missing = |kind: TemporalKind| kind.missing_parsed_field(&fields);
+    if let Some(field) = missing(TemporalKind::Date).or_else(|| missing(TemporalKind::Time)) {
+        return (atoms::error(), (atoms::missing_field(), field)).encode(env);
+    }
+
+    match format_rfc(&fields, format) {
+        Some(str) => (atoms::ok(), str).encode(env),
+        None => (atoms::error(), atoms::invalid_datetime()).encode(env),
+    }
+}
+
+/// Checks that a temporal input names a date and time that exist, with its
+/// date fields counted in the calendar `calendar_id`. Returns `:ok`, or the
+/// rejected fields with the reason for each, such as
+/// `[day: {:out_of_range, 1, 28}]`. The input is decoded exactly as the
+/// formatters decode it.
+#[rustler::nif]
+pub(crate) fn temporal_validate<'a>(
+    env: Env<'a>,
+    datetime_term: Term<'a>,
+    calendar_id: &str,
+) -> Term<'a> {
+    stats::measure(Counted::TemporalValidate, || {
+        temporal_validate_inner(env, datetime_term, calendar_id)
+    })
+}
+
+fn temporal_validate_inner<'a>(
+    env: Env<'a>,
+    datetime_term: Term<'a>,
+    calendar_id: &str,
+) -> Term<'a> {
+    let calendar = match calendar::calendar_kind(calendar_id) {
+        Some(AnyCalendarKind::Iso) => None,
+        Some(kind) => Some(AnyCalendar::new(kind)),
+        None => return (atoms::error(), atoms::invalid_calendar()).encode(env),
+    };
+
+    match decode_temporal_in(datetime_term, calendar.as_ref(), LeapSecond::Clamp) {
+        Ok(_) => atoms::ok().encode(env),
+        Err(errors) if errors.is_empty() => (atoms::error(), atoms::invalid_datetime()).encode(env),
+        Err(errors) => (atoms::error(), errors).encode(env),
+    }
+}
+
+fn format_rfc(fields: &TemporalFields, format: RfcFormat) -> Option<String> {
+    let (date, time) = (fields.date?, fields.time?);
+    let offset = fields.utc_offset.map(|offset| offset.to_seconds());
+
+    let seconds_of_day = i64::from(time.hour.number()) * 3600
+        + i64::from(time.minute.number()) * 60
+        + i64::from(time.second.number());
+    let mut seconds = calendar::rata_die(&date) * 86_400 + seconds_of_day;
+    if let RfcFormat::Http = format {
+        seconds -= i64::from(offset.unwrap_or(0));
+    }
+
+    let rata_die = seconds.div_euclid(86_400);
+    let seconds_of_day = seconds.rem_euclid(86_400);
+    let date = calendar::iso_from_rata_die(rata_die)?;
+    let year = date.extended_year();
+    if !(0..=9999).contains(&year) {
+        return None;
     }
 
-    fn finish(self) -> (String, Vec<CollectedPart>) {
-        (self.output, self.parts)
+    let zone = match (format, offset) {
+        (RfcFormat::Http, _) => "GMT".to_string(),
+        (RfcFormat::Rfc2822, None) => "-0000".to_string(),
+        (RfcFormat::Rfc2822, Some(offset)) => {
+            let sign = if offset < 0 { '-' } else { '+' };
+            let minutes = offset.abs() / 60;
+            format!("{sign}{:02}{:02}", minutes / 60, minutes % 60)
+        }
+    };
+
+    // Rata die 1 is a Monday, so a remainder of 0 is a Sunday.
+    Some(format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} {}",
+        RFC_WEEKDAYS[rata_die.rem_euclid(7) as usize],
+        date.day_of_month().0,
+        RFC_MONTHS[usize::from(date.month().ordinal) - 1],
+        year,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        zone,
+    ))
+}
+
+/// Formats with a field set formatter, retrying with the resource's zone
+/// fallbacks when the requested zone style cannot format the input. The zone
+/// style that succeeded is returned when the resource has fallbacks.
+fn with_zone_fallback<T>(
+    resource: &DateTimeFormatterResource,
+    formatter: &DateTimeFormatter<CompositeFieldSet>,
+    format: impl Fn(&DateTimeFormatter<CompositeFieldSet>) -> Result<T, ()>,
+) -> Result<(T, Option<TemporalZoneStyle>), ()> {
+    let zone_fallback = match &resource.zone_fallback {
+        Some(zone_fallback) => zone_fallback,
+        None => return format(formatter).map(|result| (result, None)),
+    };
+
+    if let Ok(result) = format(formatter) {
+        return Ok((result, Some(zone_fallback.style)));
     }
+
+    zone_fallback
+        .formatters
+        .iter()
+        .find_map(|(style, formatter)| format(formatter).ok().map(|result| (result, Some(*style))))
+        .ok_or(())
+}
+
+#[rustler::nif]
+pub(crate) fn temporal_formatter_pattern<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    sample_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalFormatterPattern, || {
+        temporal_formatter_pattern_inner(env, formatter_term, sample_term)
+    })
 }
 
-impl fmt::Write for PartsCollector {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.output.push_str(s);
-        Ok(())
+fn temporal_formatter_pattern_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    sample_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let formatter_resource: ResourceArc<DateTimeFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+
+    // Without a sample the pattern is resolved from the field set alone.
+    let fields = if sample_term.get_type() == TermType::Atom {
+        TemporalFields::default()
+    } else {
+        match decode_temporal(sample_term, formatter_resource.leap_second) {
+            Ok(fields) => fields,
+            Err(error) => return Ok((atoms::error(), error).encode(env)),
+        }
+    };
+
+    let pattern = match &formatter_resource.formatter {
+        TemporalFormatter::FieldSet(formatter) => formatter
+            .format_unchecked(fields.to_unchecked(formatter.calendar().0))
+            .pattern()
+            .to_string(),
+        TemporalFormatter::Pattern { pattern, .. } => pattern.to_string(),
+        TemporalFormatter::Quarter {
+            year,
+            pattern,
+            wide,
+            ..
+        } => {
+            let year_pattern = match year {
+                Some(formatter) => formatter
+                    .format_unchecked(fields.to_unchecked(formatter.calendar().0))
+                    .pattern()
+                    .to_string(),
+                None => String::new(),
+            };
+            pattern
+                .replace("{quarter}", if *wide { "QQQQ" } else { "QQQ" })
+                .replace("{year}", &year_pattern)
+        }
+    };
+
+    Ok((atoms::ok(), pattern).encode(env))
+}
+
+/// Returns the locale's AM/PM names, plus its noon and midnight names when
+/// it has distinct ones, each with the hours it covers (`end_hour` is
+/// exclusive; noon and midnight cover a single instant).
+///
+/// ICU4X does not support the CLDR flexible day periods ("in the morning"),
+/// so those cannot be listed.
+#[rustler::nif]
+pub(crate) fn temporal_day_periods<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    width: Atom,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TemporalDayPeriods, || {
+        temporal_day_periods_inner(env, locale_term, width)
+    })
+}
+
+fn temporal_day_periods_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    width: Atom,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let (am_pm, noon_midnight) = if width == atoms::short() {
+        ("a", "b")
+    } else if width == atoms::wide() {
+        ("aaaa", "bbbb")
+    } else if width == atoms::narrow() {
+        ("aaaaa", "bbbbb")
+    } else {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    };
+
+    let (Ok(am_pm), Ok(noon_midnight)) = (
+        DateTimePattern::try_from_pattern_str(am_pm),
+        DateTimePattern::try_from_pattern_str(noon_midnight),
+    ) else {
+        return Ok((atoms::error(), atoms::invalid_pattern()).encode(env));
+    };
+
+    let prefs: DateTimeFormatterPreferences = locale_arg.0.clone().into();
+    let mut names: FixedCalendarDateTimeNames<Gregorian, CompositeDateTimeFieldSet> =
+        match FixedCalendarDateTimeNames::try_new(prefs) {
+            Ok(names) => names,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        };
+
+    if names.include_for_pattern(&am_pm).is_err()
+        || names.include_for_pattern(&noon_midnight).is_err()
+    {
+        return Ok((atoms::error(), atoms::invalid_locale()).encode(env));
     }
 
-    fn write_char(&mut self, c: char) -> fmt::Result {
-        self.output.push(c);
-        Ok(())
+    let format = |pattern: &DateTimePattern, hour: u8| -> Result<String, ()> {
+        let date = Date::try_new_iso(1970, 1, 1).map_err(|_| ())?;
+        let time = Time::try_new(hour, 0, 0, 0).map_err(|_| ())?;
+        let datetime = DateTime {
+            date: date.to_calendar(Gregorian),
+            time,
+        };
+        names
+            .with_pattern_unchecked(pattern)
+            .format(&datetime)
+            .try_write_to_string()
+            .map(|str| str.into_owned())
+            .map_err(|_| ())
+    };
+
+    let mut periods = Vec::with_capacity(4);
+    for (period, hour, start_hour, end_hour) in [(atoms::am(), 0, 0, 12), (atoms::pm(), 12, 12, 24)]
+    {
+        match format(&am_pm, hour) {
+            Ok(name) => periods.push(DayPeriod {
+                period,
+                name,
+                start_hour,
+                end_hour,
+            }),
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        }
+    }
+
+    // Without noon or midnight names the `b` field falls back to AM/PM.
+    for (period, hour) in [(atoms::midnight(), 0), (atoms::noon(), 12)] {
+        match (format(&noon_midnight, hour), format(&am_pm, hour)) {
+            (Ok(name), Ok(fallback)) if name != fallback => periods.push(DayPeriod {
+                period,
+                name,
+                start_hour: hour,
+                end_hour: hour,
+            }),
+            (Ok(_), Ok(_)) => {}
+            _ => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        }
+    }
+
+    Ok((atoms::ok(), periods).encode(env))
+}
+
+/// Returns the CLDR relative day name for a number of days from today, such
+/// as "yesterday" for -1. Offsets the locale has no name for are formatted
+/// numerically, as in "in 3 days".
+#[rustler::nif]
+pub(crate) fn temporal_relative_day_name<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    offset: i64,
+    width: Atom,
+) -> Term<'a> {
+    stats::measure(Counted::TemporalRelativeDayName, || {
+        temporal_relative_day_name_inner(env, locale_term, offset, width)
+    })
+}
+
+fn temporal_relative_day_name_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    offset: i64,
+    width: Atom,
+) -> Term<'a> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return (atoms::error(), atoms::invalid_locale()).encode(env),
+    };
+
+    match relative_day_formatter(&locale_arg.0, width) {
+        Ok(formatter) => (atoms::ok(), relative_day_name(&formatter, offset)).encode(env),
+        Err(error) => (atoms::error(), error).encode(env),
+    }
+}
+
+fn relative_day_formatter(locale: &Locale, width: Atom) -> Result<RelativeTimeFormatter, Atom> {
+    let options = RelativeTimeFormatterOptions {
+        numeric: Numeric::Auto,
+    };
+    let prefs = locale.clone().into();
+
+    let formatter = if width == atoms::wide() {
+        RelativeTimeFormatter::try_new_long_day(prefs, options)
+    } else if width == atoms::short() {
+        RelativeTimeFormatter::try_new_short_day(prefs, options)
+    } else if width == atoms::narrow() {
+        RelativeTimeFormatter::try_new_narrow_day(prefs, options)
+    } else {
+        return Err(atoms::invalid_options());
+    };
+    formatter.map_err(|_| atoms::invalid_locale())
+}
+
+fn relative_day_name(formatter: &RelativeTimeFormatter, offset: i64) -> String {
+    formatter
+        .format(FixedDecimal::from(offset))
+        .write_to_string()
+        .into_owned()
+}
+
+/// Decodes the `:relative_to` reference date and the `:relative_days` window,
+/// which defaults to one day either side. A window without a reference date
+/// is rejected.
+fn decode_relative_days(term: Term, locale: &Locale) -> Result<Option<RelativeDays>, ()> {
+    let window: Option<u8> = decode_option(term, atoms::relative_days())?;
+    let reference = match get_option(term, atoms::relative_to()) {
+        Some(reference) => decode_reference_date(reference)?,
+        None if window.is_some() => return Err(()),
+        None => return Ok(None),
+    };
+
+    let formatter = relative_day_formatter(locale, atoms::wide()).map_err(|_| ())?;
+    Ok(Some(RelativeDays {
+        reference,
+        window: window.unwrap_or(1),
+        formatter,
+    }))
+}
+
+/// Decodes a reference date given as a temporal input or as a
+/// `{unix_seconds, utc_offset}` clock reading. The clock is always read by the
+/// caller, never here, so callers can fix the current time.
+fn decode_reference_date(term: Term) -> Result<Date<Iso>, ()> {
+    if let Ok((seconds, offset)) = term.decode::<(i64, Term)>() {
+        let local = seconds
+            .checked_add(i64::from(decode_utc_offset(offset)?))
+            .ok_or(())?;
+        let rata_die = calendar::UNIX_EPOCH_RATA_DIE.checked_add(local.div_euclid(86_400));
+        return rata_die.and_then(calendar::iso_from_rata_die).ok_or(());
+    }
+    decode_temporal(term, LeapSecond::Clamp)
+        .map_err(|_| ())?
+        .date
+        .ok_or(())
+}
+
+/// Formats a quarter formatter's pattern into parts. Quarters follow the
+/// Gregorian months of the input date.
+fn format_quarter(
+    formatter: &TemporalFormatter,
+    fields: &TemporalFields,
+) -> Result<(String, Vec<(DateTimeFormatPart, Range<usize>)>), ()> {
+    let TemporalFormatter::Quarter {
+        year,
+        labels,
+        pattern,
+        ..
+    } = formatter
+    else {
+        return Err(());
+    };
+
+    let date = fields.date.ok_or(())?;
+    let quarter = labels[usize::from((date.month().ordinal - 1) / 3)];
+
+    let mut output = String::new();
+    let mut parts = Vec::new();
+    let push = |output: &mut String,
+                parts: &mut Vec<(DateTimeFormatPart, Range<usize>)>,
+                part_type: Atom,
+                value: &str| {
+        let start = output.len();
+        output.push_str(value);
+        let part = DateTimeFormatPart {
+            part_type: part_type.into(),
+            value: value.to_string(),
+        };
+        parts.push((part, start..output.len()));
+    };
+
+    let mut rest = *pattern;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}').ok_or(())?;
+        if start > 0 {
+            push(&mut output, &mut parts, atoms::literal(), &rest[..start]);
+        }
+        match (&rest[start..=end], year) {
+            ("{quarter}", _) => push(&mut output, &mut parts, atoms::quarter(), quarter),
+            ("{year}", Some(formatter)) => {
+                let (year, year_parts) = collect_parts(
+                    &formatter.format_unchecked(fields.to_unchecked(formatter.calendar().0)),
+                    None,
+                )?;
+                parts.extend(index::shift(year_parts, output.len()));
+                output.push_str(&year);
+            }
+            _ => {}
+        }
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        push(&mut output, &mut parts, atoms::literal(), rest);
+    }
+
+    Ok((output, parts))
+}
+
+/// Quarter format names for the supported languages, abbreviated or wide.
+///
+/// ICU4X does not ship the CLDR quarter names, so they are bundled here;
+/// other languages use the abbreviated English names.
+fn quarter_labels(language: &str, wide: bool) -> [&'static str; 4] {
+    match (language, wide) {
+        ("en", true) => ["1st quarter", "2nd quarter", "3rd quarter", "4th quarter"],
+        ("de", true) => ["1. Quartal", "2. Quartal", "3. Quartal", "4. Quartal"],
+        ("fr", true) => [
+            "1er trimestre",
+            "2e trimestre",
+            "3e trimestre",
+            "4e trimestre",
+        ],
+        ("fr" | "es" | "it" | "pt", false) => ["T1", "T2", "T3", "T4"],
+        ("es", true) => [
+            "1.er trimestre",
+            "2.º trimestre",
+            "3.er trimestre",
+            "4.º trimestre",
+        ],
+        ("it" | "pt", true) => [
+            "1º trimestre",
+            "2º trimestre",
+            "3º trimestre",
+            "4º trimestre",
+        ],
+        ("nl", false) => ["K1", "K2", "K3", "K4"],
+        ("nl", true) => ["1e kwartaal", "2e kwartaal", "3e kwartaal", "4e kwartaal"],
+        ("ja", true) => ["第1四半期", "第2四半期", "第3四半期", "第4四半期"],
+        ("zh", false) => ["1季度", "2季度", "3季度", "4季度"],
+        ("zh", true) => ["第一季度", "第二季度", "第三季度", "第四季度"],
+        _ => ["Q1", "Q2", "Q3", "Q4"],
+    }
+}
+
+/// How the quarter combines with the year. The year field set already
+/// carries the "年" suffix in Chinese and Japanese.
+fn quarter_pattern(language: &str) -> &'static str {
+    match language {
+        "ja" | "zh" => "{year}{quarter}",
+        _ => "{quarter} {year}",
+    }
+}
+
+/// The single date field whose localized name `field_name` reads.
+#[derive(Clone, Copy)]
+pub(crate) enum NamedField {
+    Era,
+    Month,
+}
+
+/// Builds a long formatter in `calendar` whose output carries the name of
+/// `field`: a year with era for eras and a standalone month for months.
+pub(crate) fn field_name_formatter(
+    locale: &Locale,
+    calendar: CalendarAlgorithm,
+    field: NamedField,
+) -> Option<DateTimeFormatter<CompositeFieldSet>> {
+    use icu::datetime::fieldsets::builder::DateFields;
+
+    let mut prefs: DateTimeFormatterPreferences = locale.clone().into();
+    prefs.calendar_algorithm = Some(calendar);
+
+    let mut builder = FieldSetBuilder::new();
+    builder.length = Some(options::Length::Long);
+    match field {
+        NamedField::Era => {
+            builder.date_fields = Some(DateFields::Y);
+            builder.year_style = Some(options::YearStyle::WithEra);
+        }
+        NamedField::Month => builder.date_fields = Some(DateFields::M),
+    }
+    let field_set = builder.build_composite().ok()?;
+
+    DateTimeFormatter::try_new(prefs, field_set).ok()
+}
+
+/// Returns the name of `field` in `date` as formatted by a formatter from
+/// `field_name_formatter`.
+pub(crate) fn field_name(
+    formatter: &DateTimeFormatter<CompositeFieldSet>,
+    date: Date<Iso>,
+    field: NamedField,
+) -> Option<String> {
+    let fields = TemporalFields {
+        date: Some(date),
+        ..Default::default()
+    };
+    let (_, parts) = collect_parts(
+        &formatter.format_unchecked(fields.to_unchecked(formatter.calendar().0)),
+        None,
+    )
+    .ok()?;
+
+    let part_type = match field {
+        NamedField::Era => atoms::era(),
+        NamedField::Month => atoms::month(),
+    };
+    parts
+        .into_iter()
+        .find(|(part, _)| part.part_type == part_type)
+        .map(|(part, _)| part.value)
+}
+
+/// Collects the parts of a formatted value with their byte ranges in the
+/// output. Text outside any part is reported as literals.
+fn collect_parts(
+    formatted: &impl TryWriteable,
+    relative_day: Option<&str>,
+) -> Result<(String, Vec<(DateTimeFormatPart, Range<usize>)>), ()> {
+    let (output, collected_parts) = write_parts(formatted, relative_day)?;
+
+    let mut parts = Vec::new();
+    let mut last_index = 0usize;
+    let literal = |range: Range<usize>| {
+        let part = DateTimeFormatPart {
+            part_type: atoms::literal().into(),
+            value: output[range.clone()].to_string(),
+        };
+        (part, range)
+    };
+
+    for collected in collected_parts {
+        if collected.start > last_index && output.get(last_index..collected.start).is_some() {
+            parts.push(literal(last_index..collected.start));
+        }
+
+        if let Some(slice) = output.get(collected.range()) {
+            let part = DateTimeFormatPart {
+                part_type: PartType::from(collected.part),
+                value: slice.to_string(),
+            };
+            parts.push((part, collected.range()));
+        }
+
+        last_index = collected.end;
+    }
+
+    if last_index < output.len() && output.get(last_index..).is_some() {
+        parts.push(literal(last_index..output.len()));
+    }
+
+    Ok((output, parts))
+}
+
+/// Writes the formatted value, replacing its date fields with the relative day
+/// name when one is given.
+fn write_string(formatted: &impl TryWriteable, relative_day: Option<&str>) -> Result<String, ()> {
+    match relative_day {
+        Some(_) => write_parts(formatted, relative_day).map(|(output, _)| output),
+        None => formatted
+            .try_write_to_string()
+            .map(|str| str.into_owned())
+            .map_err(|_| ()),
+    }
+}
+
+fn write_parts(
+    formatted: &impl TryWriteable,
+    relative_day: Option<&str>,
+) -> Result<(String, Vec<CollectedPart>), ()> {
+    let mut collector = PartsCollector::new();
+    if formatted.try_write_to_parts(&mut collector).is_err() {
+        return Err(());
+    }
+    let (output, parts) = collector.finish();
+
+    Ok(match relative_day {
+        Some(name) => substitute_relative_day(output, parts, name),
+        None => (output, parts),
+    })
+}
+
+/// Replaces the span from the first to the last date field with `name`,
+/// keeping the time and zone fields and the literals around them.
+fn substitute_relative_day(
+    output: String,
+    parts: Vec<CollectedPart>,
+    name: &str,
+) -> (String, Vec<CollectedPart>) {
+    let date_parts = parts
+        .iter()
+        .filter(|collected| is_date_part(collected.part));
+    let (Some(start), Some(end)) = (
+        date_parts.clone().map(|collected| collected.start).min(),
+        date_parts.map(|collected| collected.end).max(),
+    ) else {
+        return (output, parts);
+    };
+
+    let substituted = [&output[..start], name, &output[end..]].concat();
+    let shift = |index: usize| index - end + start + name.len();
+
+    // Parts are collected as they close, so those ending before the date
+    // come first and those starting after it last.
+    let mut parts: Vec<CollectedPart> = parts
+        .into_iter()
+        .filter_map(|collected| {
+            if collected.end <= start {
+                Some(collected)
+            } else if collected.start >= end {
+                Some(CollectedPart {
+                    start: shift(collected.start),
+                    end: shift(collected.end),
+                    part: collected.part,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    let index = parts
+        .iter()
+        .take_while(|collected| collected.end <= start)
+        .count();
+    parts.insert(
+        index,
+        CollectedPart {
+            start,
+            end: start + name.len(),
+            part: RELATIVE_DAY_PART,
+        },
+    );
+
+    (substituted, parts)
+}
+
+fn is_date_part(part: WriteablePart) -> bool {
+    [
+        datetime_parts::ERA,
+        datetime_parts::YEAR,
+        datetime_parts::RELATED_YEAR,
+        datetime_parts::YEAR_NAME,
+        datetime_parts::MONTH,
+        datetime_parts::DAY,
+        datetime_parts::WEEKDAY,
+    ]
+    .contains(&part)
+}
+
+/// Temporal fields decoded from the map built by `Icu.Temporal.Formatter`.
+#[derive(Default)]
+struct TemporalFields {
+    date: Option<Date<Iso>>,
+    time: Option<Time>,
+    time_zone: Option<TimeZone>,
+    utc_offset: Option<UtcOffset>,
+}
+
+impl TemporalFields {
+    /// Builds the unchecked input for a formatter, converting the ISO date into
+    /// the formatter's calendar so eras and cyclic years resolve correctly.
+    fn to_unchecked(&self, calendar: &AnyCalendar) -> DateTimeInputUnchecked {
+        let mut unchecked = DateTimeInputUnchecked::default();
+        if let Some(date) = self.date {
+            unchecked.set_date_fields_unchecked(date.to_calendar(Ref(calendar)));
+        }
+        if let Some(time) = self.time {
+            unchecked.set_time_fields(time);
+        }
+        if let Some(time_zone) = self.time_zone {
+            unchecked.set_time_zone_id(time_zone);
+        }
+        if let Some(offset) = self.utc_offset {
+            unchecked.set_time_zone_utc_offset(offset);
+        }
+        unchecked
+    }
+
+    /// Pattern formatters always take a full datetime. The input must carry
+    /// the date and the time when `pattern` refers to them, and fails with the
+    /// first missing field otherwise. The half the pattern does not render is
+    /// filled with the Unix epoch date or midnight.
+    fn to_gregorian_datetime(
+        &self,
+        pattern: &DateTimePattern,
+    ) -> Result<DateTime<Gregorian>, Atom> {
+        let (needs_date, needs_time) = pattern_fields(&pattern.to_string());
+        let date = match self.date {
+            Some(date) => date,
+            None if needs_date => return Err(atoms::year()),
+            None => Date::try_new_iso(1970, 1, 1).map_err(|_| atoms::year())?,
+        };
+        let time = match self.time {
+            Some(time) => time,
+            None if needs_time => return Err(atoms::hour()),
+            None => Time::start_of_day(),
+        };
+
+        Ok(DateTime {
+            date: date.to_calendar(Gregorian),
+            time,
+        })
     }
 }
 
-impl PartsWrite for PartsCollector {
-    type SubPartsWrite = PartsCollector;
-
-    fn with_part(
-        &mut self,
-        part: WriteablePart,
-        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
-    ) -> fmt::Result {
-        let start = self.output.len();
-        f(self)?;
-        let end = self.output.len();
-        if start < end {
-            self.parts.push(CollectedPart { start, end, part });
+/// Whether a CLDR `pattern` has date fields and time fields, skipping
+/// literal text in quotes.
+fn pattern_fields(pattern: &str) -> (bool, bool) {
+    let mut quoted = false;
+    let (mut date, mut time) = (false, false);
+    for c in pattern.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            _ if quoted => {}
+            'G' | 'y' | 'Y' | 'u' | 'U' | 'r' | 'Q' | 'q' | 'M' | 'L' | 'w' | 'W' | 'd' | 'D'
+            | 'F' | 'g' | 'E' | 'e' | 'c' => date = true,
+            'a' | 'b' | 'B' | 'h' | 'H' | 'K' | 'k' | 'm' | 's' | 'S' | 'A' => time = true,
+            _ => {}
         }
-        Ok(())
     }
+    (date, time)
 }
 
-pub(crate) fn load(env: Env) -> bool {
-    env.register::<DateTimeFormatterResource>().is_ok()
+fn decode_temporal<'a>(
+    term: Term<'a>,
+    leap_second: LeapSecond,
+) -> Result<TemporalFields, TemporalError> {
+    decode_temporal_in(term, None, leap_second).map_err(|errors| {
+        errors
+            .into_iter()
+            .find_map(|(field, error)| match error {
+                FieldError::OutOfRange { min, max } => {
+                    Some(TemporalError::OutOfRange { field, min, max })
+                }
+                _ => None,
+            })
+            .unwrap_or(TemporalError::Invalid)
+    })
 }
 
-#[rustler::nif]
-pub(crate) fn temporal_formatter_new<'a>(
-    env: Env<'a>,
-    locale_term: Term<'a>,
-    options_term: Term<'a>,
-) -> NifResult<Term<'a>> {
-    let locale_resource: ResourceArc<LocaleResource> = match locale_term.decode() {
-        Ok(resource) => resource,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
-    };
-
-    let field_set = match build_field_set(options_term) {
-        Ok(field_set) => field_set,
-        Err(_error) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
-    };
-
-    let prefs: DateTimeFormatterPreferences = locale_resource.0.clone().into();
-
-    let formatter = match DateTimeFormatter::try_new(prefs, field_set) {
-        Ok(formatter) => formatter,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
-    };
-
-    let resource = ResourceArc::new(DateTimeFormatterResource(formatter));
-    Ok((atoms::ok(), resource).encode(env))
+/// Why a temporal input cannot be formatted: `:invalid_datetime`, or
+/// `{:out_of_range, field, min, max}` for a field outside the bounds it can
+/// take, so callers can clamp it.
+enum TemporalError {
+    Invalid,
+    OutOfRange { field: Atom, min: i64, max: i64 },
 }
 
-#[rustler::nif]
-pub(crate) fn temporal_format<'a>(
-    env: Env<'a>,
-    formatter_term: Term<'a>,
-    datetime_term: Term<'a>,
-) -> NifResult<Term<'a>> {
-    let formatter_resource: ResourceArc<DateTimeFormatterResource> = match formatter_term.decode() {
-        Ok(resource) => resource,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
-    };
+impl Encoder for TemporalError {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match *self {
+            Self::Invalid => atoms::invalid_datetime().encode(env),
+            Self::OutOfRange { field, min, max } => {
+                (atoms::out_of_range(), field, min, max).encode(env)
+            }
+        }
+    }
+}
 
-    let formatter_calendar = formatter_resource.0.calendar();
+/// Why a field of a temporal input was rejected.
+#[derive(Clone, Copy)]
+enum FieldError {
+    /// The field is needed by the fields given alongside it.
+    Missing,
+    /// The value has the wrong type, or names a month the year lacks.
+    Invalid,
+    OutOfRange {
+        min: i64,
+        max: i64,
+    },
+}
 
-    let input = match decode_temporal(datetime_term, formatter_calendar.0) {
-        Ok(datetime) => datetime,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_datetime()).encode(env)),
-    };
+impl Encoder for FieldError {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match *self {
+            Self::Missing => atoms::missing().encode(env),
+            Self::Invalid => atoms::invalid().encode(env),
+            Self::OutOfRange { min, max } => (atoms::out_of_range(), min, max).encode(env),
+        }
+    }
+}
 
-    let formatted_unchecked = formatter_resource.0.format_unchecked(input);
-    let formatted_result = formatted_unchecked.try_write_to_string();
+/// The rejected fields of a temporal input, in the order they were read.
+/// Empty when the input is not a temporal map or ISO 8601 string at all.
+type FieldErrors = Vec<(Atom, FieldError)>;
 
-    match formatted_result {
-        Ok(str) => Ok((atoms::ok(), &*str).encode(env)),
-        Err(_) => todo!(),
+/// Checks that an integer field lies within `bounds`.
+fn bounded<T: TryFrom<i64>>(
+    value: i64,
+    bounds: std::ops::RangeInclusive<i64>,
+) -> Result<T, FieldError> {
+    if !bounds.contains(&value) {
+        return Err(FieldError::OutOfRange {
+            min: *bounds.start(),
+            max: *bounds.end(),
+        });
     }
+    T::try_from(value).map_err(|_| FieldError::Invalid)
 }
 
-#[rustler::nif]
-pub(crate) fn temporal_format_to_parts<'a>(
-    env: Env<'a>,
-    formatter_term: Term<'a>,
-    datetime_term: Term<'a>,
-) -> NifResult<Term<'a>> {
-    let formatter_resource: ResourceArc<DateTimeFormatterResource> = match formatter_term.decode() {
-        Ok(resource) => resource,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
-    };
+/// Collects the fields of a temporal input as they are read, from a map or
+/// an ISO 8601 string, so both are checked the same way.
+struct TemporalReader<'c> {
+    calendar: Option<&'c AnyCalendar>,
+    leap_second: LeapSecond,
+    fields: TemporalFields,
+    errors: FieldErrors,
+    year: Option<i32>,
+    month: Option<u8>,
+    day: Option<u8>,
+    hour: Option<u8>,
+    minute: Option<u8>,
+    second: Option<u8>,
+    microsecond: Option<u32>,
+    nanosecond: Option<u32>,
+}
 
-    let formatter_calendar = formatter_resource.0.calendar();
+impl<'c> TemporalReader<'c> {
+    fn new(calendar: Option<&'c AnyCalendar>, leap_second: LeapSecond) -> Self {
+        Self {
+            calendar,
+            leap_second,
+            fields: TemporalFields::default(),
+            errors: Vec::new(),
+            year: None,
+            month: None,
+            day: None,
+            hour: None,
+            minute: None,
+            second: None,
+            microsecond: None,
+            nanosecond: None,
+        }
+    }
 
-    let input = match decode_temporal(datetime_term, formatter_calendar.0) {
-        Ok(datetime) => datetime,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_datetime()).encode(env)),
-    };
+    /// Reads the integer field `key`, rejecting it when it is outside the
+    /// bounds the field can take.
+    fn read(&mut self, key: Atom, value: i64) {
+        // Other calendars are checked against their own months once the year
+        // is known; no calendar has more than thirteen months or 31 days.
+        let max_month = if self.calendar.is_some() { 13 } else { 12 };
+        let max_second = i64::from(self.leap_second.max_second());
+
+        let result = if key == atoms::year() {
+            i32::try_from(value)
+                .map(|value| self.year = Some(value))
+                .map_err(|_| FieldError::Invalid)
+        } else if key == atoms::month() {
+            bounded(value, 1..=max_month).map(|value| self.month = Some(value))
+        } else if key == atoms::day() {
+            bounded(value, 1..=31).map(|value| self.day = Some(value))
+        } else if key == atoms::hour() {
+            bounded(value, 0..=23).map(|value| self.hour = Some(value))
+        } else if key == atoms::minute() {
+            bounded(value, 0..=59).map(|value| self.minute = Some(value))
+        } else if key == atoms::second() {
+            bounded(value, 0..=max_second).map(|value| self.second = Some(value))
+        } else if key == atoms::microsecond() {
+            bounded(value, 0..=999_999).map(|value| self.microsecond = Some(value))
+        } else if key == atoms::nanosecond() {
+            bounded(value, 0..=999_999_999).map(|value| self.nanosecond = Some(value))
+        } else {
+            Ok(())
+        };
 
-    let formatted = formatter_resource.0.format_unchecked(input);
+        if let Err(error) = result {
+            self.reject(key, error);
+        }
+    }
 
-    let mut collector = PartsCollector::new();
-    if let Err(_) = formatted.try_write_to_parts(&mut collector) {
-        return Ok((atoms::error(), atoms::invalid_datetime()).encode(env));
+    fn reject(&mut self, key: Atom, error: FieldError) {
+        self.errors.push((key, error));
     }
-    let (output, collected_parts) = collector.finish();
 
-    let mut parts = Vec::new();
-    let mut last_index = 0usize;
+    /// Builds the date and the time from the fields read, reporting the
+    /// fields they are missing and the field they fail on.
+    fn finish(mut self) -> Result<TemporalFields, FieldErrors> {
+        // A field is missing when a related field is given and it is neither
+        // given nor already rejected.
+        let missing = |errors: &FieldErrors, present: [(bool, Atom); 3]| -> FieldErrors {
+            present
+                .into_iter()
+                .filter(|&(given, key)| !given && !errors.iter().any(|(field, _)| *field == key))
+                .map(|(_, key)| (key, FieldError::Missing))
+                .collect()
+        };
 
-    for collected in collected_parts {
-        if collected.start > last_index {
-            if let Some(slice) = output.get(last_index..collected.start) {
-                if !slice.is_empty() {
-                    parts.push(DateTimeFormatPart {
-                        part_type: atoms::literal(),
-                        value: slice.to_string(),
-                    });
+        let (year, month, day) = (self.year, self.month, self.day);
+        if year.is_some() || month.is_some() || day.is_some() {
+            let present = [
+                (year.is_some(), atoms::year()),
+                (month.is_some(), atoms::month()),
+                (day.is_some(), atoms::day()),
+            ];
+            let missing = missing(&self.errors, present);
+            self.errors.extend(missing);
+            if let (Some(year), Some(month), Some(day)) = (year, month, day) {
+                match new_date(year, month, day, self.calendar) {
+                    Ok(date) => self.fields.date = Some(date),
+                    Err(error) => self.errors.push(error),
                 }
             }
         }
 
-        if let Some(atom) = part_atom(collected.part) {
-            if let Some(slice) = output.get(collected.start..collected.end) {
-                parts.push(DateTimeFormatPart {
-                    part_type: atom,
-                    value: slice.to_string(),
-                });
+        let (hour, minute, second) = (self.hour, self.minute, self.second);
+        if hour.is_some()
+            || minute.is_some()
+            || second.is_some()
+            || self.microsecond.is_some()
+            || self.nanosecond.is_some()
+        {
+            let present = [
+                (hour.is_some(), atoms::hour()),
+                (minute.is_some(), atoms::minute()),
+                (second.is_some(), atoms::second()),
+            ];
+            let missing = missing(&self.errors, present);
+            self.errors.extend(missing);
+
+            // At most one source of subsecond precision is accepted.
+            let subsecond = match (self.microsecond, self.nanosecond) {
+                (Some(us), None) => us * 1_000,
+                (None, Some(ns)) => ns,
+                (None, None) => 0,
+                (Some(_), Some(_)) => {
+                    self.reject(atoms::nanosecond(), FieldError::Invalid);
+                    0
+                }
+            };
+            if let (Some(hour), Some(minute), Some(second)) = (hour, minute, second) {
+                // A leap second is formatted as the last second of the minute.
+                match Time::try_new(hour, minute, second.min(59), subsecond) {
+                    Ok(time) => self.fields.time = Some(time),
+                    Err(error) => self.errors.push(time_range_error(error)),
+                }
             }
         }
 
-        last_index = collected.end;
-    }
-
-    if last_index < output.len() {
-        if let Some(slice) = output.get(last_index..output.len()) {
-            if !slice.is_empty() {
-                parts.push(DateTimeFormatPart {
-                    part_type: atoms::literal(),
-                    value: slice.to_string(),
-                });
-            }
+        if self.errors.is_empty() {
+            Ok(self.fields)
+        } else {
+            Err(self.errors)
         }
     }
-
-    Ok((atoms::ok(), parts).encode(env))
 }
 
-fn decode_temporal<'a>(
+/// Decodes a temporal input, collecting every rejected field rather than
+/// stopping at the first. The date fields count in `calendar`, or in ISO
+/// without one, and the date is converted to ISO. Formatting and
+/// `temporal_validate` both decode through here, so they accept the same
+/// inputs.
+fn decode_temporal_in<'a>(
     term: Term<'a>,
-    _ref_calendar: &AnyCalendar,
-) -> Result<DateTimeInputUnchecked, ()> {
+    calendar: Option<&AnyCalendar>,
+    leap_second: LeapSecond,
+) -> Result<TemporalFields, FieldErrors> {
+    let mut reader = TemporalReader::new(calendar, leap_second);
+
+    if term.get_type() == TermType::Binary {
+        let input = term.decode().map_err(|_| Vec::new())?;
+        let parsed = parse_iso8601(input).map_err(|_| Vec::new())?;
+        if let Some((year, month, day)) = parsed.date {
+            reader.read(atoms::year(), i64::from(year));
+            reader.read(atoms::month(), i64::from(month));
+            reader.read(atoms::day(), i64::from(day));
+        }
+        if let Some((hour, minute, second, nanosecond)) = parsed.time {
+            reader.read(atoms::hour(), i64::from(hour));
+            reader.read(atoms::minute(), i64::from(minute));
+            reader.read(atoms::second(), i64::from(second));
+            reader.read(atoms::nanosecond(), i64::from(nanosecond));
+        }
+        reader.fields.time_zone = parsed.time_zone;
+        reader.fields.utc_offset = parsed.utc_offset;
+        return reader.finish();
+    }
     if term.get_type() != TermType::Map {
-        return Err(());
+        return Err(Vec::new());
     }
 
-    let mut unchecked = DateTimeInputUnchecked::default();
-
-    let mut iter = MapIterator::new(term).ok_or(())?;
-    let mut year: Option<i32> = None;
-    let mut month: Option<u8> = None;
-    let mut day: Option<u8> = None;
-    let mut hour: Option<u8> = None;
-    let mut minute: Option<u8> = None;
-    let mut second: Option<u8> = None;
-    let mut microsecond: Option<(u32, u32)> = None;
-
+    let mut iter = MapIterator::new(term).ok_or_else(Vec::new)?;
     while let Some((key_term, value_term)) = iter.next() {
-        let key: Atom = key_term.decode().map_err(|_| ())?;
-        if key == atoms::year() {
-            year = Some(value_term.decode().map_err(|_| ())?);
-        } else if key == atoms::month() {
-            let value: i64 = value_term.decode().map_err(|_| ())?;
-            if !(1..=12).contains(&value) {
-                return Err(());
-            }
-            month = Some(value as u8);
-        } else if key == atoms::day() {
-            let value: i64 = value_term.decode().map_err(|_| ())?;
-            if !(1..=31).contains(&value) {
-                return Err(());
-            }
-            day = Some(value as u8);
-        } else if key == atoms::hour() {
-            let value: i64 = value_term.decode().map_err(|_| ())?;
-            if !(0..=23).contains(&value) {
-                return Err(());
+        let key: Atom = key_term.decode().map_err(|_| Vec::new())?;
+        if key == atoms::microsecond() {
+            match value_term.decode::<(i64, u32)>() {
+                Ok((us, precision)) if precision <= 6 => reader.read(key, us),
+                _ => reader.reject(key, FieldError::Invalid),
             }
-            hour = Some(value as u8);
-        } else if key == atoms::minute() {
-            let value: i64 = value_term.decode().map_err(|_| ())?;
-            if !(0..=59).contains(&value) {
-                return Err(());
-            }
-            minute = Some(value as u8);
-        } else if key == atoms::second() {
-            let value: i64 = value_term.decode().map_err(|_| ())?;
-            if !(0..=59).contains(&value) {
-                return Err(());
+        } else if key == atoms::time_zone() {
+            match value_term.decode::<&str>() {
+                Ok(str) => reader.fields.time_zone = Some(IanaParser::new().parse(str)),
+                Err(_) => reader.reject(key, FieldError::Invalid),
             }
-            second = Some(value as u8);
-        } else if key == atoms::microsecond() {
-            let (ms, us): (u32, u32) = value_term.decode().map_err(|_| ())?;
-            if !(0..=999_999).contains(&ms) {
-                return Err(());
+        } else if key == atoms::utc_offset() {
+            match decode_utc_offset(value_term)
+                .and_then(|seconds| UtcOffset::try_from_seconds(seconds).map_err(|_| ()))
+            {
+                Ok(offset) => reader.fields.utc_offset = Some(offset),
+                Err(_) => reader.reject(key, FieldError::Invalid),
             }
-            if !(0..=6).contains(&us) {
-                return Err(());
+        } else if [
+            atoms::year(),
+            atoms::month(),
+            atoms::day(),
+            atoms::hour(),
+            atoms::minute(),
+            atoms::second(),
+            atoms::nanosecond(),
+        ]
+        .contains(&key)
+        {
+            match value_term.decode::<i64>() {
+                Ok(value) => reader.read(key, value),
+                Err(_) => reader.reject(key, FieldError::Invalid),
             }
-            microsecond = Some((ms, us));
-        } else if key == atoms::time_zone() {
-            let str = value_term.decode::<&str>().map_err(|_| ())?;
-            unchecked.set_time_zone_id(IanaParser::new().parse(str));
-        } else if key == atoms::utc_offset() {
-            let seconds: i32 = value_term.decode::<i32>().map_err(|_| ())?;
-            let offset = UtcOffset::try_from_seconds(seconds).map_err(|_| ())?;
-            unchecked.set_time_zone_utc_offset(offset);
-        } else if key == atoms::calendar_identifier() {
-            // calendar: Calendar.calendar()
-            // TODO
         }
+        // calendar_identifier: Calendar.calendar()
+        // TODO
     }
 
-    if year.is_some() || month.is_some() || day.is_some() {
-        let date =
-            Date::try_new_iso(year.ok_or(())?, month.ok_or(())?, day.ok_or(())?).map_err(|_| ())?;
-        unchecked.set_date_fields_unchecked(date);
-    }
+    reader.finish()
+}
 
-    if hour.is_some() || minute.is_some() || second.is_some() || microsecond.is_some() {
-        let (us, _precision) = microsecond.ok_or(())?;
-        let time = Time::try_new(
-            hour.ok_or(())?,
-            minute.ok_or(())?,
-            second.ok_or(())?,
-            us * 1_000,
-        )
-        .map_err(|_| ())?;
-        unchecked.set_time_fields(time);
+/// Builds the ISO date of a year, month and day counted in `calendar`, or in
+/// ISO without one. `month` is the number of a non-leap month.
+fn new_date(
+    year: i32,
+    month: u8,
+    day: u8,
+    calendar: Option<&AnyCalendar>,
+) -> Result<Date<Iso>, (Atom, FieldError)> {
+    let Some(calendar) = calendar else {
+        let out_of_range = |min: i64, max: i64| FieldError::OutOfRange { min, max };
+        if !calendar::ISO_YEARS.contains(&year) {
+            let (min, max) = (*calendar::ISO_YEARS.start(), *calendar::ISO_YEARS.end());
+            return Err((atoms::year(), out_of_range(i64::from(min), i64::from(max))));
+        }
+        let days = calendar::iso_days_in_month(year, month);
+        if day > days {
+            return Err((atoms::day(), out_of_range(1, i64::from(days))));
+        }
+        return calendar::iso_date(year, month, day).ok_or((atoms::year(), FieldError::Invalid));
+    };
+
+    let month_code = MonthCode::new_normal(month).ok_or((atoms::month(), FieldError::Invalid))?;
+    match Date::try_new_from_codes(None, year, month_code, day, Ref(calendar)) {
+        Ok(date) => Ok(date.to_iso()),
+        Err(DateError::UnknownMonthCode(_)) => {
+            // A month past the end of the year is out of range. One within
+            // it can still lack a number: the thirteenth month of a Hebrew
+            // leap year is the leap month M05L, not month 13.
+            let months = MonthCode::new_normal(1)
+                .and_then(|first| {
+                    Date::try_new_from_codes(None, year, first, 1, Ref(calendar)).ok()
+                })
+                .map(|first| first.months_in_year());
+            match months {
+                Some(months) if month > months => Err((
+                    atoms::month(),
+                    FieldError::OutOfRange {
+                        min: 1,
+                        max: i64::from(months),
+                    },
+                )),
+                _ => Err((atoms::month(), FieldError::Invalid)),
+            }
+        }
+        Err(error) => Err(range_error(error)),
     }
+}
+
+/// Attributes a date error of ICU4X to the field it names. Errors naming no
+/// month or day field concern the year or era.
+fn range_error(error: DateError) -> (Atom, FieldError) {
+    let DateError::Range {
+        field, min, max, ..
+    } = error
+    else {
+        return (atoms::year(), FieldError::Invalid);
+    };
+
+    let key = match field {
+        "month" => atoms::month(),
+        "day" => atoms::day(),
+        _ => atoms::year(),
+    };
+    let error = FieldError::OutOfRange {
+        min: i64::from(min),
+        max: i64::from(max),
+    };
+    (key, error)
+}
 
-    Ok(unchecked)
+/// Attributes a time error of ICU4X to the field it names. The fields are
+/// checked against their bounds as they are read, so this only catches
+/// bounds ICU4X adds.
+fn time_range_error(error: RangeError) -> (Atom, FieldError) {
+    let key = match error.field {
+        "hour" => atoms::hour(),
+        "minute" => atoms::minute(),
+        "second" => atoms::second(),
+        _ => atoms::nanosecond(),
+    };
+    let error = FieldError::OutOfRange {
+        min: i64::from(error.min),
+        max: i64::from(error.max),
+    };
+    (key, error)
 }
 
 #[derive(NifTaggedEnum)]
@@ -321,6 +1956,8 @@ enum TemporalDateFields {
     M,
     YM,
     Y,
+    YQ,
+    Q,
 }
 
 #[derive(NifTaggedEnum)]
@@ -332,7 +1969,7 @@ enum TemporalTimePrecision {
     MinuteOptional,
 }
 
-#[derive(NifTaggedEnum)]
+#[derive(NifTaggedEnum, Clone, Copy)]
 pub enum TemporalZoneStyle {
     SpecificLong,
     SpecificShort,
@@ -350,6 +1987,21 @@ pub enum TemporalAlignment {
     Column,
 }
 
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum MonthWidth {
+    Numeric,
+    TwoDigit,
+    Short,
+    Long,
+    Narrow,
+}
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum YearWidth {
+    Numeric,
+    TwoDigit,
+}
+
 #[derive(NifTaggedEnum)]
 pub enum YearStyle {
     Auto,
@@ -357,15 +2009,277 @@ pub enum YearStyle {
     WithEra,
 }
 
-fn build_field_set(term: Term) -> Result<CompositeFieldSet, ()> {
-    let mut options_iter = MapIterator::new(term).ok_or(())?;
+/// Builds the fallback formatters for the exemplar city and location zone
+/// styles: exemplar city falls back to location, and both to the long
+/// localized offset, which only needs the offset every zoned input carries.
+fn build_zone_fallback(
+    options_term: Term,
+    kind: TemporalKind,
+    prefs: &DateTimeFormatterPreferences,
+) -> Result<Option<ZoneFallback>, ()> {
+    let style: TemporalZoneStyle = match decode_option(options_term, atoms::zone_style())? {
+        Some(style) => style,
+        None => return Ok(None),
+    };
+    let fallback_styles: &[TemporalZoneStyle] = match style {
+        TemporalZoneStyle::ExemplarCity => &[
+            TemporalZoneStyle::Location,
+            TemporalZoneStyle::LocalizedOffsetLong,
+        ],
+        TemporalZoneStyle::Location => &[TemporalZoneStyle::LocalizedOffsetLong],
+        _ => return Ok(None),
+    };
+
+    let mut formatters = Vec::with_capacity(fallback_styles.len());
+    for &fallback_style in fallback_styles {
+        let options = options_term
+            .map_put(atoms::zone_style(), fallback_style)
+            .map_err(|_| ())?;
+        let (field_set, _) = build_field_set(options, kind).map_err(|_| ())?;
+        let formatter = DateTimeFormatter::try_new(prefs.clone(), field_set).map_err(|_| ())?;
+        formatters.push((fallback_style, formatter));
+    }
+
+    Ok(Some(ZoneFallback { style, formatters }))
+}
+
+/// The fields of an ISO 8601 string as written. They are checked against
+/// their bounds by `decode_temporal_in`, like the fields of a map.
+#[derive(Default)]
+struct IsoFields {
+    date: Option<(i32, u8, u8)>,
+    time: Option<(u8, u8, u8, u32)>,
+    time_zone: Option<TimeZone>,
+    utc_offset: Option<UtcOffset>,
+}
+
+/// Parses an ISO 8601 / RFC 3339 date, time or date-time string, such as
+/// `"2025-06-01"`, `"14:30:00.5"` or `"2025-06-01T14:30:00+02:00"`,
+/// followed by an optional RFC 9557 time zone annotation like
+/// `"[Europe/Berlin]"`. Other annotations, such as `"[u-ca=japanese]"`, are
+/// ignored.
+fn parse_iso8601(input: &str) -> Result<IsoFields, ()> {
+    if !input.is_ascii() {
+        return Err(());
+    }
+
+    let mut fields = IsoFields::default();
+
+    let (value, annotations) = match input.find('[') {
+        Some(index) => input.split_at(index),
+        None => (input, ""),
+    };
+    fields.time_zone = parse_zone_annotation(annotations)?;
+
+    let (date, time) = match value.find(['T', 't', ' ']) {
+        Some(index) => (
+            (index > 0).then(|| &value[..index]),
+            Some(&value[index + 1..]),
+        ),
+        None if value.contains(':') => (None, Some(value)),
+        None => (Some(value), None),
+    };
+
+    if let Some(date) = date {
+        // Four-digit years, or six digits with a sign for expanded years.
+        let (year, month_day) = date.split_at(date.len().checked_sub(6).ok_or(())?);
+        let year: i32 = match year.as_bytes().first() {
+            Some(b'+') => parse_digits(&year[1..], 6..=6)?,
+            Some(b'-') => -parse_digits::<i32>(&year[1..], 6..=6)?,
+            _ => parse_digits(year, 4..=4)?,
+        };
+        let (month, day) = match month_day.as_bytes() {
+            [b'-', _, _, b'-', _, _] => (&month_day[1..3], &month_day[4..]),
+            _ => return Err(()),
+        };
+        fields.date = Some((year, parse_digits(month, 2..=2)?, parse_digits(day, 2..=2)?));
+    }
+
+    if let Some(time) = time {
+        let offset_start = time.find(['Z', 'z', '+', '-']).unwrap_or(time.len());
+        let (time, offset) = time.split_at(offset_start);
+        fields.time = Some(parse_time(time)?);
+        fields.utc_offset = match offset {
+            "" => None,
+            "Z" | "z" => Some(UtcOffset::zero()),
+            offset => Some(UtcOffset::try_from_seconds(parse_offset(offset)?).map_err(|_| ())?),
+        };
+    }
+
+    Ok(fields)
+}
+
+/// Parses `HH:MM`, `HH:MM:SS` or `HH:MM:SS.fff` with up to nine fraction
+/// digits, separated by `.` or `,`, into the hour, minute, second and
+/// nanosecond.
+fn parse_time(time: &str) -> Result<(u8, u8, u8, u32), ()> {
+    let (time, fraction) = match time.split_once(['.', ',']) {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+
+    let mut parts = time.split(':');
+    let hour = parse_digits(parts.next().ok_or(())?, 2..=2)?;
+    let minute = parse_digits(parts.next().ok_or(())?, 2..=2)?;
+    let second = match parts.next() {
+        Some(second) => parse_digits(second, 2..=2)?,
+        None if fraction.is_none() => 0,
+        None => return Err(()),
+    };
+    if parts.next().is_some() {
+        return Err(());
+    }
+
+    let nanosecond = match fraction {
+        Some(fraction) => {
+            let digits: u32 = parse_digits(fraction, 1..=9)?;
+            digits * 10u32.pow(9 - fraction.len() as u32)
+        }
+        None => 0,
+    };
+
+    Ok((hour, minute, second, nanosecond))
+}
+
+/// Parses the `+HH:MM`, `+HHMM` or `+HH` offset of an ISO 8601 string.
+fn parse_offset(offset: &str) -> Result<i32, ()> {
+    let (sign, rest) = match offset.strip_prefix('+') {
+        Some(rest) => (1, rest),
+        None => (-1, offset.strip_prefix('-').ok_or(())?),
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some(parts) => parts,
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "00"),
+    };
+
+    let hours: i32 = parse_digits(hours, 2..=2)?;
+    let minutes: i32 = parse_digits(minutes, 2..=2)?;
+    if hours > 23 || minutes > 59 {
+        return Err(());
+    }
+    Ok(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Returns the time zone of the first annotation naming one. Annotations
+/// with a key, like `[u-ca=japanese]`, are skipped, and IANA ids unknown to
+/// ICU4X are rejected.
+fn parse_zone_annotation(annotations: &str) -> Result<Option<TimeZone>, ()> {
+    let mut rest = annotations;
+    while !rest.is_empty() {
+        let (annotation, tail) = rest
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .ok_or(())?;
+        rest = tail;
+
+        let annotation = annotation.trim_start_matches('!');
+        if annotation.contains('=') {
+            continue;
+        }
+        if annotation.starts_with(['+', '-']) {
+            // An offset annotation carries nothing the offset does not.
+            return Ok(None);
+        }
+        let zone = IanaParser::new().parse(annotation);
+        if zone == TimeZone::UNKNOWN && !annotation.eq_ignore_ascii_case("Etc/Unknown") {
+            return Err(());
+        }
+        return Ok(Some(zone));
+    }
+    Ok(None)
+}
+
+/// Parses an unsigned number written with a number of ASCII digits in `len`.
+fn parse_digits<T: std::str::FromStr>(
+    digits: &str,
+    len: std::ops::RangeInclusive<usize>,
+) -> Result<T, ()> {
+    if !len.contains(&digits.len()) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(());
+    }
+    digits.parse().map_err(|_| ())
+}
+
+/// Decodes a UTC offset given as whole seconds, as an `{hours, minutes}`
+/// tuple, or as a string such as `"+05:45"`, `"-0330"` or `"−03"`.
+///
+/// The sign of the hours applies to the minutes, so `{-3, 30}` is three and a
+/// half hours behind UTC. Negative minutes are only accepted with zero hours.
+fn decode_utc_offset(term: Term) -> Result<i32, ()> {
+    if let Ok(seconds) = term.decode::<i32>() {
+        return Ok(seconds);
+    }
+
+    if let Ok((hours, minutes)) = term.decode::<(i32, i32)>() {
+        if !(-18..=18).contains(&hours) || !(-59..=59).contains(&minutes) {
+            return Err(());
+        }
+        if hours != 0 && minutes < 0 {
+            return Err(());
+        }
+        let sign = if hours < 0 { -1 } else { 1 };
+        return Ok(hours * 3600 + sign * minutes * 60);
+    }
+
+    let string: &str = term.decode().map_err(|_| ())?;
+    let (sign, rest) = if let Some(rest) = string.strip_prefix('+') {
+        (1, rest)
+    } else if let Some(rest) = string.strip_prefix(['-', '\u{2212}']) {
+        (-1, rest)
+    } else {
+        return Err(());
+    };
+
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) if minutes.len() == 2 => (hours, minutes),
+        Some(_) => return Err(()),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "00"),
+    };
+    if hours.len() != 2
+        || !hours
+            .bytes()
+            .chain(minutes.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(());
+    }
+
+    let hours: i32 = hours.parse().map_err(|_| ())?;
+    let minutes: i32 = minutes.parse().map_err(|_| ())?;
+    if hours > 18 || minutes > 59 {
+        return Err(());
+    }
+    Ok(sign * (hours * 3600 + minutes * 60))
+}
 
+/// Builds the field set described by the options. Fails with
+/// `invalid_options` for options that do not decode and with
+/// `invalid_field_combination` for fields that cannot be shown together.
+fn build_field_set(
+    term: Term,
+    kind: TemporalKind,
+) -> Result<(CompositeFieldSet, Option<HourCycle>), Atom> {
     let mut builder = FieldSetBuilder::new();
 
-    while let Some((key_term, value_term)) = options_iter.next() {
-        let key: Atom = key_term.decode().map_err(|_| ())?;
+    // The skeleton only provides a baseline, explicit options always win
+    // regardless of map iteration order.
+    let skeleton: Option<&str> =
+        decode_option(term, atoms::skeleton()).map_err(|_| atoms::invalid_options())?;
+    let hour_cycle = match skeleton {
+        Some(skeleton) => {
+            apply_skeleton(&mut builder, skeleton).map_err(|_| atoms::invalid_options())?
+        }
+        None => None,
+    };
 
-        if key == atoms::length() {
+    // The other keys configure the calendar, hour cycle and the formatters
+    // built around the field set, and are decoded by their callers.
+    decode_map(term, UnknownKeys::Ignore, |key, value_term| {
+        if key == atoms::skeleton() {
+            // Applied above.
+        } else if key == atoms::length() {
             let len_term: TemporalLength = value_term.decode().map_err(|_| ())?;
             use options::Length;
             builder.length = Some(match len_term {
@@ -387,6 +2301,8 @@ fn build_field_set(term: Term) -> Result<CompositeFieldSet, ()> {
                 TemporalDateFields::M => DateFields::M,
                 TemporalDateFields::YM => DateFields::YM,
                 TemporalDateFields::Y => DateFields::Y,
+                // Handled by `new_quarter_formatter` before the builder runs.
+                TemporalDateFields::YQ | TemporalDateFields::Q => return Err(()),
             });
         } else if key == atoms::time_precision() {
             let precision: TemporalTimePrecision = value_term.decode().map_err(|_| ())?;
@@ -451,44 +2367,282 @@ fn build_field_set(term: Term) -> Result<CompositeFieldSet, ()> {
                 YearStyle::Full => options::YearStyle::Full,
                 YearStyle::WithEra => options::YearStyle::WithEra,
             });
+        } else {
+            return Ok(false);
         }
+        Ok(true)
+    })
+    .map_err(|_| atoms::invalid_options())?;
+
+    let has_date = builder.date_fields.is_some();
+    let has_time = builder.time_precision.is_some();
+    let has_zone = builder.zone_style.is_some();
+    let valid = match kind {
+        TemporalKind::Any => true,
+        TemporalKind::Date => has_date && !has_time && !has_zone,
+        TemporalKind::Time => !has_date && has_time && !has_zone,
+        TemporalKind::ZonedDateTime => has_date && has_time && has_zone,
+    };
+    if !valid {
+        return Err(atoms::invalid_options());
+    }
+
+    Ok((build_composite(builder)?, hour_cycle))
+}
+
+/// Builds the composite field set, routing a date with a time through the
+/// date-and-time field sets, which also cover the weekday-only `ET` ("Tue
+/// 14:30").
+///
+/// The calendar period fields (month, year and month, year) have no pattern
+/// that includes a time or a zone, so those combinations are rejected.
+fn build_composite(builder: FieldSetBuilder) -> Result<CompositeFieldSet, Atom> {
+    use icu::datetime::fieldsets::builder::DateFields;
+
+    let is_period = matches!(
+        builder.date_fields,
+        Some(DateFields::M | DateFields::YM | DateFields::Y)
+    );
+    let has_time = builder.time_precision.is_some();
+    let has_zone = builder.zone_style.is_some();
+    if is_period && (has_time || has_zone) {
+        return Err(atoms::invalid_field_combination());
     }
 
-    builder.build_composite().map_err(|_| ())
-}
-
-fn part_atom(part: WriteablePart) -> Option<Atom> {
-    if part == datetime_parts::ERA {
-        Some(atoms::era())
-    } else if part == datetime_parts::YEAR {
-        Some(atoms::year())
-    } else if part == datetime_parts::RELATED_YEAR {
-        Some(atoms::related_year())
-    } else if part == datetime_parts::YEAR_NAME {
-        Some(atoms::year_name())
-    } else if part == datetime_parts::MONTH {
-        Some(atoms::month())
-    } else if part == datetime_parts::DAY {
-        Some(atoms::day())
-    } else if part == datetime_parts::WEEKDAY {
-        Some(atoms::weekday())
-    } else if part == datetime_parts::DAY_PERIOD {
-        Some(atoms::day_period())
-    } else if part == datetime_parts::HOUR {
-        Some(atoms::hour())
-    } else if part == datetime_parts::MINUTE {
-        Some(atoms::minute())
-    } else if part == datetime_parts::SECOND {
-        Some(atoms::second())
-    } else if part == datetime_parts::TIME_ZONE_NAME {
-        Some(atoms::time_zone_name())
-    } else if part == decimal_parts::INTEGER {
-        Some(atoms::integer())
-    } else if part == decimal_parts::DECIMAL {
-        Some(atoms::decimal())
-    } else if part == decimal_parts::FRACTION {
-        Some(atoms::fraction())
+    let field_set = if builder.date_fields.is_some() && has_time && !has_zone {
+        builder
+            .build_date_and_time()
+            .map(CompositeFieldSet::DateTime)
     } else {
-        None
+        builder.build_composite()
+    };
+    field_set.map_err(|_| atoms::invalid_field_combination())
+}
+
+/// Translates a CLDR/ICU4C skeleton such as `"yMMMEd Hm"` into field set
+/// builder settings. Returns the hour cycle implied by the hour symbol, if any.
+///
+/// Field widths only influence the length: `MMMM`/`EEEE` select long, `MMM`
+/// medium and numeric months short.
+fn apply_skeleton(builder: &mut FieldSetBuilder, skeleton: &str) -> Result<Option<HourCycle>, ()> {
+    use icu::datetime::fieldsets::builder::{DateFields, ZoneStyle};
+    use options::{Length, SubsecondDigits, TimePrecision};
+
+    let mut year = false;
+    let mut era = false;
+    let mut month = 0usize;
+    let mut day = false;
+    let mut weekday = 0usize;
+    let mut hour: Option<char> = None;
+    let mut minute = false;
+    let mut second = false;
+    let mut fraction = 0usize;
+    let mut zone: Option<(char, usize)> = None;
+
+    let mut chars = skeleton.chars().peekable();
+    while let Some(symbol) = chars.next() {
+        let mut count = 1;
+        while chars.peek() == Some(&symbol) {
+            chars.next();
+            count += 1;
+        }
+
+        match symbol {
+            ' ' | ',' => {}
+            'G' => era = true,
+            'y' | 'Y' | 'u' | 'r' => year = true,
+            'M' | 'L' => month = count,
+            'd' => day = true,
+            'E' | 'c' | 'e' => weekday = count,
+            'h' | 'H' | 'K' | 'k' | 'j' | 'J' | 'C' => hour = Some(symbol),
+            'm' => minute = true,
+            's' => second = true,
+            'S' => fraction = count,
+            // Day periods follow from the hour cycle.
+            'a' | 'b' | 'B' => {}
+            'z' | 'v' | 'O' | 'V' | 'Z' | 'X' | 'x' => zone = Some((symbol, count)),
+            _ => return Err(()),
+        }
+    }
+
+    builder.date_fields = match (year, month > 0, day, weekday > 0) {
+        (false, false, false, false) => None,
+        (false, false, true, false) => Some(DateFields::D),
+        (false, true, true, false) => Some(DateFields::MD),
+        (true, true, true, false) => Some(DateFields::YMD),
+        (false, false, true, true) => Some(DateFields::DE),
+        (false, true, true, true) => Some(DateFields::MDE),
+        (true, true, true, true) => Some(DateFields::YMDE),
+        (false, false, false, true) => Some(DateFields::E),
+        (false, true, false, false) => Some(DateFields::M),
+        (true, true, false, false) => Some(DateFields::YM),
+        (true, false, false, false) => Some(DateFields::Y),
+        _ => return Err(()),
+    };
+
+    if builder.date_fields.is_some() {
+        builder.length = Some(match (month, weekday) {
+            (4.., _) => Length::Long,
+            (3, _) => Length::Medium,
+            (1..=2, _) => Length::Short,
+            (_, 4..) => Length::Long,
+            _ => Length::Medium,
+        });
+    }
+
+    if era {
+        builder.year_style = Some(options::YearStyle::WithEra);
+    }
+
+    builder.time_precision = match (hour.is_some(), minute, second, fraction) {
+        (false, false, false, 0) => None,
+        (true, false, false, 0) => Some(TimePrecision::Hour),
+        (true, true, false, 0) => Some(TimePrecision::Minute),
+        (true, true, true, 0) => Some(TimePrecision::Second),
+        (true, true, true, digits) => Some(TimePrecision::Subsecond(match digits {
+            1 => SubsecondDigits::S1,
+            2 => SubsecondDigits::S2,
+            3 => SubsecondDigits::S3,
+            4 => SubsecondDigits::S4,
+            5 => SubsecondDigits::S5,
+            6 => SubsecondDigits::S6,
+            7 => SubsecondDigits::S7,
+            8 => SubsecondDigits::S8,
+            9 => SubsecondDigits::S9,
+            _ => return Err(()),
+        })),
+        _ => return Err(()),
+    };
+
+    builder.zone_style = match zone {
+        None => None,
+        Some(('z', 4..)) => Some(ZoneStyle::SpecificLong),
+        Some(('z', _)) => Some(ZoneStyle::SpecificShort),
+        Some(('v', 4..)) => Some(ZoneStyle::GenericLong),
+        Some(('v', _)) => Some(ZoneStyle::GenericShort),
+        Some(('O', 4..)) => Some(ZoneStyle::LocalizedOffsetLong),
+        Some(('V', 3)) => Some(ZoneStyle::ExemplarCity),
+        Some(('V', 4..)) => Some(ZoneStyle::Location),
+        Some(('Z', 4)) => Some(ZoneStyle::LocalizedOffsetLong),
+        Some(_) => Some(ZoneStyle::LocalizedOffsetShort),
+    };
+
+    Ok(match hour {
+        Some('h') => Some(HourCycle::H12),
+        Some('K') => Some(HourCycle::H11),
+        Some('H') | Some('k') => Some(HourCycle::H23),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_fields_tells_date_and_time_apart() {
+        assert_eq!(pattern_fields("HH:mm:ss"), (false, true));
+        assert_eq!(pattern_fields("EEE, dd.MM.yyyy"), (true, false));
+        assert_eq!(pattern_fields("d MMMM y 'at' h a"), (true, true));
+    }
+
+    #[test]
+    fn pattern_fields_skips_quoted_text() {
+        assert_eq!(pattern_fields("HH 'Uhr, day' mm"), (false, true));
+        assert_eq!(pattern_fields("'o''clock' d"), (true, false));
+    }
+
+    #[test]
+    fn parse_iso8601_reads_dates_times_and_offsets() {
+        let fields = parse_iso8601("2025-06-01").unwrap();
+        assert_eq!(fields.date, Some((2025, 6, 1)));
+        assert_eq!(fields.time, None);
+
+        let fields = parse_iso8601("14:30:00.5").unwrap();
+        assert_eq!(fields.date, None);
+        assert_eq!(fields.time, Some((14, 30, 0, 500_000_000)));
+
+        let fields = parse_iso8601("2025-06-01T14:30:00+02:00").unwrap();
+        assert_eq!(fields.date, Some((2025, 6, 1)));
+        assert_eq!(fields.time, Some((14, 30, 0, 0)));
+        assert_eq!(fields.utc_offset, UtcOffset::try_from_seconds(7200).ok());
+
+        let fields = parse_iso8601("2025-06-01 14:30Z").unwrap();
+        assert_eq!(fields.utc_offset, Some(UtcOffset::zero()));
+    }
+
+    #[test]
+    fn parse_iso8601_reads_expanded_years() {
+        let fields = parse_iso8601("+012345-01-02").unwrap();
+        assert_eq!(fields.date, Some((12_345, 1, 2)));
+
+        let fields = parse_iso8601("-000001-12-31").unwrap();
+        assert_eq!(fields.date, Some((-1, 12, 31)));
+    }
+
+    #[test]
+    fn parse_iso8601_leaves_bounds_to_the_reader() {
+        let fields = parse_iso8601("2025-02-30T25:61:60").unwrap();
+        assert_eq!(fields.date, Some((2025, 2, 30)));
+        assert_eq!(fields.time, Some((25, 61, 60, 0)));
+    }
+
+    #[test]
+    fn parse_iso8601_rejects_malformed_input() {
+        for input in [
+            "",
+            "2025-6-01",
+            "25-06-01",
+            "2025/06/01",
+            "2025-06-01T14:30+2",
+            "2025-06-01T14:30[Europe/Berlin",
+            "２０２５-06-01",
+        ] {
+            assert!(parse_iso8601(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_time_reads_fractions() {
+        assert_eq!(parse_time("09:05"), Ok((9, 5, 0, 0)));
+        assert_eq!(parse_time("09:05:07,123"), Ok((9, 5, 7, 123_000_000)));
+        assert_eq!(parse_time("09:05:07.123456789"), Ok((9, 5, 7, 123_456_789)));
+        assert_eq!(parse_time("09:05.5"), Err(()));
+        assert_eq!(parse_time("09:05:07.1234567890"), Err(()));
+        assert_eq!(parse_time("9:05"), Err(()));
+    }
+
+    #[test]
+    fn parse_offset_reads_every_form() {
+        assert_eq!(parse_offset("+05:45"), Ok(20_700));
+        assert_eq!(parse_offset("-0330"), Ok(-12_600));
+        assert_eq!(parse_offset("+01"), Ok(3600));
+        assert_eq!(parse_offset("+24:00"), Err(()));
+        assert_eq!(parse_offset("+05:60"), Err(()));
+        assert_eq!(parse_offset("05:00"), Err(()));
+    }
+
+    #[test]
+    fn parse_zone_annotation_finds_the_zone() {
+        let berlin = IanaParser::new().parse("Europe/Berlin");
+        assert_ne!(berlin, TimeZone::UNKNOWN);
+
+        assert_eq!(parse_zone_annotation(""), Ok(None));
+        assert_eq!(parse_zone_annotation("[Europe/Berlin]"), Ok(Some(berlin)));
+        assert_eq!(
+            parse_zone_annotation("[u-ca=japanese][!Europe/Berlin]"),
+            Ok(Some(berlin))
+        );
+        assert_eq!(parse_zone_annotation("[+02:00]"), Ok(None));
+        assert_eq!(
+            parse_zone_annotation("[Etc/Unknown]"),
+            Ok(Some(TimeZone::UNKNOWN))
+        );
+    }
+
+    #[test]
+    fn parse_zone_annotation_rejects_unknown_ids() {
+        assert_eq!(parse_zone_annotation("[Mars/Olympus_Mons]"), Err(()));
+        assert_eq!(parse_zone_annotation("[Europe/Berlin"), Err(()));
     }
 }
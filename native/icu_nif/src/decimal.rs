@@ -0,0 +1,117 @@
+//! Decimals held as a resource (`Icu.Decimal`), so a number converted once
+//! can be rounded and handed to every formatter without converting it again.
+//!
+//! The number, currency and unit formatters take the resource, or the
+//! `%Icu.Decimal{}` struct wrapping it, wherever they take a number.
+
+use fixed_decimal::Decimal as FixedDecimal;
+use fixed_decimal::{SignedRoundingMode, UnsignedRoundingMode};
+use rustler::{Encoder, Env, NifResult, NifTaggedEnum, ResourceArc, Term, TermType};
+
+use crate::atoms;
+use crate::number;
+use crate::stats::{self, Counted};
+
+pub(crate) struct DecimalResource {
+    decimal: FixedDecimal,
+}
+
+impl rustler::Resource for DecimalResource {}
+
+pub(crate) fn load(env: Env) -> bool {
+    env.register::<DecimalResource>().is_ok()
+}
+
+/// Rounding modes, named as in the `Decimal` library.
+#[derive(NifTaggedEnum, Clone, Copy)]
+pub(crate) enum RoundingMode {
+    Down,
+    Up,
+    Ceiling,
+    Floor,
+    HalfUp,
+    HalfDown,
+    HalfEven,
+}
+
+impl From<RoundingMode> for SignedRoundingMode {
+    fn from(mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::Down => Self::Unsigned(UnsignedRoundingMode::Trunc),
+            RoundingMode::Up => Self::Unsigned(UnsignedRoundingMode::Expand),
+            RoundingMode::Ceiling => Self::Ceil,
+            RoundingMode::Floor => Self::Floor,
+            RoundingMode::HalfUp => Self::Unsigned(UnsignedRoundingMode::HalfExpand),
+            RoundingMode::HalfDown => Self::Unsigned(UnsignedRoundingMode::HalfTrunc),
+            RoundingMode::HalfEven => Self::Unsigned(UnsignedRoundingMode::HalfEven),
+        }
+    }
+}
+
+/// The decimal held by a resource given directly or wrapped in an
+/// `%Icu.Decimal{}` struct.
+pub(crate) fn decode(term: Term) -> Option<FixedDecimal> {
+    let term = if term.get_type() == TermType::Map {
+        term.map_get(atoms::resource()).ok()?
+    } else {
+        term
+    };
+    let resource: ResourceArc<DecimalResource> = term.decode().ok()?;
+    Some(resource.decimal.clone())
+}
+
+fn encode_resource(env: Env, decimal: FixedDecimal) -> Term {
+    (atoms::ok(), ResourceArc::new(DecimalResource { decimal })).encode(env)
+}
+
+#[rustler::nif]
+pub(crate) fn decimal_new<'a>(env: Env<'a>, number_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::DecimalNew, || decimal_new_inner(env, number_term))
+}
+
+fn decimal_new_inner<'a>(env: Env<'a>, number_term: Term<'a>) -> NifResult<Term<'a>> {
+    match number::term_to_decimal(number_term) {
+        Ok(decimal) => Ok(encode_resource(env, decimal)),
+        Err(()) => Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    }
+}
+
+/// Rounds to the power of ten `position`: -2 keeps two fraction digits,
+/// 3 rounds to thousands.
+#[rustler::nif]
+pub(crate) fn decimal_round<'a>(
+    env: Env<'a>,
+    resource: ResourceArc<DecimalResource>,
+    position: i16,
+    mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::DecimalRound, || {
+        decimal_round_inner(env, resource, position, mode_term)
+    })
+}
+
+fn decimal_round_inner<'a>(
+    env: Env<'a>,
+    resource: ResourceArc<DecimalResource>,
+    position: i16,
+    mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let mode: RoundingMode = match mode_term.decode() {
+        Ok(mode) => mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+    let mut decimal = resource.decimal.clone();
+    decimal.round_with_mode(position, mode.into());
+    Ok(encode_resource(env, decimal))
+}
+
+#[rustler::nif]
+pub(crate) fn decimal_to_string(resource: ResourceArc<DecimalResource>) -> String {
+    stats::measure(Counted::DecimalToString, || {
+        decimal_to_string_inner(resource)
+    })
+}
+
+fn decimal_to_string_inner(resource: ResourceArc<DecimalResource>) -> String {
+    resource.decimal.to_string()
+}
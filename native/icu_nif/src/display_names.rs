@@ -1,30 +1,74 @@
 use std::str::FromStr;
+use std::sync::OnceLock;
 
+use icu::experimental::dimension::units::options::Width;
+use icu::experimental::displaynames::provider::RegionDisplayNamesV1;
 use icu::experimental::displaynames::{
     DisplayNamesOptions, Fallback, LanguageDisplay, LanguageDisplayNames,
     LocaleDisplayNamesFormatter, RegionDisplayNames, ScriptDisplayNames, Style,
     VariantDisplayNames,
 };
+use icu::experimental::provider::Baked as ExperimentalBaked;
 use icu::locale::subtags::{Language, Region, Script, Variant};
 use icu::locale::Locale;
-use rustler::types::map::MapIterator;
+use icu_provider::prelude::*;
 use rustler::{Atom, Encoder, Env, NifResult, ResourceArc, Term, TermType};
 
 use crate::atoms;
-use crate::locale::LocaleResource;
+use crate::locale::{self, resolve_data_locale, LocaleArg, LocaleResource};
+use crate::options::{decode_map, is_nil, UnknownKeys};
+use crate::stats::{self, Counted};
+use crate::units::UnitNames;
 
 pub(crate) struct DisplayNamesFormatterResource {
     formatter: DisplayNameFormatter,
+    locale: Locale,
 }
 
 impl rustler::Resource for DisplayNamesFormatterResource {}
 
+impl DisplayNamesFormatterResource {
+    /// The locale of the display name data the formatter loaded, probed
+    /// through the region names which every locale with names data has.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        resolve_data_locale::<RegionDisplayNamesV1, _>(
+            &ExperimentalBaked,
+            &self.locale,
+            DataMarkerAttributes::empty(),
+        )
+    }
+
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        let names = match &self.formatter {
+            DisplayNameFormatter::Unit { names, .. } => names.memory(),
+            _ => 0,
+        };
+        std::mem::size_of::<Self>() + locale::heap_size(&self.locale) + names
+    }
+}
+
 enum DisplayNameFormatter {
-    Locale(LocaleDisplayNamesFormatter),
+    /// `extensions` appends the names of the locale's `-u-` keywords. A
+    /// call asking for the other language display gets `alternate`, built
+    /// from the same options when first needed.
+    Locale {
+        formatter: LocaleDisplayNamesFormatter,
+        options: DisplayNamesOptions,
+        alternate: OnceLock<Option<LocaleDisplayNamesFormatter>>,
+        extensions: bool,
+    },
     Language(LanguageDisplayNames),
     Region(RegionDisplayNames),
     Script(ScriptDisplayNames),
     Variant(VariantDisplayNames),
+    Unit {
+        names: UnitNames,
+        fallback: Fallback,
+    },
+    NumberingSystem {
+        fallback: Fallback,
+    },
 }
 
 enum FormatterKind {
@@ -33,6 +77,8 @@ enum FormatterKind {
     Region,
     Script,
     Variant,
+    Unit,
+    NumberingSystem,
 }
 
 pub(crate) fn load(env: Env) -> bool {
@@ -46,8 +92,19 @@ pub(crate) fn display_names_formatter_new<'a>(
     kind_term: Term<'a>,
     options_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
-    let locale_resource: ResourceArc<LocaleResource> = match locale_term.decode() {
-        Ok(resource) => resource,
+    stats::measure(Counted::DisplayNamesFormatterNew, || {
+        display_names_formatter_new_inner(env, locale_term, kind_term, options_term)
+    })
+}
+
+fn display_names_formatter_new_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    kind_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
         Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
     };
 
@@ -56,32 +113,51 @@ pub(crate) fn display_names_formatter_new<'a>(
         Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
     };
 
-    let options = match decode_options(options_term) {
+    let (options, extensions) = match decode_options(options_term) {
         Ok(options) => options,
         Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
     };
 
     let formatter = match kind {
         FormatterKind::Locale => {
-            LocaleDisplayNamesFormatter::try_new(locale_resource.0.clone().into(), options)
-                .map(DisplayNameFormatter::Locale)
+            LocaleDisplayNamesFormatter::try_new(locale_arg.0.clone().into(), options).map(
+                |formatter| DisplayNameFormatter::Locale {
+                    formatter,
+                    options,
+                    alternate: OnceLock::new(),
+                    extensions,
+                },
+            )
         }
         FormatterKind::Language => {
-            LanguageDisplayNames::try_new(locale_resource.0.clone().into(), options)
+            LanguageDisplayNames::try_new(locale_arg.0.clone().into(), options)
                 .map(DisplayNameFormatter::Language)
         }
-        FormatterKind::Region => {
-            RegionDisplayNames::try_new(locale_resource.0.clone().into(), options)
-                .map(DisplayNameFormatter::Region)
-        }
-        FormatterKind::Script => {
-            ScriptDisplayNames::try_new(locale_resource.0.clone().into(), options)
-                .map(DisplayNameFormatter::Script)
-        }
+        FormatterKind::Region => RegionDisplayNames::try_new(locale_arg.0.clone().into(), options)
+            .map(DisplayNameFormatter::Region),
+        FormatterKind::Script => ScriptDisplayNames::try_new(locale_arg.0.clone().into(), options)
+            .map(DisplayNameFormatter::Script),
         FormatterKind::Variant => {
-            VariantDisplayNames::try_new(locale_resource.0.clone().into(), options)
+            VariantDisplayNames::try_new(locale_arg.0.clone().into(), options)
                 .map(DisplayNameFormatter::Variant)
         }
+        FormatterKind::Unit => {
+            let width = match options.style {
+                Some(Style::Short) | Some(Style::Menu) => Width::Short,
+                Some(Style::Narrow) => Width::Narrow,
+                _ => Width::Long,
+            };
+            match UnitNames::try_new(&locale_arg.0, width) {
+                Ok(names) => Ok(DisplayNameFormatter::Unit {
+                    names,
+                    fallback: options.fallback,
+                }),
+                Err(reason) => return Ok((atoms::error(), reason).encode(env)),
+            }
+        }
+        FormatterKind::NumberingSystem => Ok(DisplayNameFormatter::NumberingSystem {
+            fallback: options.fallback,
+        }),
     };
 
     let formatter = match formatter {
@@ -89,15 +165,33 @@ pub(crate) fn display_names_formatter_new<'a>(
         Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
     };
 
-    let resource = DisplayNamesFormatterResource { formatter };
+    let resource = DisplayNamesFormatterResource {
+        formatter,
+        locale: locale_arg.0,
+    };
     Ok((atoms::ok(), ResourceArc::new(resource)).encode(env))
 }
 
+/// Looks up the display name of a value. `options_term` may override the
+/// `language_display` of a locale formatter for this call; other kinds name
+/// a single subtag, which has no dialect name, and ignore it.
 #[rustler::nif]
 pub(crate) fn display_names_of<'a>(
     env: Env<'a>,
     formatter_term: Term<'a>,
     value_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::DisplayNamesOf, || {
+        display_names_of_inner(env, formatter_term, value_term, options_term)
+    })
+}
+
+fn display_names_of_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    value_term: Term<'a>,
+    options_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
     let formatter_resource: ResourceArc<DisplayNamesFormatterResource> =
         match formatter_term.decode() {
@@ -105,14 +199,47 @@ pub(crate) fn display_names_of<'a>(
             Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
         };
 
+    let language_display = match decode_call_options(options_term) {
+        Ok(language_display) => language_display,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
     match &formatter_resource.formatter {
-        DisplayNameFormatter::Locale(formatter) => {
+        DisplayNameFormatter::Locale {
+            formatter,
+            options,
+            alternate,
+            extensions,
+        } => {
             let locale = match decode_locale(value_term) {
                 Ok(locale) => locale,
                 Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
             };
 
-            let display_name = formatter.of(&locale).into_owned();
+            let formatter = match language_display {
+                Some(display) if display != options.language_display => {
+                    let alternate = alternate.get_or_init(|| {
+                        let mut options = *options;
+                        options.language_display = display;
+                        let prefs = formatter_resource.locale.clone().into();
+                        LocaleDisplayNamesFormatter::try_new(prefs, options).ok()
+                    });
+                    match alternate {
+                        Some(alternate) => alternate,
+                        None => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+                    }
+                }
+                _ => formatter,
+            };
+
+            let mut display_name = formatter.of(&locale).into_owned();
+            if *extensions {
+                let language = formatter_resource.locale.id.language;
+                display_name = match with_keywords(language.as_str(), display_name, &locale) {
+                    Ok(display_name) => display_name,
+                    Err(_) => return Ok((atoms::error(), atoms::unsupported_locale()).encode(env)),
+                };
+            }
             Ok((atoms::ok(), display_name).encode(env))
         }
         DisplayNameFormatter::Language(formatter) => {
@@ -151,9 +278,371 @@ pub(crate) fn display_names_of<'a>(
             let display_name = formatter.of(variant).map(|value| value.to_string());
             Ok((atoms::ok(), display_name).encode(env))
         }
+        DisplayNameFormatter::Unit { names, fallback } => {
+            let unit = match term_to_string(value_term) {
+                Ok(unit) => unit,
+                Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+            };
+
+            let display_name = match names.name(&unit) {
+                Ok(name) => Some(name),
+                Err(_) if *fallback == Fallback::Code => Some(unit),
+                Err(_) => None,
+            };
+            Ok((atoms::ok(), display_name).encode(env))
+        }
+        DisplayNameFormatter::NumberingSystem { fallback } => {
+            let id = match term_to_string(value_term) {
+                Ok(id) => id,
+                Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+            };
+
+            let language = formatter_resource.locale.id.language;
+            let display_name = match numbering_system_name(language.as_str(), &id) {
+                Some(name) => Some(name.to_string()),
+                None if *fallback == Fallback::Code => Some(id),
+                None => None,
+            };
+            Ok((atoms::ok(), display_name).encode(env))
+        }
+    }
+}
+
+/// Looks up a language, region or script name in the style of the options,
+/// together with the style the name was found in.
+///
+/// ICU4X falls back to the name without a style when its data has none in
+/// the requested style, as for most languages in the `menu` style. The name
+/// is looked up without a style as well to tell the two apart, and a name
+/// without a style is reported as `long`, the default.
+#[rustler::nif]
+pub(crate) fn display_names_of_styled<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    kind_term: Term<'a>,
+    value_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::DisplayNamesOfStyled, || {
+        display_names_of_styled_inner(env, locale_term, kind_term, value_term, options_term)
+    })
+}
+
+fn display_names_of_styled_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    kind_term: Term<'a>,
+    value_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let (options, _extensions) = match decode_options(options_term) {
+        Ok(options) => options,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let locale = &locale_arg.0;
+    let name = match decode_kind(kind_term) {
+        Ok(FormatterKind::Language) => decode_language(value_term).map(|language| {
+            styled_name(options, |options| {
+                LanguageDisplayNames::try_new(locale.clone().into(), options)
+                    .map(|formatter| formatter.of(language).map(str::to_string))
+            })
+        }),
+        Ok(FormatterKind::Region) => decode_region(value_term).map(|region| {
+            styled_name(options, |options| {
+                RegionDisplayNames::try_new(locale.clone().into(), options)
+                    .map(|formatter| formatter.of(region).map(str::to_string))
+            })
+        }),
+        Ok(FormatterKind::Script) => decode_script(value_term).map(|script| {
+            styled_name(options, |options| {
+                ScriptDisplayNames::try_new(locale.clone().into(), options)
+                    .map(|formatter| formatter.of(script).map(str::to_string))
+            })
+        }),
+        _ => Err(()),
+    };
+
+    match name {
+        Ok(Ok(Some((name, style)))) => Ok((atoms::ok(), (name, style)).encode(env)),
+        Ok(Ok(None)) => Ok((atoms::ok(), atoms::nil()).encode(env)),
+        Ok(Err(_)) => Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    }
+}
+
+/// Runs `lookup` with `options`, and without a style when the name may have
+/// come from the fallback, returning the name and the atom of its style.
+fn styled_name<E>(
+    options: DisplayNamesOptions,
+    lookup: impl Fn(DisplayNamesOptions) -> Result<Option<String>, E>,
+) -> Result<Option<(String, Atom)>, E> {
+    let Some(name) = lookup(options)? else {
+        return Ok(None);
+    };
+
+    let style = match options.style {
+        Some(Style::Narrow) => atoms::narrow(),
+        Some(Style::Short) => atoms::short(),
+        Some(Style::Menu) => atoms::menu(),
+        _ => atoms::long(),
+    };
+    if style == atoms::long() {
+        return Ok(Some((name, style)));
+    }
+
+    let mut plain = options;
+    plain.style = None;
+    if lookup(plain)?.as_deref() == Some(name.as_str()) {
+        Ok(Some((name, atoms::long())))
+    } else {
+        Ok(Some((name, style)))
+    }
+}
+
+/// Appends the names of the `-u-` keywords of `locale` to its display name,
+/// inside the parentheses that hold the region and variants.
+///
+/// ICU4X leaves the keywords out of locale display names and has no names
+/// for them, so the CLDR English names are bundled here. Keywords in any
+/// other language are an error rather than being dropped from the name.
+fn with_keywords(language: &str, name: String, locale: &Locale) -> Result<String, ()> {
+    if locale.extensions.unicode.keywords.is_empty() {
+        return Ok(name);
+    }
+    if language != "en" {
+        return Err(());
+    }
+
+    let keywords: Vec<String> = locale
+        .extensions
+        .unicode
+        .keywords
+        .iter()
+        .map(|(key, value)| keyword_name(key.as_str(), &value.to_string()))
+        .collect();
+
+    let keywords = keywords.join(", ");
+    Ok(match name.strip_suffix(')') {
+        Some(head) => format!("{head}, {keywords})"),
+        None => format!("{name} ({keywords})"),
+    })
+}
+
+/// The English name of a keyword, following the CLDR key-type pattern for
+/// values without a name of their own.
+fn keyword_name(key: &str, value: &str) -> String {
+    let name = match (key, value) {
+        ("ca", "buddhist") => "Buddhist Calendar",
+        ("ca", "chinese") => "Chinese Calendar",
+        ("ca", "coptic") => "Coptic Calendar",
+        ("ca", "dangi") => "Dangi Calendar",
+        ("ca", "ethioaa") => "Ethiopic Amete Alem Calendar",
+        ("ca", "ethiopic") => "Ethiopic Calendar",
+        ("ca", "gregory") => "Gregorian Calendar",
+        ("ca", "hebrew") => "Hebrew Calendar",
+        ("ca", "indian") => "Indian National Calendar",
+        ("ca", "islamic") => "Hijri Calendar",
+        ("ca", "islamic-civil") => "Hijri Calendar (tabular, civil epoch)",
+        ("ca", "islamic-tbla") => "Hijri Calendar (tabular, astronomical epoch)",
+        ("ca", "islamic-umalqura") => "Hijri Calendar (Umm al-Qura)",
+        ("ca", "iso8601") => "ISO-8601 Calendar",
+        ("ca", "japanese") => "Japanese Calendar",
+        ("ca", "persian") => "Persian Calendar",
+        ("ca", "roc") => "Minguo Calendar",
+        ("cf", "account") => "Accounting Currency Format",
+        ("cf", "standard") => "Standard Currency Format",
+        ("co", "dictionary") => "Dictionary Sort Order",
+        ("co", "ducet") => "Default Unicode Sort Order",
+        ("co", "emoji") => "Emoji Sort Order",
+        ("co", "eor") => "European Ordering Rules",
+        ("co", "phonebook") => "Phonebook Sort Order",
+        ("co", "phonetic") => "Phonetic Sort Order",
+        ("co", "pinyin") => "Pinyin Sort Order",
+        ("co", "search") => "General-Purpose Search",
+        ("co", "searchjl") => "Search By Hangul Initial Consonant",
+        ("co", "standard") => "Standard Sort Order",
+        ("co", "stroke") => "Stroke Sort Order",
+        ("co", "traditional") => "Traditional Sort Order",
+        ("co", "unihan") => "Radical-Stroke Sort Order",
+        ("co", "zhuyin") => "Zhuyin Sort Order",
+        ("hc", "h11") => "12 Hour System (0–11)",
+        ("hc", "h12") => "12 Hour System (1–12)",
+        ("hc", "h23") => "24 Hour System (0–23)",
+        ("hc", "h24") => "24 Hour System (1–24)",
+        ("lb", "loose") => "Loose Line Break Style",
+        ("lb", "normal") => "Normal Line Break Style",
+        ("lb", "strict") => "Strict Line Break Style",
+        ("ms", "metric") => "Metric System",
+        ("ms", "uksystem") => "Imperial Measurement System",
+        ("ms", "ussystem") => "US Measurement System",
+        ("nu", id) => match numbering_system_name("en", id) {
+            Some(name) => name,
+            None => return format!("Numbers: {id}"),
+        },
+        ("cu", code) => return format!("Currency: {}", code.to_ascii_uppercase()),
+        _ => {
+            return match key {
+                "ca" => format!("Calendar: {value}"),
+                "cf" => format!("Currency Format: {value}"),
+                "co" => format!("Sort Order: {value}"),
+                "hc" => format!("Hour Cycle (12 vs 24): {value}"),
+                "lb" => format!("Line Break Style: {value}"),
+                "ms" => format!("Measurement System: {value}"),
+                _ => format!("{key}={value}"),
+            };
+        }
+    };
+
+    name.to_string()
+}
+
+/// Numbering system names keyed by language and numbering system.
+///
+/// ICU4X carries no numbering system names, so the CLDR English names are
+/// bundled here.
+fn numbering_system_name(language: &str, id: &str) -> Option<&'static str> {
+    if language != "en" {
+        return None;
     }
+
+    let name = match id {
+        "adlm" => "Adlam Digits",
+        "ahom" => "Ahom Digits",
+        "arab" => "Arabic-Indic Digits",
+        "arabext" => "Extended Arabic-Indic Digits",
+        "bali" => "Balinese Digits",
+        "beng" => "Bangla Digits",
+        "bhks" => "Bhaiksuki Digits",
+        "brah" => "Brahmi Digits",
+        "cakm" => "Chakma Digits",
+        "cham" => "Cham Digits",
+        "deva" => "Devanagari Digits",
+        "diak" => "Dives Akuru Digits",
+        "fullwide" => "Full-Width Digits",
+        "gara" => "Garay Digits",
+        "gong" => "Gunjala Gondi Digits",
+        "gonm" => "Masaram Gondi Digits",
+        "gujr" => "Gujarati Digits",
+        "gukh" => "Gurung Khema Digits",
+        "guru" => "Gurmukhi Digits",
+        "hanidec" => "Chinese Decimal Numerals",
+        "hmng" => "Pahawh Hmong Digits",
+        "hmnp" => "Nyiakeng Puachue Hmong Digits",
+        "java" => "Javanese Digits",
+        "kali" => "Kayah Li Digits",
+        "kawi" => "Kawi Digits",
+        "khmr" => "Khmer Digits",
+        "knda" => "Kannada Digits",
+        "krai" => "Kirat Rai Digits",
+        "lana" => "Tai Tham Hora Digits",
+        "lanatham" => "Tai Tham Tham Digits",
+        "laoo" => "Lao Digits",
+        "latn" => "Western Digits",
+        "lepc" => "Lepcha Digits",
+        "limb" => "Limbu Digits",
+        "mathbold" => "Mathematical Bold Digits",
+        "mathdbl" => "Mathematical Double-Struck Digits",
+        "mathmono" => "Mathematical Monospace Digits",
+        "mathsanb" => "Mathematical Sans-Serif Bold Digits",
+        "mathsans" => "Mathematical Sans-Serif Digits",
+        "mlym" => "Malayalam Digits",
+        "modi" => "Modi Digits",
+        "mong" => "Mongolian Digits",
+        "mroo" => "Mro Digits",
+        "mtei" => "Meetei Mayek Digits",
+        "mymr" => "Myanmar Digits",
+        "mymrepka" => "Myanmar Eastern Pwo Karen Digits",
+        "mymrpao" => "Myanmar Pao Digits",
+        "mymrshan" => "Myanmar Shan Digits",
+        "mymrtlng" => "Myanmar Tai Laing Digits",
+        "nagm" => "Nag Mundari Digits",
+        "newa" => "Newa Digits",
+        "nkoo" => "N’Ko Digits",
+        "olck" => "Ol Chiki Digits",
+        "onao" => "Ol Onal Digits",
+        "orya" => "Odia Digits",
+        "osma" => "Osmanya Digits",
+        "outlined" => "Outlined Digits",
+        "rohg" => "Hanifi Rohingya Digits",
+        "saur" => "Saurashtra Digits",
+        "segment" => "Segmented Digits",
+        "shrd" => "Sharada Digits",
+        "sind" => "Khudawadi Digits",
+        "sinh" => "Sinhala Lith Digits",
+        "sora" => "Sora Sompeng Digits",
+        "sund" => "Sundanese Digits",
+        "sunu" => "Sunuwar Digits",
+        "takr" => "Takri Digits",
+        "talu" => "New Tai Lue Digits",
+        "tamldec" => "Tamil Digits",
+        "telu" => "Telugu Digits",
+        "thai" => "Thai Digits",
+        "tibt" => "Tibetan Digits",
+        "tirh" => "Tirhuta Digits",
+        "tnsa" => "Tangsa Digits",
+        "vaii" => "Vai Digits",
+        "wara" => "Warang Citi Digits",
+        "wcho" => "Wancho Digits",
+        _ => return None,
+    };
+
+    Some(name)
 }
 
+/// Returns the flag emoji of a region, built from the regional indicator
+/// symbols of its code, or `nil` when the region has no flag.
+#[rustler::nif]
+pub(crate) fn display_names_region_flag<'a>(env: Env<'a>, region: &str) -> NifResult<Term<'a>> {
+    stats::measure(Counted::DisplayNamesRegionFlag, || {
+        display_names_region_flag_inner(env, region)
+    })
+}
+
+fn display_names_region_flag_inner<'a>(env: Env<'a>, region: &str) -> NifResult<Term<'a>> {
+    let region = match Region::from_str(region) {
+        Ok(region) => region,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_region()).encode(env)),
+    };
+
+    let flag: Option<String> = FLAG_REGIONS.contains(&region.as_str()).then(|| {
+        region
+            .as_str()
+            .chars()
+            .filter_map(|c| char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32)))
+            .collect()
+    });
+
+    Ok((atoms::ok(), flag).encode(env))
+}
+
+/// The regions with an RGI emoji flag sequence. Numeric regions and codes
+/// such as `EZ` or `ZZ` have none.
+const FLAG_REGIONS: &[&str] = &[
+    "AC", "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX",
+    "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR",
+    "BS", "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM",
+    "CN", "CO", "CP", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DG", "DJ", "DK", "DM", "DO",
+    "DZ", "EA", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "EU", "FI", "FJ", "FK", "FM", "FO", "FR",
+    "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT",
+    "GU", "GW", "GY", "HK", "HM", "HN", "HR", "HT", "HU", "IC", "ID", "IE", "IL", "IM", "IN", "IO",
+    "IQ", "IR", "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR",
+    "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC",
+    "MD", "ME", "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU",
+    "MV", "MW", "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU",
+    "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY",
+    "QA", "RE", "RO", "RS", "RU", "RW", "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK",
+    "SL", "SM", "SN", "SO", "SR", "SS", "ST", "SV", "SX", "SY", "SZ", "TA", "TC", "TD", "TF", "TG",
+    "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "UN",
+    "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI", "VN", "VU", "WF", "WS", "XK", "YE", "YT", "ZA",
+    "ZM", "ZW",
+];
+
 fn decode_kind<'a>(term: Term<'a>) -> Result<FormatterKind, ()> {
     let value = if term.get_type() == TermType::Atom {
         term.atom_to_string().map_err(|_| ())?
@@ -167,34 +656,20 @@ fn decode_kind<'a>(term: Term<'a>) -> Result<FormatterKind, ()> {
         "region" => Ok(FormatterKind::Region),
         "script" => Ok(FormatterKind::Script),
         "variant" => Ok(FormatterKind::Variant),
+        "unit" => Ok(FormatterKind::Unit),
+        "numbering_system" => Ok(FormatterKind::NumberingSystem),
         _ => Err(()),
     }
 }
 
-fn decode_options<'a>(term: Term<'a>) -> Result<DisplayNamesOptions, ()> {
-    if term.get_type() != TermType::Map {
-        if let Ok(atom_name) = term.atom_to_string() {
-            if atom_name == "nil" {
-                return Ok(DisplayNamesOptions::default());
-            }
-        }
-        return Err(());
-    }
-
+/// Decodes the ICU4X options together with the `extensions` flag of the
+/// locale formatter.
+fn decode_options<'a>(term: Term<'a>) -> Result<(DisplayNamesOptions, bool), ()> {
     let mut options = DisplayNamesOptions::default();
-    let mut iter = MapIterator::new(term).ok_or(())?;
-
-    while let Some((key_term, value_term)) = iter.next() {
-        let key = key_term.atom_to_string().map_err(|_| ())?;
-
-        if key == "style" {
-            if let Ok(atom_name) = value_term.atom_to_string() {
-                if atom_name == "nil" {
-                    options.style = None;
-                    continue;
-                }
-            }
+    let mut extensions = true;
 
+    decode_map(term, UnknownKeys::Error, |key, value_term| {
+        if key == atoms::style() {
             let value: Atom = value_term.decode().map_err(|_| ())?;
             options.style = if value == atoms::narrow() {
                 Some(Style::Narrow)
@@ -207,14 +682,7 @@ fn decode_options<'a>(term: Term<'a>) -> Result<DisplayNamesOptions, ()> {
             } else {
                 return Err(());
             };
-        } else if key == "fallback" {
-            if let Ok(atom_name) = value_term.atom_to_string() {
-                if atom_name == "nil" {
-                    options.fallback = Fallback::default();
-                    continue;
-                }
-            }
-
+        } else if key == atoms::fallback() {
             let value: Atom = value_term.decode().map_err(|_| ())?;
             options.fallback = if value == atoms::code() {
                 Fallback::Code
@@ -223,40 +691,47 @@ fn decode_options<'a>(term: Term<'a>) -> Result<DisplayNamesOptions, ()> {
             } else {
                 return Err(());
             };
-        } else if key == "language_display" {
-            if let Ok(atom_name) = value_term.atom_to_string() {
-                if atom_name == "nil" {
-                    options.language_display = LanguageDisplay::default();
-                    continue;
-                }
-            }
-
-            let value: Atom = value_term.decode().map_err(|_| ())?;
-            options.language_display = if value == atoms::dialect() {
-                LanguageDisplay::Dialect
-            } else if value == atoms::standard() {
-                LanguageDisplay::Standard
-            } else {
-                return Err(());
-            };
-        } else if key == "locale" {
-            continue;
+        } else if key == atoms::language_display() {
+            options.language_display = decode_language_display(value_term)?;
+        } else if key == atoms::extensions() {
+            extensions = value_term.decode().map_err(|_| ())?;
+        } else if key == atoms::locale() {
+            // Locale is handled on the Elixir side.
         } else {
-            if value_term.get_type() != TermType::Atom {
-                return Err(());
-            }
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
 
-            if let Ok(atom_name) = value_term.atom_to_string() {
-                if atom_name == "nil" {
-                    continue;
-                }
-            }
+    Ok((options, extensions))
+}
 
-            return Err(());
+/// Decodes the options of a single lookup: the `language_display` to use
+/// instead of the formatter's, if any.
+fn decode_call_options<'a>(term: Term<'a>) -> Result<Option<LanguageDisplay>, ()> {
+    let mut language_display = None;
+
+    decode_map(term, UnknownKeys::Error, |key, value_term| {
+        if key == atoms::language_display() {
+            language_display = Some(decode_language_display(value_term)?);
+            Ok(true)
+        } else {
+            Ok(false)
         }
-    }
+    })?;
+
+    Ok(language_display)
+}
 
-    Ok(options)
+fn decode_language_display<'a>(term: Term<'a>) -> Result<LanguageDisplay, ()> {
+    let value: Atom = term.decode().map_err(|_| ())?;
+    if value == atoms::dialect() {
+        Ok(LanguageDisplay::Dialect)
+    } else if value == atoms::standard() {
+        Ok(LanguageDisplay::Standard)
+    } else {
+        Err(())
+    }
 }
 
 fn decode_locale<'a>(term: Term<'a>) -> Result<Locale, ()> {
@@ -288,13 +763,14 @@ fn decode_variant<'a>(term: Term<'a>) -> Result<Variant, ()> {
     Variant::from_str(&value).map_err(|_| ())
 }
 
+/// Reads a code given as an atom or a string. `nil` is not a code.
 fn term_to_string<'a>(term: Term<'a>) -> Result<String, ()> {
+    if is_nil(term) {
+        return Err(());
+    }
+
     if term.get_type() == TermType::Atom {
-        let atom_name = term.atom_to_string().map_err(|_| ())?;
-        if atom_name == "nil" {
-            return Err(());
-        }
-        Ok(atom_name)
+        term.atom_to_string().map_err(|_| ())
     } else {
         term.decode::<String>().map_err(|_| ())
     }
@@ -0,0 +1,510 @@
+//! NIFs that apply to every formatter resource type, and the `Formatter`
+//! trait with the `formatter_nifs!` macro generating the NIFs of a formatter
+//! kind from it.
+
+use std::ops::Range;
+
+use icu::collator::options::CollatorOptions;
+use icu::collator::Collator;
+use icu::datetime::fieldsets::YMD;
+use icu::datetime::DateTimeFormatter;
+use icu::decimal::options::DecimalFormatterOptions;
+use icu::decimal::DecimalFormatter;
+use icu::experimental::dimension::currency::formatter::CurrencyFormatter;
+use icu::experimental::dimension::units::formatter::UnitsFormatter;
+use icu::experimental::displaynames::{DisplayNamesOptions, LocaleDisplayNamesFormatter};
+use icu::list::options::ListFormatterOptions;
+use icu::list::ListFormatter;
+use icu::locale::Locale;
+use icu_provider::{DataError, DataLocale};
+use rustler::{Atom, Encoder, Env, NifResult, NifTaggedEnum, ResourceArc, Term};
+
+use crate::atoms;
+use crate::collator::CollatorResource;
+use crate::currency::CurrencyFormatterResource;
+use crate::datetime::DateTimeFormatterResource;
+use crate::display_names::DisplayNamesFormatterResource;
+use crate::index;
+use crate::list::ListFormatterResource;
+use crate::locale::{self, LocaleArg, LocaleResource};
+use crate::number::NumberFormatterResource;
+use crate::stats::{self, Counted};
+use crate::units::UnitFormatterResource;
+use crate::writeable_parts::{FormattedPart, PartType};
+
+// Resources are shared between Erlang processes and used from any scheduler
+// thread at once, without locking. Every resource type must therefore stay
+// `Send + Sync` and only expose `&self` methods.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<NumberFormatterResource>();
+    assert_send_sync::<DateTimeFormatterResource>();
+    assert_send_sync::<ListFormatterResource>();
+    assert_send_sync::<DisplayNamesFormatterResource>();
+    assert_send_sync::<CurrencyFormatterResource>();
+    assert_send_sync::<UnitFormatterResource>();
+    assert_send_sync::<CollatorResource>();
+    assert_send_sync::<LocaleResource>();
+};
+
+/// A formatter kind whose NIFs are generated by `formatter_nifs!`.
+///
+/// The generated NIFs decode the resource, the locale and the input, and
+/// return each failure as `{:error, reason}`, so implementations only deal
+/// with decoded values and report errors as the reason atom.
+pub(crate) trait Formatter: rustler::Resource + Sized {
+    /// The constructor arguments between the locale and the options, such as
+    /// the unit of a unit formatter, as a tuple.
+    type Args;
+    /// The value formatted.
+    type Input;
+
+    fn new(locale: Locale, args: Self::Args, options: Term) -> Result<Self, Atom>;
+
+    fn decode_input(term: Term) -> Result<Self::Input, Atom>;
+
+    fn format(&self, input: &Self::Input) -> Result<String, Atom>;
+
+    /// The formatted value and its parts, with their byte ranges in it.
+    fn format_to_parts(
+        &self,
+        input: &Self::Input,
+    ) -> Result<(String, Vec<(PartType, Range<usize>)>), Atom>;
+
+    /// The options in effect after locale defaults, as a map.
+    fn resolved_options<'a>(&self, env: Env<'a>) -> Term<'a>;
+}
+
+/// Declares the NIFs of a `Formatter`. The constructor takes the locale, the
+/// listed arguments and the options map; `format_to_parts` takes an index
+/// mode after the input. Each NIF is counted under the given `Counted`
+/// variant.
+///
+/// ```ignore
+/// formatter_nifs! {
+///     UnitFormatterResource {
+///         new: unit_formatter_new(unit: String) => UnitFormatterNew,
+///         format: unit_format => UnitFormat,
+///         format_to_parts: unit_format_to_parts => UnitFormatToParts,
+///         resolved_options: unit_resolved_options => UnitResolvedOptions,
+///     }
+/// }
+/// ```
+macro_rules! formatter_nifs {
+    (
+        $resource:ty {
+            new: $new:ident($($arg:ident: $arg_ty:ty),*) => $new_counted:ident,
+            format: $format:ident => $format_counted:ident,
+            format_to_parts: $to_parts:ident => $to_parts_counted:ident,
+            resolved_options: $resolved_options:ident => $resolved_counted:ident,
+        }
+    ) => {
+        #[rustler::nif]
+        pub(crate) fn $new<'a>(
+            env: Env<'a>,
+            locale_term: Term<'a>,
+            $($arg: $arg_ty,)*
+            options_term: Term<'a>,
+        ) -> NifResult<Term<'a>> {
+            $crate::stats::measure($crate::stats::Counted::$new_counted, || {
+                $crate::formatter::new_nif::<$resource>(
+                    env,
+                    locale_term,
+                    ($($arg,)*),
+                    options_term,
+                )
+            })
+        }
+
+        #[rustler::nif]
+        pub(crate) fn $format<'a>(
+            env: Env<'a>,
+            formatter_term: Term<'a>,
+            input_term: Term<'a>,
+        ) -> NifResult<Term<'a>> {
+            $crate::stats::measure($crate::stats::Counted::$format_counted, || {
+                $crate::formatter::format_nif::<$resource>(env, formatter_term, input_term)
+            })
+        }
+
+        #[rustler::nif]
+        pub(crate) fn $to_parts<'a>(
+            env: Env<'a>,
+            formatter_term: Term<'a>,
+            input_term: Term<'a>,
+            index_mode_term: Term<'a>,
+        ) -> NifResult<Term<'a>> {
+            $crate::stats::measure($crate::stats::Counted::$to_parts_counted, || {
+                $crate::formatter::format_to_parts_nif::<$resource>(
+                    env,
+                    formatter_term,
+                    input_term,
+                    index_mode_term,
+                )
+            })
+        }
+
+        #[rustler::nif]
+        pub(crate) fn $resolved_options<'a>(
+            env: Env<'a>,
+            formatter_term: Term<'a>,
+        ) -> NifResult<Term<'a>> {
+            $crate::stats::measure($crate::stats::Counted::$resolved_counted, || {
+                $crate::formatter::resolved_options_nif::<$resource>(env, formatter_term)
+            })
+        }
+    };
+}
+
+pub(crate) use formatter_nifs;
+
+pub(crate) fn new_nif<'a, F: Formatter>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    args: F::Args,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    match F::new(locale_arg.0, args, options_term) {
+        Ok(formatter) => Ok((atoms::ok(), ResourceArc::new(formatter)).encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
+
+pub(crate) fn format_nif<'a, F: Formatter>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    input_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let result = decode_call::<F>(formatter_term, input_term)
+        .and_then(|(formatter, input)| formatter.format(&input));
+
+    match result {
+        Ok(output) => Ok((atoms::ok(), output).encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
+
+pub(crate) fn format_to_parts_nif<'a, F: Formatter>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    input_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let result = decode_call::<F>(formatter_term, input_term)
+        .and_then(|(formatter, input)| formatter.format_to_parts(&input));
+
+    let (output, parts) = match result {
+        Ok(formatted) => formatted,
+        Err(reason) => return Ok((atoms::error(), reason).encode(env)),
+    };
+
+    let parts = parts
+        .into_iter()
+        .map(|(part_type, range)| {
+            let value = output[range.clone()].to_string();
+            (FormattedPart { part_type, value }, range)
+        })
+        .collect();
+    let parts = index::encode_parts(env, &output, parts, index_mode);
+    Ok((atoms::ok(), parts).encode(env))
+}
+
+pub(crate) fn resolved_options_nif<'a, F: Formatter>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    match formatter_term.decode::<ResourceArc<F>>() {
+        Ok(formatter) => Ok((atoms::ok(), formatter.resolved_options(env)).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    }
+}
+
+fn decode_call<F: Formatter>(
+    formatter_term: Term,
+    input_term: Term,
+) -> Result<(ResourceArc<F>, F::Input), Atom> {
+    let formatter = formatter_term
+        .decode::<ResourceArc<F>>()
+        .map_err(|_| atoms::invalid_formatter())?;
+    let input = F::decode_input(input_term)?;
+    Ok((formatter, input))
+}
+
+/// Returns the locale whose data the formatter actually loaded after
+/// fallback, e.g. `"en-001"` for a formatter requested for `"en-NZ"`.
+#[rustler::nif]
+pub(crate) fn formatter_data_locale<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::FormatterDataLocale, || {
+        formatter_data_locale_inner(env, formatter_term)
+    })
+}
+
+fn formatter_data_locale_inner<'a>(env: Env<'a>, formatter_term: Term<'a>) -> NifResult<Term<'a>> {
+    let data_locale = match data_locale(formatter_term) {
+        Ok(data_locale) => data_locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+
+    match data_locale {
+        Some(locale) => Ok((atoms::ok(), locale.to_string()).encode(env)),
+        None => Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    }
+}
+
+fn data_locale(term: Term) -> Result<Option<DataLocale>, ()> {
+    if let Ok(resource) = term.decode::<ResourceArc<NumberFormatterResource>>() {
+        Ok(resource.data_locale())
+    } else if let Ok(resource) = term.decode::<ResourceArc<DateTimeFormatterResource>>() {
+        Ok(resource.data_locale())
+    } else if let Ok(resource) = term.decode::<ResourceArc<ListFormatterResource>>() {
+        Ok(resource.data_locale())
+    } else if let Ok(resource) = term.decode::<ResourceArc<DisplayNamesFormatterResource>>() {
+        Ok(resource.data_locale())
+    } else if let Ok(resource) = term.decode::<ResourceArc<CurrencyFormatterResource>>() {
+        Ok(resource.data_locale())
+    } else if let Ok(resource) = term.decode::<ResourceArc<UnitFormatterResource>>() {
+        Ok(resource.data_locale())
+    } else if let Ok(resource) = term.decode::<ResourceArc<CollatorResource>>() {
+        Ok(resource.data_locale())
+    } else {
+        Err(())
+    }
+}
+
+/// Returns an estimate of the bytes a formatter or locale resource holds.
+///
+/// Formatters built from compiled data borrow it from the NIF library's
+/// read-only segment rather than copying it to the heap, so the estimate is
+/// the resource's own allocation plus the strings and locale extensions it
+/// owns. The BEAM only accounts for the fixed-size part of a resource, which
+/// makes this the better figure for capacity planning.
+#[rustler::nif]
+pub(crate) fn formatter_memory<'a>(env: Env<'a>, formatter_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::FormatterMemory, || {
+        formatter_memory_inner(env, formatter_term)
+    })
+}
+
+fn formatter_memory_inner<'a>(env: Env<'a>, formatter_term: Term<'a>) -> NifResult<Term<'a>> {
+    match memory(formatter_term) {
+        Ok(bytes) => Ok((atoms::ok(), bytes).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    }
+}
+
+fn memory(term: Term) -> Result<usize, ()> {
+    if let Ok(resource) = term.decode::<ResourceArc<NumberFormatterResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<DateTimeFormatterResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<ListFormatterResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<DisplayNamesFormatterResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<CurrencyFormatterResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<UnitFormatterResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<CollatorResource>>() {
+        Ok(resource.memory())
+    } else if let Ok(resource) = term.decode::<ResourceArc<LocaleResource>>() {
+        Ok(resource.memory())
+    } else {
+        Err(())
+    }
+}
+
+/// A formatter family that `formatter_warmup` prepares.
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum WarmupKind {
+    Number,
+    Temporal,
+    List,
+    DisplayNames,
+    Currency,
+    Units,
+    Collator,
+}
+
+/// Builds the shared locale singletons and the fallback chains of `locales`,
+/// then constructs and drops a default formatter of each kind for each
+/// locale, so the compiled data they use is paged in before the first
+/// request. Runs on a dirty scheduler since it may take a while for many
+/// locales.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub(crate) fn formatter_warmup<'a>(
+    env: Env<'a>,
+    locales_term: Term<'a>,
+    kinds_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::FormatterWarmup, || {
+        formatter_warmup_inner(env, locales_term, kinds_term)
+    })
+}
+
+fn formatter_warmup_inner<'a>(
+    env: Env<'a>,
+    locales_term: Term<'a>,
+    kinds_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locales: Vec<Locale> = match locales_term.decode::<Vec<LocaleArg>>() {
+        Ok(locales) => locales.into_iter().map(|locale| locale.0).collect(),
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+    let kinds: Vec<WarmupKind> = match kinds_term.decode() {
+        Ok(kinds) => kinds,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    match warm_up(&locales, &kinds) {
+        Ok(()) => Ok(atoms::ok().encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    }
+}
+
+fn warm_up(locales: &[Locale], kinds: &[WarmupKind]) -> Result<(), DataError> {
+    locale::warm_up(locales);
+
+    for kind in kinds {
+        for locale in locales {
+            warm_up_kind(*kind, locale)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the default formatter of `kind` for `locale`.
+fn warm_up_kind(kind: WarmupKind, locale: &Locale) -> Result<(), DataError> {
+    match kind {
+        WarmupKind::Number => {
+            DecimalFormatter::try_new(locale.into(), DecimalFormatterOptions::default()).map(drop)
+        }
+        WarmupKind::Temporal => {
+            DateTimeFormatter::try_new(locale.into(), YMD::medium().with_time_hm()).map(drop)
+        }
+        WarmupKind::List => {
+            ListFormatter::try_new_and(locale.into(), ListFormatterOptions::default()).map(drop)
+        }
+        WarmupKind::DisplayNames => {
+            LocaleDisplayNamesFormatter::try_new(locale.into(), DisplayNamesOptions::default())
+                .map(drop)
+        }
+        WarmupKind::Currency => {
+            CurrencyFormatter::try_new(locale.into(), Default::default()).map(drop)
+        }
+        WarmupKind::Units => {
+            UnitsFormatter::try_new(locale.into(), "meter", Default::default()).map(drop)
+        }
+        WarmupKind::Collator => {
+            Collator::try_new(locale.into(), CollatorOptions::default()).map(drop)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use fixed_decimal::Decimal;
+    use icu::calendar::Date;
+    use icu::locale::locale;
+    use writeable::Writeable;
+
+    use super::*;
+
+    const THREADS: usize = 16;
+    const ITERATIONS: usize = 2_000;
+
+    /// Calls `format` from many threads at once on one shared formatter and
+    /// checks every result against a single-threaded run.
+    fn stress<F, T>(formatter: F, format: fn(&F, usize) -> T)
+    where
+        F: Send + Sync + 'static,
+        T: PartialEq + std::fmt::Debug + Send + Sync + 'static,
+    {
+        let formatter = Arc::new(formatter);
+        let expected: Vec<T> = (0..ITERATIONS).map(|i| format(&formatter, i)).collect();
+        let expected = Arc::new(expected);
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|offset| {
+                let formatter = Arc::clone(&formatter);
+                let expected = Arc::clone(&expected);
+                thread::spawn(move || {
+                    for step in 0..ITERATIONS {
+                        let i = (step + offset * 37) % ITERATIONS;
+                        assert_eq!(format(&formatter, i), expected[i]);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn decimal_formatter_is_shareable() {
+        let formatter =
+            DecimalFormatter::try_new(locale!("de").into(), DecimalFormatterOptions::default())
+                .unwrap();
+        stress(formatter, |formatter, i| {
+            let decimal = Decimal::from(i as i64 * 1_001);
+            formatter.format(&decimal).write_to_string().into_owned()
+        });
+    }
+
+    #[test]
+    fn datetime_formatter_is_shareable() {
+        let formatter = DateTimeFormatter::try_new(locale!("ja").into(), YMD::long()).unwrap();
+        stress(formatter, |formatter, i| {
+            let date = Date::try_new_iso(2000 + (i % 50) as i32, 1 + (i % 12) as u8, 1).unwrap();
+            formatter.format(&date).write_to_string().into_owned()
+        });
+    }
+
+    #[test]
+    fn list_formatter_is_shareable() {
+        let formatter =
+            ListFormatter::try_new_and(locale!("es").into(), ListFormatterOptions::default())
+                .unwrap();
+        stress(formatter, |formatter, i| {
+            let items = (0..i % 5).map(|n| n.to_string());
+            formatter.format_to_string(items)
+        });
+    }
+
+    #[test]
+    fn collator_is_shareable() {
+        let collator = Collator::try_new(locale!("sv").into(), CollatorOptions::default()).unwrap();
+        stress(collator, |collator, i| {
+            let left = format!("ö{i}");
+            let right = format!("z{}", ITERATIONS - i);
+            collator.compare(&left, &right)
+        });
+    }
+
+    #[test]
+    fn warm_up_fills_the_shared_locale_data() {
+        let kinds = [WarmupKind::Temporal, WarmupKind::Collator];
+        warm_up(&[locale!("en"), locale!("fr-CA")], &kinds).unwrap();
+
+        assert!(crate::locale::warmed_up());
+    }
+}
@@ -0,0 +1,145 @@
+//! Grammatical gender of units, weekdays and months.
+//!
+//! ICU4X does not ship CLDR's grammatical features or unit genders, so the
+//! tables below cover the common units and the date field names for the
+//! gendered languages with the most speakers. Anything else resolves to no
+//! gender rather than a guess.
+
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, Term};
+
+use crate::atoms;
+use crate::locale::LocaleArg;
+use crate::stats::{self, Counted};
+
+#[derive(Clone, Copy)]
+enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+}
+
+impl Gender {
+    fn atom(self) -> Atom {
+        match self {
+            Gender::Masculine => atoms::masculine(),
+            Gender::Feminine => atoms::feminine(),
+            Gender::Neuter => atoms::neuter(),
+        }
+    }
+}
+
+#[derive(NifMap)]
+struct GrammarInfo {
+    gender: Option<Atom>,
+}
+
+/// Returns the grammatical features of `thing`, given as `{:unit, "meter"}`,
+/// `{:weekday, 1..7}` (Monday is 1) or `{:month, 1..12}`.
+#[rustler::nif]
+pub(crate) fn grammar_info<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    thing_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::GrammarInfo, || {
+        grammar_info_inner(env, locale_term, thing_term)
+    })
+}
+
+fn grammar_info_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    thing_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let (kind, value): (Atom, Term) = match thing_term.decode() {
+        Ok(thing) => thing,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let language = locale_arg.0.id.language.as_str();
+    let gender = if kind == atoms::unit() {
+        match value.decode::<&str>() {
+            Ok(unit) => unit_gender(language, unit),
+            Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+        }
+    } else if kind == atoms::weekday() {
+        match value.decode::<u8>() {
+            Ok(weekday @ 1..=7) => weekday_gender(language, weekday),
+            _ => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+        }
+    } else if kind == atoms::month() {
+        match value.decode::<u8>() {
+            Ok(1..=12) => month_gender(language),
+            _ => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+        }
+    } else {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    };
+
+    let info = GrammarInfo {
+        gender: gender.map(Gender::atom),
+    };
+    Ok((atoms::ok(), info).encode(env))
+}
+
+fn weekday_gender(language: &str, weekday: u8) -> Option<Gender> {
+    use Gender::*;
+
+    match (language, weekday) {
+        ("de" | "fr" | "es", _) => Some(Masculine),
+        // "la domenica" is the only feminine Italian weekday.
+        ("it", 7) => Some(Feminine),
+        ("it", _) => Some(Masculine),
+        // Monday to Friday are compounds of the feminine "feira".
+        ("pt", 1..=5) => Some(Feminine),
+        ("pt", _) => Some(Masculine),
+        _ => None,
+    }
+}
+
+fn month_gender(language: &str) -> Option<Gender> {
+    match language {
+        "de" | "fr" | "es" | "it" | "pt" => Some(Gender::Masculine),
+        _ => None,
+    }
+}
+
+fn unit_gender(language: &str, unit: &str) -> Option<Gender> {
+    use Gender::*;
+
+    let gender = match (language, unit) {
+        ("de", "meter" | "kilometer" | "centimeter" | "millimeter" | "liter") => Masculine,
+        ("de", "day" | "month" | "foot" | "inch") => Masculine,
+        ("de", "kilogram" | "gram" | "year" | "byte" | "percent") => Neuter,
+        ("de", "hour" | "minute" | "second" | "week" | "mile") => Feminine,
+
+        ("fr", "meter" | "kilometer" | "centimeter" | "millimeter" | "liter") => Masculine,
+        ("fr", "kilogram" | "gram" | "day" | "month" | "year") => Masculine,
+        ("fr", "foot" | "inch" | "byte" | "percent") => Masculine,
+        ("fr", "hour" | "minute" | "second" | "week") => Feminine,
+
+        ("es", "meter" | "kilometer" | "centimeter" | "millimeter" | "liter") => Masculine,
+        ("es", "kilogram" | "gram" | "minute" | "second" | "day") => Masculine,
+        ("es", "month" | "year" | "foot" | "byte") => Masculine,
+        ("es", "hour" | "week" | "mile" | "inch") => Feminine,
+
+        ("it", "meter" | "kilometer" | "centimeter" | "millimeter" | "liter") => Masculine,
+        ("it", "kilogram" | "gram" | "minute" | "second" | "day") => Masculine,
+        ("it", "month" | "year" | "mile" | "foot" | "inch" | "byte") => Masculine,
+        ("it", "hour" | "week") => Feminine,
+
+        ("pt", "meter" | "kilometer" | "centimeter" | "millimeter" | "liter") => Masculine,
+        ("pt", "kilogram" | "gram" | "minute" | "second" | "day") => Masculine,
+        ("pt", "month" | "year" | "foot" | "byte") => Masculine,
+        ("pt", "hour" | "week" | "mile" | "inch") => Feminine,
+
+        _ => return None,
+    };
+
+    Some(gender)
+}
@@ -0,0 +1,97 @@
+//! Offsets of formatted parts, counted in the unit the consumer indexes
+//! strings by.
+//!
+//! Parts are reported without offsets unless an index mode is given. With
+//! one, every part gains `:start` and `:end` (exclusive) offsets into the
+//! formatted string: UTF-8 bytes as Elixir's `binary_part/3` takes them,
+//! grapheme clusters as `String.slice/3` counts them, or UTF-16 code units as
+//! JavaScript strings are indexed.
+
+use std::ops::Range;
+
+use icu::segmenter::GraphemeClusterSegmenter;
+use rustler::{Encoder, Env, NifTaggedEnum, Term};
+
+use crate::atoms;
+use crate::options;
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+pub(crate) enum IndexMode {
+    Byte,
+    Grapheme,
+    Utf16,
+}
+
+/// Decodes the index mode argument of a `*_format_to_parts` NIF, where `nil`
+/// leaves the parts without offsets.
+pub(crate) fn decode_mode(term: Term) -> Result<Option<IndexMode>, ()> {
+    if options::is_nil(term) {
+        return Ok(None);
+    }
+    term.decode().map(Some).map_err(|_| ())
+}
+
+/// Encodes parts given with their byte ranges in `output`, adding the
+/// offsets when an index mode is given.
+pub(crate) fn encode_parts<'a, T: Encoder>(
+    env: Env<'a>,
+    output: &str,
+    parts: Vec<(T, Range<usize>)>,
+    mode: Option<IndexMode>,
+) -> Term<'a> {
+    let indexer = mode.map(|mode| Indexer::new(output, mode));
+
+    let parts: Vec<Term<'a>> = parts
+        .into_iter()
+        .map(|(part, range)| {
+            let term = part.encode(env);
+            let Some(indexer) = &indexer else {
+                return term;
+            };
+            term.map_put(atoms::start(), indexer.index(output, range.start))
+                .and_then(|term| term.map_put(atoms::range_end(), indexer.index(output, range.end)))
+                .unwrap_or(term)
+        })
+        .collect();
+
+    parts.encode(env)
+}
+
+/// Shifts byte ranges by `offset`, for parts formatted separately and then
+/// concatenated.
+pub(crate) fn shift<T>(parts: Vec<(T, Range<usize>)>, offset: usize) -> Vec<(T, Range<usize>)> {
+    parts
+        .into_iter()
+        .map(|(part, range)| (part, range.start + offset..range.end + offset))
+        .collect()
+}
+
+struct Indexer {
+    mode: IndexMode,
+    /// Byte offsets of the grapheme cluster boundaries, in grapheme mode.
+    boundaries: Vec<usize>,
+}
+
+impl Indexer {
+    fn new(output: &str, mode: IndexMode) -> Self {
+        let boundaries = match mode {
+            IndexMode::Grapheme => GraphemeClusterSegmenter::new()
+                .segment_str(output)
+                .collect(),
+            IndexMode::Byte | IndexMode::Utf16 => Vec::new(),
+        };
+        Self { mode, boundaries }
+    }
+
+    /// Converts a byte offset into `output` to the indexer's unit. An offset
+    /// inside a grapheme cluster counts the cluster as preceding it.
+    fn index(&self, output: &str, byte: usize) -> usize {
+        match self.mode {
+            IndexMode::Byte => byte,
+            IndexMode::Utf16 => output
+                .get(..byte)
+                .map_or(0, |text| text.encode_utf16().count()),
+            IndexMode::Grapheme => self.boundaries.partition_point(|&boundary| boundary < byte),
+        }
+    }
+}
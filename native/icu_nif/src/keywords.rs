@@ -0,0 +1,204 @@
+//! Validation of `-u-` extension keywords against the CLDR BCP 47 data.
+//!
+//! ICU4X does not ship the `bcp47/*.xml` keyword data, so the values of the
+//! keys with a closed set of values are listed below. Keys whose values are
+//! open ended, such as currencies, regions and time zones, are only checked
+//! for the shape of their values.
+
+use icu::locale::extensions::unicode::{Key, Value};
+use rustler::{Atom, Encoder, Env, NifResult, Term};
+
+use crate::atoms;
+use crate::stats::{self, Counted};
+
+/// What the values of a key may be.
+enum Values {
+    /// One of the listed values.
+    Enumerated(&'static [&'static str]),
+    /// `true`, which may be left out, or `false`.
+    Boolean,
+    /// An ISO 4217 currency code.
+    Currency,
+    /// A region followed by a subdivision suffix, such as `usca` or `uszzzz`.
+    Subdivision,
+    /// A short time zone identifier such as `usnyc`.
+    TimeZone,
+    /// A sequence of script codes, or the listed reordering groups.
+    Scripts(&'static [&'static str]),
+}
+
+const KEYWORDS: &[(&str, Values)] = &[
+    ("ca", Values::Enumerated(CALENDARS)),
+    ("cf", Values::Enumerated(&["account", "standard"])),
+    ("co", Values::Enumerated(COLLATIONS)),
+    ("cu", Values::Currency),
+    ("dx", Values::Scripts(&[])),
+    ("em", Values::Enumerated(&["default", "emoji", "text"])),
+    (
+        "fw",
+        Values::Enumerated(&["sun", "mon", "tue", "wed", "thu", "fri", "sat"]),
+    ),
+    ("hc", Values::Enumerated(&["h11", "h12", "h23", "h24"])),
+    ("ka", Values::Enumerated(&["noignore", "shifted"])),
+    ("kb", Values::Boolean),
+    ("kc", Values::Boolean),
+    ("kf", Values::Enumerated(&["false", "lower", "upper"])),
+    ("kk", Values::Boolean),
+    ("kn", Values::Boolean),
+    ("kr", Values::Scripts(REORDER_GROUPS)),
+    (
+        "ks",
+        Values::Enumerated(&["identic", "level1", "level2", "level3", "level4"]),
+    ),
+    (
+        "kv",
+        Values::Enumerated(&["currency", "punct", "space", "symbol"]),
+    ),
+    ("lb", Values::Enumerated(&["loose", "normal", "strict"])),
+    (
+        "lw",
+        Values::Enumerated(&["breakall", "keepall", "normal", "phrase"]),
+    ),
+    (
+        "ms",
+        Values::Enumerated(&["metric", "uksystem", "ussystem"]),
+    ),
+    ("mu", Values::Enumerated(&["celsius", "fahrenhe", "kelvin"])),
+    ("nu", Values::Enumerated(NUMBERING_SYSTEMS)),
+    ("rg", Values::Subdivision),
+    ("sd", Values::Subdivision),
+    ("ss", Values::Enumerated(&["none", "standard"])),
+    ("tz", Values::TimeZone),
+    ("va", Values::Enumerated(&["posix"])),
+];
+
+const CALENDARS: &[&str] = &[
+    "buddhist",
+    "chinese",
+    "coptic",
+    "dangi",
+    "ethioaa",
+    "ethiopic",
+    "gregory",
+    "hebrew",
+    "indian",
+    "islamic",
+    "islamic-civil",
+    "islamic-rgsa",
+    "islamic-tbla",
+    "islamic-umalqura",
+    "iso8601",
+    "japanese",
+    "persian",
+    "roc",
+];
+
+const COLLATIONS: &[&str] = &[
+    "big5han", "compat", "dict", "direct", "ducet", "emoji", "eor", "gb2312", "phonebk",
+    "phonetic", "pinyin", "reformed", "search", "searchjl", "standard", "stroke", "trad", "unihan",
+    "zhuyin",
+];
+
+const REORDER_GROUPS: &[&str] = &["currency", "digit", "others", "punct", "space", "symbol"];
+
+const NUMBERING_SYSTEMS: &[&str] = &[
+    "adlm", "ahom", "arab", "arabext", "armn", "armnlow", "bali", "beng", "bhks", "brah", "cakm",
+    "cham", "cyrl", "deva", "diak", "ethi", "finance", "fullwide", "gara", "geor", "gong", "gonm",
+    "grek", "greklow", "gujr", "gukh", "guru", "hanidays", "hanidec", "hans", "hansfin", "hant",
+    "hantfin", "hebr", "hmng", "hmnp", "java", "jpan", "jpanfin", "jpanyear", "kali", "kawi",
+    "khmr", "knda", "krai", "lana", "lanatham", "laoo", "latn", "lepc", "limb", "mathbold",
+    "mathdbl", "mathmono", "mathsanb", "mathsans", "mlym", "modi", "mong", "mroo", "mtei", "mymr",
+    "mymrepka", "mymrpao", "mymrshan", "mymrtlng", "nagm", "native", "newa", "nkoo", "olck",
+    "onao", "orya", "osma", "outlined", "rohg", "roman", "romanlow", "saur", "segment", "shrd",
+    "sind", "sinh", "sora", "sund", "sunu", "takr", "talu", "taml", "tamldec", "telu", "thai",
+    "tibt", "tirh", "tnsa", "tols", "traditio", "vaii", "wara", "wcho",
+];
+
+/// Parses a keyword, checking that `key` is a key CLDR defines and `value`
+/// one of its values. Returns the error atom to report otherwise.
+pub(crate) fn parse_keyword(key: &str, value: &str) -> Result<(Key, Value), Atom> {
+    let key = Key::try_from_str(key).map_err(|_| atoms::unknown_extension_key())?;
+    let values = KEYWORDS
+        .iter()
+        .find(|(name, _)| *name == key.as_str())
+        .map(|(_, values)| values)
+        .ok_or_else(atoms::unknown_extension_key)?;
+
+    let value = Value::try_from_str(value).map_err(|_| atoms::invalid_extension_value())?;
+    // Values are compared in the canonical, lowercase form of the parser.
+    let canonical = value.to_string();
+    if is_valid(values, &canonical) {
+        Ok((key, value))
+    } else {
+        Err(atoms::invalid_extension_value())
+    }
+}
+
+fn is_valid(values: &Values, value: &str) -> bool {
+    let subtags: Vec<&str> = value
+        .split('-')
+        .filter(|subtag| !subtag.is_empty())
+        .collect();
+    match values {
+        Values::Enumerated(allowed) => allowed.contains(&value),
+        Values::Boolean => matches!(value, "" | "true" | "false"),
+        Values::Currency => value.len() == 3 && value.bytes().all(|byte| byte.is_ascii_lowercase()),
+        Values::Subdivision => subtags.len() == 1 && is_subdivision(value),
+        Values::TimeZone => {
+            subtags.len() == 1
+                && (3..=8).contains(&value.len())
+                && value.bytes().all(|byte| byte.is_ascii_alphanumeric())
+        }
+        Values::Scripts(groups) => {
+            !subtags.is_empty()
+                && subtags.iter().all(|subtag| {
+                    groups.contains(subtag)
+                        || (subtag.len() == 4 && subtag.bytes().all(|b| b.is_ascii_alphabetic()))
+                })
+        }
+    }
+}
+
+/// A region code (two letters or three digits) followed by a suffix of one
+/// to four letters or digits.
+fn is_subdivision(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let region_len = if bytes.first().is_some_and(u8::is_ascii_digit) {
+        3
+    } else {
+        2
+    };
+    if bytes.len() <= region_len || bytes.len() > region_len + 4 {
+        return false;
+    }
+
+    let (region, suffix) = bytes.split_at(region_len);
+    let region_valid = if region_len == 3 {
+        region.iter().all(u8::is_ascii_digit)
+    } else {
+        region.iter().all(u8::is_ascii_alphabetic)
+    };
+    region_valid && suffix.iter().all(u8::is_ascii_alphanumeric)
+}
+
+#[rustler::nif]
+pub(crate) fn locale_validate_extension_value<'a>(
+    env: Env<'a>,
+    key: &str,
+    value: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleValidateExtensionValue, || {
+        locale_validate_extension_value_inner(env, key, value)
+    })
+}
+
+fn locale_validate_extension_value_inner<'a>(
+    env: Env<'a>,
+    key: &str,
+    value: &str,
+) -> NifResult<Term<'a>> {
+    match parse_keyword(key, value) {
+        Ok(_) => Ok(atoms::ok().encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
@@ -1,9 +1,30 @@
+mod calendar;
+mod collator;
 mod currency;
 mod datetime;
+mod decimal;
 mod display_names;
+mod formatter;
+mod grammar;
+mod index;
+mod keywords;
 mod list;
 mod locale;
+mod normalizer;
 mod number;
+mod options;
+mod preferences;
+mod properties;
+mod range;
+mod segmenter;
+mod snapshot;
+mod spellout;
+mod spoof;
+mod stats;
+mod terms;
+mod text;
+mod units;
+mod writeable_parts;
 
 mod atoms {
     rustler::atoms! {
@@ -15,10 +36,26 @@ mod atoms {
         invalid_formatter,
         invalid_number,
         invalid_options,
+        invalid_option_value,
+        no_data,
         invalid_datetime,
         invalid_items,
         invalid_currency,
+        invalid_unit,
+        unsupported_locale,
+        invalid_numbering_system,
+        incompatible_units,
+        invalid_pattern,
+        invalid_region,
+        invalid_field_combination,
+        invalid_calendar,
+        invalid_date,
+        missing_field,
+        missing,
+        invalid,
+        out_of_range,
         minimum_integer_digits,
+        maximum_integer_digits,
         minimum_fraction_digits,
         maximum_fraction_digits,
         grouping,
@@ -63,6 +100,10 @@ mod atoms {
         h12,
         h23,
         h24,
+        unknown_extension_key,
+        invalid_extension_value,
+        preferences,
+        numbering_system,
         year,
         month,
         day,
@@ -70,6 +111,7 @@ mod atoms {
         minute,
         second,
         literal,
+        unknown,
         day_period,
         time_zone_name,
         weekday,
@@ -91,12 +133,87 @@ mod atoms {
         year_style,
         width,
         calendar_identifier,
+        skeleton,
+        relative_to,
+        relative_days,
+        relative_day,
+        leap_second,
         modified,
         unmodified,
         no_match,
+        case_insensitive,
+        strip_script,
+        output,
         sign,
         coef,
-        exp
+        exp,
+        resource,
+        currency,
+        compact,
+        compact_short,
+        number,
+        emoji,
+        text,
+        done,
+        strength,
+        primary,
+        secondary,
+        tertiary,
+        quaternary,
+        identical,
+        lt,
+        eq,
+        gt,
+        numeric,
+        case_first,
+        upper,
+        lower,
+        off,
+        ellipsis,
+        word_boundary,
+        ambiguous,
+        leading_adjustment,
+        trailing_case,
+        stop_words,
+        to_cased,
+        unchanged,
+        am,
+        pm,
+        noon,
+        midnight,
+        quarter,
+        masculine,
+        feminine,
+        neuter,
+        zero,
+        one,
+        two,
+        few,
+        many,
+        other,
+        float_precision,
+        scale,
+        absolute_value,
+        round_trip,
+        magnitude,
+        significant_digits,
+        compact_rounding,
+        significant,
+        source,
+        start,
+        shared,
+        range_end = "end",
+        approximately,
+        approximately_sign,
+        digest,
+        locales,
+        style,
+        fallback,
+        language_display,
+        extensions,
+        month_code,
+        era_year,
+        list_type = "type"
     }
 }
 
@@ -109,6 +226,10 @@ fn load(env: Env, _term: Term) -> bool {
         && list::load(env)
         && display_names::load(env)
         && currency::load(env)
+        && units::load(env)
+        && collator::load(env)
+        && decimal::load(env)
+        && segmenter::load(env)
 }
 
 rustler::init!("Elixir.Icu.Nif", load = load);
@@ -1,20 +1,66 @@
-use std::fmt;
-
 use icu::list::options::{ListFormatterOptions, ListLength};
+use icu::list::provider::{Baked as ListBaked, ListAndV1};
 use icu::list::{parts, ListFormatter};
-use rustler::types::map::MapIterator;
-use rustler::{Atom, Encoder, Env, NifMap, NifResult, ResourceArc, Term, TermType};
-use writeable::{Part as WriteablePart, PartsWrite, Writeable};
+use icu::locale::Locale;
+use icu_provider::prelude::*;
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, ResourceArc, Term};
+use writeable::Writeable;
 
 use crate::atoms;
-use crate::locale::LocaleResource;
+use crate::index;
+use crate::locale::{self, resolve_data_locale, LocaleArg};
+use crate::options::{self, UnknownKeys};
+use crate::stats::{self, Counted};
+use crate::writeable_parts::{PartType, PartsCollector};
 
 pub(crate) struct ListFormatterResource {
     formatter: ListFormatter,
+    locale: Locale,
 }
 
 impl rustler::Resource for ListFormatterResource {}
 
+impl ListFormatterResource {
+    /// Builds the formatter for `locale` from a formatter option map,
+    /// failing with the error atom to return.
+    pub(crate) fn try_new(locale: Locale, options_term: Term) -> Result<Self, Atom> {
+        let config = decode_formatter_config(options_term).map_err(|_| atoms::invalid_options())?;
+
+        let options = ListFormatterOptions::default().with_length(config.length);
+        let formatter = match config.list_type {
+            ListType::And => ListFormatter::try_new_and(locale.clone().into(), options),
+            ListType::Or => ListFormatter::try_new_or(locale.clone().into(), options),
+            ListType::Unit => ListFormatter::try_new_unit(locale.clone().into(), options),
+        }
+        .map_err(|_| atoms::invalid_locale())?;
+
+        Ok(Self { formatter, locale })
+    }
+
+    /// Formats the items into one string.
+    pub(crate) fn format(&self, items: &[&str]) -> String {
+        self.formatter
+            .format(items.iter().copied())
+            .write_to_string()
+            .into_owned()
+    }
+
+    /// The locale of the list patterns the formatter loaded. All list types
+    /// share the same locale coverage, so the wide "and" patterns are probed.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        resolve_data_locale::<ListAndV1, _>(
+            &ListBaked,
+            &self.locale,
+            DataMarkerAttributes::from_str_or_panic("W"),
+        )
+    }
+
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        std::mem::size_of::<Self>() + locale::heap_size(&self.locale)
+    }
+}
+
 #[derive(Copy, Clone)]
 enum ListType {
     And,
@@ -40,64 +86,10 @@ impl Default for FormatterConfig {
 #[derive(NifMap)]
 struct ListFormatPart {
     #[rustler(map = "type")]
-    part_type: Atom,
+    part_type: PartType,
     value: String,
 }
 
-struct CollectedPart {
-    start: usize,
-    end: usize,
-    part: WriteablePart,
-}
-
-struct PartsCollector {
-    output: String,
-    parts: Vec<CollectedPart>,
-}
-
-impl PartsCollector {
-    fn new() -> Self {
-        Self {
-            output: String::new(),
-            parts: Vec::new(),
-        }
-    }
-
-    fn into_parts(self) -> (String, Vec<CollectedPart>) {
-        (self.output, self.parts)
-    }
-}
-
-impl fmt::Write for PartsCollector {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.output.push_str(s);
-        Ok(())
-    }
-
-    fn write_char(&mut self, c: char) -> fmt::Result {
-        self.output.push(c);
-        Ok(())
-    }
-}
-
-impl PartsWrite for PartsCollector {
-    type SubPartsWrite = PartsCollector;
-
-    fn with_part(
-        &mut self,
-        part: WriteablePart,
-        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
-    ) -> fmt::Result {
-        let start = self.output.len();
-        f(self)?;
-        let end = self.output.len();
-        if start < end {
-            self.parts.push(CollectedPart { start, end, part });
-        }
-        Ok(())
-    }
-}
-
 pub(crate) fn load(env: Env) -> bool {
     env.register::<ListFormatterResource>().is_ok()
 }
@@ -108,46 +100,95 @@ pub(crate) fn list_formatter_new<'a>(
     locale_term: Term<'a>,
     options_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
-    let locale_resource: ResourceArc<LocaleResource> = match locale_term.decode() {
-        Ok(resource) => resource,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
-    };
+    stats::measure(Counted::ListFormatterNew, || {
+        list_formatter_new_inner(env, locale_term, options_term)
+    })
+}
 
-    let config = match decode_formatter_config(options_term) {
-        Ok(config) => config,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+fn list_formatter_new_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
     };
 
-    let options = ListFormatterOptions::default().with_length(config.length);
+    match ListFormatterResource::try_new(locale_arg.0, options_term) {
+        Ok(resource) => Ok((atoms::ok(), ResourceArc::new(resource)).encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
 
-    let formatter = match config.list_type {
-        ListType::And => ListFormatter::try_new_and(locale_resource.0.clone().into(), options),
-        ListType::Or => ListFormatter::try_new_or(locale_resource.0.clone().into(), options),
-        ListType::Unit => ListFormatter::try_new_unit(locale_resource.0.clone().into(), options),
-    };
+#[rustler::nif]
+pub(crate) fn list_format<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    items_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::ListFormat, || {
+        format_items(env, formatter_term, items_term, false)
+    })
+}
 
-    let formatter = match formatter {
-        Ok(formatter) => formatter,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
-    };
+/// Same as `list_format`, but runs on a dirty CPU scheduler. The Elixir side
+/// routes large inputs here so they cannot stall a normal scheduler.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub(crate) fn list_format_dirty<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    items_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::ListFormat, || {
+        format_items(env, formatter_term, items_term, false)
+    })
+}
 
-    let resource = ListFormatterResource { formatter };
+/// Formats the list as an iolist whose elements are the caller's own item
+/// binaries, so only the literals between them are allocated.
+#[rustler::nif]
+pub(crate) fn list_format_iodata<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    items_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::ListFormat, || {
+        format_items(env, formatter_term, items_term, true)
+    })
+}
 
-    Ok((atoms::ok(), ResourceArc::new(resource)).encode(env))
+/// Same as `list_format_iodata`, but runs on a dirty CPU scheduler.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub(crate) fn list_format_iodata_dirty<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    items_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::ListFormat, || {
+        format_items(env, formatter_term, items_term, true)
+    })
 }
 
-#[rustler::nif]
-pub(crate) fn list_format<'a>(
+fn format_items<'a>(
     env: Env<'a>,
     formatter_term: Term<'a>,
     items_term: Term<'a>,
+    iodata: bool,
 ) -> NifResult<Term<'a>> {
     let formatter_resource: ResourceArc<ListFormatterResource> = match formatter_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
     };
 
-    let items: Vec<String> = match items_term.decode() {
+    let item_terms: Vec<Term<'a>> = match items_term.decode() {
+        Ok(items) => items,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_items()).encode(env)),
+    };
+
+    // Items borrow the binaries' bytes for the duration of the call rather
+    // than being copied into owned strings.
+    let items: Vec<&str> = match item_terms.iter().map(|term| term.decode()).collect() {
         Ok(items) => items,
         Err(_) => return Ok((atoms::error(), atoms::invalid_items()).encode(env)),
     };
@@ -156,11 +197,49 @@ pub(crate) fn list_format<'a>(
         return Ok((atoms::error(), atoms::invalid_items()).encode(env));
     }
 
-    let iter = items.iter().map(|value| value.as_str());
+    let iter = items.iter().copied();
     let formatted = formatter_resource.formatter.format(iter);
-    let output = formatted.write_to_string().into_owned();
 
-    Ok((atoms::ok(), output).encode(env))
+    if !iodata {
+        let output = formatted.write_to_string().into_owned();
+        return Ok((atoms::ok(), output).encode(env));
+    }
+
+    let mut collector = PartsCollector::new();
+    if formatted.write_to_parts(&mut collector).is_err() {
+        return Ok((atoms::error(), atoms::invalid_items()).encode(env));
+    }
+
+    let (output, collected_parts) = collector.finish();
+    let mut iolist: Vec<Term<'a>> = Vec::with_capacity(collected_parts.len());
+    // Empty items write nothing and so produce no element part.
+    let mut element_terms = item_terms
+        .iter()
+        .zip(&items)
+        .filter(|(_, item)| !item.is_empty())
+        .map(|(term, _)| *term);
+    let mut cursor = 0;
+
+    for collected in collected_parts {
+        if collected.start > cursor {
+            iolist.push(output[cursor..collected.start].encode(env));
+        }
+        let element = if collected.part == parts::ELEMENT {
+            element_terms.next()
+        } else {
+            None
+        };
+        match element {
+            Some(term) => iolist.push(term),
+            None => iolist.push(output[collected.start..collected.end].encode(env)),
+        }
+        cursor = collected.end;
+    }
+    if cursor < output.len() {
+        iolist.push(output[cursor..].encode(env));
+    }
+
+    Ok((atoms::ok(), iolist).encode(env))
 }
 
 #[rustler::nif]
@@ -168,13 +247,38 @@ pub(crate) fn list_format_to_parts<'a>(
     env: Env<'a>,
     formatter_term: Term<'a>,
     items_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::ListFormatToParts, || {
+        format_items_to_parts(env, formatter_term, items_term, index_mode_term)
+    })
+}
+
+/// Same as `list_format_to_parts`, but runs on a dirty CPU scheduler.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub(crate) fn list_format_to_parts_dirty<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    items_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::ListFormatToParts, || {
+        format_items_to_parts(env, formatter_term, items_term, index_mode_term)
+    })
+}
+
+fn format_items_to_parts<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    items_term: Term<'a>,
+    index_mode_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
     let formatter_resource: ResourceArc<ListFormatterResource> = match formatter_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
     };
 
-    let items: Vec<String> = match items_term.decode() {
+    let items: Vec<&str> = match items_term.decode() {
         Ok(items) => items,
         Err(_) => return Ok((atoms::error(), atoms::invalid_items()).encode(env)),
     };
@@ -183,7 +287,12 @@ pub(crate) fn list_format_to_parts<'a>(
         return Ok((atoms::error(), atoms::invalid_items()).encode(env));
     }
 
-    let iter = items.iter().map(|value| value.as_str());
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let iter = items.iter().copied();
     let formatted = formatter_resource.formatter.format(iter);
 
     let mut collector = PartsCollector::new();
@@ -191,40 +300,28 @@ pub(crate) fn list_format_to_parts<'a>(
         return Ok((atoms::error(), atoms::invalid_items()).encode(env));
     }
 
-    let (output, collected_parts) = collector.into_parts();
+    let (output, collected_parts) = collector.finish();
     let mut parts = Vec::with_capacity(collected_parts.len());
 
     for collected in collected_parts {
-        if let Some(atom) = part_atom(collected.part) {
-            if let Some(slice) = output.get(collected.start..collected.end) {
-                parts.push(ListFormatPart {
-                    part_type: atom,
-                    value: slice.to_string(),
-                });
-            }
+        if let Some(slice) = output.get(collected.range()) {
+            let part = ListFormatPart {
+                part_type: PartType::from(collected.part),
+                value: slice.to_string(),
+            };
+            parts.push((part, collected.range()));
         }
     }
 
+    let parts = index::encode_parts(env, &output, parts, index_mode);
     Ok((atoms::ok(), parts).encode(env))
 }
 
 fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
-    if term.get_type() != TermType::Map {
-        if let Ok(atom_name) = term.atom_to_string() {
-            if atom_name == "nil" {
-                return Ok(FormatterConfig::default());
-            }
-        }
-        return Err(());
-    }
-
     let mut config = FormatterConfig::default();
-    let mut iter = MapIterator::new(term).ok_or(())?;
-
-    while let Some((key_term, value_term)) = iter.next() {
-        let key = key_term.atom_to_string().map_err(|_| ())?;
 
-        if key == "type" {
+    options::decode_map(term, UnknownKeys::Error, |key, value_term| {
+        if key == atoms::list_type() {
             let value: Atom = value_term.decode().map_err(|_| ())?;
             config.list_type = if value == atoms::and() {
                 ListType::And
@@ -235,7 +332,7 @@ fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
             } else {
                 return Err(());
             };
-        } else if key == "width" {
+        } else if key == atoms::width() {
             let value: Atom = value_term.decode().map_err(|_| ())?;
             config.length = if value == atoms::wide() {
                 ListLength::Wide
@@ -246,32 +343,13 @@ fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
             } else {
                 return Err(());
             };
-        } else if key == "locale" {
+        } else if key == atoms::locale() {
             // Locale is handled on the Elixir side and should not be forwarded to the NIF.
-            continue;
         } else {
-            // Ignore unknown keys so long as they are nil.
-            if value_term.get_type() != TermType::Atom {
-                return Err(());
-            }
-            if let Ok(atom_name) = value_term.atom_to_string() {
-                if atom_name == "nil" {
-                    continue;
-                }
-            }
-            return Err(());
+            return Ok(false);
         }
-    }
+        Ok(true)
+    })?;
 
     Ok(config)
 }
-
-fn part_atom(part: WriteablePart) -> Option<Atom> {
-    if part == parts::ELEMENT {
-        Some(atoms::element())
-    } else if part == parts::LITERAL {
-        Some(atoms::literal())
-    } else {
-        None
-    }
-}
@@ -1,17 +1,126 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::OnceLock;
 
+use icu::datetime::fieldsets::builder::FieldSetBuilder;
+use icu::datetime::options::TimePrecision;
+use icu::datetime::unchecked::DateTimeInputUnchecked;
+use icu::datetime::DateTimeFormatter;
+use icu::experimental::displaynames::provider::RegionDisplayNamesV1;
+use icu::experimental::displaynames::{DisplayNamesOptions, Fallback, RegionDisplayNames};
+use icu::experimental::provider::Baked as ExperimentalBaked;
 use icu::locale::extensions::unicode::{key, value};
 use icu::locale::fallback::LocaleFallbackConfig;
-use icu::locale::{subtags::Language, LocaleExpander};
-use icu::locale::{Locale, LocaleFallbacker};
-use rustler::{Atom, Encoder, Env, NifResult, NifStruct, ResourceArc, Term};
+use icu::locale::provider::{Baked as LocaleBaked, LocaleParentsV1, Parents};
+use icu::locale::subtags::{Language, Region, Script, Variants};
+use icu::locale::{locale, LanguageIdentifier, Locale, LocaleExpander, LocaleFallbacker};
+use icu_provider::prelude::*;
+use rustler::{
+    Atom, Decoder, Encoder, Env, NifResult, NifStruct, NifTaggedEnum, ResourceArc, Term,
+};
 
 use crate::atoms;
+use crate::keywords;
+use crate::number;
+use crate::options::{self, UnknownKeys};
+use crate::stats::{self, Counted};
 
 pub(crate) struct LocaleResource(pub Locale);
 
 impl rustler::Resource for LocaleResource {}
 
+impl LocaleResource {
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        std::mem::size_of::<Self>() + heap_size(&self.0)
+    }
+}
+
+/// Approximates the heap a locale owns beyond its inline size. Subtags are
+/// stored inline, but extensions and any variants past the first are boxed.
+pub(crate) fn heap_size(locale: &Locale) -> usize {
+    if locale.extensions.is_empty() && locale.id.variants.len() <= 1 {
+        0
+    } else {
+        locale.to_string().len()
+    }
+}
+
+static EXPANDER: OnceLock<LocaleExpander> = OnceLock::new();
+static PARENTS: OnceLock<Option<DataPayload<LocaleParentsV1>>> = OnceLock::new();
+static TERRITORIES: OnceLock<Vec<Region>> = OnceLock::new();
+
+/// The shared likely-subtags expander, built on first use.
+pub(crate) fn expander() -> &'static LocaleExpander {
+    EXPANDER.get_or_init(LocaleExpander::new_common)
+}
+
+/// Builds the shared locale data now instead of on first use: the
+/// likely-subtags expander, the parent locales and the territory list. Also
+/// walks the fallback chain of each locale so the fallback data is paged in.
+pub(crate) fn warm_up(locales: &[Locale]) {
+    expander();
+    parents();
+    territories();
+
+    let fallbacker = LocaleFallbacker::new().for_config(LocaleFallbackConfig::default());
+    for locale in locales {
+        let mut fallback_iterator = fallbacker.fallback_for(locale.clone().into());
+        while !fallback_iterator.get().is_unknown() {
+            fallback_iterator.step();
+        }
+    }
+}
+
+/// Whether `warm_up` has built every shared locale datum.
+#[cfg(test)]
+pub(crate) fn warmed_up() -> bool {
+    EXPANDER.get().is_some() && PARENTS.get().is_some() && TERRITORIES.get().is_some()
+}
+
+/// A locale argument given either as a `LocaleResource` or as a BCP-47
+/// string. Strings are parsed inline, which saves callers the separate
+/// `locale_from_string` call.
+pub(crate) struct LocaleArg(pub Locale);
+
+impl<'a> Decoder<'a> for LocaleArg {
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        if let Ok(resource) = term.decode::<ResourceArc<LocaleResource>>() {
+            return Ok(LocaleArg(resource.0.clone()));
+        }
+
+        let locale_string: &str = term.decode()?;
+        locale_string
+            .parse::<Locale>()
+            .map(LocaleArg)
+            .map_err(|_| rustler::Error::BadArg)
+    }
+}
+
+/// Returns the locale whose data `provider` serves for `locale` once locale
+/// fallback has run, probing the marker `M`. The compiled providers only
+/// report a resolved locale when they had to fall back, so an exact match
+/// resolves to the requested locale itself.
+pub(crate) fn resolve_data_locale<M, P>(
+    provider: &P,
+    locale: &Locale,
+    attributes: &DataMarkerAttributes,
+) -> Option<DataLocale>
+where
+    M: DataMarker,
+    P: DataProvider<M>,
+{
+    let requested = M::make_locale(locale.into());
+    let response = provider
+        .load(DataRequest {
+            id: DataIdentifierBorrowed::for_marker_attributes_and_locale(attributes, &requested),
+            ..Default::default()
+        })
+        .ok()?;
+    Some(response.metadata.locale.unwrap_or(requested))
+}
+
 #[derive(NifStruct)]
 #[module = "Icu.LanguageTag.Components"]
 struct LanguageTagComponents {
@@ -27,6 +136,12 @@ pub(crate) fn load(env: Env) -> bool {
 
 #[rustler::nif]
 pub(crate) fn locale_from_string<'a>(env: Env<'a>, locale_string: String) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleFromString, || {
+        locale_from_string_inner(env, locale_string)
+    })
+}
+
+fn locale_from_string_inner<'a>(env: Env<'a>, locale_string: String) -> NifResult<Term<'a>> {
     match locale_string.parse::<Locale>() {
         Ok(locale) => {
             let resource = ResourceArc::new(LocaleResource(locale));
@@ -38,6 +153,12 @@ pub(crate) fn locale_from_string<'a>(env: Env<'a>, locale_string: String) -> Nif
 
 #[rustler::nif]
 pub(crate) fn locale_to_string<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleToString, || {
+        locale_to_string_inner(env, resource_term)
+    })
+}
+
+fn locale_to_string_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
@@ -52,6 +173,12 @@ pub(crate) fn locale_get_components<'a>(
     env: Env<'a>,
     resource_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleGetComponents, || {
+        locale_get_components_inner(env, resource_term)
+    })
+}
+
+fn locale_get_components_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
@@ -85,12 +212,18 @@ pub(crate) fn locale_get_components<'a>(
 
 #[rustler::nif]
 pub(crate) fn locale_maximize<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleMaximize, || {
+        locale_maximize_inner(env, resource_term)
+    })
+}
+
+fn locale_maximize_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
     };
 
-    let lc = LocaleExpander::new_common();
+    let lc = expander();
 
     let mut locale = resource.0.clone();
     match lc.maximize(&mut locale.id) {
@@ -103,12 +236,18 @@ pub(crate) fn locale_maximize<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifR
 
 #[rustler::nif]
 pub(crate) fn locale_minimize<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleMinimize, || {
+        locale_minimize_inner(env, resource_term)
+    })
+}
+
+fn locale_minimize_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
     };
 
-    let lc = LocaleExpander::new_common();
+    let lc = expander();
 
     let mut locale = resource.0.clone();
     match lc.minimize(&mut locale.id) {
@@ -123,13 +262,22 @@ pub(crate) fn locale_minimize<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifR
 pub(crate) fn locale_minimize_favor_script<'a>(
     env: Env<'a>,
     resource_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleMinimizeFavorScript, || {
+        locale_minimize_favor_script_inner(env, resource_term)
+    })
+}
+
+fn locale_minimize_favor_script_inner<'a>(
+    env: Env<'a>,
+    resource_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
     };
 
-    let lc = LocaleExpander::new_common();
+    let lc = expander();
 
     let mut locale = resource.0.clone();
     match lc.minimize_favor_script(&mut locale.id) {
@@ -142,6 +290,12 @@ pub(crate) fn locale_minimize_favor_script<'a>(
 
 #[rustler::nif]
 pub(crate) fn locale_fallbacks<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleFallbacks, || {
+        locale_fallbacks_inner(env, resource_term)
+    })
+}
+
+fn locale_fallbacks_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
@@ -166,46 +320,615 @@ pub(crate) fn locale_fallbacks<'a>(env: Env<'a>, resource_term: Term<'a>) -> Nif
     Ok((atoms::ok(), fallbacks).encode(env))
 }
 
+/// The CLDR `parentLocales` data, loaded on first use.
+fn parents() -> Option<&'static Parents<'static>> {
+    PARENTS
+        .get_or_init(|| {
+            LocaleBaked
+                .load(DataRequest::default())
+                .ok()
+                .map(|response| response.payload)
+        })
+        .as_ref()
+        .map(|payload| payload.get())
+}
+
+/// The parent of `id` in CLDR inheritance: the `parentLocales` entry when
+/// there is one, and otherwise `id` with its last subtag removed.
+fn cldr_parent(mut id: LanguageIdentifier) -> LanguageIdentifier {
+    let explicit = parents().and_then(|parents| {
+        parents
+            .parents
+            .get_copied_by(|child| id.strict_cmp(child).reverse())
+    });
+    if let Some((language, script, region)) = explicit {
+        let mut parent = LanguageIdentifier::UNKNOWN;
+        parent.language = language;
+        parent.script = script;
+        parent.region = region;
+        return parent;
+    }
+
+    if !id.variants.is_empty() {
+        id.variants = Variants::new();
+    } else if id.region.is_some() {
+        id.region = None;
+    } else if id.script.is_some() {
+        id.script = None;
+    } else {
+        id.language = Language::UNKNOWN;
+    }
+    id
+}
+
+/// Returns the CLDR inheritance chain of a locale, starting with the locale
+/// itself without its extensions and ending before the root locale. Unlike
+/// `locale_fallbacks`, the chain follows `parentLocales` and truncation
+/// only, without adding likely subtags.
+#[rustler::nif]
+pub(crate) fn locale_parents<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleParents, || {
+        locale_parents_inner(env, resource_term)
+    })
+}
+
+fn locale_parents_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let mut chain = vec![];
+    let mut id = resource.0.id.clone();
+    while id != LanguageIdentifier::UNKNOWN {
+        chain.push(ResourceArc::new(LocaleResource(Locale::from(id.clone()))));
+        id = cldr_parent(id);
+    }
+
+    Ok((atoms::ok(), chain).encode(env))
+}
+
+/// Pairs of closely related languages and their distance. All other
+/// differing languages are 80 apart.
+const CLOSE_LANGUAGES: [(&str, &str, u16); 7] = [
+    ("nb", "no", 1),
+    ("nn", "nb", 10),
+    ("nn", "no", 10),
+    ("bs", "hr", 4),
+    ("sh", "bs", 4),
+    ("sh", "hr", 4),
+    ("sh", "sr", 4),
+];
+
+fn language_distance(desired: Language, supported: Language) -> u16 {
+    if desired == supported {
+        return 0;
+    }
+
+    CLOSE_LANGUAGES
+        .iter()
+        .find(|(a, b, _)| {
+            (desired.as_str(), supported.as_str()) == (*a, *b)
+                || (desired.as_str(), supported.as_str()) == (*b, *a)
+        })
+        .map_or(80, |(_, _, distance)| *distance)
+}
+
+/// The distance between two different regions of the same language and
+/// script. CLDR parent locales group some regions of a language, like `es-MX`
+/// and `es-AR` under `es-419`: regions in the same group are 4 apart and
+/// regions in different groups 5. Regions of languages without groups are 4
+/// apart.
+fn region_distance(desired: &LanguageIdentifier, supported: &LanguageIdentifier) -> u16 {
+    // A region's group is the region of its CLDR parent, if it has one.
+    let group = |id: &LanguageIdentifier| {
+        let mut id = id.clone();
+        id.script = None;
+        let region = id.region;
+        match cldr_parent(id).region {
+            Some(parent) => (Some(parent), true),
+            None => (region, false),
+        }
+    };
+
+    let (desired_group, desired_grouped) = group(desired);
+    let (supported_group, supported_grouped) = group(supported);
+    if desired_group != supported_group && (desired_grouped || supported_grouped) {
+        5
+    } else {
+        4
+    }
+}
+
+/// A simplified locale distance: 0 for the same locale, 4 or 5 for a
+/// different region, 50 for a different script and 80 for a different
+/// language. Both locales are maximized first, so `en` and `en-Latn-US` are
+/// 0 apart. Unlike CLDR language matching, no pair of scripts or regions is
+/// closer than the rest.
+fn distance(desired: &LanguageIdentifier, supported: &LanguageIdentifier) -> u16 {
+    let mut desired = desired.clone();
+    let mut supported = supported.clone();
+    expander().maximize(&mut desired);
+    expander().maximize(&mut supported);
+
+    let language = language_distance(desired.language, supported.language);
+    if language >= 80 {
+        return language;
+    }
+
+    let script = if desired.script == supported.script {
+        0
+    } else {
+        50
+    };
+    let region = if desired.region == supported.region {
+        0
+    } else {
+        region_distance(&desired, &supported)
+    };
+    language + script + region
+}
+
+/// Returns the distance from a desired locale to a supported one. See
+/// `distance` for the scale.
+#[rustler::nif]
+pub(crate) fn locale_distance<'a>(
+    env: Env<'a>,
+    desired_term: Term<'a>,
+    supported_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleDistance, || {
+        locale_distance_inner(env, desired_term, supported_term)
+    })
+}
+
+fn locale_distance_inner<'a>(
+    env: Env<'a>,
+    desired_term: Term<'a>,
+    supported_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let (desired, supported): (LocaleArg, LocaleArg) =
+        match (desired_term.decode(), supported_term.decode()) {
+            (Ok(desired), Ok(supported)) => (desired, supported),
+            _ => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        };
+
+    Ok((atoms::ok(), distance(&desired.0.id, &supported.0.id)).encode(env))
+}
+
+/// Orders two locales by their canonical BCP-47 form, returning `:lt`, `:eq`
+/// or `:gt`. Locales are canonicalized when parsed, so `"EN-us"` and
+/// `"en-US"` compare equal.
+#[rustler::nif]
+pub(crate) fn locale_compare<'a>(
+    env: Env<'a>,
+    left_term: Term<'a>,
+    right_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleCompare, || {
+        locale_compare_inner(env, left_term, right_term)
+    })
+}
+
+fn locale_compare_inner<'a>(
+    env: Env<'a>,
+    left_term: Term<'a>,
+    right_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let (left, right): (ResourceArc<LocaleResource>, ResourceArc<LocaleResource>) =
+        match (left_term.decode(), right_term.decode()) {
+            (Ok(left), Ok(right)) => (left, right),
+            _ => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+        };
+
+    let order = match left.0.total_cmp(&right.0) {
+        Ordering::Less => atoms::lt(),
+        Ordering::Equal => atoms::eq(),
+        Ordering::Greater => atoms::gt(),
+    };
+    Ok((atoms::ok(), order).encode(env))
+}
+
+/// Hashes the canonical form of a locale, so that locales comparing equal
+/// hash the same. The value is stable for a given build of the NIF only.
+#[rustler::nif]
+pub(crate) fn locale_hash<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleHash, || {
+        locale_hash_inner(env, resource_term)
+    })
+}
+
+fn locale_hash_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    resource.0.hash(&mut hasher);
+    Ok((atoms::ok(), hasher.finish()).encode(env))
+}
+
+/// Region codes that do not name a country or territory a user would pick:
+/// groupings such as the EU or the UN, the unknown region and the
+/// pseudo-locale codes.
+const NON_TERRITORY_REGIONS: [&str; 7] = ["EU", "EZ", "QO", "UN", "XA", "XB", "ZZ"];
+
+/// The assigned two-letter region codes, in alphabetical order. A code
+/// counts as assigned when CLDR has an English name for it.
+fn territories() -> &'static [Region] {
+    TERRITORIES.get_or_init(|| {
+        let mut options = DisplayNamesOptions::default();
+        options.fallback = Fallback::None;
+        let Ok(names) = RegionDisplayNames::try_new(locale!("en").into(), options) else {
+            return Vec::new();
+        };
+
+        (b'A'..=b'Z')
+            .flat_map(|first| (b'A'..=b'Z').map(move |second| [first, second]))
+            .filter_map(|code| Region::try_from_utf8(&code).ok())
+            .filter(|region| !NON_TERRITORY_REGIONS.contains(&region.as_str()))
+            .filter(|region| names.of(*region).is_some())
+            .collect()
+    })
+}
+
+/// Whether CLDR has data of its own for `locale` rather than only what it
+/// inherits from its parent, probed through the number symbols and the
+/// region names.
+fn has_own_data(locale: &Locale) -> bool {
+    let region_names = resolve_data_locale::<RegionDisplayNamesV1, _>(
+        &ExperimentalBaked,
+        locale,
+        DataMarkerAttributes::empty(),
+    );
+    [number::data_locale(locale), region_names]
+        .into_iter()
+        .flatten()
+        .any(|data_locale| data_locale.region == locale.id.region)
+}
+
+/// The regions `language` is used in: those whose likely language it is,
+/// its own likely region and those CLDR has data for in the language.
+fn language_regions(language: Language) -> Vec<Region> {
+    if language == Language::UNKNOWN {
+        return Vec::new();
+    }
+
+    let mut regions: Vec<Region> = territories()
+        .iter()
+        .copied()
+        .filter(|&region| {
+            let mut id = LanguageIdentifier::UNKNOWN;
+            id.region = Some(region);
+            expander().maximize(&mut id);
+            if id.language == language {
+                return true;
+            }
+
+            let mut locale = Locale::UNKNOWN;
+            locale.id.language = language;
+            locale.id.region = Some(region);
+            has_own_data(&locale)
+        })
+        .collect();
+
+    let mut id = LanguageIdentifier::UNKNOWN;
+    id.language = language;
+    expander().maximize(&mut id);
+    if let Some(region) = id.region.filter(|region| !regions.contains(region)) {
+        regions.push(region);
+        regions.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+    }
+
+    regions
+}
+
+/// The scripts `language` is written in, as the likely scripts of the
+/// language on its own and in each of its regions.
+fn language_scripts(language: Language, regions: &[Region]) -> Vec<Script> {
+    if language == Language::UNKNOWN {
+        return Vec::new();
+    }
+
+    let mut scripts: Vec<Script> = std::iter::once(None)
+        .chain(regions.iter().copied().map(Some))
+        .filter_map(|region| {
+            let mut id = LanguageIdentifier::UNKNOWN;
+            id.language = language;
+            id.region = region;
+            expander().maximize(&mut id);
+            id.script
+        })
+        .collect();
+
+    scripts.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+    scripts.dedup();
+    scripts
+}
+
+/// Returns the region codes a locale picker can offer for the language of
+/// `locale`, e.g. `["AR", "BO", ...]` for `"es"`. Other subtags are ignored.
+#[rustler::nif]
+pub(crate) fn locale_language_regions<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleLanguageRegions, || {
+        locale_language_regions_inner(env, locale_term)
+    })
+}
+
+fn locale_language_regions_inner<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let regions: Vec<&str> = language_regions(locale_arg.0.id.language)
+        .iter()
+        .map(|region| region.as_str())
+        .collect();
+    Ok((atoms::ok(), regions).encode(env))
+}
+
+/// Returns the script codes the language of `locale` is written in, e.g.
+/// `["Hans", "Hant"]` for `"zh"`. Other subtags are ignored.
+#[rustler::nif]
+pub(crate) fn locale_language_scripts<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleLanguageScripts, || {
+        locale_language_scripts_inner(env, locale_term)
+    })
+}
+
+fn locale_language_scripts_inner<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let language = locale_arg.0.id.language;
+    let scripts: Vec<&str> = language_scripts(language, &language_regions(language))
+        .iter()
+        .map(|script| script.as_str())
+        .collect();
+    Ok((atoms::ok(), scripts).encode(env))
+}
+
+/// The form in which `locale_match_gettext` returns the matched locale.
+#[derive(NifTaggedEnum, Clone, Copy, Default)]
+enum GettextOutput {
+    /// The available locale exactly as the caller gave it.
+    #[default]
+    Original,
+    /// The canonical BCP-47 form of the available locale.
+    Canonical,
+}
+
+#[derive(Default)]
+struct GettextMatchOptions {
+    case_insensitive: bool,
+    strip_script: bool,
+    output: GettextOutput,
+}
+
+impl GettextMatchOptions {
+    fn decode(term: Term) -> Result<Self, ()> {
+        let mut match_options = Self::default();
+        options::decode_map(term, UnknownKeys::Error, |key, value_term| {
+            if key == atoms::case_insensitive() {
+                match_options.case_insensitive = value_term.decode().map_err(|_| ())?;
+            } else if key == atoms::strip_script() {
+                match_options.strip_script = value_term.decode().map_err(|_| ())?;
+            } else if key == atoms::output() {
+                match_options.output = value_term.decode().map_err(|_| ())?;
+            } else {
+                return Ok(false);
+            }
+            Ok(true)
+        })?;
+        Ok(match_options)
+    }
+
+    /// The form of `tag` that is compared: hyphen-separated, without the
+    /// script when stripping scripts and lowercased when ignoring case.
+    fn match_key(&self, tag: &str) -> String {
+        let mut key = tag.replace('_', "-");
+        if self.strip_script {
+            if let Ok(mut id) = key.parse::<LanguageIdentifier>() {
+                id.script = None;
+                key = id.to_string();
+            }
+        }
+        if self.case_insensitive {
+            key.make_ascii_lowercase();
+        }
+        key
+    }
+}
+
+/// Matches a locale against gettext locale names such as `"pt_BR"`.
+///
+/// Walks the fallback chain of the locale and returns the first available
+/// locale it reaches, together with its distance: the number of fallback
+/// steps taken, 0 for an exact match.
 #[rustler::nif]
 pub(crate) fn locale_match_gettext<'a>(
     env: Env<'a>,
     resource_term: Term<'a>,
     available: Vec<String>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleMatchGettext, || {
+        locale_match_gettext_inner(env, resource_term, available, options_term)
+    })
+}
+
+fn locale_match_gettext_inner<'a>(
+    env: Env<'a>,
+    resource_term: Term<'a>,
+    available: Vec<String>,
+    options_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
     };
+    let match_options = match GettextMatchOptions::decode(options_term) {
+        Ok(match_options) => match_options,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
 
     let fallbacker = LocaleFallbacker::new();
     let config = LocaleFallbackConfig::default();
 
-    let available_norm: HashMap<String, &str> = available
-        .iter()
-        .map(|v| (v.replace("_", "-"), &**v))
-        .collect();
+    // The first of several available locales with the same key wins.
+    let mut available_norm: HashMap<String, &str> = HashMap::new();
+    for tag in &available {
+        available_norm
+            .entry(match_options.match_key(tag))
+            .or_insert(tag);
+    }
 
     let mut fallback_iterator = fallbacker
         .for_config(config)
         .fallback_for(resource.0.clone().into());
 
+    let mut distance: u32 = 0;
     while !fallback_iterator.get().is_unknown() {
-        let data_locale = fallback_iterator.get().clone();
-        let locale_string = data_locale.to_string();
-        if let Some(input) = available_norm.get(&locale_string) {
-            return Ok((atoms::ok(), input).encode(env));
+        let locale_string = fallback_iterator.get().to_string();
+        if let Some(&input) = available_norm.get(&match_options.match_key(&locale_string)) {
+            let matched = match match_options.output {
+                GettextOutput::Original => input.to_string(),
+                GettextOutput::Canonical => input
+                    .replace('_', "-")
+                    .parse::<Locale>()
+                    .map_or_else(|_| input.to_string(), |locale| locale.to_string()),
+            };
+            return Ok((atoms::ok(), matched, distance).encode(env));
         }
         fallback_iterator.step();
+        distance += 1;
     }
 
     Ok((atoms::error(), atoms::no_match()).encode(env))
 }
 
+/// The locale matching algorithm of `Intl.supportedLocalesOf`.
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum LocaleMatcher {
+    Lookup,
+    BestFit,
+}
+
+/// RFC 4647 lookup: removes subtags from the end of `id` until it is in
+/// `available`, dropping a singleton left at the end along with its subtag.
+fn lookup_match(id: &LanguageIdentifier, available: &HashSet<String>) -> bool {
+    let mut candidate = id.to_string();
+    loop {
+        if available.contains(&candidate) {
+            return true;
+        }
+
+        let Some(mut end) = candidate.rfind('-') else {
+            return false;
+        };
+        if end >= 2 && candidate.as_bytes()[end - 2] == b'-' {
+            end -= 2;
+        }
+        candidate.truncate(end);
+    }
+}
+
+/// Walks the locale fallback chain of `id`, which also tries the likely
+/// script and the CLDR parent locales, e.g. `"zh-Hant"` for `"zh-TW"`.
+fn fallback_match(id: &LanguageIdentifier, available: &HashSet<String>) -> bool {
+    let fallbacker = LocaleFallbacker::new();
+    let mut fallback_iterator = fallbacker
+        .for_config(LocaleFallbackConfig::default())
+        .fallback_for(id.into());
+
+    while !fallback_iterator.get().is_unknown() {
+        if available.contains(&fallback_iterator.get().to_string()) {
+            return true;
+        }
+        fallback_iterator.step();
+    }
+    false
+}
+
+/// Returns the canonical forms of the `requested` locales that one of the
+/// `available` locales can serve, without duplicates and in request order,
+/// like `Intl.supportedLocalesOf`. Extensions are kept in the result but do
+/// not take part in matching.
+#[rustler::nif]
+pub(crate) fn locale_filter_supported<'a>(
+    env: Env<'a>,
+    requested_term: Term<'a>,
+    available_term: Term<'a>,
+    matcher_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleFilterSupported, || {
+        locale_filter_supported_inner(env, requested_term, available_term, matcher_term)
+    })
+}
+
+fn locale_filter_supported_inner<'a>(
+    env: Env<'a>,
+    requested_term: Term<'a>,
+    available_term: Term<'a>,
+    matcher_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let (requested, available): (Vec<LocaleArg>, Vec<LocaleArg>) =
+        match (requested_term.decode(), available_term.decode()) {
+            (Ok(requested), Ok(available)) => (requested, available),
+            _ => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        };
+    let matcher: LocaleMatcher = match matcher_term.decode() {
+        Ok(matcher) => matcher,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let available: HashSet<String> = available
+        .into_iter()
+        .map(|LocaleArg(locale)| locale.id.to_string())
+        .collect();
+
+    let mut supported: Vec<String> = vec![];
+    for LocaleArg(locale) in requested {
+        let matched = match matcher {
+            LocaleMatcher::Lookup => lookup_match(&locale.id, &available),
+            LocaleMatcher::BestFit => {
+                lookup_match(&locale.id, &available) || fallback_match(&locale.id, &available)
+            }
+        };
+        let locale_string = locale.to_string();
+        if matched && !supported.contains(&locale_string) {
+            supported.push(locale_string);
+        }
+    }
+
+    Ok((atoms::ok(), supported).encode(env))
+}
+
 #[rustler::nif]
 pub(crate) fn locale_set_hour_cycle<'a>(
     env: Env<'a>,
     resource_term: Term<'a>,
     hour_cycle: Atom,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleSetHourCycle, || {
+        locale_set_hour_cycle_inner(env, resource_term, hour_cycle)
+    })
+}
+
+fn locale_set_hour_cycle_inner<'a>(
+    env: Env<'a>,
+    resource_term: Term<'a>,
+    hour_cycle: Atom,
 ) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
@@ -228,11 +951,52 @@ pub(crate) fn locale_set_hour_cycle<'a>(
     Ok((atoms::ok(), ResourceArc::new(LocaleResource(locale))).encode(env))
 }
 
+/// Sets a `-u-` keyword after checking it against the CLDR keyword data.
+#[rustler::nif]
+pub(crate) fn locale_set_extension<'a>(
+    env: Env<'a>,
+    resource_term: Term<'a>,
+    key: &str,
+    value: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleSetExtension, || {
+        locale_set_extension_inner(env, resource_term, key, value)
+    })
+}
+
+fn locale_set_extension_inner<'a>(
+    env: Env<'a>,
+    resource_term: Term<'a>,
+    key: &str,
+    value: &str,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let (key, value) = match keywords::parse_keyword(key, value) {
+        Ok(keyword) => keyword,
+        Err(reason) => return Ok((atoms::error(), reason).encode(env)),
+    };
+
+    let mut locale = resource.0.clone();
+    locale.extensions.unicode.keywords.set(key, value);
+
+    Ok((atoms::ok(), ResourceArc::new(LocaleResource(locale))).encode(env))
+}
+
 #[rustler::nif]
 pub(crate) fn locale_get_hour_cycle<'a>(
     env: Env<'a>,
     resource_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleGetHourCycle, || {
+        locale_get_hour_cycle_inner(env, resource_term)
+    })
+}
+
+fn locale_get_hour_cycle_inner<'a>(env: Env<'a>, resource_term: Term<'a>) -> NifResult<Term<'a>> {
     let resource: ResourceArc<LocaleResource> = match resource_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
@@ -253,3 +1017,59 @@ pub(crate) fn locale_get_hour_cycle<'a>(
         None => Ok((atoms::ok(), atoms::nil()).encode(env)),
     }
 }
+
+/// Resolves the hour cycle the locale's time formats use, honouring a
+/// `-u-hc-` keyword. The hour symbol is read from the pattern the locale's
+/// hour-and-minute format resolves to.
+#[rustler::nif]
+pub(crate) fn locale_hour_cycle<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::LocaleHourCycle, || {
+        locale_hour_cycle_inner(env, locale_term)
+    })
+}
+
+fn locale_hour_cycle_inner<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let mut builder = FieldSetBuilder::new();
+    builder.time_precision = Some(TimePrecision::Minute);
+    let field_set = match builder.build_composite() {
+        Ok(field_set) => field_set,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let formatter = match DateTimeFormatter::try_new(locale_arg.0.into(), field_set) {
+        Ok(formatter) => formatter,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let pattern = formatter
+        .format_unchecked(DateTimeInputUnchecked::default())
+        .pattern()
+        .to_string();
+
+    match hour_symbol(&pattern) {
+        Some('h') => Ok((atoms::ok(), atoms::h12()).encode(env)),
+        Some('K') => Ok((atoms::ok(), atoms::h11()).encode(env)),
+        Some('k') => Ok((atoms::ok(), atoms::h24()).encode(env)),
+        Some(_) => Ok((atoms::ok(), atoms::h23()).encode(env)),
+        None => Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    }
+}
+
+/// Finds the first hour field symbol in a CLDR pattern, skipping quoted
+/// literals.
+fn hour_symbol(pattern: &str) -> Option<char> {
+    let mut quoted = false;
+    for c in pattern.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            'h' | 'H' | 'K' | 'k' if !quoted => return Some(c),
+            _ => {}
+        }
+    }
+    None
+}
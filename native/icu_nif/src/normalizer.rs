@@ -0,0 +1,104 @@
+//! Unicode normalization of strings as they are joined and compared.
+
+use icu::normalizer::{ComposingNormalizerBorrowed, DecomposingNormalizerBorrowed};
+use rustler::{Encoder, Env, NifResult, NifTaggedEnum, Term};
+
+use crate::atoms;
+use crate::stats::{self, Counted};
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum NormalizationForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+/// `NFC(left <> right)`, copying the text only once when the joined text
+/// is already normalized.
+#[rustler::nif]
+pub(crate) fn normalizer_nfc_concat<'a>(
+    env: Env<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NormalizerNfcConcat, || {
+        normalizer_nfc_concat_inner(env, left, right)
+    })
+}
+
+fn normalizer_nfc_concat_inner<'a>(env: Env<'a>, left: &str, right: &str) -> NifResult<Term<'a>> {
+    let nfc = ComposingNormalizerBorrowed::new_nfc();
+
+    let mut joined = String::with_capacity(left.len() + right.len());
+    joined.push_str(left);
+    joined.push_str(right);
+
+    // The prefix followed by the normalized rest is the normalized whole.
+    let (normalized, rest) = nfc.split_normalized(&joined);
+    if rest.is_empty() {
+        return Ok(joined.encode(env));
+    }
+
+    let mut output = String::with_capacity(joined.len());
+    output.push_str(normalized);
+    output.push_str(&nfc.normalize(rest));
+    Ok(output.encode(env))
+}
+
+/// Whether `left` and `right` have the same NFC form. Inputs that are
+/// already in NFC are compared as they are, without a normalized copy.
+#[rustler::nif]
+pub(crate) fn normalizer_canonical_equal<'a>(
+    env: Env<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NormalizerCanonicalEqual, || {
+        normalizer_canonical_equal_inner(env, left, right)
+    })
+}
+
+fn normalizer_canonical_equal_inner<'a>(
+    env: Env<'a>,
+    left: &str,
+    right: &str,
+) -> NifResult<Term<'a>> {
+    let nfc = ComposingNormalizerBorrowed::new_nfc();
+    let equal = left == right || nfc.normalize(left) == nfc.normalize(right);
+    Ok(equal.encode(env))
+}
+
+/// The length in bytes of the longest prefix of `text` known to be in
+/// `form`. Normalizing the rest and appending it to the prefix gives the
+/// normalization of the whole text, so the text is normalized when this is
+/// its length.
+#[rustler::nif]
+pub(crate) fn normalizer_normalized_up_to<'a>(
+    env: Env<'a>,
+    text: &str,
+    form_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NormalizerNormalizedUpTo, || {
+        normalizer_normalized_up_to_inner(env, text, form_term)
+    })
+}
+
+fn normalizer_normalized_up_to_inner<'a>(
+    env: Env<'a>,
+    text: &str,
+    form_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let form: NormalizationForm = match form_term.decode() {
+        Ok(form) => form,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let (normalized, _rest) = match form {
+        NormalizationForm::Nfc => ComposingNormalizerBorrowed::new_nfc().split_normalized(text),
+        NormalizationForm::Nfkc => ComposingNormalizerBorrowed::new_nfkc().split_normalized(text),
+        NormalizationForm::Nfd => DecomposingNormalizerBorrowed::new_nfd().split_normalized(text),
+        NormalizationForm::Nfkd => DecomposingNormalizerBorrowed::new_nfkd().split_normalized(text),
+    };
+    Ok((atoms::ok(), normalized.len()).encode(env))
+}
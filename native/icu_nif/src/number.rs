@@ -1,223 +1,1087 @@
 use std::convert::TryFrom;
-use std::fmt;
+use std::ops::Range;
 
 use fixed_decimal::Decimal as FixedDecimal;
-use fixed_decimal::{FloatPrecision, SignDisplay};
+use fixed_decimal::{FloatPrecision, Sign, SignDisplay};
 use icu::decimal::options::{DecimalFormatterOptions, GroupingStrategy};
+use icu::decimal::provider::{Baked as DecimalBaked, DecimalDigitsV1, DecimalSymbolsV1};
 use icu::decimal::{parts, DecimalFormatter};
+use icu::experimental::dimension::percent::formatter::PercentFormatter;
+use icu::experimental::dimension::percent::options::PercentFormatterOptions;
+use icu::experimental::dimension::provider::percent::PercentEssentialsV1;
+use icu::experimental::provider::Baked as ExperimentalBaked;
+use icu::locale::extensions::unicode::{key, Value};
+use icu::locale::Locale;
+use icu::plurals::{PluralCategory, PluralRules};
+use icu::properties::props::GeneralCategory;
+use icu::properties::CodePointMapData;
+use icu_provider::prelude::*;
 use rustler::types::map::MapIterator;
 use rustler::types::BigInt;
 use rustler::{Atom, Encoder, Env, NifMap, NifResult, ResourceArc, Term, TermType};
-use writeable::{Part as WriteablePart, PartsWrite, Writeable};
+use writeable::{Part as WriteablePart, Writeable};
 
 use crate::atoms;
-use crate::locale::LocaleResource;
+use crate::decimal;
+use crate::index;
+use crate::locale::{self, resolve_data_locale, LocaleArg};
+use crate::options::{self, OptionError, UnknownKeys};
+use crate::preferences;
+use crate::range::{self, RangeParts};
+use crate::stats::{self, Counted};
+use crate::writeable_parts::{CollectedPart, PartType, PartsCollector};
 
 pub(crate) struct NumberFormatterResource {
     formatter: DecimalFormatter,
     config: FormatterConfig,
+    locale: Locale,
 }
 
 impl rustler::Resource for NumberFormatterResource {}
 
+impl NumberFormatterResource {
+    /// Builds the formatter for `locale` from a formatter option map,
+    /// failing with the error to return.
+    pub(crate) fn try_new(mut locale: Locale, options_term: Term) -> Result<Self, OptionError> {
+        let config = decode_formatter_config(options_term).map_err(|_| atoms::invalid_options())?;
+        preferences::apply(&mut locale, options_term)?;
+
+        let mut formatter_options = DecimalFormatterOptions::default();
+        formatter_options.grouping_strategy = Some(config.grouping_strategy);
+
+        let formatter = DecimalFormatter::try_new(locale.clone().into(), formatter_options)
+            .map_err(|_| atoms::invalid_locale())?;
+
+        Ok(Self {
+            formatter,
+            config,
+            locale,
+        })
+    }
+
+    /// Formats a number argument the way `number_format` does.
+    pub(crate) fn format(&self, term: Term) -> Result<String, ()> {
+        let decimal = self.decimal(term)?;
+        Ok(self.approximated(self.formatter.format(&decimal).to_string()))
+    }
+
+    fn approximated(&self, output: String) -> String {
+        approximated(&self.locale, self.config.approximately, output)
+    }
+
+    /// Decodes a number argument, scales it and applies the formatter's
+    /// digit options.
+    fn decimal(&self, term: Term) -> Result<FixedDecimal, ()> {
+        self.signed_decimal(term).map(|(decimal, _)| decimal)
+    }
+
+    /// Like `decimal`, also returning the sign the number had before
+    /// `absolute_value` dropped it.
+    fn signed_decimal(&self, term: Term) -> Result<(FixedDecimal, Sign), ()> {
+        let mut decimal = term_to_decimal_with_precision(term, self.config.float_precision)?;
+        decimal.multiply_pow10(self.config.scale);
+        let sign = decimal.sign();
+        if self.config.absolute_value {
+            decimal.set_sign(Sign::None);
+        }
+        apply_config(&mut decimal, &self.config);
+        Ok((decimal, sign))
+    }
+
+    /// The empty `:minus_sign` part standing in for the sign of a negative
+    /// number formatted as its absolute value, so the sign can be rendered
+    /// apart from the digits.
+    fn suppressed_sign(&self, sign: Sign) -> Option<NumberFormatPart> {
+        (self.config.absolute_value && sign == Sign::Negative).then(|| NumberFormatPart {
+            part_type: atoms::minus_sign().into(),
+            value: String::new(),
+        })
+    }
+
+    /// The segments of the range from `start_term` to `end_term`, as
+    /// `range::segments` splits them, shared by `number_format_range` and
+    /// `number_format_range_to_parts`. The empty sign parts of
+    /// `absolute_value` are added after equal ends collapsed, so ends that
+    /// only differ in sign collapse too: the value of a collapsed range only
+    /// gets a sign part when both ends were negative.
+    fn range_segments(
+        &self,
+        start_term: Term,
+        end_term: Term,
+    ) -> Result<Vec<(Atom, PartType, String)>, ()> {
+        let (start, start_sign) = self.signed_decimal(start_term)?;
+        let (end, end_sign) = self.signed_decimal(end_term)?;
+
+        let end_parts = |decimal: &FixedDecimal| -> range::EndParts {
+            let (_, parts) = number_parts(&self.formatter, decimal);
+            parts
+                .into_iter()
+                .map(|(part, _)| (part.part_type, part.value))
+                .collect()
+        };
+        let mut segments = range::segments(&self.locale, end_parts(&start), end_parts(&end));
+
+        let sign_part = |source: Atom, sign: Sign| {
+            self.suppressed_sign(sign)
+                .map(|part| (source, part.part_type, part.value))
+        };
+        let collapsed = segments
+            .iter()
+            .all(|(source, _, _)| *source == atoms::shared());
+        if collapsed {
+            let sign = match (start_sign, end_sign) {
+                (Sign::Negative, Sign::Negative) => Sign::Negative,
+                _ => Sign::None,
+            };
+            if let Some(part) = sign_part(atoms::shared(), sign) {
+                segments.insert(0, part);
+            }
+        } else {
+            // The end first, so inserting the start's part does not move it.
+            for (source, sign) in [(atoms::range_end(), end_sign), (atoms::start(), start_sign)] {
+                let index = segments
+                    .iter()
+                    .position(|(segment_source, _, _)| *segment_source == source);
+                if let (Some(index), Some(part)) = (index, sign_part(source, sign)) {
+                    segments.insert(index, part);
+                }
+            }
+        }
+
+        Ok(segments)
+    }
+
+    /// The locale of the number symbols the formatter loaded.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        data_locale(&self.locale)
+    }
+
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        std::mem::size_of::<Self>() + locale::heap_size(&self.locale)
+    }
+}
+
+/// The locale of the number symbols loaded for `locale`, shared by the
+/// formatters built on top of `DecimalFormatter`.
+pub(crate) fn data_locale(locale: &Locale) -> Option<DataLocale> {
+    resolve_data_locale::<DecimalSymbolsV1, _>(&DecimalBaked, locale, DataMarkerAttributes::empty())
+}
+
+/// The CLDR approximately sign of the locale, written in front of estimated
+/// amounts. ICU4X loads it with the percent symbols.
+pub(crate) fn approximately_sign(locale: &Locale) -> String {
+    let requested = PercentEssentialsV1::make_locale(locale.into());
+    let response: Result<DataResponse<PercentEssentialsV1>, _> =
+        ExperimentalBaked.load(DataRequest {
+            id: DataIdentifierBorrowed::for_locale(&requested),
+            ..Default::default()
+        });
+    // Compiled data falls back to the root locale, whose sign is the tilde.
+    response.map_or_else(
+        |_| String::from("~"),
+        |response| response.payload.get().approximately_sign.to_string(),
+    )
+}
+
+/// Prepends the approximately sign of `locale` to `output` when requested.
+pub(crate) fn approximated(locale: &Locale, approximately: bool, output: String) -> String {
+    if approximately {
+        format!("{}{}", approximately_sign(locale), output)
+    } else {
+        output
+    }
+}
+
+/// Reads the `approximately` flag from a formatter option map, ignoring the
+/// other keys.
+pub(crate) fn decode_approximately(term: Term) -> Result<bool, ()> {
+    Ok(options::decode_option(term, atoms::approximately())?.unwrap_or(false))
+}
+
 #[derive(Clone)]
 struct FormatterConfig {
     minimum_integer_digits: u16,
+    /// Integer digits above this many are dropped, not rounded.
+    maximum_integer_digits: Option<u16>,
     minimum_fraction_digits: u16,
     maximum_fraction_digits: Option<u16>,
     grouping_strategy: GroupingStrategy,
     sign_display: SignDisplay,
+    /// How floats are turned into decimals before any rounding is applied.
+    float_precision: FloatPrecision,
+    /// The power of ten numbers are multiplied by before rounding, so a
+    /// fraction can be shown in percent or basis points without float error.
+    scale: i16,
+    /// Whether numbers are formatted without their sign.
+    absolute_value: bool,
+    /// Whether output is prefixed with the approximately sign.
+    approximately: bool,
 }
 
 impl Default for FormatterConfig {
     fn default() -> Self {
         Self {
             minimum_integer_digits: 1,
+            maximum_integer_digits: None,
             minimum_fraction_digits: 0,
             maximum_fraction_digits: Some(3),
             grouping_strategy: GroupingStrategy::Auto,
             sign_display: SignDisplay::Auto,
+            float_precision: FloatPrecision::RoundTrip,
+            scale: 0,
+            absolute_value: false,
+            approximately: false,
         }
     }
 }
 
-#[derive(NifMap)]
+#[derive(NifMap, PartialEq)]
 struct NumberFormatPart {
     #[rustler(map = "type")]
-    part_type: Atom,
+    part_type: PartType,
     value: String,
 }
 
-struct CollectedPart {
-    start: usize,
-    end: usize,
-    part: WriteablePart,
+#[derive(NifMap)]
+struct NumberingSystem {
+    id: String,
+    digits: Vec<String>,
 }
 
-struct PartsCollector {
-    output: String,
-    parts: Vec<CollectedPart>,
+#[derive(NifMap)]
+struct NumberSymbols {
+    decimal: String,
+    group: String,
+    plus_sign: String,
+    minus_sign: String,
+    percent: String,
+    digits: Vec<String>,
 }
 
-impl PartsCollector {
-    fn new() -> Self {
-        Self {
-            output: String::new(),
-            parts: Vec::new(),
-        }
-    }
+pub(crate) fn load(env: Env) -> bool {
+    env.register::<NumberFormatterResource>().is_ok()
+}
 
-    fn into_number_parts(self) -> (String, Vec<CollectedPart>) {
-        (self.output, self.parts)
-    }
+#[rustler::nif]
+pub(crate) fn number_formatter_new<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberFormatterNew, || {
+        number_formatter_new_inner(env, locale_term, options_term)
+    })
 }
 
-impl fmt::Write for PartsCollector {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.output.push_str(s);
-        Ok(())
-    }
+fn number_formatter_new_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
 
-    fn write_char(&mut self, c: char) -> fmt::Result {
-        self.output.push(c);
-        Ok(())
+    match NumberFormatterResource::try_new(locale_arg.0, options_term) {
+        Ok(resource) => Ok((atoms::ok(), ResourceArc::new(resource)).encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
     }
 }
 
-impl PartsWrite for PartsCollector {
-    type SubPartsWrite = PartsCollector;
+#[rustler::nif]
+pub(crate) fn number_format<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberFormat, || {
+        number_format_inner(env, formatter_term, number_term)
+    })
+}
+
+fn number_format_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let formatter_resource: ResourceArc<NumberFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
 
-    fn with_part(
-        &mut self,
-        part: WriteablePart,
-        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
-    ) -> fmt::Result {
-        let start = self.output.len();
-        f(self)?;
-        let end = self.output.len();
-        if start < end {
-            self.parts.push(CollectedPart { start, end, part });
-        }
-        Ok(())
+    match formatter_resource.format(number_term) {
+        Ok(formatted) => Ok((atoms::ok(), formatted).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::invalid_number()).encode(env)),
     }
 }
 
-pub(crate) fn load(env: Env) -> bool {
-    env.register::<NumberFormatterResource>().is_ok()
+#[rustler::nif]
+pub(crate) fn number_format_to_parts<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberFormatToParts, || {
+        number_format_to_parts_inner(env, formatter_term, number_term, index_mode_term)
+    })
 }
 
-#[rustler::nif]
-pub(crate) fn number_formatter_new<'a>(
+fn number_format_to_parts_inner<'a>(
     env: Env<'a>,
-    locale_term: Term<'a>,
-    options_term: Term<'a>,
+    formatter_term: Term<'a>,
+    number_term: Term<'a>,
+    index_mode_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
-    let locale_resource: ResourceArc<LocaleResource> = match locale_term.decode() {
+    let formatter_resource: ResourceArc<NumberFormatterResource> = match formatter_term.decode() {
         Ok(resource) => resource,
-        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
     };
 
-    let config = match decode_formatter_config(options_term) {
-        Ok(config) => config,
+    let (decimal, sign) = match formatter_resource.signed_decimal(number_term) {
+        Ok(decimal) => decimal,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
         Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
     };
 
-    let mut formatter_options = DecimalFormatterOptions::default();
-    formatter_options.grouping_strategy = Some(config.grouping_strategy);
+    let (mut output, mut parts) = number_parts(&formatter_resource.formatter, &decimal);
+    if let Some(part) = formatter_resource.suppressed_sign(sign) {
+        parts.insert(0, (part, 0..0));
+    }
+    if formatter_resource.config.approximately {
+        let sign = approximately_sign(&formatter_resource.locale);
+        parts = index::shift(parts, sign.len());
+        parts.insert(
+            0,
+            (
+                NumberFormatPart {
+                    part_type: atoms::approximately_sign().into(),
+                    value: sign.clone(),
+                },
+                0..sign.len(),
+            ),
+        );
+        output.insert_str(0, &sign);
+    }
 
-    let formatter =
-        match DecimalFormatter::try_new(locale_resource.0.clone().into(), formatter_options) {
-            Ok(formatter) => formatter,
-            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
-        };
+    let parts = index::encode_parts(env, &output, parts, index_mode);
+    Ok((atoms::ok(), parts).encode(env))
+}
+
+/// The formatted number and its parts, with their byte ranges in it.
+fn number_parts(
+    formatter: &DecimalFormatter,
+    decimal: &FixedDecimal,
+) -> (String, Vec<(NumberFormatPart, Range<usize>)>) {
+    let (output, collected_parts) = format_parts(formatter, decimal);
 
-    let resource = NumberFormatterResource { formatter, config };
-    Ok((atoms::ok(), ResourceArc::new(resource)).encode(env))
+    let parts = collected_parts
+        .into_iter()
+        .filter_map(|collected| {
+            let part_type = PartType::from(collected.part);
+            let value = output.get(collected.range())?.to_string();
+            Some((NumberFormatPart { part_type, value }, collected.range()))
+        })
+        .collect();
+    (output, parts)
 }
 
+/// Formats the range from `start` to `end`, e.g. "3–5".
 #[rustler::nif]
-pub(crate) fn number_format<'a>(
+pub(crate) fn number_format_range<'a>(
     env: Env<'a>,
     formatter_term: Term<'a>,
-    number_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
-    let formatter_resource: ResourceArc<NumberFormatterResource> = match formatter_term.decode() {
+    stats::measure(Counted::NumberFormatRange, || {
+        number_format_range_inner(env, formatter_term, start_term, end_term)
+    })
+}
+
+fn number_format_range_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<NumberFormatterResource> = match formatter_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
     };
 
-    let mut decimal = match term_to_decimal(number_term) {
-        Ok(decimal) => decimal,
+    let segments = match resource.range_segments(start_term, end_term) {
+        Ok(segments) => segments,
         Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
     };
 
-    apply_config(&mut decimal, &formatter_resource.config);
+    let formatted: String = segments.into_iter().map(|(_, _, text)| text).collect();
+    let formatted = resource.approximated(formatted);
 
-    let formatted = formatter_resource.formatter.format(&decimal).to_string();
     Ok((atoms::ok(), formatted).encode(env))
 }
 
+/// Like `number_format_range`, returning the parts of each end tagged with
+/// the end they belong to and the separator as a shared literal.
 #[rustler::nif]
-pub(crate) fn number_format_to_parts<'a>(
+pub(crate) fn number_format_range_to_parts<'a>(
     env: Env<'a>,
     formatter_term: Term<'a>,
-    number_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+    index_mode_term: Term<'a>,
 ) -> NifResult<Term<'a>> {
-    let formatter_resource: ResourceArc<NumberFormatterResource> = match formatter_term.decode() {
+    stats::measure(Counted::NumberFormatRangeToParts, || {
+        number_format_range_to_parts_inner(
+            env,
+            formatter_term,
+            start_term,
+            end_term,
+            index_mode_term,
+        )
+    })
+}
+
+fn number_format_range_to_parts_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+    index_mode_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<NumberFormatterResource> = match formatter_term.decode() {
         Ok(resource) => resource,
         Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
     };
 
-    let mut decimal = match term_to_decimal(number_term) {
+    let segments = match resource.range_segments(start_term, end_term) {
+        Ok(segments) => segments,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    let index_mode = match index::decode_mode(index_mode_term) {
+        Ok(index_mode) => index_mode,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let mut parts = RangeParts::default();
+    if resource.config.approximately {
+        let sign = approximately_sign(&resource.locale);
+        parts.push(atoms::approximately_sign(), &sign, atoms::shared());
+    }
+    for (source, part_type, value) in segments {
+        parts.push(part_type, &value, source);
+    }
+
+    let parts = index::encode_parts(env, &parts.output, parts.parts, index_mode);
+    Ok((atoms::ok(), parts).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn number_ordinal_format<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    number_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberOrdinalFormat, || {
+        number_ordinal_format_inner(env, locale_term, number_term)
+    })
+}
+
+fn number_ordinal_format_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    number_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let decimal = match term_to_integer(number_term) {
         Ok(decimal) => decimal,
         Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
     };
 
-    apply_config(&mut decimal, &formatter_resource.config);
+    let locale = &locale_arg.0;
 
-    let formatted = formatter_resource.formatter.format(&decimal);
-    let mut collector = PartsCollector::new();
-    if let Err(_) = formatted.write_to_parts(&mut collector) {
-        return Ok((atoms::error(), atoms::invalid_number()).encode(env));
-    }
-    let (output, collected_parts) = collector.into_number_parts();
-    let mut parts = Vec::with_capacity(collected_parts.len());
-
-    for collected in collected_parts {
-        if let Some(atom) = part_atom(collected.part) {
-            if let Some(slice) = output.get(collected.start..collected.end) {
-                parts.push(NumberFormatPart {
-                    part_type: atom,
-                    value: slice.to_string(),
-                });
+    let rules = match PluralRules::try_new_ordinal(locale.clone().into(), Default::default()) {
+        Ok(rules) => rules,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let category = rules.category_for(&decimal);
+
+    let pattern = match ordinal_pattern(locale.id.language.as_str(), category) {
+        Some(pattern) => pattern,
+        None => return Ok((atoms::error(), atoms::unsupported_locale()).encode(env)),
+    };
+
+    let formatter = match DecimalFormatter::try_new(
+        locale.clone().into(),
+        DecimalFormatterOptions::default(),
+    ) {
+        Ok(formatter) => formatter,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let formatted = pattern.replace("{0}", &formatter.format(&decimal).write_to_string());
+    Ok((atoms::ok(), formatted).encode(env))
+}
+
+/// Formats `numerator / denominator` as a percentage in one step.
+///
+/// The ratio is computed exactly from the decimal value of the inputs, with
+/// floats taken at their shortest round-trip value, so integers beyond 2^53
+/// keep every digit and `0.125` is a tie rather than a value near it. It is
+/// rounded to `significant_digits` when given, otherwise to
+/// `maximum_fraction_digits` (0 by default). Trailing zeros left by the
+/// rounding are dropped again unless `minimum_fraction_digits` asks for them.
+#[rustler::nif]
+pub(crate) fn number_percent_format<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    numerator_term: Term<'a>,
+    denominator_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberPercentFormat, || {
+        number_percent_format_inner(
+            env,
+            locale_term,
+            numerator_term,
+            denominator_term,
+            options_term,
+        )
+    })
+}
+
+fn number_percent_format_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    numerator_term: Term<'a>,
+    denominator_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let config = match decode_percent_config(options_term) {
+        Ok(config) => config,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let maximum_fraction_digits = match config.significant_digits {
+        Some(_) => config.maximum_fraction_digits,
+        None => Some(config.maximum_fraction_digits.unwrap_or(0)),
+    };
+
+    let ratio = match (
+        term_to_decimal(numerator_term),
+        term_to_decimal(denominator_term),
+    ) {
+        (Ok(numerator), Ok(denominator)) => percent_ratio(
+            &numerator,
+            &denominator,
+            config.significant_digits,
+            maximum_fraction_digits,
+        ),
+        _ => None,
+    };
+    let mut decimal = match ratio {
+        Some(decimal) => decimal,
+        None => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    if let Some(digits) = config.significant_digits {
+        decimal.round(decimal.nonzero_magnitude_start() - i16::from(digits) + 1);
+    }
+    if let Some(max) = maximum_fraction_digits {
+        decimal.round(-(max as i16));
+    }
+    decimal.trim_end();
+    if config.minimum_fraction_digits > 0 {
+        decimal.pad_end(-(config.minimum_fraction_digits as i16));
+    }
+
+    let formatter = match PercentFormatter::try_new(
+        locale_arg.0.clone().into(),
+        PercentFormatterOptions::default(),
+    ) {
+        Ok(formatter) => formatter,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let formatted = formatter.format(&decimal).write_to_string().into_owned();
+    Ok((atoms::ok(), formatted).encode(env))
+}
+
+#[derive(Default)]
+struct PercentConfig {
+    significant_digits: Option<u8>,
+    minimum_fraction_digits: u8,
+    maximum_fraction_digits: Option<u8>,
+}
+
+fn decode_percent_config(term: Term) -> Result<PercentConfig, ()> {
+    let mut config = PercentConfig::default();
+
+    options::decode_map(term, UnknownKeys::Error, |key, value_term| {
+        if key == atoms::significant_digits() {
+            let value: u8 = value_term.decode().map_err(|_| ())?;
+            if value == 0 {
+                return Err(());
             }
+            config.significant_digits = Some(value);
+        } else if key == atoms::minimum_fraction_digits() {
+            config.minimum_fraction_digits = value_term.decode().map_err(|_| ())?;
+        } else if key == atoms::maximum_fraction_digits() {
+            config.maximum_fraction_digits = Some(value_term.decode().map_err(|_| ())?);
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+
+    if let Some(max) = config.maximum_fraction_digits {
+        if max < config.minimum_fraction_digits {
+            return Err(());
         }
     }
 
-    Ok((atoms::ok(), parts).encode(env))
+    Ok(config)
 }
 
-fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
-    if term.get_type() != TermType::Map {
-        if let Ok(atom_name) = term.atom_to_string() {
-            if atom_name == "nil" {
-                return Ok(FormatterConfig::default());
-            }
+/// `numerator / denominator * 100`, computed with integers to as many
+/// fraction digits as the rounding needs, plus one. A nonzero remainder is
+/// kept as a trailing 1 in the place after that, so the rounding sees a
+/// value above a tie rather than the tie itself. `None` for a zero
+/// denominator.
+fn percent_ratio(
+    numerator: &FixedDecimal,
+    denominator: &FixedDecimal,
+    significant_digits: Option<u8>,
+    maximum_fraction_digits: Option<u8>,
+) -> Option<FixedDecimal> {
+    let zero = BigInt::from(0);
+    let (numerator, numerator_exponent) = decimal_to_integer(numerator)?;
+    let (denominator, denominator_exponent) = decimal_to_integer(denominator)?;
+    if denominator == zero {
+        return None;
+    }
+
+    let negative = (numerator < zero) != (denominator < zero);
+    let numerator = if numerator < zero {
+        -numerator
+    } else {
+        numerator
+    } * BigInt::from(100);
+    let denominator = if denominator < zero {
+        -denominator
+    } else {
+        denominator
+    };
+    let exponent = numerator_exponent - denominator_exponent;
+
+    // A quotient of an `a`-digit and a `b`-digit integer has at least
+    // `a - b` integer digits, so it is at least 10^(a - b - 1).
+    let magnitude =
+        numerator.to_string().len() as i32 - denominator.to_string().len() as i32 - 1 + exponent;
+    let mut fraction_digits = i32::from(maximum_fraction_digits.unwrap_or(0)) + 1;
+    if let Some(digits) = significant_digits {
+        fraction_digits = fraction_digits.max(i32::from(digits) - magnitude);
+    }
+
+    let shift = exponent + fraction_digits;
+    let (numerator, denominator) = match u32::try_from(shift) {
+        Ok(shift) => (numerator * BigInt::from(10).pow(shift), denominator),
+        Err(_) => (
+            numerator,
+            denominator * BigInt::from(10).pow(shift.unsigned_abs()),
+        ),
+    };
+
+    let mut digits = (&numerator / &denominator).to_string();
+    if &numerator % &denominator != zero {
+        digits.push('1');
+        fraction_digits += 1;
+    }
+    if negative {
+        digits.insert(0, '-');
+    }
+
+    let mut decimal = FixedDecimal::try_from_str(&digits).ok()?;
+    decimal.multiply_pow10(i16::try_from(-fraction_digits).ok()?);
+    Some(decimal)
+}
+
+/// Splits `decimal` into an integer and the power of ten it is scaled by:
+/// `-12.5` is `(-125, -1)`.
+fn decimal_to_integer(decimal: &FixedDecimal) -> Option<(BigInt, i32)> {
+    let string = decimal.to_string();
+    let (integer, fraction) = string.split_once('.').unwrap_or((&string, ""));
+    let value = format!("{integer}{fraction}").parse().ok()?;
+    Some((value, -(fraction.len() as i32)))
+}
+
+/// Replaces native decimal digits with ASCII digits. With a numbering system
+/// only that system's digits are replaced, otherwise every decimal digit.
+#[rustler::nif]
+pub(crate) fn number_digits_to_latin<'a>(
+    env: Env<'a>,
+    string: &str,
+    numbering_system: Option<&str>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberDigitsToLatin, || {
+        number_digits_to_latin_inner(env, string, numbering_system)
+    })
+}
+
+fn number_digits_to_latin_inner<'a>(
+    env: Env<'a>,
+    string: &str,
+    numbering_system: Option<&str>,
+) -> NifResult<Term<'a>> {
+    let converted: String = match numbering_system {
+        Some(system) => {
+            let digits = match numbering_system_digits(system) {
+                Some(digits) => digits,
+                None => return Ok((atoms::error(), atoms::invalid_numbering_system()).encode(env)),
+            };
+            string
+                .chars()
+                .map(|c| match digits.iter().position(|&digit| digit == c) {
+                    Some(value) => char::from(b'0' + value as u8),
+                    None => c,
+                })
+                .collect()
         }
-        return Err(());
+        None => {
+            let categories = CodePointMapData::<GeneralCategory>::new();
+            let is_digit = |c: char| categories.get(c) == GeneralCategory::DecimalNumber;
+            string
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_digit() || !is_digit(c) {
+                        return c;
+                    }
+                    // Decimal digits are encoded in contiguous runs of ten
+                    // starting at zero, so the offset from the start of the
+                    // run gives the value.
+                    let mut start = c as u32;
+                    while let Some(previous) = start.checked_sub(1).and_then(char::from_u32) {
+                        if !is_digit(previous) {
+                            break;
+                        }
+                        start -= 1;
+                    }
+                    char::from(b'0' + ((c as u32 - start) % 10) as u8)
+                })
+                .collect()
+        }
+    };
+
+    Ok((atoms::ok(), converted).encode(env))
+}
+
+/// Replaces ASCII digits with the digits of `numbering_system`.
+#[rustler::nif]
+pub(crate) fn number_digits_from_latin<'a>(
+    env: Env<'a>,
+    string: &str,
+    numbering_system: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberDigitsFromLatin, || {
+        number_digits_from_latin_inner(env, string, numbering_system)
+    })
+}
+
+fn number_digits_from_latin_inner<'a>(
+    env: Env<'a>,
+    string: &str,
+    numbering_system: &str,
+) -> NifResult<Term<'a>> {
+    let digits = match numbering_system_digits(numbering_system) {
+        Some(digits) => digits,
+        None => return Ok((atoms::error(), atoms::invalid_numbering_system()).encode(env)),
+    };
+
+    let converted: String = string
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(value) if c.is_ascii_digit() => digits[value as usize],
+            _ => c,
+        })
+        .collect();
+
+    Ok((atoms::ok(), converted).encode(env))
+}
+
+/// Returns the numeric numbering systems with digit data, in identifier
+/// order.
+#[rustler::nif]
+pub(crate) fn number_numbering_systems<'a>(env: Env<'a>) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberNumberingSystems, || {
+        number_numbering_systems_inner(env)
+    })
+}
+
+fn number_numbering_systems_inner<'a>(env: Env<'a>) -> NifResult<Term<'a>> {
+    let systems: Vec<NumberingSystem> = NUMBERING_SYSTEMS
+        .iter()
+        .filter_map(|&id| {
+            let digits = numbering_system_digits(id)?;
+            Some(NumberingSystem {
+                id: id.to_string(),
+                digits: digits.iter().map(char::to_string).collect(),
+            })
+        })
+        .collect();
+
+    Ok(systems.encode(env))
+}
+
+/// Returns the locale's number symbols, optionally for another numbering
+/// system.
+///
+/// The symbols are read back from formatted sample numbers rather than from
+/// the provider structs, so they match exactly what the formatters output.
+/// ICU4X does not carry the CLDR `nan` and `infinity` symbols, so those are
+/// not included.
+#[rustler::nif]
+pub(crate) fn number_symbols<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    numbering_system: Option<&str>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberSymbols, || {
+        number_symbols_inner(env, locale_term, numbering_system)
+    })
+}
+
+fn number_symbols_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    numbering_system: Option<&str>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let mut locale = locale_arg.0.clone();
+    if let Some(system) = numbering_system {
+        let value = match Value::try_from_str(system) {
+            Ok(value) if numbering_system_digits(system).is_some() => value,
+            _ => return Ok((atoms::error(), atoms::invalid_numbering_system()).encode(env)),
+        };
+        locale.extensions.unicode.keywords.set(key!("nu"), value);
     }
 
+    let mut formatter_options = DecimalFormatterOptions::default();
+    formatter_options.grouping_strategy = Some(GroupingStrategy::Always);
+
+    let formatter = match DecimalFormatter::try_new(locale.clone().into(), formatter_options) {
+        Ok(formatter) => formatter,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let percent_formatter =
+        match PercentFormatter::try_new(locale.into(), PercentFormatterOptions::default()) {
+            Ok(formatter) => formatter,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        };
+
+    let mut negative = FixedDecimal::from(-12345);
+    negative.multiply_pow10(-1);
+    let mut positive = FixedDecimal::from(1);
+    positive.apply_sign_display(SignDisplay::Always);
+
+    let negative_parts = format_parts(&formatter, &negative);
+    let positive_parts = format_parts(&formatter, &positive);
+
+    let one = FixedDecimal::from(1);
+    let percent = percent_formatter
+        .format(&one)
+        .write_to_string()
+        .replacen(formatter.format(&one).write_to_string().as_ref(), "", 1)
+        .trim_matches(char::is_whitespace)
+        .to_string();
+
+    let digits = (0..10)
+        .map(|digit| {
+            formatter
+                .format(&FixedDecimal::from(digit))
+                .write_to_string()
+                .into_owned()
+        })
+        .collect();
+
+    let symbols = NumberSymbols {
+        decimal: part_value(&negative_parts, parts::DECIMAL),
+        group: part_value(&negative_parts, parts::GROUP),
+        plus_sign: part_value(&positive_parts, parts::PLUS_SIGN),
+        minus_sign: part_value(&negative_parts, parts::MINUS_SIGN),
+        percent,
+        digits,
+    };
+
+    Ok((atoms::ok(), symbols).encode(env))
+}
+
+#[derive(NifMap)]
+struct GroupingSizes {
+    primary: u8,
+    secondary: u8,
+    min_grouping: u8,
+}
+
+/// Returns the locale's digit grouping sizes: the size of the group nearest
+/// the decimal separator, of the groups above it, and the number of digits
+/// required before the first group is separated at all. Indian English and
+/// Hindi group as 2,2,3 ("12,34,567"), so their secondary size is 2.
+#[rustler::nif]
+pub(crate) fn number_grouping_sizes<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberGroupingSizes, || {
+        number_grouping_sizes_inner(env, locale_term)
+    })
+}
+
+fn number_grouping_sizes_inner<'a>(env: Env<'a>, locale_term: Term<'a>) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let requested = DecimalSymbolsV1::make_locale((&locale_arg.0).into());
+    let response: DataResponse<DecimalSymbolsV1> = match DecimalBaked.load(DataRequest {
+        id: DataIdentifierBorrowed::for_marker_attributes_and_locale(
+            DataMarkerAttributes::empty(),
+            &requested,
+        ),
+        ..Default::default()
+    }) {
+        Ok(response) => response,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let sizes = response.payload.get().grouping_sizes;
+    // A secondary size of 0 means the groups above the first repeat it.
+    let secondary = if sizes.secondary == 0 {
+        sizes.primary
+    } else {
+        sizes.secondary
+    };
+
+    let grouping_sizes = GroupingSizes {
+        primary: sizes.primary,
+        secondary,
+        min_grouping: sizes.min_grouping,
+    };
+    Ok((atoms::ok(), grouping_sizes).encode(env))
+}
+
+fn format_parts(
+    formatter: &DecimalFormatter,
+    decimal: &FixedDecimal,
+) -> (String, Vec<CollectedPart>) {
+    let mut collector = PartsCollector::new();
+    // Writing into a `String` cannot fail.
+    let _ = formatter.format(decimal).write_to_parts(&mut collector);
+    collector.finish_flat()
+}
+
+fn part_value((output, parts): &(String, Vec<CollectedPart>), part: WriteablePart) -> String {
+    parts
+        .iter()
+        .find(|collected| collected.part == part)
+        .and_then(|collected| output.get(collected.start..collected.end))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The CLDR numeric numbering systems. Only those the decimal data has digits
+/// for are reported by `number_numbering_systems`.
+const NUMBERING_SYSTEMS: &[&str] = &[
+    "adlm", "ahom", "arab", "arabext", "bali", "beng", "bhks", "brah", "cakm", "cham", "deva",
+    "diak", "fullwide", "gara", "gong", "gonm", "gujr", "gukh", "guru", "hanidec", "hmng", "hmnp",
+    "java", "kali", "kawi", "khmr", "knda", "krai", "lana", "lanatham", "laoo", "latn", "lepc",
+    "limb", "mathbold", "mathdbl", "mathmono", "mathsanb", "mathsans", "mlym", "modi", "mong",
+    "mroo", "mtei", "mymr", "mymrepka", "mymrpao", "mymrshan", "mymrtlng", "nagm", "newa", "nkoo",
+    "olck", "onao", "orya", "osma", "outlined", "rohg", "saur", "segment", "shrd", "sind", "sinh",
+    "sora", "sund", "sunu", "takr", "talu", "tamldec", "telu", "thai", "tibt", "tirh", "tnsa",
+    "vaii", "wara", "wcho",
+];
+
+fn numbering_system_digits(numbering_system: &str) -> Option<[char; 10]> {
+    let attributes = DataMarkerAttributes::try_from_str(numbering_system).ok()?;
+    let response: DataResponse<DecimalDigitsV1> = DecimalBaked
+        .load(DataRequest {
+            id: DataIdentifierBorrowed::for_marker_attributes(attributes),
+            ..Default::default()
+        })
+        .ok()?;
+    Some(*response.payload.get())
+}
+
+/// Ordinal patterns keyed by language and ordinal plural category.
+///
+/// ICU4X does not expose RBNF, so these mirror the CLDR `digits-ordinal`
+/// rules for the supported languages.
+fn ordinal_pattern(language: &str, category: PluralCategory) -> Option<&'static str> {
+    let pattern = match (language, category) {
+        ("en", PluralCategory::One) => "{0}st",
+        ("en", PluralCategory::Two) => "{0}nd",
+        ("en", PluralCategory::Few) => "{0}rd",
+        ("en", _) => "{0}th",
+        ("fr", PluralCategory::One) => "{0}er",
+        ("fr", _) => "{0}e",
+        ("ca", PluralCategory::One) => "{0}r",
+        ("ca", PluralCategory::Two) => "{0}n",
+        ("ca", PluralCategory::Few) => "{0}t",
+        ("ca", _) => "{0}è",
+        ("sv", PluralCategory::One) => "{0}:a",
+        ("sv", _) => "{0}:e",
+        ("es" | "it" | "pt" | "gl", _) => "{0}º",
+        ("nl", _) => "{0}e",
+        (
+            "de" | "da" | "nb" | "nn" | "no" | "fi" | "is" | "et" | "cs" | "sk" | "pl" | "hu"
+            | "tr" | "sl" | "hr" | "bs" | "sr" | "lv" | "lt",
+            _,
+        ) => "{0}.",
+        ("zh" | "ja", _) => "第{0}",
+        _ => return None,
+    };
+
+    Some(pattern)
+}
+
+fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
     let mut config = FormatterConfig::default();
-    let mut iter = MapIterator::new(term).ok_or(())?;
 
-    while let Some((key_term, value_term)) = iter.next() {
-        let key: Atom = key_term.decode().map_err(|_| ())?;
+    options::decode_map(term, UnknownKeys::Error, |key, value_term| {
         if key == atoms::minimum_integer_digits() {
             let value: i64 = value_term.decode().map_err(|_| ())?;
             if value < 1 || value > i64::from(i16::MAX) {
                 return Err(());
             }
             config.minimum_integer_digits = value as u16;
+        } else if key == atoms::maximum_integer_digits() {
+            let value: i64 = value_term.decode().map_err(|_| ())?;
+            if value < 1 || value > i64::from(i16::MAX) {
+                return Err(());
+            }
+            config.maximum_integer_digits = Some(value as u16);
         } else if key == atoms::minimum_fraction_digits() {
             let value: i64 = value_term.decode().map_err(|_| ())?;
             if value < 0 || value > i64::from(i16::MAX) {
@@ -225,15 +1089,6 @@ fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
             }
             config.minimum_fraction_digits = value as u16;
         } else if key == atoms::maximum_fraction_digits() {
-            if value_term.get_type() == TermType::Atom {
-                if let Ok(atom_name) = value_term.atom_to_string() {
-                    if atom_name == "nil" {
-                        config.maximum_fraction_digits = None;
-                        continue;
-                    }
-                }
-            }
-
             let value: i64 = value_term.decode().map_err(|_| ())?;
             if value < 0 || value > i64::from(i16::MAX) {
                 return Err(());
@@ -258,10 +1113,21 @@ fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
                 _ if value == atoms::negative() => SignDisplay::Negative,
                 _ => return Err(()),
             };
+        } else if key == atoms::float_precision() {
+            config.float_precision = decode_float_precision(value_term)?;
+        } else if key == atoms::scale() {
+            config.scale = decode_scale(value_term)?;
+        } else if key == atoms::absolute_value() {
+            config.absolute_value = value_term.decode().map_err(|_| ())?;
+        } else if key == atoms::approximately() {
+            config.approximately = value_term.decode().map_err(|_| ())?;
+        } else if key == atoms::preferences() {
+            // Applied to the locale by `preferences::apply`.
         } else {
-            return Err(());
+            return Ok(false);
         }
-    }
+        Ok(true)
+    })?;
 
     if let Some(max) = config.maximum_fraction_digits {
         if max < config.minimum_fraction_digits {
@@ -269,10 +1135,73 @@ fn decode_formatter_config<'a>(term: Term<'a>) -> Result<FormatterConfig, ()> {
         }
     }
 
+    if let Some(max) = config.maximum_integer_digits {
+        if max < config.minimum_integer_digits {
+            return Err(());
+        }
+    }
+
     Ok(config)
 }
 
+/// Decodes `:round_trip`, `:integer` or `{:magnitude, n}`.
+fn decode_float_precision(term: Term) -> Result<FloatPrecision, ()> {
+    if let Ok(value) = term.decode::<Atom>() {
+        return if value == atoms::round_trip() {
+            Ok(FloatPrecision::RoundTrip)
+        } else if value == atoms::integer() {
+            Ok(FloatPrecision::Integer)
+        } else {
+            Err(())
+        };
+    }
+
+    let (tag, magnitude): (Atom, i16) = term.decode().map_err(|_| ())?;
+    if tag == atoms::magnitude() {
+        Ok(FloatPrecision::Magnitude(magnitude))
+    } else {
+        Err(())
+    }
+}
+
+/// Decodes a scale factor, a power of ten such as `100` or `10_000`, into
+/// its exponent.
+fn decode_scale(term: Term) -> Result<i16, ()> {
+    let mut factor: u64 = term.decode().map_err(|_| ())?;
+    let mut exponent = 0;
+    while factor > 1 && factor % 10 == 0 {
+        factor /= 10;
+        exponent += 1;
+    }
+    if factor == 1 {
+        Ok(exponent)
+    } else {
+        Err(())
+    }
+}
+
 pub(crate) fn term_to_decimal<'a>(term: Term<'a>) -> Result<FixedDecimal, ()> {
+    term_to_decimal_with_precision(term, FloatPrecision::RoundTrip)
+}
+
+/// Decodes an integral number from any input `term_to_decimal` accepts.
+/// Zeros after the decimal point, as in `Decimal.new("2.0")`, are dropped;
+/// any other fraction is rejected.
+pub(crate) fn term_to_integer<'a>(term: Term<'a>) -> Result<FixedDecimal, ()> {
+    let mut decimal = term_to_decimal_with_precision(term, FloatPrecision::Integer)?;
+    decimal.trim_end();
+    if decimal.nonzero_magnitude_end() < 0 {
+        return Err(());
+    }
+    Ok(decimal)
+}
+
+/// Like `term_to_decimal`, converting floats with `precision`. Integer
+/// precision rejects floats with a fractional part.
+pub(crate) fn term_to_decimal_with_precision<'a>(
+    term: Term<'a>,
+    precision: FloatPrecision,
+) -> Result<FixedDecimal, ()> {
     if let Ok(value) = term.decode::<i64>() {
         return Ok(FixedDecimal::from(value));
     }
@@ -286,7 +1215,17 @@ pub(crate) fn term_to_decimal<'a>(term: Term<'a>) -> Result<FixedDecimal, ()> {
         if !value.is_finite() {
             return Err(());
         }
-        return FixedDecimal::try_from_f64(value, FloatPrecision::RoundTrip).map_err(|_| ());
+        return FixedDecimal::try_from_f64(value, precision).map_err(|_| ());
+    }
+
+    // Decimal strings keep every digit, which floats cannot.
+    if let Ok(value) = term.decode::<&str>() {
+        return FixedDecimal::try_from_str(value).map_err(|_| ());
+    }
+
+    // An `Icu.Decimal` is used as is, with no conversion.
+    if let Some(decimal) = decimal::decode(term) {
+        return Ok(decimal);
     }
 
     // Try decoding as %Decimal{sign: 1|-1, coef: integer, exp: integer}
@@ -348,6 +1287,16 @@ fn apply_config(decimal: &mut FixedDecimal, config: &FormatterConfig) {
         }
     }
 
+    // `set_max_position` also pads, so only call it when there are integer
+    // digits to drop and leave the padding to `minimum_integer_digits`.
+    if let Some(max_integer_digits) = config.maximum_integer_digits {
+        if let Ok(position) = i16::try_from(max_integer_digits) {
+            if decimal.nonzero_magnitude_start() >= position {
+                decimal.set_max_position(position);
+            }
+        }
+    }
+
     if config.minimum_integer_digits > 0 {
         if let Ok(position) = i16::try_from(config.minimum_integer_digits) {
             decimal.pad_start(position);
@@ -362,21 +1311,3 @@ fn apply_config(decimal: &mut FixedDecimal, config: &FormatterConfig) {
 
     decimal.apply_sign_display(config.sign_display);
 }
-
-fn part_atom(part: WriteablePart) -> Option<Atom> {
-    if part == parts::INTEGER {
-        Some(atoms::integer())
-    } else if part == parts::DECIMAL {
-        Some(atoms::decimal())
-    } else if part == parts::FRACTION {
-        Some(atoms::fraction())
-    } else if part == parts::GROUP {
-        Some(atoms::group())
-    } else if part == parts::PLUS_SIGN {
-        Some(atoms::plus_sign())
-    } else if part == parts::MINUS_SIGN {
-        Some(atoms::minus_sign())
-    } else {
-        None
-    }
-}
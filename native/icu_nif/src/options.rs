@@ -0,0 +1,101 @@
+//! Decoding of the option maps passed to the formatter constructors.
+//!
+//! The Elixir side validates option keys before they reach the NIF, so a key
+//! the decoder does not know is a programming error on that side. Every
+//! module walks its map with `decode_map` so that such keys are treated the
+//! same way everywhere.
+//!
+//! A key set to `nil` means the same as a missing key: the option takes its
+//! default. `decode_map` skips such keys and `get_option` reports them as absent, so
+//! decoders never see a `nil` value.
+
+use rustler::types::map::MapIterator;
+use rustler::{Atom, Decoder, Encoder, Env, Term, TermType};
+
+use crate::atoms;
+
+/// What `decode_map` does with a key the handler did not consume.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnknownKeys {
+    /// Fail the decoding.
+    Error,
+    /// Skip the key. For maps whose keys are consumed by several decoders.
+    Ignore,
+}
+
+/// Why an option map was rejected: a bare reason such as `invalid_options`,
+/// or `{:invalid_option_value, key}` for the option whose value is unknown.
+#[derive(Clone, Copy)]
+pub(crate) enum OptionError {
+    Reason(Atom),
+    InvalidValue(Atom),
+}
+
+impl From<Atom> for OptionError {
+    fn from(reason: Atom) -> Self {
+        Self::Reason(reason)
+    }
+}
+
+impl Encoder for OptionError {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match *self {
+            Self::Reason(reason) => reason.encode(env),
+            Self::InvalidValue(key) => (atoms::invalid_option_value(), key).encode(env),
+        }
+    }
+}
+
+/// Calls `handle` with each key and value of the option map `term`.
+///
+/// `handle` returns `Ok(true)` for the keys it consumed and `Ok(false)` for
+/// the ones it does not know, which are then dealt with according to
+/// `unknown`. Keys set to `nil` are skipped, and a `nil` term decodes as an
+/// empty map.
+pub(crate) fn decode_map<'a>(
+    term: Term<'a>,
+    unknown: UnknownKeys,
+    mut handle: impl FnMut(Atom, Term<'a>) -> Result<bool, ()>,
+) -> Result<(), ()> {
+    if is_nil(term) {
+        return Ok(());
+    }
+
+    for (key_term, value_term) in MapIterator::new(term).ok_or(())? {
+        let key: Atom = key_term.decode().map_err(|_| ())?;
+        if is_nil(value_term) || handle(key, value_term)? {
+            continue;
+        }
+
+        if unknown == UnknownKeys::Error {
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks up `key` in the option map `term`, treating a `nil` value like a
+/// missing key. Anything but a map has no keys.
+pub(crate) fn get_option<'a>(term: Term<'a>, key: Atom) -> Option<Term<'a>> {
+    if term.get_type() != TermType::Map {
+        return None;
+    }
+
+    term.map_get(key).ok().filter(|value| !is_nil(*value))
+}
+
+/// Decodes the value of `key` in the option map `term`, `None` when the key
+/// is missing or `nil`.
+pub(crate) fn decode_option<'a, T: Decoder<'a>>(
+    term: Term<'a>,
+    key: Atom,
+) -> Result<Option<T>, ()> {
+    get_option(term, key)
+        .map(|value| value.decode().map_err(|_| ()))
+        .transpose()
+}
+
+pub(crate) fn is_nil(term: Term) -> bool {
+    term.get_type() == TermType::Atom && term.decode::<Atom>().ok() == Some(atoms::nil())
+}
@@ -0,0 +1,63 @@
+//! The `preferences` option of the formatter constructors.
+//!
+//! Preferences are what a locale's `-u-` keywords carry: its calendar, hour
+//! cycle and numbering system. ICU4X derives each
+//! formatter's `*Preferences` type from the locale, so the preferences are
+//! set as keywords of the locale before the formatter is built, replacing
+//! the ones it already has. Values are checked against the CLDR keyword data
+//! like `locale_set_extension` does.
+
+use icu::locale::Locale;
+use rustler::{Atom, Term};
+
+use crate::atoms;
+use crate::keywords;
+use crate::options::{self, OptionError, UnknownKeys};
+
+/// Applies the `preferences` map of the option map `options_term` to
+/// `locale`, failing with `{:invalid_option_value, :preferences}` for an
+/// unknown key or value.
+pub(crate) fn apply(locale: &mut Locale, options_term: Term) -> Result<(), OptionError> {
+    let Some(preferences_term) = options::get_option(options_term, atoms::preferences()) else {
+        return Ok(());
+    };
+
+    let mut keywords = Vec::new();
+    options::decode_map(preferences_term, UnknownKeys::Error, |key, value_term| {
+        let (name, value) = if key == atoms::calendar() {
+            ("ca", value_term.decode::<String>().map_err(|_| ())?)
+        } else if key == atoms::hour_cycle() {
+            (
+                "hc",
+                decode_hour_cycle(value_term.decode().map_err(|_| ())?)?,
+            )
+        } else if key == atoms::numbering_system() {
+            ("nu", value_term.decode::<String>().map_err(|_| ())?)
+        } else {
+            return Ok(false);
+        };
+        keywords.push(keywords::parse_keyword(name, &value).map_err(|_| ())?);
+        Ok(true)
+    })
+    .map_err(|_| OptionError::InvalidValue(atoms::preferences()))?;
+
+    for (key, value) in keywords {
+        locale.extensions.unicode.keywords.set(key, value);
+    }
+    Ok(())
+}
+
+fn decode_hour_cycle(hour_cycle: Atom) -> Result<String, ()> {
+    let value = if hour_cycle == atoms::h11() {
+        "h11"
+    } else if hour_cycle == atoms::h12() {
+        "h12"
+    } else if hour_cycle == atoms::h23() {
+        "h23"
+    } else if hour_cycle == atoms::h24() {
+        "h24"
+    } else {
+        return Err(());
+    };
+    Ok(value.to_string())
+}
@@ -0,0 +1,108 @@
+use icu::properties::props::{Emoji, EmojiPresentation};
+use icu::properties::CodePointSetData;
+use icu::segmenter::GraphemeClusterSegmenter;
+use rustler::{Atom, Binary, Encoder, Env, NifResult, Term};
+
+use crate::atoms;
+use crate::stats::{self, Counted};
+
+const VARIATION_SELECTOR_16: char = '\u{FE0F}';
+const COMBINING_KEYCAP: char = '\u{20E3}';
+
+#[rustler::nif]
+pub(crate) fn properties_is_emoji<'a>(env: Env<'a>, string: &str) -> NifResult<Term<'a>> {
+    stats::measure(Counted::PropertiesIsEmoji, || {
+        properties_is_emoji_inner(env, string)
+    })
+}
+
+fn properties_is_emoji_inner<'a>(env: Env<'a>, string: &str) -> NifResult<Term<'a>> {
+    let is_emoji = !string.is_empty() && graphemes(string).all(is_emoji_grapheme);
+    Ok(is_emoji.encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn properties_contains_emoji<'a>(env: Env<'a>, string: &str) -> NifResult<Term<'a>> {
+    stats::measure(Counted::PropertiesContainsEmoji, || {
+        properties_contains_emoji_inner(env, string)
+    })
+}
+
+fn properties_contains_emoji_inner<'a>(env: Env<'a>, string: &str) -> NifResult<Term<'a>> {
+    Ok(graphemes(string).any(is_emoji_grapheme).encode(env))
+}
+
+/// Segments are returned as sub-binaries of the input, so no text is copied.
+#[rustler::nif]
+pub(crate) fn properties_emoji_segments<'a>(
+    env: Env<'a>,
+    binary: Binary<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::PropertiesEmojiSegments, || {
+        properties_emoji_segments_inner(env, binary)
+    })
+}
+
+fn properties_emoji_segments_inner<'a>(env: Env<'a>, binary: Binary<'a>) -> NifResult<Term<'a>> {
+    let string = std::str::from_utf8(binary.as_slice()).map_err(|_| rustler::Error::BadArg)?;
+    let mut segments: Vec<(Atom, Binary<'a>)> = Vec::new();
+    let mut run_start = 0;
+    let mut run_is_emoji = None;
+    let mut offset = 0;
+
+    for grapheme in graphemes(string) {
+        let is_emoji = is_emoji_grapheme(grapheme);
+        if let Some(previous) = run_is_emoji {
+            if previous != is_emoji {
+                let segment = binary.make_subbinary(run_start, offset - run_start)?;
+                segments.push((segment_atom(previous), segment));
+                run_start = offset;
+            }
+        }
+        run_is_emoji = Some(is_emoji);
+        offset += grapheme.len();
+    }
+
+    if let Some(is_emoji) = run_is_emoji {
+        let segment = binary.make_subbinary(run_start, string.len() - run_start)?;
+        segments.push((segment_atom(is_emoji), segment));
+    }
+
+    Ok(segments.encode(env))
+}
+
+fn segment_atom(is_emoji: bool) -> Atom {
+    if is_emoji {
+        atoms::emoji()
+    } else {
+        atoms::text()
+    }
+}
+
+fn graphemes(string: &str) -> impl Iterator<Item = &str> {
+    let breakpoints: Vec<usize> = GraphemeClusterSegmenter::new()
+        .segment_str(string)
+        .collect();
+    let mut bounds = Vec::with_capacity(breakpoints.len());
+    for window in breakpoints.windows(2) {
+        bounds.push(&string[window[0]..window[1]]);
+    }
+    bounds.into_iter()
+}
+
+/// A grapheme renders as an emoji when it contains a character with default
+/// emoji presentation (including regional indicator flags), or an emoji
+/// character turned into one by VS16 or a combining keycap.
+fn is_emoji_grapheme(grapheme: &str) -> bool {
+    let emoji = CodePointSetData::new::<Emoji>();
+    let presentation = CodePointSetData::new::<EmojiPresentation>();
+
+    if grapheme.chars().any(|c| presentation.contains(c)) {
+        return true;
+    }
+
+    grapheme.chars().any(|c| emoji.contains(c))
+        && grapheme
+            .chars()
+            .any(|c| c == VARIATION_SELECTOR_16 || c == COMBINING_KEYCAP)
+}
@@ -0,0 +1,116 @@
+//! Range patterns shared by the number, currency and unit formatters
+//! ("3–5", "10–20 €", "$10–$20").
+//!
+//! ICU4X has no number range formatter, so the separators of the CLDR range
+//! patterns (`{0}–{1}` in most languages) are bundled for the languages that
+//! deviate from the en dash. A unit or currency written after the number is
+//! shared by both ends of the range, as CLDR collapses it: "3–5 km" rather
+//! than "3 km–5 km". Affixes in front of the number are kept on both ends.
+
+use std::ops::Range;
+
+use icu::locale::Locale;
+use rustler::{Atom, NifMap};
+
+use crate::atoms;
+use crate::writeable_parts::PartType;
+
+#[derive(NifMap)]
+pub(crate) struct RangePart {
+    #[rustler(map = "type")]
+    pub(crate) part_type: PartType,
+    pub(crate) value: String,
+    /// `:start`, `:end`, or `:shared` for text common to both ends.
+    pub(crate) source: Atom,
+}
+
+/// The parts of a range in order, with the text they concatenate to and the
+/// byte range of each part in it.
+#[derive(Default)]
+pub(crate) struct RangeParts {
+    pub(crate) output: String,
+    pub(crate) parts: Vec<(RangePart, Range<usize>)>,
+}
+
+impl RangeParts {
+    pub(crate) fn push(&mut self, part_type: impl Into<PartType>, value: &str, source: Atom) {
+        let start = self.output.len();
+        self.output.push_str(value);
+        let part = RangePart {
+            part_type: part_type.into(),
+            value: value.to_string(),
+            source,
+        };
+        self.parts.push((part, start..self.output.len()));
+    }
+
+    pub(crate) fn extend<T: Into<PartType>>(
+        &mut self,
+        parts: impl IntoIterator<Item = (T, String)>,
+        source: Atom,
+    ) {
+        for (part_type, value) in parts {
+            self.push(part_type, &value, source);
+        }
+    }
+}
+
+/// The text placed between the two ends of a range.
+pub(crate) fn separator(locale: &Locale) -> &'static str {
+    match locale.id.language.as_str() {
+        "es" | "it" | "nl" | "zh" => "-",
+        "ja" => "～",
+        "ko" => "~",
+        _ => "–",
+    }
+}
+
+/// The parts of one formatted end of a range, in order.
+pub(crate) type EndParts = Vec<(PartType, String)>;
+
+/// Splits the range of the formatted `start` and `end` into the parts to
+/// concatenate, each tagged with its source. Equal ends collapse into a
+/// single shared value, and the text after the number is written once when
+/// both ends start with their number and share that text.
+pub(crate) fn segments(
+    locale: &Locale,
+    mut start: EndParts,
+    mut end: EndParts,
+) -> Vec<(Atom, PartType, String)> {
+    let tagged = |parts: EndParts, source: Atom| {
+        parts
+            .into_iter()
+            .map(move |(part_type, value)| (source, part_type, value))
+    };
+    if start == end {
+        return tagged(start, atoms::shared()).collect();
+    }
+
+    let suffix = if shares_suffix(&start, &end) {
+        end.truncate(1);
+        start.split_off(1)
+    } else {
+        Vec::new()
+    };
+
+    let mut segments: Vec<_> = tagged(start, atoms::start()).collect();
+    segments.push((
+        atoms::shared(),
+        atoms::literal().into(),
+        separator(locale).to_string(),
+    ));
+    segments.extend(tagged(end, atoms::range_end()));
+    segments.extend(tagged(suffix, atoms::shared()));
+    segments
+}
+
+/// Whether both ends start with their number and have the same text after
+/// it.
+fn shares_suffix(start: &EndParts, end: &EndParts) -> bool {
+    let leads_with_number = |parts: &EndParts| {
+        parts
+            .first()
+            .is_some_and(|(part_type, _)| *part_type == atoms::number())
+    };
+    leads_with_number(start) && leads_with_number(end) && start.len() > 1 && start[1..] == end[1..]
+}
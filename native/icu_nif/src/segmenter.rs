@@ -0,0 +1,180 @@
+//! Break iterators held as resources, so the breaks of a large text can be
+//! taken a chunk at a time instead of as one list.
+//!
+//! The resource keeps the segmenter, a copy of the text and the offset of
+//! the last break handed out. Each chunk resumes segmentation at that break,
+//! where the Unicode segmentation rules start afresh. Words and lines in
+//! Chinese, Japanese and the South East Asian scripts are split with a
+//! dictionary or LSTM model over the whole run of such characters instead,
+//! so a chunk ending within a run resumes from the start of the run.
+
+use std::sync::{Mutex, PoisonError};
+
+use icu::properties::props::{LineBreak, Script};
+use icu::properties::CodePointMapData;
+use icu::segmenter::options::{
+    LineBreakOptions, SentenceBreakInvariantOptions, WordBreakInvariantOptions,
+};
+use icu::segmenter::{
+    GraphemeClusterSegmenter, GraphemeClusterSegmenterBorrowed, LineSegmenter,
+    LineSegmenterBorrowed, SentenceSegmenter, SentenceSegmenterBorrowed, WordSegmenter,
+    WordSegmenterBorrowed,
+};
+use rustler::{Encoder, Env, NifResult, NifTaggedEnum, ResourceArc, Term};
+
+use crate::atoms;
+use crate::stats::{self, Counted};
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum SegmenterKind {
+    Grapheme,
+    Word,
+    Sentence,
+    Line,
+}
+
+enum Segmenter {
+    Grapheme(GraphemeClusterSegmenterBorrowed<'static>),
+    Word(WordSegmenterBorrowed<'static>),
+    Sentence(SentenceSegmenterBorrowed<'static>),
+    Line(LineSegmenterBorrowed<'static>),
+}
+
+impl Segmenter {
+    fn new(kind: SegmenterKind) -> Self {
+        match kind {
+            SegmenterKind::Grapheme => Self::Grapheme(GraphemeClusterSegmenter::new()),
+            SegmenterKind::Word => {
+                Self::Word(WordSegmenter::new_auto(WordBreakInvariantOptions::default()))
+            }
+            SegmenterKind::Sentence => Self::Sentence(SentenceSegmenter::new(
+                SentenceBreakInvariantOptions::default(),
+            )),
+            SegmenterKind::Line => Self::Line(LineSegmenter::new_auto(LineBreakOptions::default())),
+        }
+    }
+
+    /// The breaks of `text`, starting with 0 and ending with its length.
+    fn breaks<'t>(&self, text: &'t str) -> Box<dyn Iterator<Item = usize> + 't> {
+        match self {
+            Self::Grapheme(segmenter) => Box::new(segmenter.segment_str(text)),
+            Self::Word(segmenter) => Box::new(segmenter.segment_str(text)),
+            Self::Sentence(segmenter) => Box::new(segmenter.segment_str(text)),
+            Self::Line(segmenter) => Box::new(segmenter.segment_str(text)),
+        }
+    }
+
+    /// Where to resume segmentation to find the breaks after `cursor`: the
+    /// cursor itself, or the start of the complex-script run it ends within.
+    fn resume_point(&self, text: &str, cursor: usize) -> usize {
+        if matches!(self, Self::Grapheme(_) | Self::Sentence(_)) {
+            return cursor;
+        }
+        text[..cursor]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_complex(c))
+            .last()
+            .map_or(cursor, |(start, _)| start)
+    }
+}
+
+/// Whether words and lines containing `c` are split with a dictionary or
+/// LSTM model rather than by the Unicode rules.
+fn is_complex(c: char) -> bool {
+    CodePointMapData::<LineBreak>::new().get(c) == LineBreak::ComplexContext
+        || matches!(
+            CodePointMapData::<Script>::new().get(c),
+            Script::Han | Script::Hiragana | Script::Katakana
+        )
+}
+
+pub(crate) struct SegmentIteratorResource {
+    segmenter: Segmenter,
+    text: String,
+    /// The last break handed out, `None` until the first chunk is taken.
+    cursor: Mutex<Option<usize>>,
+}
+
+impl rustler::Resource for SegmentIteratorResource {}
+
+pub(crate) fn load(env: Env) -> bool {
+    env.register::<SegmentIteratorResource>().is_ok()
+}
+
+#[rustler::nif]
+pub(crate) fn segmenter_new<'a>(
+    env: Env<'a>,
+    kind_term: Term<'a>,
+    text: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::SegmenterNew, || {
+        segmenter_new_inner(env, kind_term, text)
+    })
+}
+
+fn segmenter_new_inner<'a>(env: Env<'a>, kind_term: Term<'a>, text: &str) -> NifResult<Term<'a>> {
+    let kind: SegmenterKind = match kind_term.decode() {
+        Ok(kind) => kind,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let resource = SegmentIteratorResource {
+        segmenter: Segmenter::new(kind),
+        text: text.to_string(),
+        cursor: Mutex::new(None),
+    };
+    Ok((atoms::ok(), ResourceArc::new(resource)).encode(env))
+}
+
+/// Takes the next `count` (at least one) breaks as byte offsets into the
+/// text, or `:done` once every break has been taken. The first chunk starts
+/// with 0 and the last one ends with the length of the text.
+#[rustler::nif]
+pub(crate) fn segments_next<'a>(
+    env: Env<'a>,
+    iterator_term: Term<'a>,
+    count: usize,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::SegmentsNext, || {
+        segments_next_inner(env, iterator_term, count)
+    })
+}
+
+fn segments_next_inner<'a>(
+    env: Env<'a>,
+    iterator_term: Term<'a>,
+    count: usize,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<SegmentIteratorResource> = match iterator_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_resource()).encode(env)),
+    };
+
+    let mut cursor = resource
+        .cursor
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let taken = *cursor;
+    let start = match taken {
+        Some(taken) => resource.segmenter.resume_point(&resource.text, taken),
+        None => 0,
+    };
+
+    // Breaks up to the cursor, which is itself a break, were already taken.
+    let breaks: Vec<usize> = resource
+        .segmenter
+        .breaks(&resource.text[start..])
+        .map(|offset| start + offset)
+        .skip_while(|&offset| taken.is_some_and(|taken| offset <= taken))
+        .take(count)
+        .collect();
+
+    match breaks.last() {
+        Some(&last) => {
+            *cursor = Some(last);
+            Ok((atoms::ok(), breaks).encode(env))
+        }
+        None => Ok(atoms::done().encode(env)),
+    }
+}
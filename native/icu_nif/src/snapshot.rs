@@ -0,0 +1,202 @@
+//! Snapshots of formatter output across many locales, for test suites that
+//! want to notice when an ICU4X data update changes what users see.
+//!
+//! A snapshot formats the same samples in every locale and reduces the
+//! output to a digest per locale and one for the whole snapshot. The digest
+//! is 64-bit FNV-1a, which is fixed by its definition rather than by the
+//! Rust release the NIF is built with, so it can be checked into a test.
+
+use std::collections::BTreeMap;
+
+use icu::locale::Locale;
+use rustler::types::map::map_new;
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, NifTaggedEnum, Term};
+
+use crate::atoms;
+use crate::list::ListFormatterResource;
+use crate::locale::LocaleArg;
+use crate::number::NumberFormatterResource;
+use crate::options::{self, OptionError};
+use crate::stats::{self, Counted};
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum SnapshotKind {
+    Number,
+    List,
+}
+
+/// Numbers covering signs, grouping, fraction rounding and digits beyond
+/// 64 bits.
+const NUMBER_SAMPLES: &[&str] = &[
+    "0",
+    "1",
+    "-1",
+    "0.5",
+    "1.25",
+    "0.001",
+    "1000",
+    "-1234.5678",
+    "1234567.891",
+    "123456789012345678901",
+];
+
+/// Lists of every length the patterns distinguish, plus an item starting
+/// with "I" for languages whose conjunction depends on the next word.
+const LIST_SAMPLES: &[&[&str]] = &[
+    &["Alpha"],
+    &["Alpha", "Beta"],
+    &["Alpha", "Iota"],
+    &["Alpha", "Beta", "Gamma"],
+    &["Alpha", "Beta", "Gamma", "Delta"],
+];
+
+#[derive(NifMap)]
+struct LocaleSnapshot {
+    digest: String,
+    outputs: Vec<String>,
+}
+
+/// 64-bit FNV-1a over a sequence of strings, each terminated by a zero byte
+/// so that `["ab", "c"]` and `["a", "bc"]` differ.
+struct Digest(u64);
+
+impl Digest {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, value: &str) {
+        for byte in value.bytes().chain([0]) {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+/// Formats `samples`, or the built-in samples of `kind` when `nil`, in every
+/// locale with the formatter options of `kind`.
+///
+/// Locales are keyed by their canonical form, and the overall digest covers
+/// them in sorted order, so it does not depend on the order they are given
+/// in.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub(crate) fn icu_snapshot<'a>(
+    env: Env<'a>,
+    kind_term: Term<'a>,
+    locales_term: Term<'a>,
+    options_term: Term<'a>,
+    samples_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::IcuSnapshot, || {
+        icu_snapshot_inner(env, kind_term, locales_term, options_term, samples_term)
+    })
+}
+
+fn icu_snapshot_inner<'a>(
+    env: Env<'a>,
+    kind_term: Term<'a>,
+    locales_term: Term<'a>,
+    options_term: Term<'a>,
+    samples_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let kind: SnapshotKind = match kind_term.decode() {
+        Ok(kind) => kind,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let locales: Vec<LocaleArg> = match locales_term.decode() {
+        Ok(locales) => locales,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let samples = if options::is_nil(samples_term) {
+        default_samples(env, kind)
+    } else {
+        match samples_term.decode::<Vec<Term<'a>>>() {
+            Ok(samples) => samples,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+        }
+    };
+
+    let mut snapshots = BTreeMap::new();
+    for LocaleArg(locale) in locales {
+        let name = locale.to_string();
+        let outputs = match kind {
+            SnapshotKind::Number => format_numbers(locale, options_term, &samples),
+            SnapshotKind::List => {
+                format_lists(locale, options_term, &samples).map_err(OptionError::from)
+            }
+        };
+        let outputs = match outputs {
+            Ok(outputs) => outputs,
+            Err(reason) => return Ok((atoms::error(), reason).encode(env)),
+        };
+
+        let mut digest = Digest::new();
+        outputs.iter().for_each(|output| digest.write(output));
+        let snapshot = LocaleSnapshot {
+            digest: digest.finish(),
+            outputs,
+        };
+        snapshots.insert(name, snapshot);
+    }
+
+    let mut digest = Digest::new();
+    for (name, snapshot) in &snapshots {
+        digest.write(name);
+        digest.write(&snapshot.digest);
+    }
+
+    let mut locales = map_new(env);
+    for (name, snapshot) in snapshots {
+        locales = locales.map_put(name, snapshot)?;
+    }
+    let snapshot = map_new(env)
+        .map_put(atoms::digest(), digest.finish())?
+        .map_put(atoms::locales(), locales)?;
+    Ok((atoms::ok(), snapshot).encode(env))
+}
+
+fn default_samples(env: Env, kind: SnapshotKind) -> Vec<Term> {
+    match kind {
+        SnapshotKind::Number => NUMBER_SAMPLES
+            .iter()
+            .map(|sample| sample.encode(env))
+            .collect(),
+        SnapshotKind::List => LIST_SAMPLES
+            .iter()
+            .map(|sample| sample.encode(env))
+            .collect(),
+    }
+}
+
+fn format_numbers(
+    locale: Locale,
+    options_term: Term,
+    samples: &[Term],
+) -> Result<Vec<String>, OptionError> {
+    let formatter = NumberFormatterResource::try_new(locale, options_term)?;
+    samples
+        .iter()
+        .map(|sample| {
+            formatter
+                .format(*sample)
+                .map_err(|_| atoms::invalid_number().into())
+        })
+        .collect()
+}
+
+fn format_lists(locale: Locale, options_term: Term, samples: &[Term]) -> Result<Vec<String>, Atom> {
+    let formatter = ListFormatterResource::try_new(locale, options_term)?;
+    samples
+        .iter()
+        .map(|sample| match sample.decode::<Vec<&str>>() {
+            Ok(items) if !items.is_empty() => Ok(formatter.format(&items)),
+            _ => Err(atoms::invalid_items()),
+        })
+        .collect()
+}
@@ -0,0 +1,312 @@
+//! Spelled-out cardinal numbers.
+//!
+//! ICU4X does not implement rule-based number formatting (RBNF), so the
+//! CLDR `spellout-cardinal` rules for the supported languages are bundled
+//! here as code. CLDR defines `spellout-numbering` as `spellout-cardinal`
+//! for the integers of both languages, so the two rule sets share them.
+
+use fixed_decimal::Sign;
+use icu::decimal::options::DecimalFormatterOptions;
+use icu::decimal::DecimalFormatter;
+use rustler::{Encoder, Env, NifResult, Term};
+use writeable::Writeable;
+
+use crate::atoms;
+use crate::locale::LocaleArg;
+use crate::number;
+use crate::stats::{self, Counted};
+
+/// Numbers from here on are written in digits, as the CLDR rules do past
+/// their largest scale.
+const SPELLED_LIMIT: u128 = 1_000_000_000_000_000_000_000;
+
+#[rustler::nif]
+pub(crate) fn number_spellout_format<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    number_term: Term<'a>,
+    ruleset: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::NumberSpelloutFormat, || {
+        number_spellout_format_inner(env, locale_term, number_term, ruleset)
+    })
+}
+
+fn number_spellout_format_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    number_term: Term<'a>,
+    ruleset: &str,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let mut decimal = match number::term_to_integer(number_term) {
+        Ok(decimal) => decimal,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    if !matches!(ruleset, "spellout-cardinal" | "spellout-numbering") {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    }
+
+    let cardinal = match locale_arg.0.id.language.as_str() {
+        "en" => english::cardinal,
+        "de" => german::cardinal,
+        _ => return Ok((atoms::error(), atoms::unsupported_locale()).encode(env)),
+    };
+
+    let negative = decimal.sign() == Sign::Negative;
+    decimal.set_sign(Sign::None);
+    let magnitude = decimal
+        .to_string()
+        .parse::<u128>()
+        .ok()
+        .filter(|magnitude| *magnitude < SPELLED_LIMIT);
+    let words = match magnitude {
+        Some(magnitude) => cardinal(magnitude),
+        None => match DecimalFormatter::try_new(
+            locale_arg.0.clone().into(),
+            DecimalFormatterOptions::default(),
+        ) {
+            Ok(formatter) => formatter.format(&decimal).write_to_string().into_owned(),
+            Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+        },
+    };
+
+    let spelled = if negative {
+        format!("minus {words}")
+    } else {
+        words
+    };
+    Ok((atoms::ok(), spelled).encode(env))
+}
+
+/// Powers of 1000 paired with their scale index, largest first.
+const SCALES: [(u128, usize); 6] = [
+    (1_000_000_000_000_000_000, 6),
+    (1_000_000_000_000_000, 5),
+    (1_000_000_000_000, 4),
+    (1_000_000_000, 3),
+    (1_000_000, 2),
+    (1_000, 1),
+];
+
+mod english {
+    use super::SCALES;
+
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    const SCALE_NAMES: [&str; 7] = [
+        "",
+        "thousand",
+        "million",
+        "billion",
+        "trillion",
+        "quadrillion",
+        "quintillion",
+    ];
+
+    pub(super) fn cardinal(mut number: u128) -> String {
+        if number == 0 {
+            return ONES[0].to_string();
+        }
+
+        let mut words = Vec::new();
+        for (scale, index) in SCALES {
+            if number >= scale {
+                words.push(format!(
+                    "{} {}",
+                    below_thousand(number / scale),
+                    SCALE_NAMES[index]
+                ));
+                number %= scale;
+            }
+        }
+        if number > 0 {
+            words.push(below_thousand(number));
+        }
+
+        words.join(" ")
+    }
+
+    fn below_thousand(number: u128) -> String {
+        let hundreds = number / 100;
+        let rest = number % 100;
+
+        match (hundreds, rest) {
+            (0, rest) => below_hundred(rest),
+            (hundreds, 0) => format!("{} hundred", ONES[hundreds as usize]),
+            (hundreds, rest) => format!(
+                "{} hundred {}",
+                ONES[hundreds as usize],
+                below_hundred(rest)
+            ),
+        }
+    }
+
+    fn below_hundred(number: u128) -> String {
+        match number {
+            0..=19 => ONES[number as usize].to_string(),
+            _ if number % 10 == 0 => TENS[(number / 10) as usize].to_string(),
+            _ => format!(
+                "{}-{}",
+                TENS[(number / 10) as usize],
+                ONES[(number % 10) as usize]
+            ),
+        }
+    }
+}
+
+mod german {
+    use super::SCALES;
+
+    const ONES: [&str; 20] = [
+        "null",
+        "eins",
+        "zwei",
+        "drei",
+        "vier",
+        "fünf",
+        "sechs",
+        "sieben",
+        "acht",
+        "neun",
+        "zehn",
+        "elf",
+        "zwölf",
+        "dreizehn",
+        "vierzehn",
+        "fünfzehn",
+        "sechzehn",
+        "siebzehn",
+        "achtzehn",
+        "neunzehn",
+    ];
+
+    const TENS: [&str; 10] = [
+        "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig",
+        "neunzig",
+    ];
+
+    /// Singular and plural names of the scales from a million upwards.
+    const LARGE_SCALES: [(&str, &str); 7] = [
+        ("", ""),
+        ("", ""),
+        ("Million", "Millionen"),
+        ("Milliarde", "Milliarden"),
+        ("Billion", "Billionen"),
+        ("Billiarde", "Billiarden"),
+        ("Trillion", "Trillionen"),
+    ];
+
+    pub(super) fn cardinal(mut number: u128) -> String {
+        if number == 0 {
+            return ONES[0].to_string();
+        }
+
+        // Scales from a million up are separate feminine nouns ("eine
+        // Million"); thousands and below are written as a single word.
+        let mut words = Vec::new();
+        for (scale, index) in SCALES {
+            if number < scale || index == 1 {
+                continue;
+            }
+            let count = number / scale;
+            let (singular, plural) = LARGE_SCALES[index];
+            if count == 1 {
+                words.push(format!("eine {singular}"));
+            } else {
+                words.push(format!("{} {plural}", compound(count)));
+            }
+            number %= scale;
+        }
+        if number > 0 {
+            words.push(below_million(number));
+        }
+
+        words.join(" ")
+    }
+
+    fn below_million(number: u128) -> String {
+        let thousands = number / 1000;
+        let rest = number % 1000;
+
+        let mut word = String::new();
+        if thousands > 0 {
+            word.push_str(&compound(thousands));
+            word.push_str("tausend");
+        }
+        if rest > 0 {
+            word.push_str(&below_thousand(rest, true));
+        }
+        word
+    }
+
+    /// Spells a count that is followed by another word ("ein", not "eins").
+    fn compound(number: u128) -> String {
+        below_thousand(number, false)
+    }
+
+    fn below_thousand(number: u128, standalone: bool) -> String {
+        let hundreds = number / 100;
+        let rest = number % 100;
+
+        let mut word = String::new();
+        if hundreds > 0 {
+            word.push_str(unit(hundreds, false));
+            word.push_str("hundert");
+        }
+        if rest > 0 {
+            word.push_str(&below_hundred(rest, standalone));
+        }
+        word
+    }
+
+    fn below_hundred(number: u128, standalone: bool) -> String {
+        match number {
+            0..=19 => unit(number, standalone).to_string(),
+            _ if number % 10 == 0 => TENS[(number / 10) as usize].to_string(),
+            _ => format!(
+                "{}und{}",
+                unit(number % 10, false),
+                TENS[(number / 10) as usize]
+            ),
+        }
+    }
+
+    fn unit(number: u128, standalone: bool) -> &'static str {
+        if number == 1 && !standalone {
+            "ein"
+        } else {
+            ONES[number as usize]
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! UTS #39 confusable skeletons.
+//!
+//! ICU4X does not ship the `confusables.txt` data, so the prototypes below
+//! cover the common Latin look-alikes from Cyrillic, Greek and the fullwidth
+//! forms, plus the classic ASCII confusables.
+
+use icu::normalizer::DecomposingNormalizerBorrowed;
+use rustler::{Encoder, Env, NifResult, Term};
+
+use crate::stats::{self, Counted};
+
+#[rustler::nif]
+pub(crate) fn spoof_skeleton<'a>(env: Env<'a>, string: &str) -> NifResult<Term<'a>> {
+    stats::measure(Counted::SpoofSkeleton, || spoof_skeleton_inner(env, string))
+}
+
+fn spoof_skeleton_inner<'a>(env: Env<'a>, string: &str) -> NifResult<Term<'a>> {
+    Ok(skeleton(string).encode(env))
+}
+
+#[rustler::nif]
+pub(crate) fn spoof_confusable<'a>(env: Env<'a>, left: &str, right: &str) -> NifResult<Term<'a>> {
+    stats::measure(Counted::SpoofConfusable, || {
+        spoof_confusable_inner(env, left, right)
+    })
+}
+
+fn spoof_confusable_inner<'a>(env: Env<'a>, left: &str, right: &str) -> NifResult<Term<'a>> {
+    Ok((skeleton(left) == skeleton(right)).encode(env))
+}
+
+/// `skeleton(X) = NFD(map(NFD(X)))` as defined by UTS #39, section 4.
+fn skeleton(input: &str) -> String {
+    let nfd = DecomposingNormalizerBorrowed::new_nfd();
+    let decomposed = nfd.normalize(input);
+
+    let mut mapped = String::with_capacity(decomposed.len());
+    for c in decomposed.chars() {
+        push_prototype(&mut mapped, c);
+    }
+
+    nfd.normalize(&mapped).into_owned()
+}
+
+fn push_prototype(out: &mut String, c: char) {
+    // Fullwidth ASCII variants map onto their ASCII counterpart, which may
+    // itself have a prototype (e.g. fullwidth "I" -> "I" -> "l").
+    if ('\u{FF01}'..='\u{FF5E}').contains(&c) {
+        if let Some(ascii) = char::from_u32(c as u32 - 0xFEE0) {
+            push_prototype(out, ascii);
+            return;
+        }
+    }
+
+    match prototype(c) {
+        Some(prototype) => out.push_str(prototype),
+        None => out.push(c),
+    }
+}
+
+fn prototype(c: char) -> Option<&'static str> {
+    let prototype = match c {
+        // ASCII
+        '0' => "O",
+        '1' | 'I' | '|' => "l",
+        'm' => "rn",
+        // Cyrillic
+        'а' => "a",
+        'с' => "c",
+        'ԁ' => "d",
+        'е' => "e",
+        'һ' => "h",
+        'і' => "i",
+        'ј' => "j",
+        'ӏ' | 'І' | 'Ӏ' => "l",
+        'о' => "o",
+        'р' => "p",
+        'ԛ' => "q",
+        'ѕ' => "s",
+        'у' => "y",
+        'ԝ' => "w",
+        'х' => "x",
+        'А' => "A",
+        'В' => "B",
+        'С' => "C",
+        'Е' => "E",
+        'Н' => "H",
+        'Ј' => "J",
+        'К' => "K",
+        'М' => "M",
+        'О' => "O",
+        'Р' => "P",
+        'Ѕ' => "S",
+        'Т' => "T",
+        'Х' => "X",
+        'Ү' => "Y",
+        // Greek
+        'α' => "a",
+        'ι' => "i",
+        'ν' => "v",
+        'ο' => "o",
+        'ρ' => "p",
+        'Α' => "A",
+        'Β' => "B",
+        'Ε' => "E",
+        'Ζ' => "Z",
+        'Η' => "H",
+        'Ι' => "l",
+        'Κ' => "K",
+        'Μ' => "M",
+        'Ν' => "N",
+        'Ο' => "O",
+        'Ρ' => "P",
+        'Τ' => "T",
+        'Χ' => "X",
+        'Υ' => "Y",
+        // Latin
+        'ɡ' => "g",
+        'ı' => "i",
+        _ => return None,
+    };
+
+    Some(prototype)
+}
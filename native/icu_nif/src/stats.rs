@@ -0,0 +1,265 @@
+//! Opt-in counters of calls, errors and time spent in each NIF.
+//!
+//! Counting is off until `icu_stats_enable` turns it on, so the only cost of
+//! an instrumented NIF is then a relaxed atomic load. The counters are plain
+//! atomics, updated without locking from whichever scheduler thread runs the
+//! NIF.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+use rustler::types::tuple::get_tuple;
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, Term, TermType};
+
+use crate::atoms;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Declares the counted NIFs, each with an index into `COUNTERS`.
+macro_rules! counted_nifs {
+    ($($variant:ident => $name:literal,)*) => {
+        /// A NIF whose calls are counted.
+        #[derive(Clone, Copy)]
+        pub(crate) enum Counted {
+            $($variant,)*
+        }
+
+        const NAMES: &[&str] = &[$($name,)*];
+    };
+}
+
+counted_nifs! {
+    CalendarDateToRataDie => "calendar_date_to_rata_die",
+    CalendarDateFromRataDie => "calendar_date_from_rata_die",
+    CalendarJapaneseEras => "calendar_japanese_eras",
+    CalendarJapaneseEraForDate => "calendar_japanese_era_for_date",
+    CalendarWeekend => "calendar_weekend",
+    CalendarYearInfo => "calendar_year_info",
+    CalendarMonths => "calendar_months",
+    CalendarDateAdd => "calendar_date_add",
+    CalendarDateDiff => "calendar_date_diff",
+    CollatorNew => "collator_new",
+    CollatorCompare => "collator_compare",
+    CollatorEqual => "collator_equal",
+    CollatorEqualIgnoring => "collator_equal_ignoring",
+    CollatorResolvedOptions => "collator_resolved_options",
+    CollatorSort => "collator_sort",
+    CollatorUnique => "collator_unique",
+    CollatorFind => "collator_find",
+    CurrencyFractions => "currency_fractions",
+    CurrencyRegionCurrencies => "currency_region_currencies",
+    CurrencyFormatterNew => "currency_formatter_new",
+    CurrencyFormat => "currency_format",
+    CurrencyFormatToParts => "currency_format_to_parts",
+    CurrencyFormatRange => "currency_format_range",
+    CurrencyFormatRangeToParts => "currency_format_range_to_parts",
+    TemporalFormatterNew => "temporal_formatter_new",
+    TemporalDateFormatterNew => "temporal_date_formatter_new",
+    TemporalTimeFormatterNew => "temporal_time_formatter_new",
+    TemporalZonedDatetimeFormatterNew => "temporal_zoned_datetime_formatter_new",
+    TemporalFormatterFromPattern => "temporal_formatter_from_pattern",
+    TemporalFormat => "temporal_format",
+    TemporalFormatToParts => "temporal_format_to_parts",
+    TemporalFormatRfc => "temporal_format_rfc",
+    TemporalValidate => "temporal_validate",
+    TemporalFormatterPattern => "temporal_formatter_pattern",
+    TemporalDayPeriods => "temporal_day_periods",
+    TemporalRelativeDayName => "temporal_relative_day_name",
+    DecimalNew => "decimal_new",
+    DecimalRound => "decimal_round",
+    DecimalToString => "decimal_to_string",
+    DisplayNamesFormatterNew => "display_names_formatter_new",
+    DisplayNamesOf => "display_names_of",
+    DisplayNamesOfStyled => "display_names_of_styled",
+    DisplayNamesRegionFlag => "display_names_region_flag",
+    FormatterDataLocale => "formatter_data_locale",
+    FormatterMemory => "formatter_memory",
+    FormatterWarmup => "formatter_warmup",
+    GrammarInfo => "grammar_info",
+    LocaleValidateExtensionValue => "locale_validate_extension_value",
+    ListFormatterNew => "list_formatter_new",
+    ListFormat => "list_format",
+    ListFormatToParts => "list_format_to_parts",
+    LocaleFromString => "locale_from_string",
+    LocaleToString => "locale_to_string",
+    LocaleGetComponents => "locale_get_components",
+    LocaleMaximize => "locale_maximize",
+    LocaleMinimize => "locale_minimize",
+    LocaleMinimizeFavorScript => "locale_minimize_favor_script",
+    LocaleFallbacks => "locale_fallbacks",
+    LocaleParents => "locale_parents",
+    LocaleDistance => "locale_distance",
+    LocaleCompare => "locale_compare",
+    LocaleHash => "locale_hash",
+    LocaleLanguageRegions => "locale_language_regions",
+    LocaleLanguageScripts => "locale_language_scripts",
+    LocaleMatchGettext => "locale_match_gettext",
+    LocaleFilterSupported => "locale_filter_supported",
+    LocaleSetHourCycle => "locale_set_hour_cycle",
+    LocaleSetExtension => "locale_set_extension",
+    LocaleGetHourCycle => "locale_get_hour_cycle",
+    LocaleHourCycle => "locale_hour_cycle",
+    NormalizerNfcConcat => "normalizer_nfc_concat",
+    NormalizerCanonicalEqual => "normalizer_canonical_equal",
+    NormalizerNormalizedUpTo => "normalizer_normalized_up_to",
+    NumberFormatterNew => "number_formatter_new",
+    NumberFormat => "number_format",
+    NumberFormatToParts => "number_format_to_parts",
+    NumberFormatRange => "number_format_range",
+    NumberFormatRangeToParts => "number_format_range_to_parts",
+    NumberOrdinalFormat => "number_ordinal_format",
+    NumberPercentFormat => "number_percent_format",
+    NumberDigitsToLatin => "number_digits_to_latin",
+    NumberDigitsFromLatin => "number_digits_from_latin",
+    NumberNumberingSystems => "number_numbering_systems",
+    NumberSymbols => "number_symbols",
+    NumberGroupingSizes => "number_grouping_sizes",
+    PropertiesIsEmoji => "properties_is_emoji",
+    PropertiesContainsEmoji => "properties_contains_emoji",
+    PropertiesEmojiSegments => "properties_emoji_segments",
+    SegmenterNew => "segmenter_new",
+    SegmentsNext => "segments_next",
+    IcuSnapshot => "icu_snapshot",
+    NumberSpelloutFormat => "number_spellout_format",
+    SpoofSkeleton => "spoof_skeleton",
+    SpoofConfusable => "spoof_confusable",
+    CommonTerms => "common_terms",
+    TextQuote => "text_quote",
+    TextTruncate => "text_truncate",
+    TextDisplayWidth => "text_display_width",
+    TextTitlecaseWords => "text_titlecase_words",
+    TextWordStats => "text_word_stats",
+    TextInsertSoftBreaks => "text_insert_soft_breaks",
+    UnitFormatterNew => "unit_formatter_new",
+    UnitFormat => "unit_format",
+    UnitFormatToParts => "unit_format_to_parts",
+    UnitResolvedOptions => "unit_resolved_options",
+    UnitFormatRange => "unit_format_range",
+    UnitPatterns => "unit_patterns",
+    UnitConvert => "unit_convert",
+    UnitConvertForLocale => "unit_convert_for_locale",
+    UnitPreferredUnits => "unit_preferred_units",
+    UnitFormatMixed => "unit_format_mixed",
+}
+
+struct Counter {
+    calls: AtomicU64,
+    errors: AtomicU64,
+    nanos: AtomicU64,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const COUNTER: Counter = Counter {
+    calls: AtomicU64::new(0),
+    errors: AtomicU64::new(0),
+    nanos: AtomicU64::new(0),
+};
+
+static COUNTERS: [Counter; NAMES.len()] = [COUNTER; NAMES.len()];
+
+/// Runs the body of a counted NIF, recording the call when counting is on.
+///
+/// A call counts as an error when the NIF raises or returns a tuple tagged
+/// `:error`.
+pub(crate) fn measure<R: Outcome>(nif: Counted, body: impl FnOnce() -> R) -> R {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return body();
+    }
+
+    let start = Instant::now();
+    let result = body();
+    let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+
+    let counter = &COUNTERS[nif as usize];
+    counter.calls.fetch_add(1, Ordering::Relaxed);
+    counter.nanos.fetch_add(nanos, Ordering::Relaxed);
+    if result.is_error() {
+        counter.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    result
+}
+
+/// The value a counted NIF returns.
+pub(crate) trait Outcome {
+    fn is_error(&self) -> bool;
+}
+
+impl Outcome for NifResult<Term<'_>> {
+    fn is_error(&self) -> bool {
+        match self {
+            Ok(term) => is_error_tuple(*term),
+            Err(_) => true,
+        }
+    }
+}
+
+impl Outcome for Term<'_> {
+    fn is_error(&self) -> bool {
+        is_error_tuple(*self)
+    }
+}
+
+impl Outcome for String {
+    fn is_error(&self) -> bool {
+        false
+    }
+}
+
+fn is_error_tuple(term: Term) -> bool {
+    if term.get_type() != TermType::Tuple {
+        return false;
+    }
+
+    get_tuple(term)
+        .ok()
+        .and_then(|elements| elements.first().map(|tag| tag.decode::<Atom>()))
+        .is_some_and(|tag| tag.ok() == Some(atoms::error()))
+}
+
+#[derive(NifMap)]
+struct NifStats {
+    calls: u64,
+    errors: u64,
+    total_time: u64,
+}
+
+/// Turns counting on or off. The counters keep their values either way.
+#[rustler::nif]
+pub(crate) fn icu_stats_enable(enabled: bool) -> Atom {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    atoms::ok()
+}
+
+/// Returns `{name, %{calls, errors, total_time}}` for every NIF called since
+/// the last reset, with the time in nanoseconds.
+#[rustler::nif]
+pub(crate) fn icu_stats(env: Env) -> Term {
+    let stats: Vec<(Atom, NifStats)> = NAMES
+        .iter()
+        .zip(COUNTERS.iter())
+        .filter(|(_, counter)| counter.calls.load(Ordering::Relaxed) > 0)
+        .filter_map(|(name, counter)| {
+            let name = Atom::from_str(env, name).ok()?;
+            let stats = NifStats {
+                calls: counter.calls.load(Ordering::Relaxed),
+                errors: counter.errors.load(Ordering::Relaxed),
+                total_time: counter.nanos.load(Ordering::Relaxed),
+            };
+            Some((name, stats))
+        })
+        .collect();
+
+    stats.encode(env)
+}
+
+/// Sets every counter back to zero.
+#[rustler::nif]
+pub(crate) fn icu_stats_reset() -> Atom {
+    for counter in &COUNTERS {
+        counter.calls.store(0, Ordering::Relaxed);
+        counter.errors.store(0, Ordering::Relaxed);
+        counter.nanos.store(0, Ordering::Relaxed);
+    }
+    atoms::ok()
+}
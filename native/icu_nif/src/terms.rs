@@ -0,0 +1,198 @@
+//! Short localized strings that UI code needs on their own: the words that
+//! join list items, quotation marks and the ellipsis, and quoting text with
+//! the marks.
+//!
+//! The list words come from the ICU4X list patterns. ICU4X does not ship the
+//! CLDR `delimiters` and `characters` data, so the quotation marks of the
+//! common locales are listed below and looked up along the locale fallback
+//! chain. Other locales have no marks rather than guessed ones, and fail with
+//! `no_data`. The ellipsis does not depend on the marks: every locale gets
+//! the CLDR root one.
+
+use icu::list::options::ListFormatterOptions;
+use icu::list::ListFormatter;
+use icu::locale::fallback::LocaleFallbackConfig;
+use icu::locale::{Locale, LocaleFallbacker};
+use rustler::{Atom, Encoder, Env, NifResult, NifTaggedEnum, Term};
+use writeable::Writeable;
+
+use crate::atoms;
+use crate::locale::LocaleArg;
+use crate::stats::{self, Counted};
+
+#[derive(NifTaggedEnum, Clone, Copy)]
+enum CommonTerm {
+    And,
+    Or,
+    QuotationStart,
+    QuotationEnd,
+    AlternateQuotationStart,
+    AlternateQuotationEnd,
+    Ellipsis,
+}
+
+/// The CLDR root ellipsis. The locales inherit it and only tailor where it
+/// goes around the text, which truncation handles, so it is the ellipsis of
+/// every locale.
+pub(crate) const ELLIPSIS: &str = "…";
+
+/// The CLDR `delimiters` of a locale: the start and end of a quotation,
+/// then the start and end of a quotation nested in another.
+type QuotationMarks = [&'static str; 4];
+
+const QUOTATION_MARKS: &[(&str, QuotationMarks)] = &[
+    ("cs", ["„", "“", "‚", "‘"]),
+    ("da", ["“", "”", "‘", "’"]),
+    ("de", ["„", "“", "‚", "‘"]),
+    ("de-CH", ["«", "»", "‹", "›"]),
+    ("el", ["«", "»", "“", "”"]),
+    ("en", ["“", "”", "‘", "’"]),
+    ("es", ["«", "»", "“", "”"]),
+    ("et", ["„", "“", "‚", "‘"]),
+    ("fi", ["”", "”", "’", "’"]),
+    ("fr", ["«", "»", "«", "»"]),
+    ("fr-CH", ["«", "»", "‹", "›"]),
+    ("hu", ["„", "”", "»", "«"]),
+    ("it", ["«", "»", "“", "”"]),
+    ("ja", ["「", "」", "『", "』"]),
+    ("ko", ["“", "”", "‘", "’"]),
+    ("lt", ["„", "“", "„", "“"]),
+    ("nb", ["«", "»", "‘", "’"]),
+    ("nl", ["‘", "’", "“", "”"]),
+    ("nn", ["«", "»", "‘", "’"]),
+    ("no", ["«", "»", "‘", "’"]),
+    ("pl", ["„", "”", "«", "»"]),
+    ("pt", ["“", "”", "‘", "’"]),
+    ("pt-PT", ["«", "»", "“", "”"]),
+    ("ro", ["„", "”", "«", "»"]),
+    ("ru", ["«", "»", "„", "“"]),
+    ("sk", ["„", "“", "‚", "‘"]),
+    ("sv", ["”", "”", "’", "’"]),
+    ("tr", ["“", "”", "‘", "’"]),
+    ("uk", ["«", "»", "„", "“"]),
+    ("zh", ["“", "”", "‘", "’"]),
+    ("zh-Hant", ["「", "」", "『", "』"]),
+];
+
+/// The quotation marks of the closest locale in the fallback chain of
+/// `locale` that has an entry, e.g. `"de"` for `"de-AT"`, or `None` when no
+/// locale in the chain has one.
+fn quotation_marks(locale: &Locale) -> Option<QuotationMarks> {
+    let fallbacker = LocaleFallbacker::new();
+    let mut fallback_iterator = fallbacker
+        .for_config(LocaleFallbackConfig::default())
+        .fallback_for(locale.clone().into());
+
+    while !fallback_iterator.get().is_unknown() {
+        let id = fallback_iterator.get().to_string();
+        if let Some((_, marks)) = QUOTATION_MARKS.iter().find(|(name, _)| *name == id) {
+            return Some(*marks);
+        }
+        fallback_iterator.step();
+    }
+    None
+}
+
+/// The literal between the two items of a two item list, without the
+/// spaces around it, e.g. `"and"` for English.
+fn list_word(locale: &Locale, term: CommonTerm) -> Result<String, Atom> {
+    let options = ListFormatterOptions::default();
+    let formatter = match term {
+        CommonTerm::Or => ListFormatter::try_new_or(locale.clone().into(), options),
+        _ => ListFormatter::try_new_and(locale.clone().into(), options),
+    }
+    .map_err(|_| atoms::invalid_locale())?;
+
+    // Private use characters stand in for the items, so they cannot be
+    // mistaken for a part of the literal.
+    let list = formatter
+        .format(["\u{E000}", "\u{E001}"].into_iter())
+        .write_to_string();
+    let word = list
+        .split_once('\u{E000}')
+        .and_then(|(_, rest)| rest.split_once('\u{E001}'))
+        .map_or("", |(word, _)| word);
+    Ok(word.trim().to_string())
+}
+
+#[rustler::nif]
+pub(crate) fn common_terms<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    term_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::CommonTerms, || {
+        common_terms_inner(env, locale_term, term_term)
+    })
+}
+
+fn common_terms_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    term_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+    let term: CommonTerm = match term_term.decode() {
+        Ok(term) => term,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let locale = &locale_arg.0;
+    let string = match (term, quotation_marks(locale)) {
+        (CommonTerm::And | CommonTerm::Or, _) => match list_word(locale, term) {
+            Ok(word) => word,
+            Err(reason) => return Ok((atoms::error(), reason).encode(env)),
+        },
+        (CommonTerm::Ellipsis, _) => ELLIPSIS.to_string(),
+        (_, None) => return Ok((atoms::error(), atoms::no_data()).encode(env)),
+        (CommonTerm::QuotationStart, Some([start, ..])) => start.to_string(),
+        (CommonTerm::QuotationEnd, Some([_, end, ..])) => end.to_string(),
+        (CommonTerm::AlternateQuotationStart, Some([_, _, start, _])) => start.to_string(),
+        (CommonTerm::AlternateQuotationEnd, Some([.., end])) => end.to_string(),
+    };
+
+    Ok((atoms::ok(), string).encode(env))
+}
+
+/// Wraps `text` in the quotation marks of the locale, the `primary` marks
+/// or the `secondary` ones for a quotation inside another. Fails with
+/// `no_data` for a locale without listed marks.
+#[rustler::nif]
+pub(crate) fn text_quote<'a>(
+    env: Env<'a>,
+    text: &str,
+    locale_term: Term<'a>,
+    level: Atom,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TextQuote, || {
+        text_quote_inner(env, text, locale_term, level)
+    })
+}
+
+fn text_quote_inner<'a>(
+    env: Env<'a>,
+    text: &str,
+    locale_term: Term<'a>,
+    level: Atom,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let Some([start, end, alternate_start, alternate_end]) = quotation_marks(&locale_arg.0) else {
+        return Ok((atoms::error(), atoms::no_data()).encode(env));
+    };
+    let (start, end) = if level == atoms::primary() {
+        (start, end)
+    } else if level == atoms::secondary() {
+        (alternate_start, alternate_end)
+    } else {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    };
+
+    Ok((atoms::ok(), format!("{start}{text}{end}")).encode(env))
+}
@@ -0,0 +1,447 @@
+use std::collections::HashMap;
+
+use icu::casemap::options::{LeadingAdjustment, TitlecaseOptions, TrailingCase};
+use icu::casemap::{CaseMapper, TitlecaseMapper};
+use icu::properties::props::{Dash, EastAsianWidth, LineBreak, Script};
+use icu::properties::{CodePointMapData, CodePointSetData};
+use icu::segmenter::options::{LineBreakOptions, WordBreakInvariantOptions};
+use icu::segmenter::{GraphemeClusterSegmenter, LineSegmenter, WordSegmenter};
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, Term};
+
+use crate::atoms;
+use crate::locale::LocaleArg;
+use crate::options::{self, OptionError, UnknownKeys};
+use crate::stats::{self, Counted};
+use crate::terms;
+
+struct TruncateConfig {
+    ellipsis: String,
+    word_boundary: bool,
+}
+
+impl Default for TruncateConfig {
+    fn default() -> Self {
+        Self {
+            ellipsis: terms::ELLIPSIS.to_string(),
+            word_boundary: false,
+        }
+    }
+}
+
+#[derive(NifMap)]
+struct WordStats {
+    words: usize,
+    unique: usize,
+    top: Vec<(String, usize)>,
+}
+
+#[derive(Default)]
+struct TitlecaseConfig {
+    options: TitlecaseOptions,
+    /// Case-folded words kept lowercase unless they start or end the text.
+    stop_words: Vec<String>,
+}
+
+/// Truncates `text` to at most `max` grapheme clusters including the
+/// ellipsis. Text that already fits is returned unchanged. Clusters are
+/// counted regardless of their display width, so a wide character counts as
+/// one.
+#[rustler::nif]
+pub(crate) fn text_truncate<'a>(
+    env: Env<'a>,
+    text: &str,
+    max: usize,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TextTruncate, || {
+        text_truncate_inner(env, text, max, options_term)
+    })
+}
+
+fn text_truncate_inner<'a>(
+    env: Env<'a>,
+    text: &str,
+    max: usize,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let config = match decode_truncate_config(options_term) {
+        Ok(config) => config,
+        Err(error) => return Ok((atoms::error(), error).encode(env)),
+    };
+
+    let segmenter = GraphemeClusterSegmenter::new();
+    let boundaries: Vec<usize> = segmenter.segment_str(text).collect();
+    let length = boundaries.len().saturating_sub(1);
+
+    if length <= max {
+        return Ok((atoms::ok(), text).encode(env));
+    }
+
+    let ellipsis_length = segmenter
+        .segment_str(&config.ellipsis)
+        .count()
+        .saturating_sub(1);
+    if ellipsis_length > max {
+        return Ok((atoms::error(), atoms::invalid_options()).encode(env));
+    }
+
+    let mut cut = boundaries[max - ellipsis_length];
+
+    if config.word_boundary {
+        let word_cut = WordSegmenter::new_auto(WordBreakInvariantOptions::default())
+            .segment_str(text)
+            .take_while(|&boundary| boundary <= cut)
+            .last()
+            .unwrap_or(0);
+        // A single word longer than the limit is cut mid-word instead.
+        if !text[..word_cut].trim_end().is_empty() {
+            cut = word_cut;
+        }
+    }
+
+    let mut truncated = text[..cut].trim_end().to_string();
+    truncated.push_str(&config.ellipsis);
+
+    Ok((atoms::ok(), truncated).encode(env))
+}
+
+/// Computes the number of monospace columns `text` occupies.
+///
+/// Each grapheme cluster is measured as a whole: clusters containing a wide
+/// or fullwidth character (including default-emoji-presentation characters)
+/// or an emoji presentation selector take two columns, control characters
+/// take none, and East Asian ambiguous characters take one column unless
+/// `ambiguous: :wide` is given.
+#[rustler::nif]
+pub(crate) fn text_display_width<'a>(
+    env: Env<'a>,
+    text: &str,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TextDisplayWidth, || {
+        text_display_width_inner(env, text, options_term)
+    })
+}
+
+fn text_display_width_inner<'a>(
+    env: Env<'a>,
+    text: &str,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let ambiguous_width = match decode_ambiguous_width(options_term) {
+        Ok(width) => width,
+        Err(error) => return Ok((atoms::error(), error).encode(env)),
+    };
+
+    let widths = CodePointMapData::<EastAsianWidth>::new();
+    let boundaries: Vec<usize> = GraphemeClusterSegmenter::new().segment_str(text).collect();
+
+    let width: usize = boundaries
+        .windows(2)
+        .map(|window| {
+            let grapheme = &text[window[0]..window[1]];
+            let wide = grapheme.chars().any(|c| {
+                matches!(
+                    widths.get(c),
+                    EastAsianWidth::Wide | EastAsianWidth::Fullwidth
+                ) || c == '\u{FE0F}'
+            });
+
+            match grapheme.chars().next() {
+                _ if wide => 2,
+                Some(c) if c.is_control() => 0,
+                Some(c) if widths.get(c) == EastAsianWidth::Ambiguous => ambiguous_width,
+                _ => 1,
+            }
+        })
+        .sum();
+
+    Ok((atoms::ok(), width).encode(env))
+}
+
+fn decode_ambiguous_width(term: Term) -> Result<usize, OptionError> {
+    let mut width = 1;
+    decode_options(term, |key, value_term| {
+        if key == atoms::ambiguous() {
+            let value: Atom = value_term.decode().map_err(|_| ())?;
+            width = if value == atoms::narrow() {
+                1
+            } else if value == atoms::wide() {
+                2
+            } else {
+                return Err(());
+            };
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    Ok(width)
+}
+
+fn decode_truncate_config(term: Term) -> Result<TruncateConfig, OptionError> {
+    let mut config = TruncateConfig::default();
+    decode_options(term, |key, value_term| {
+        if key == atoms::ellipsis() {
+            config.ellipsis = value_term.decode().map_err(|_| ())?;
+        } else if key == atoms::word_boundary() {
+            config.word_boundary = value_term.decode().map_err(|_| ())?;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    Ok(config)
+}
+
+/// Walks the option map `term` with `options::decode_map`, failing with
+/// `{:invalid_option_value, key}` for a value `handle` rejects and with
+/// `invalid_options` for an unknown key.
+fn decode_options<'a>(
+    term: Term<'a>,
+    mut handle: impl FnMut(Atom, Term<'a>) -> Result<bool, ()>,
+) -> Result<(), OptionError> {
+    let mut invalid = None;
+    options::decode_map(term, UnknownKeys::Error, |key, value_term| {
+        handle(key, value_term).inspect_err(|_| invalid = Some(key))
+    })
+    .map_err(|_| match invalid {
+        Some(key) => OptionError::InvalidValue(key),
+        None => atoms::invalid_options().into(),
+    })
+}
+
+/// Titlecases every word of `text` with the casing rules of the locale, so
+/// Dutch "ijssel" becomes "IJssel" rather than "Ijssel".
+///
+/// Words are found with the Unicode word segmenter and each is titlecased
+/// as a segment of its own. Stop words are lowercased instead, except for
+/// the first and last word.
+#[rustler::nif]
+pub(crate) fn text_titlecase_words<'a>(
+    env: Env<'a>,
+    text: &str,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TextTitlecaseWords, || {
+        text_titlecase_words_inner(env, text, locale_term, options_term)
+    })
+}
+
+fn text_titlecase_words_inner<'a>(
+    env: Env<'a>,
+    text: &str,
+    locale_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let config = match decode_titlecase_config(options_term) {
+        Ok(config) => config,
+        Err(error) => return Ok((atoms::error(), error).encode(env)),
+    };
+
+    let langid = &locale_arg.0.id;
+    let titlecase = TitlecaseMapper::new();
+    let casemapper = CaseMapper::new();
+
+    let segments = word_segments(text);
+    let first = segments.iter().position(|&(_, word)| word);
+    let last = segments.iter().rposition(|&(_, word)| word);
+
+    let mut output = String::with_capacity(text.len());
+    for (index, &(segment, word)) in segments.iter().enumerate() {
+        if !word {
+            output.push_str(segment);
+            continue;
+        }
+
+        let folded = casemapper.fold_string(segment);
+        let stop_word = Some(index) != first
+            && Some(index) != last
+            && config.stop_words.iter().any(|word| *word == *folded);
+        let cased = if stop_word {
+            casemapper.lowercase_to_string(segment, langid)
+        } else {
+            titlecase.titlecase_segment_to_string(segment, langid, config.options)
+        };
+        output.push_str(&cased);
+    }
+
+    Ok((atoms::ok(), output).encode(env))
+}
+
+/// Counts the words of `text`, and the words that are the same once case
+/// folded, returning the `top` most frequent with their counts. Ties are
+/// broken by the folded word, so the result does not depend on the order
+/// words first appear in.
+#[rustler::nif]
+pub(crate) fn text_word_stats<'a>(env: Env<'a>, text: &str, top: usize) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TextWordStats, || {
+        text_word_stats_inner(env, text, top)
+    })
+}
+
+fn text_word_stats_inner<'a>(env: Env<'a>, text: &str, top: usize) -> NifResult<Term<'a>> {
+    let casemapper = CaseMapper::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut words = 0;
+
+    for (segment, _) in word_segments(text).into_iter().filter(|&(_, word)| word) {
+        words += 1;
+        *counts
+            .entry(casemapper.fold_string(segment).into())
+            .or_default() += 1;
+    }
+
+    let unique = counts.len();
+    let mut frequent: Vec<(String, usize)> = counts.into_iter().collect();
+    frequent.sort_unstable_by(|(left, left_count), (right, right_count)| {
+        right_count.cmp(left_count).then_with(|| left.cmp(right))
+    });
+    frequent.truncate(top);
+
+    let stats = WordStats {
+        words,
+        unique,
+        top: frequent,
+    };
+    Ok((atoms::ok(), stats).encode(env))
+}
+
+/// Inserts `marker` at the line break opportunities of `text` that are
+/// inside a run of text, so that a renderer may wrap there.
+///
+/// Breaks after whitespace or a dash already wrap without a marker, and so
+/// do breaks next to Chinese, Japanese and Korean characters, which
+/// renderers wrap on their own. A marker is never doubled, including by a
+/// soft hyphen already in the text. The locale tailors the breaks, such as
+/// for Japanese.
+#[rustler::nif]
+pub(crate) fn text_insert_soft_breaks<'a>(
+    env: Env<'a>,
+    text: &str,
+    locale_term: Term<'a>,
+    marker: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::TextInsertSoftBreaks, || {
+        text_insert_soft_breaks_inner(env, text, locale_term, marker)
+    })
+}
+
+fn text_insert_soft_breaks_inner<'a>(
+    env: Env<'a>,
+    text: &str,
+    locale_term: Term<'a>,
+    marker: &str,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let mut options = LineBreakOptions::default();
+    options.content_locale = Some(&locale_arg.0.id);
+    let segmenter = LineSegmenter::new_auto(options);
+    let dashes = CodePointSetData::new::<Dash>();
+
+    let mut output = String::with_capacity(text.len());
+    let mut start = 0;
+    for offset in segmenter.segment_str(text) {
+        output.push_str(&text[start..offset]);
+        start = offset;
+
+        let inside = match (
+            text[..offset].chars().next_back(),
+            text[offset..].chars().next(),
+        ) {
+            (Some(previous), Some(next)) => {
+                !previous.is_whitespace()
+                    && !dashes.contains(previous)
+                    && previous != '\u{AD}'
+                    && !wraps_unaided(previous)
+                    && !wraps_unaided(next)
+            }
+            _ => false,
+        };
+        if inside && !output.ends_with(marker) {
+            output.push_str(marker);
+        }
+    }
+
+    Ok((atoms::ok(), output).encode(env))
+}
+
+/// Whether renderers break lines next to `c` without a marker: ideographs,
+/// kana and Hangul syllables, by line break class or script.
+fn wraps_unaided(c: char) -> bool {
+    matches!(
+        CodePointMapData::<LineBreak>::new().get(c),
+        LineBreak::Ideographic
+            | LineBreak::ConditionalJapaneseStarter
+            | LineBreak::H2
+            | LineBreak::H3
+    ) || matches!(
+        CodePointMapData::<Script>::new().get(c),
+        Script::Han | Script::Hiragana | Script::Katakana
+    )
+}
+
+/// The segments of `text` between word breaks, including the spaces,
+/// punctuation and emoji between words, each with whether the segmenter
+/// classifies it as a word (letters, numbers or ideographs).
+fn word_segments(text: &str) -> Vec<(&str, bool)> {
+    let segmenter = WordSegmenter::new_auto(WordBreakInvariantOptions::default());
+    let mut breaks = segmenter.segment_str(text);
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while let Some(end) = breaks.next() {
+        if end > start {
+            segments.push((&text[start..end], breaks.is_word_like()));
+        }
+        start = end;
+    }
+    segments
+}
+
+fn decode_titlecase_config(term: Term) -> Result<TitlecaseConfig, OptionError> {
+    let mut config = TitlecaseConfig::default();
+    decode_options(term, |key, value_term| {
+        if key == atoms::leading_adjustment() {
+            let value: Atom = value_term.decode().map_err(|_| ())?;
+            config.options.leading_adjustment = Some(if value == atoms::auto() {
+                LeadingAdjustment::Auto
+            } else if value == atoms::none() {
+                LeadingAdjustment::None
+            } else if value == atoms::to_cased() {
+                LeadingAdjustment::ToCased
+            } else {
+                return Err(());
+            });
+        } else if key == atoms::trailing_case() {
+            let value: Atom = value_term.decode().map_err(|_| ())?;
+            config.options.trailing_case = Some(if value == atoms::lower() {
+                TrailingCase::Lower
+            } else if value == atoms::unchanged() {
+                TrailingCase::Unchanged
+            } else {
+                return Err(());
+            });
+        } else if key == atoms::stop_words() {
+            let words: Vec<&str> = value_term.decode().map_err(|_| ())?;
+            let mapper = CaseMapper::new();
+            config.stop_words = words
+                .into_iter()
+                .map(|word| mapper.fold_string(word).into())
+                .collect();
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    Ok(config)
+}
@@ -0,0 +1,1010 @@
+use std::ops::Range;
+
+use fixed_decimal::Decimal as FixedDecimal;
+use icu::decimal::options::DecimalFormatterOptions;
+use icu::decimal::DecimalFormatter;
+use icu::experimental::dimension::provider::units::display_names::UnitsDisplayNamesV1;
+use icu::experimental::dimension::provider::units::essentials::UnitsEssentialsV1;
+use icu::experimental::dimension::units::formatter::UnitsFormatter;
+use icu::experimental::dimension::units::options::{UnitsFormatterOptions, Width};
+use icu::experimental::measure::measureunit::MeasureUnit;
+use icu::experimental::provider::Baked as ExperimentalBaked;
+use icu::experimental::units::converter::UnitsConverter;
+use icu::experimental::units::converter_factory::ConverterFactory;
+use icu::list::options::{ListFormatterOptions, ListLength};
+use icu::list::ListFormatter;
+use icu::locale::extensions::unicode::key;
+use icu::locale::Locale;
+use icu::plurals::{PluralCategory, PluralRules};
+use icu_provider::prelude::*;
+use rustler::{Atom, Encoder, Env, NifMap, NifResult, ResourceArc, Term, TermType};
+use writeable::Writeable;
+
+use crate::atoms;
+use crate::formatter::{formatter_nifs, Formatter};
+use crate::index;
+use crate::locale::{self, LocaleArg};
+use crate::number;
+use crate::options::{decode_map, UnknownKeys};
+use crate::range;
+use crate::stats::{self, Counted};
+use crate::writeable_parts::{self, PartType, PartsCollector};
+
+pub(crate) struct UnitFormatterResource {
+    formatter: UnitFormatterKind,
+    unit: String,
+    width: Width,
+    locale: Locale,
+    approximately: bool,
+}
+
+impl rustler::Resource for UnitFormatterResource {}
+
+impl UnitFormatterResource {
+    /// The locale of the number symbols the formatter loaded.
+    pub(crate) fn data_locale(&self) -> Option<DataLocale> {
+        number::data_locale(&self.locale)
+    }
+
+    /// Estimated bytes held by the resource.
+    pub(crate) fn memory(&self) -> usize {
+        let denominator = match &self.formatter {
+            UnitFormatterKind::Simple(_) => 0,
+            UnitFormatterKind::Per {
+                denominator,
+                pattern,
+                ..
+            } => denominator.capacity() + pattern.capacity(),
+        };
+        std::mem::size_of::<Self>()
+            + self.unit.capacity()
+            + denominator
+            + locale::heap_size(&self.locale)
+    }
+}
+
+enum UnitFormatterKind {
+    Simple(UnitsFormatter),
+    /// A `-per-` compound without precomposed data: the formatted numerator
+    /// and the pre-rendered denominator are placed in the locale's per
+    /// pattern, such as "{0} per {1}".
+    Per {
+        numerator: UnitsFormatter,
+        denominator: String,
+        pattern: String,
+    },
+}
+
+pub(crate) fn load(env: Env) -> bool {
+    env.register::<UnitFormatterResource>().is_ok()
+}
+
+formatter_nifs! {
+    UnitFormatterResource {
+        new: unit_formatter_new(unit: String) => UnitFormatterNew,
+        format: unit_format => UnitFormat,
+        format_to_parts: unit_format_to_parts => UnitFormatToParts,
+        resolved_options: unit_resolved_options => UnitResolvedOptions,
+    }
+}
+
+impl Formatter for UnitFormatterResource {
+    type Args = (String,);
+    type Input = FixedDecimal;
+
+    fn new(locale: Locale, (unit,): Self::Args, options: Term) -> Result<Self, Atom> {
+        let (width, approximately) =
+            match (decode_width(options), number::decode_approximately(options)) {
+                (Ok(width), Ok(approximately)) => (width, approximately),
+                _ => return Err(atoms::invalid_options()),
+            };
+
+        let formatter = new_unit_formatter(&locale, &unit, width)?;
+        Ok(Self {
+            formatter,
+            unit,
+            width,
+            locale,
+            approximately,
+        })
+    }
+
+    fn decode_input(term: Term) -> Result<FixedDecimal, Atom> {
+        number::term_to_decimal(term).map_err(|_| atoms::invalid_number())
+    }
+
+    fn format(&self, decimal: &FixedDecimal) -> Result<String, Atom> {
+        let (output, _) = format_kind(&self.formatter, decimal);
+        Ok(number::approximated(
+            &self.locale,
+            self.approximately,
+            output,
+        ))
+    }
+
+    fn format_to_parts(
+        &self,
+        decimal: &FixedDecimal,
+    ) -> Result<(String, Vec<(PartType, Range<usize>)>), Atom> {
+        let (mut output, number) = format_kind(&self.formatter, decimal);
+        let mut parts = split_parts(&output, number);
+        if self.approximately {
+            let sign = number::approximately_sign(&self.locale);
+            parts = index::shift(parts, sign.len());
+            parts.insert(0, (atoms::approximately_sign().into(), 0..sign.len()));
+            output.insert_str(0, &sign);
+        }
+        Ok((output, parts))
+    }
+
+    fn resolved_options<'a>(&self, env: Env<'a>) -> Term<'a> {
+        let width = match self.width {
+            Width::Narrow => atoms::narrow(),
+            Width::Long => atoms::long(),
+            _ => atoms::short(),
+        };
+        let options = ResolvedOptions {
+            unit: self.unit.clone(),
+            width,
+            approximately: self.approximately,
+        };
+        options.encode(env)
+    }
+}
+
+#[derive(NifMap)]
+struct ResolvedOptions {
+    unit: String,
+    width: Atom,
+    approximately: bool,
+}
+
+/// ICU4X unit formatters only annotate the number, at `number` in `output`,
+/// so the text around it is the unit, apart from the spaces separating the
+/// two.
+fn split_parts(output: &str, number: Option<Range<usize>>) -> Vec<(PartType, Range<usize>)> {
+    let Some(number) = number else {
+        return vec![(atoms::unit().into(), 0..output.len())];
+    };
+
+    let mut parts = Vec::new();
+    push_text_parts(&mut parts, output, 0..number.start);
+    parts.push((atoms::number().into(), number.clone()));
+    push_text_parts(&mut parts, output, number.end..output.len());
+    parts
+}
+
+fn push_text_parts(parts: &mut Vec<(PartType, Range<usize>)>, output: &str, range: Range<usize>) {
+    let text = &output[range.clone()];
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        if !range.is_empty() {
+            parts.push((atoms::literal().into(), range));
+        }
+        return;
+    }
+
+    let start = range.start + text.len() - text.trim_start().len();
+    let end = start + trimmed.len();
+    for (part_type, range) in [
+        (atoms::literal(), range.start..start),
+        (atoms::unit(), start..end),
+        (atoms::literal(), end..range.end),
+    ] {
+        if !range.is_empty() {
+            parts.push((part_type.into(), range));
+        }
+    }
+}
+
+/// Formats the range from `start` to `end` with the unit written once when
+/// the locale places it after the number: "3–5 km".
+#[rustler::nif]
+pub(crate) fn unit_format_range<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::UnitFormatRange, || {
+        unit_format_range_inner(env, formatter_term, start_term, end_term)
+    })
+}
+
+fn unit_format_range_inner<'a>(
+    env: Env<'a>,
+    formatter_term: Term<'a>,
+    start_term: Term<'a>,
+    end_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let resource: ResourceArc<UnitFormatterResource> = match formatter_term.decode() {
+        Ok(resource) => resource,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_formatter()).encode(env)),
+    };
+
+    let (start, end) = match (
+        number::term_to_decimal(start_term),
+        number::term_to_decimal(end_term),
+    ) {
+        (Ok(start), Ok(end)) => (start, end),
+        _ => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    let end_parts = |decimal: &FixedDecimal| -> range::EndParts {
+        let (output, number) = format_kind(&resource.formatter, decimal);
+        split_parts(&output, number)
+            .into_iter()
+            .map(|(part_type, range)| (part_type, output[range].to_string()))
+            .collect()
+    };
+    let output: String = range::segments(&resource.locale, end_parts(&start), end_parts(&end))
+        .into_iter()
+        .map(|(_, _, text)| text)
+        .collect();
+    let output = number::approximated(&resource.locale, resource.approximately, output);
+
+    Ok((atoms::ok(), output).encode(env))
+}
+
+/// The formatted amount and the byte range of the number in it.
+fn format_kind(
+    formatter: &UnitFormatterKind,
+    decimal: &FixedDecimal,
+) -> (String, Option<Range<usize>>) {
+    match formatter {
+        UnitFormatterKind::Simple(formatter) => format_unit(formatter, decimal),
+        UnitFormatterKind::Per {
+            numerator,
+            denominator,
+            pattern,
+        } => {
+            let (numerator, number) = format_unit(numerator, decimal);
+            // The numerator follows whatever the pattern writes before `{0}`.
+            let offset = pattern.find("{0}").map_or(0, |placeholder| {
+                interpolate(&pattern[..placeholder], ["", denominator]).len()
+            });
+            let output = interpolate(pattern, [&numerator, denominator]);
+            (
+                output,
+                number.map(|number| number.start + offset..number.end + offset),
+            )
+        }
+    }
+}
+
+/// ICU4X writes the number of a unit through a decimal formatter, so its
+/// decimal parts locate the number in the output.
+fn format_unit(
+    formatter: &UnitsFormatter,
+    decimal: &FixedDecimal,
+) -> (String, Option<Range<usize>>) {
+    let mut collector = PartsCollector::new();
+    // Writing into a `String` cannot fail.
+    let _ = formatter
+        .format_fixed_decimal(decimal)
+        .write_to_parts(&mut collector);
+    let (output, parts) = collector.finish();
+    let number = writeable_parts::number_range(&parts);
+    (output, number)
+}
+
+/// Samples tried after the integers 0 to 200 to find a number of each plural
+/// category: a million for the French and Spanish "many", and fractions for
+/// the categories that only apply to decimals.
+const PLURAL_SAMPLES: &[&str] = &["1000000", "0.5", "1.5", "2.5"];
+
+/// The numbers tried, in order, to find a number of a plural category.
+fn plural_samples() -> impl Iterator<Item = FixedDecimal> {
+    (0..=200).map(FixedDecimal::from).chain(
+        PLURAL_SAMPLES
+            .iter()
+            .filter_map(|s| FixedDecimal::try_from_str(s).ok()),
+    )
+}
+
+/// Returns the unit's pattern for each cardinal plural category of the
+/// locale, with `{0}` standing for the number: `%{one: "{0} Tag", other:
+/// "{0} Tage"}`.
+///
+/// Each pattern is recovered by formatting a sample number of its category
+/// and putting the placeholder in place of the number's parts. This keeps
+/// the patterns consistent with what `unit_format` produces, including the
+/// composed `-per-` compounds, which have no patterns of their own.
+#[rustler::nif]
+pub(crate) fn unit_patterns<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    unit: &str,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::UnitPatterns, || {
+        unit_patterns_inner(env, locale_term, unit, options_term)
+    })
+}
+
+fn unit_patterns_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    unit: &str,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let width = match decode_width(options_term) {
+        Ok(width) => width,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let locale = &locale_arg.0;
+
+    let formatter = match new_unit_formatter(locale, unit, width) {
+        Ok(formatter) => formatter,
+        Err(reason) => return Ok((atoms::error(), reason).encode(env)),
+    };
+
+    let rules = match PluralRules::try_new_cardinal(locale.clone().into()) {
+        Ok(rules) => rules,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let samples = plural_samples().collect::<Vec<_>>();
+
+    let mut patterns = Term::map_new(env);
+    for category in rules.categories() {
+        let Some(sample) = samples
+            .iter()
+            .find(|sample| rules.category_for(*sample) == category)
+        else {
+            continue;
+        };
+
+        let (mut pattern, number) = format_kind(&formatter, sample);
+        if let Some(number) = number {
+            pattern.replace_range(number, "{0}");
+        }
+        patterns = patterns.map_put(plural_category_atom(category), pattern)?;
+    }
+
+    Ok((atoms::ok(), patterns).encode(env))
+}
+
+/// The display names of units in one locale and width. CLDR names a unit
+/// in its plural patterns, so the name is the pattern without the number:
+/// "Kilometer" for "{0} Kilometer".
+pub(crate) struct UnitNames {
+    locale: Locale,
+    width: Width,
+    rules: PluralRules,
+    decimal_formatter: DecimalFormatter,
+    /// A number of the `other` plural category, whose pattern names the
+    /// unit in general.
+    other: FixedDecimal,
+    /// The locale's pattern for a unit per another unit.
+    per: String,
+}
+
+impl UnitNames {
+    pub(crate) fn try_new(locale: &Locale, width: Width) -> Result<Self, Atom> {
+        let rules = PluralRules::try_new_cardinal(locale.clone().into())
+            .map_err(|_| atoms::invalid_locale())?;
+        let decimal_formatter =
+            DecimalFormatter::try_new(locale.clone().into(), DecimalFormatterOptions::default())
+                .map_err(|_| atoms::invalid_locale())?;
+        let other = plural_samples()
+            .find(|sample| rules.category_for(sample) == PluralCategory::Other)
+            .unwrap_or_else(|| FixedDecimal::from(2));
+
+        Ok(Self {
+            locale: locale.clone(),
+            width,
+            rules,
+            decimal_formatter,
+            other,
+            per: per_pattern(locale, width)?,
+        })
+    }
+
+    /// Returns the display name of `unit`, e.g. "Kilometer pro Stunde" for
+    /// "kilometer-per-hour" in German. Compounds without names of their own
+    /// are composed from their components with the locale's per pattern.
+    pub(crate) fn name(&self, unit: &str) -> Result<String, Atom> {
+        if let Some(name) = self.pattern_text(unit, &self.other, "") {
+            return Ok(name);
+        }
+
+        let (numerator, denominator) = unit.split_once("-per-").ok_or_else(atoms::invalid_unit)?;
+        let numerator = self
+            .pattern_text(numerator, &self.other, "")
+            .ok_or_else(atoms::invalid_unit)?;
+
+        // A leading constant ("100-kilometer") is written with the
+        // denominator, which is otherwise named in the singular.
+        let denominator = match denominator.split_once('-') {
+            Some((constant, rest)) if constant.bytes().all(|b| b.is_ascii_digit()) => {
+                let value =
+                    FixedDecimal::try_from_str(constant).map_err(|_| atoms::invalid_unit())?;
+                let number = self.decimal_formatter.format(&value).write_to_string();
+                self.pattern_text(rest, &value, &number)
+            }
+            _ => self.pattern_text(denominator, &FixedDecimal::from(1), ""),
+        }
+        .ok_or_else(atoms::invalid_unit)?;
+
+        Ok(interpolate(&self.per, [&numerator, &denominator]))
+    }
+
+    /// The pattern of `unit` for the plural category of `value`, with
+    /// `number` in place of `{0}`. `None` when CLDR has no patterns for the
+    /// unit.
+    fn pattern_text(&self, unit: &str, value: &FixedDecimal, number: &str) -> Option<String> {
+        let attribute = format!("{}-{unit}", width_attribute(self.width));
+        let attributes = DataMarkerAttributes::try_from_str(&attribute).ok()?;
+        let requested = UnitsDisplayNamesV1::make_locale((&self.locale).into());
+        let response: DataResponse<UnitsDisplayNamesV1> = ExperimentalBaked
+            .load(DataRequest {
+                id: DataIdentifierBorrowed::for_marker_attributes_and_locale(
+                    attributes, &requested,
+                ),
+                ..Default::default()
+            })
+            .ok()?;
+
+        let pattern = response.payload.get().get(value.into(), &self.rules);
+        Some(
+            pattern
+                .interpolate((number,))
+                .write_to_string()
+                .trim()
+                .to_string(),
+        )
+    }
+
+    /// Estimated bytes held by the names.
+    pub(crate) fn memory(&self) -> usize {
+        locale::heap_size(&self.locale) + self.per.capacity()
+    }
+}
+
+fn plural_category_atom(category: PluralCategory) -> Atom {
+    match category {
+        PluralCategory::Zero => atoms::zero(),
+        PluralCategory::One => atoms::one(),
+        PluralCategory::Two => atoms::two(),
+        PluralCategory::Few => atoms::few(),
+        PluralCategory::Many => atoms::many(),
+        PluralCategory::Other => atoms::other(),
+    }
+}
+
+fn new_unit_formatter(
+    locale: &Locale,
+    unit: &str,
+    width: Width,
+) -> Result<UnitFormatterKind, Atom> {
+    let mut options = UnitsFormatterOptions::default();
+    options.width = width;
+
+    if let Ok(formatter) = UnitsFormatter::try_new(locale.clone().into(), unit, options) {
+        return Ok(UnitFormatterKind::Simple(formatter));
+    }
+
+    // CLDR only precomposes a few compounds ("kilometer-per-hour"); others
+    // such as "liter-per-100-kilometer" are composed from their components.
+    let (numerator_unit, denominator_unit) =
+        unit.split_once("-per-").ok_or_else(atoms::invalid_unit)?;
+
+    let numerator = UnitsFormatter::try_new(locale.clone().into(), numerator_unit, options)
+        .map_err(|_| atoms::invalid_unit())?;
+
+    // A leading constant ("100-kilometer") is rendered as part of the
+    // denominator; otherwise the number of "1 h" is dropped, leaving "h".
+    let (constant, denominator_unit) = match denominator_unit.split_once('-') {
+        Some((prefix, rest)) if prefix.bytes().all(|b| b.is_ascii_digit()) => (Some(prefix), rest),
+        _ => (None, denominator_unit),
+    };
+
+    let denominator_formatter =
+        UnitsFormatter::try_new(locale.clone().into(), denominator_unit, options)
+            .map_err(|_| atoms::invalid_unit())?;
+
+    let denominator = match constant {
+        Some(constant) => {
+            let value = FixedDecimal::try_from_str(constant).map_err(|_| atoms::invalid_unit())?;
+            denominator_formatter
+                .format_fixed_decimal(&value)
+                .write_to_string()
+                .into_owned()
+        }
+        None => {
+            let (mut output, number) = format_unit(&denominator_formatter, &FixedDecimal::from(1));
+            if let Some(number) = number {
+                output.replace_range(number, "");
+            }
+            output.trim().to_string()
+        }
+    };
+
+    Ok(UnitFormatterKind::Per {
+        numerator,
+        denominator,
+        pattern: per_pattern(locale, width)?,
+    })
+}
+
+/// The locale's pattern for a unit per another unit, with `{0}` standing
+/// for the numerator and `{1}` for the denominator: "{0} per {1}" in
+/// English, "{0}/{1}" for the short width.
+fn per_pattern(locale: &Locale, width: Width) -> Result<String, Atom> {
+    let attributes = DataMarkerAttributes::from_str_or_panic(width_attribute(width));
+    let requested = UnitsEssentialsV1::make_locale(locale.into());
+    let response: DataResponse<UnitsEssentialsV1> = ExperimentalBaked
+        .load(DataRequest {
+            id: DataIdentifierBorrowed::for_marker_attributes_and_locale(attributes, &requested),
+            ..Default::default()
+        })
+        .map_err(|_| atoms::invalid_locale())?;
+    Ok(response.payload.get().per.to_string())
+}
+
+/// The data marker attribute of `width` in the unit data.
+fn width_attribute(width: Width) -> &'static str {
+    match width {
+        Width::Long => "long",
+        Width::Narrow => "narrow",
+        _ => "short",
+    }
+}
+
+/// Replaces the `{0}` and `{1}` placeholders of `pattern` with `values`,
+/// in whichever order the pattern has them.
+fn interpolate(pattern: &str, values: [&str; 2]) -> String {
+    let mut output = String::with_capacity(pattern.len() + values[0].len() + values[1].len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let index = match rest.as_bytes().get(1..3) {
+            Some([b'0', b'}']) => 0,
+            Some([b'1', b'}']) => 1,
+            _ => {
+                output.push('{');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        output.push_str(values[index]);
+        rest = &rest[3..];
+    }
+    output.push_str(rest);
+    output
+}
+
+#[rustler::nif]
+pub(crate) fn unit_convert<'a>(
+    env: Env<'a>,
+    value_term: Term<'a>,
+    from: &str,
+    to: &str,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::UnitConvert, || {
+        unit_convert_inner(env, value_term, from, to)
+    })
+}
+
+fn unit_convert_inner<'a>(
+    env: Env<'a>,
+    value_term: Term<'a>,
+    from: &str,
+    to: &str,
+) -> NifResult<Term<'a>> {
+    let value = match decode_value(value_term) {
+        Ok(value) => value,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    match convert(value, from, to) {
+        Ok(converted) => Ok((atoms::ok(), converted).encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
+
+#[rustler::nif]
+pub(crate) fn unit_convert_for_locale<'a>(
+    env: Env<'a>,
+    value_term: Term<'a>,
+    from: &str,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::UnitConvertForLocale, || {
+        unit_convert_for_locale_inner(env, value_term, from, locale_term)
+    })
+}
+
+fn unit_convert_for_locale_inner<'a>(
+    env: Env<'a>,
+    value_term: Term<'a>,
+    from: &str,
+    locale_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let value = match decode_value(value_term) {
+        Ok(value) => value,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    if MeasureUnit::try_from_str(from).is_err() {
+        return Ok((atoms::error(), atoms::invalid_unit()).encode(env));
+    }
+
+    let system = MeasurementSystem::for_locale(&locale_arg.0);
+    let to = system.counterpart(from).unwrap_or(from);
+
+    match convert(value, from, to) {
+        Ok(converted) => Ok((atoms::ok(), (converted, to)).encode(env)),
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
+
+#[rustler::nif]
+pub(crate) fn unit_preferred_units<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    usage: &str,
+    value_term: Term<'a>,
+    unit: &str,
+    fraction_digits: u8,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::UnitPreferredUnits, || {
+        unit_preferred_units_inner(env, locale_term, usage, value_term, unit, fraction_digits)
+    })
+}
+
+fn unit_preferred_units_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    usage: &str,
+    value_term: Term<'a>,
+    unit: &str,
+    fraction_digits: u8,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let value = match decode_value(value_term) {
+        Ok(value) => value,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+    };
+
+    if MeasureUnit::try_from_str(unit).is_err() {
+        return Ok((atoms::error(), atoms::invalid_unit()).encode(env));
+    }
+
+    let system = MeasurementSystem::for_locale(&locale_arg.0);
+    let targets = match system.usage_units(usage) {
+        Some(units) => units.to_vec(),
+        None => vec![system.counterpart(unit).unwrap_or(unit)],
+    };
+
+    match split_mixed(value, unit, &targets, fraction_digits) {
+        Ok(parts) => {
+            let encoded: Vec<Term<'a>> = parts
+                .into_iter()
+                .map(|(amount, unit)| match amount {
+                    MixedAmount::Whole(whole) => (whole, unit).encode(env),
+                    MixedAmount::Rest(rest) => (rest, unit).encode(env),
+                })
+                .collect();
+            Ok((atoms::ok(), encoded).encode(env))
+        }
+        Err(reason) => Ok((atoms::error(), reason).encode(env)),
+    }
+}
+
+enum MixedAmount {
+    Whole(i64),
+    Rest(f64),
+}
+
+/// Breaks `value` down into `targets` (largest unit first). Every unit but
+/// the last receives a whole amount; the last one receives the remainder,
+/// rounded to `fraction_digits`.
+///
+/// The remainder is rounded before the breakdown is final, and a remainder
+/// that rounds up to a whole unit above it carries over, so 5.99999 ft is
+/// 6 ft 0 in rather than 5 ft 12 in.
+fn split_mixed<'u>(
+    value: f64,
+    unit: &str,
+    targets: &[&'u str],
+    fraction_digits: u8,
+) -> Result<Vec<(MixedAmount, &'u str)>, Atom> {
+    let mut wholes = Vec::with_capacity(targets.len() - 1);
+    let mut remaining = convert(value, unit, targets[0])?;
+    for pair in targets.windows(2) {
+        let whole = remaining.trunc();
+        wholes.push(whole as i64);
+        remaining = convert(remaining - whole, pair[0], pair[1])?;
+    }
+
+    // Digits beyond what an f64 holds do not change the rounded value.
+    let scale = 10f64.powi(i32::from(fraction_digits.min(15)));
+    let round = |amount: f64| (amount * scale).round() / scale;
+    let mut rest = round(remaining);
+
+    // Carries from the smallest unit up. The size of a unit in the one below
+    // it is rounded, as the conversion factors are not exact in binary.
+    for (index, pair) in targets.windows(2).enumerate().rev() {
+        let size = convert(1.0, pair[0], pair[1])?;
+        if index + 2 == targets.len() {
+            let size = round(size);
+            if rest.abs() >= size {
+                let sign = rest.signum();
+                rest = round(rest - size * sign);
+                wholes[index] += sign as i64;
+            }
+        } else {
+            let size = size.round() as i64;
+            let amount = wholes[index + 1];
+            if amount.abs() >= size {
+                wholes[index + 1] = amount - size * amount.signum();
+                wholes[index] += amount.signum();
+            }
+        }
+    }
+
+    let mut parts: Vec<(MixedAmount, &'u str)> = wholes
+        .into_iter()
+        .zip(targets)
+        .map(|(whole, target)| (MixedAmount::Whole(whole), *target))
+        .collect();
+    parts.push((MixedAmount::Rest(rest), targets[targets.len() - 1]));
+    Ok(parts)
+}
+
+#[rustler::nif]
+pub(crate) fn unit_format_mixed<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    measures_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    stats::measure(Counted::UnitFormatMixed, || {
+        unit_format_mixed_inner(env, locale_term, measures_term, options_term)
+    })
+}
+
+fn unit_format_mixed_inner<'a>(
+    env: Env<'a>,
+    locale_term: Term<'a>,
+    measures_term: Term<'a>,
+    options_term: Term<'a>,
+) -> NifResult<Term<'a>> {
+    let locale_arg: LocaleArg = match locale_term.decode() {
+        Ok(locale) => locale,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let width = match decode_width(options_term) {
+        Ok(width) => width,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_options()).encode(env)),
+    };
+
+    let measures: Vec<(Term<'a>, String)> = match measures_term.decode() {
+        Ok(measures) => measures,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_items()).encode(env)),
+    };
+
+    if measures.is_empty() {
+        return Ok((atoms::error(), atoms::invalid_items()).encode(env));
+    }
+
+    let mut options = UnitsFormatterOptions::default();
+    options.width = width;
+
+    let mut items = Vec::with_capacity(measures.len());
+    for (value_term, unit) in measures {
+        let decimal = match number::term_to_decimal(value_term) {
+            Ok(decimal) => decimal,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_number()).encode(env)),
+        };
+
+        let formatter = match UnitsFormatter::try_new(locale_arg.0.clone().into(), &unit, options) {
+            Ok(formatter) => formatter,
+            Err(_) => return Ok((atoms::error(), atoms::invalid_unit()).encode(env)),
+        };
+
+        items.push(
+            formatter
+                .format_fixed_decimal(&decimal)
+                .write_to_string()
+                .into_owned(),
+        );
+    }
+
+    // Mixed units are joined with the unit list patterns ("5 ft, 11 in").
+    let length = match width {
+        Width::Long => ListLength::Wide,
+        Width::Narrow => ListLength::Narrow,
+        _ => ListLength::Short,
+    };
+    let list_formatter = match ListFormatter::try_new_unit(
+        locale_arg.0.clone().into(),
+        ListFormatterOptions::default().with_length(length),
+    ) {
+        Ok(formatter) => formatter,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_locale()).encode(env)),
+    };
+
+    let output = list_formatter
+        .format(items.iter().map(|item| item.as_str()))
+        .write_to_string()
+        .into_owned();
+
+    Ok((atoms::ok(), output).encode(env))
+}
+
+fn decode_width(term: Term) -> Result<Width, ()> {
+    if term.get_type() != TermType::Map {
+        return Ok(Width::Short);
+    }
+
+    let mut width = Width::Short;
+    decode_map(term, UnknownKeys::Ignore, |key, value_term| {
+        if key != atoms::width() {
+            return Ok(false);
+        }
+
+        let value: Atom = value_term.decode().map_err(|_| ())?;
+        width = if value == atoms::short() {
+            Width::Short
+        } else if value == atoms::narrow() {
+            Width::Narrow
+        } else if value == atoms::long() {
+            Width::Long
+        } else {
+            return Err(());
+        };
+        Ok(true)
+    })?;
+
+    Ok(width)
+}
+
+pub(crate) fn convert(value: f64, from: &str, to: &str) -> Result<f64, Atom> {
+    let from = MeasureUnit::try_from_str(from).map_err(|_| atoms::invalid_unit())?;
+    let to = MeasureUnit::try_from_str(to).map_err(|_| atoms::invalid_unit())?;
+
+    let converter: UnitsConverter<f64> = ConverterFactory::new()
+        .converter(&from, &to)
+        .ok_or_else(atoms::incompatible_units)?;
+
+    Ok(converter.convert(&value))
+}
+
+fn decode_value(term: Term) -> Result<f64, ()> {
+    if let Ok(value) = term.decode::<f64>() {
+        return Ok(value);
+    }
+    term.decode::<i64>()
+        .map(|value| value as f64)
+        .map_err(|_| ())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MeasurementSystem {
+    Metric,
+    UsSystem,
+    UkSystem,
+}
+
+/// Units with a customary counterpart, as `(metric, US, UK)`.
+///
+/// ICU4X does not ship the CLDR unit preference data, so this covers the
+/// common everyday units only. The UK column follows road and speed usage
+/// (miles) and is metric otherwise.
+const COUNTERPARTS: &[(&str, &str, &str)] = &[
+    ("kilometer", "mile", "mile"),
+    ("meter", "foot", "meter"),
+    ("centimeter", "inch", "centimeter"),
+    ("millimeter", "inch", "millimeter"),
+    ("square-kilometer", "square-mile", "square-kilometer"),
+    ("square-meter", "square-foot", "square-meter"),
+    ("hectare", "acre", "hectare"),
+    ("kilogram", "pound", "kilogram"),
+    ("gram", "ounce", "gram"),
+    ("liter", "gallon", "liter"),
+    ("milliliter", "fluid-ounce", "milliliter"),
+    ("celsius", "fahrenheit", "celsius"),
+    ("kilometer-per-hour", "mile-per-hour", "mile-per-hour"),
+];
+
+/// Preferred (mixed) units per usage, as `(usage, metric, US, UK)`.
+///
+/// A subset of the CLDR unit preferences for the usages whose units differ
+/// from the plain counterparts above.
+const USAGE_PREFERENCES: &[(&str, &[&str], &[&str], &[&str])] = &[
+    (
+        "person-height",
+        &["centimeter"],
+        &["foot", "inch"],
+        &["foot", "inch"],
+    ),
+    ("person", &["kilogram"], &["pound"], &["stone", "pound"]),
+    ("road", &["kilometer"], &["mile"], &["mile"]),
+    ("weather", &["celsius"], &["fahrenheit"], &["celsius"]),
+];
+
+impl MeasurementSystem {
+    /// Resolves the system from the `-u-ms-` keyword, then the `-u-rg-`
+    /// region override, then the (maximized) region of the locale.
+    pub(crate) fn for_locale(locale: &Locale) -> Self {
+        let keywords = &locale.extensions.unicode.keywords;
+
+        if let Some(ms) = keywords.get(&key!("ms")) {
+            match ms.to_string().as_str() {
+                "metric" => return MeasurementSystem::Metric,
+                "ussystem" => return MeasurementSystem::UsSystem,
+                "uksystem" => return MeasurementSystem::UkSystem,
+                _ => {}
+            }
+        }
+
+        if let Some(rg) = keywords.get(&key!("rg")) {
+            let rg = rg.to_string();
+            if let Some(region) = rg.get(..2) {
+                return Self::for_region(&region.to_ascii_uppercase());
+            }
+        }
+
+        let mut id = locale.id.clone();
+        locale::expander().maximize(&mut id);
+
+        match id.region {
+            Some(region) => Self::for_region(region.as_str()),
+            None => MeasurementSystem::Metric,
+        }
+    }
+
+    fn for_region(region: &str) -> Self {
+        match region {
+            "US" | "LR" | "MM" => MeasurementSystem::UsSystem,
+            "GB" => MeasurementSystem::UkSystem,
+            _ => MeasurementSystem::Metric,
+        }
+    }
+
+    /// Returns the preferred units for `usage`, or `None` for usages without
+    /// dedicated preferences (which fall back to the default counterparts).
+    pub(crate) fn usage_units(self, usage: &str) -> Option<&'static [&'static str]> {
+        USAGE_PREFERENCES
+            .iter()
+            .find(|(name, ..)| *name == usage)
+            .map(|(_, metric, us, uk)| match self {
+                MeasurementSystem::Metric => *metric,
+                MeasurementSystem::UsSystem => *us,
+                MeasurementSystem::UkSystem => *uk,
+            })
+    }
+
+    /// Returns the unit this system uses in place of `unit`, or `None` when
+    /// the unit has no known counterpart (e.g. time units).
+    pub(crate) fn counterpart(self, unit: &str) -> Option<&'static str> {
+        COUNTERPARTS
+            .iter()
+            .find(|(metric, us, uk)| *metric == unit || *us == unit || *uk == unit)
+            .map(|(metric, us, uk)| match self {
+                MeasurementSystem::Metric => *metric,
+                MeasurementSystem::UsSystem => *us,
+                MeasurementSystem::UkSystem => *uk,
+            })
+    }
+}
@@ -0,0 +1,274 @@
+//! Collects the parts ICU4X annotates its formatted output with.
+//!
+//! The writeable parts of every formatter are mapped to atoms in one
+//! registry, so a formatter gaining parts support only has to add the parts
+//! it writes. A part missing from the registry is reported with the part type
+//! `{:unknown, category, value}` of the writeable part, such as
+//! `{:unknown, "decimal", "exponent"}`, instead of being dropped, so parts
+//! added by a newer ICU4X surface before the crate maps them.
+
+use std::fmt;
+use std::ops::Range;
+
+use icu::datetime::parts as datetime_parts;
+use icu::decimal::parts as decimal_parts;
+use icu::list::parts as list_parts;
+use rustler::{Atom, Decoder, Encoder, Env, NifMap, NifResult, Term};
+use writeable::{Part as WriteablePart, PartsWrite};
+
+use crate::atoms;
+use crate::datetime::RELATIVE_DAY_PART;
+
+/// The atom of each writeable part the formatters report. Parts of nested
+/// formatters, such as the decimal parts of a formatted day, share the
+/// entries of the formatter that writes them.
+const PART_ATOMS: &[(WriteablePart, fn() -> Atom)] = &[
+    (decimal_parts::INTEGER, atoms::integer),
+    (decimal_parts::DECIMAL, atoms::decimal),
+    (decimal_parts::FRACTION, atoms::fraction),
+    (decimal_parts::GROUP, atoms::group),
+    (decimal_parts::PLUS_SIGN, atoms::plus_sign),
+    (decimal_parts::MINUS_SIGN, atoms::minus_sign),
+    (datetime_parts::ERA, atoms::era),
+    (datetime_parts::YEAR, atoms::year),
+    (datetime_parts::RELATED_YEAR, atoms::related_year),
+    (datetime_parts::YEAR_NAME, atoms::year_name),
+    (datetime_parts::MONTH, atoms::month),
+    (datetime_parts::DAY, atoms::day),
+    (datetime_parts::WEEKDAY, atoms::weekday),
+    (datetime_parts::DAY_PERIOD, atoms::day_period),
+    (datetime_parts::HOUR, atoms::hour),
+    (datetime_parts::MINUTE, atoms::minute),
+    (datetime_parts::SECOND, atoms::second),
+    (datetime_parts::TIME_ZONE_NAME, atoms::time_zone_name),
+    (RELATIVE_DAY_PART, atoms::relative_day),
+    (list_parts::ELEMENT, atoms::element),
+    (list_parts::LITERAL, atoms::literal),
+];
+
+/// The type of a formatted part: its atom, or the writeable part itself when
+/// the registry does not know it.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PartType {
+    Known(Atom),
+    Unknown(WriteablePart),
+}
+
+impl From<WriteablePart> for PartType {
+    fn from(part: WriteablePart) -> Self {
+        PART_ATOMS
+            .iter()
+            .find(|(known, _)| *known == part)
+            .map_or(Self::Unknown(part), |(_, atom)| Self::Known(atom()))
+    }
+}
+
+impl From<Atom> for PartType {
+    fn from(atom: Atom) -> Self {
+        Self::Known(atom)
+    }
+}
+
+impl PartialEq<Atom> for PartType {
+    fn eq(&self, other: &Atom) -> bool {
+        matches!(self, Self::Known(atom) if atom == other)
+    }
+}
+
+impl Encoder for PartType {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            Self::Known(atom) => atom.encode(env),
+            Self::Unknown(part) => (atoms::unknown(), part.category, part.value).encode(env),
+        }
+    }
+}
+
+impl<'a> Decoder<'a> for PartType {
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        term.decode().map(Self::Known)
+    }
+}
+
+/// A part of the output of a formatter without parts of its own shape.
+#[derive(NifMap)]
+pub(crate) struct FormattedPart {
+    #[rustler(map = "type")]
+    pub(crate) part_type: PartType,
+    pub(crate) value: String,
+}
+
+/// A part written by a formatter, with its byte range in the output.
+pub(crate) struct CollectedPart {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) part: WriteablePart,
+}
+
+impl CollectedPart {
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// The byte range of the number among `parts`, from its first to its last
+/// decimal part. Unit and currency formatters write the number through a
+/// decimal formatter, so its parts mark where the number is in their output.
+pub(crate) fn number_range(parts: &[CollectedPart]) -> Option<Range<usize>> {
+    parts
+        .iter()
+        .filter(|collected| collected.part.category == decimal_parts::INTEGER.category)
+        .map(CollectedPart::range)
+        .reduce(|number, range| number.start.min(range.start)..number.end.max(range.end))
+}
+
+/// A `PartsWrite` sink recording the output and the parts it is made of.
+/// Parts are recorded as they close, so nested parts precede the part
+/// enclosing them, and empty parts are skipped.
+pub(crate) struct PartsCollector {
+    output: String,
+    parts: Vec<CollectedPart>,
+}
+
+impl PartsCollector {
+    pub(crate) fn new() -> Self {
+        Self {
+            output: String::new(),
+            parts: Vec::new(),
+        }
+    }
+
+    pub(crate) fn finish(self) -> (String, Vec<CollectedPart>) {
+        (self.output, self.parts)
+    }
+
+    /// Returns the output with its parts flattened into output order.
+    ///
+    /// Every byte is attributed to the innermost part covering it, so a part
+    /// nested inside another one splits it: ICU4X writes each group separator
+    /// inside the integer part, and "1,234" becomes integer "1", group ",",
+    /// integer "234".
+    pub(crate) fn finish_flat(self) -> (String, Vec<CollectedPart>) {
+        let mut boundaries: Vec<usize> = self
+            .parts
+            .iter()
+            .flat_map(|collected| [collected.start, collected.end])
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut flat: Vec<CollectedPart> = Vec::with_capacity(self.parts.len());
+        for window in boundaries.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            let innermost = self
+                .parts
+                .iter()
+                .filter(|collected| collected.start <= start && end <= collected.end)
+                .min_by_key(|collected| collected.end - collected.start);
+            let Some(innermost) = innermost else {
+                continue;
+            };
+
+            match flat.last_mut() {
+                Some(last) if last.end == start && last.part == innermost.part => last.end = end,
+                _ => flat.push(CollectedPart {
+                    start,
+                    end,
+                    part: innermost.part,
+                }),
+            }
+        }
+
+        (self.output, flat)
+    }
+}
+
+impl fmt::Write for PartsCollector {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.output.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.output.push(c);
+        Ok(())
+    }
+}
+
+impl PartsWrite for PartsCollector {
+    type SubPartsWrite = PartsCollector;
+
+    fn with_part(
+        &mut self,
+        part: WriteablePart,
+        mut f: impl FnMut(&mut Self::SubPartsWrite) -> fmt::Result,
+    ) -> fmt::Result {
+        let start = self.output.len();
+        f(self)?;
+        let end = self.output.len();
+        if start < end {
+            self.parts.push(CollectedPart { start, end, part });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fixed_decimal::Decimal;
+    use icu::decimal::options::DecimalFormatterOptions;
+    use icu::decimal::{parts, DecimalFormatter};
+    use icu::locale::locale;
+    use writeable::Writeable;
+
+    use super::*;
+
+    fn collect(number: i64) -> PartsCollector {
+        let formatter =
+            DecimalFormatter::try_new(locale!("en").into(), DecimalFormatterOptions::default())
+                .unwrap();
+        let mut collector = PartsCollector::new();
+        formatter
+            .format(&Decimal::from(number))
+            .write_to_parts(&mut collector)
+            .unwrap();
+        collector
+    }
+
+    #[test]
+    fn records_nested_parts_before_their_parent() {
+        let (output, collected) = collect(1234).finish();
+        let parts: Vec<_> = collected
+            .iter()
+            .map(|c| (c.part, &output[c.range()]))
+            .collect();
+        assert_eq!(parts, [(parts::GROUP, ","), (parts::INTEGER, "1,234")]);
+    }
+
+    #[test]
+    fn flattens_to_the_innermost_parts() {
+        let (output, collected) = collect(-1234).finish_flat();
+        let parts: Vec<_> = collected
+            .iter()
+            .map(|c| (c.part, &output[c.range()]))
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                (parts::MINUS_SIGN, "-"),
+                (parts::INTEGER, "1"),
+                (parts::GROUP, ","),
+                (parts::INTEGER, "234"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_parts_without_an_atom() {
+        let part = WriteablePart {
+            category: "decimal",
+            value: "exponent",
+        };
+        assert!(PartType::from(part) == PartType::Unknown(part));
+    }
+}
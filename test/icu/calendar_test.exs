@@ -1,6 +1,8 @@
 defmodule Icu.CalendarTest do
   use ExUnit.Case, async: true
 
+  doctest Icu.Calendar
+
   defmodule CalendarWithType do
     def calendar_type, do: :buddhist
   end
@@ -51,4 +53,285 @@ defmodule Icu.CalendarTest do
       assert {:error, :unsupported_calendar} = Icu.Calendar.normalize_identifier(123)
     end
   end
+
+  describe "rata die conversion" do
+    test "counts 0001-01-01 as day 1" do
+      assert {:ok, 1} = Icu.Calendar.date_to_rata_die(~D[0001-01-01])
+      assert {:ok, 0} = Icu.Calendar.date_to_rata_die(~D[0000-12-31])
+      assert {:ok, 719_163} = Icu.Calendar.date_to_rata_die(~D[1970-01-01])
+    end
+
+    test "round-trips through other calendars" do
+      for calendar <- [:gregorian, :hebrew, :islamic_civil, :persian, :chinese, :japanese] do
+        assert {:ok, date} = Icu.Calendar.date_from_rata_die(738_985, calendar)
+
+        assert {:ok, 738_985} =
+                 Icu.Calendar.date_to_rata_die(
+                   %{year: date.year, month_code: date.month_code, day: date.day},
+                   calendar
+                 )
+      end
+    end
+
+    test "reads the year in the given era" do
+      assert {:ok, date} = Icu.Calendar.date_from_rata_die(738_985, :japanese)
+      assert %{era: "reiwa", era_year: 6} = date
+
+      fields = %{era: "reiwa", era_year: 6, month: 4, day: 9}
+      assert {:ok, 738_985} = Icu.Calendar.date_to_rata_die(fields, :japanese)
+    end
+
+    test "ignores the calendar for Elixir dates" do
+      assert Icu.Calendar.date_to_rata_die(~D[2024-04-09], :hebrew) ==
+               Icu.Calendar.date_to_rata_die(~D[2024-04-09])
+    end
+
+    test "rejects unknown calendars and invalid dates" do
+      assert {:error, :invalid_calendar} = Icu.Calendar.date_from_rata_die(1, :mayan)
+
+      assert {:error, :invalid_date} =
+               Icu.Calendar.date_to_rata_die(%{year: 2024, month: 2, day: 30}, :gregorian)
+    end
+  end
+
+  describe "Julian day numbers" do
+    test "are offset from the rata die" do
+      assert {:ok, 2_451_545} = Icu.Calendar.date_to_julian_day(~D[2000-01-01])
+
+      assert {:ok, %{year: 2000, month: 1, day: 1}} =
+               Icu.Calendar.date_from_julian_day(2_451_545, :gregorian)
+    end
+  end
+
+  describe "date_add/3" do
+    test "adds each unit in the calendar" do
+      assert {:ok, %{year: 2025, month: 3, day: 8}} =
+               Icu.Calendar.date_add(~D[2024-01-01], years: 1, months: 2, weeks: 1)
+
+      assert {:ok, %{year: 2023, month: 12, day: 29}} =
+               Icu.Calendar.date_add(~D[2024-01-01], %{days: -3})
+    end
+
+    test "steps through leap months" do
+      adar_i = %{year: 5784, month_code: "M05L", day: 1}
+
+      assert {:ok, %{month_code: "M06", month: 7}} =
+               Icu.Calendar.date_add(adar_i, [months: 1], :hebrew)
+    end
+
+    test "rejects unknown duration units" do
+      assert {:error, :invalid_duration} = Icu.Calendar.date_add(~D[2024-01-01], hours: 1)
+      assert {:error, :invalid_duration} = Icu.Calendar.date_add(~D[2024-01-01], days: 1.5)
+    end
+  end
+
+  describe "date_diff/4" do
+    test "counts whole units" do
+      assert {:ok, 2} = Icu.Calendar.date_diff(~D[2024-01-15], ~D[2024-01-01], :weeks)
+      assert {:ok, 14} = Icu.Calendar.date_diff(~D[2024-01-15], ~D[2024-01-01], :days)
+      assert {:ok, 0} = Icu.Calendar.date_diff(~D[2024-12-31], ~D[2024-01-01], :years)
+      assert {:ok, 1} = Icu.Calendar.date_diff(~D[2025-01-01], ~D[2024-01-01], :years)
+    end
+
+    test "is negative when the date is earlier" do
+      assert {:ok, -11} = Icu.Calendar.date_diff(~D[2024-01-01], ~D[2024-12-31], :months)
+    end
+
+    test "counts months in the calendar" do
+      # 1 Tishrei 5784 to 1 Tishrei 5785 spans a leap year.
+      assert {:ok, 13} =
+               Icu.Calendar.date_diff(~D[2024-10-03], ~D[2023-09-16], :months, :hebrew)
+
+      assert {:ok, 12} = Icu.Calendar.date_diff(~D[2024-10-03], ~D[2023-09-16], :months)
+    end
+  end
+
+  describe "Chinese and Dangi calendars" do
+    test "report leap months" do
+      assert {:ok, date} = Icu.Calendar.convert(~D[2023-04-20], :chinese)
+      assert %{month_code: "M02L", leap_month: true, month: 3, day: 1} = date
+
+      assert {:ok, %{month_code: "M02", leap_month: false}} =
+               Icu.Calendar.convert(~D[2023-03-22], :chinese)
+    end
+
+    test "report the cyclic year and related ISO year" do
+      assert {:ok, %{cyclic_year: 41, related_iso: 2024}} =
+               Icu.Calendar.convert(~D[2024-06-01], :chinese)
+
+      assert {:ok, %{cyclic_year: 41, related_iso: 2024}} =
+               Icu.Calendar.convert(~D[2024-06-01], :dangi)
+
+      assert {:ok, %{cyclic_year: nil, related_iso: nil}} =
+               Icu.Calendar.convert(~D[2024-06-01], :gregorian)
+    end
+
+    test "accept leap month codes" do
+      date = %{calendar: "chinese", year: 2023, month_code: "M02L", day: 1}
+      assert {:ok, %{year: 2023, month: 4, day: 20}} = Icu.Calendar.convert(date, :iso)
+    end
+
+    test "round-trip returned dates" do
+      {:ok, date} = Icu.Calendar.convert(~D[2023-04-20], :chinese)
+      assert {:ok, %{year: 2023, month: 4, day: 20}} = Icu.Calendar.convert(date, :iso8601)
+    end
+  end
+
+  describe "japanese_eras/1" do
+    test "lists the modern eras in order" do
+      assert {:ok, eras} = Icu.Calendar.japanese_eras(locale: "en")
+      assert Enum.map(eras, & &1.code) == ["meiji", "taisho", "showa", "heisei", "reiwa"]
+      assert Enum.find(eras, &(&1.code == "heisei")).start == ~D[1989-01-08]
+    end
+
+    test "names the eras in the locale" do
+      assert {:ok, eras} = Icu.Calendar.japanese_eras(locale: "ja")
+      assert Enum.map(eras, & &1.name) == ["明治", "大正", "昭和", "平成", "令和"]
+    end
+
+    test "rejects invalid locales" do
+      assert {:error, _} = Icu.Calendar.japanese_eras(locale: 42)
+    end
+  end
+
+  describe "japanese_era_for_date/2" do
+    test "returns the era a date falls in" do
+      assert {:ok, %{code: "heisei"}} = Icu.Calendar.japanese_era_for_date(~D[2019-04-30])
+      assert {:ok, %{code: "reiwa"}} = Icu.Calendar.japanese_era_for_date(~D[2019-05-01])
+    end
+
+    test "reads field maps in the Gregorian calendar" do
+      assert {:ok, %{code: "taisho", start: ~D[1912-07-30]}} =
+               Icu.Calendar.japanese_era_for_date(%{year: 1920, month: 1, day: 1})
+    end
+
+    test "returns nil before the Meiji era" do
+      assert Icu.Calendar.japanese_era_for_date!(~D[1600-01-01]) == nil
+    end
+  end
+
+  describe "Islamic calendars" do
+    test "select the variant by identifier" do
+      date = ~D[2024-03-11]
+
+      assert %{year: 1445, month: 9, day: 1} = Icu.Calendar.convert!(date, :islamic_umalqura)
+      assert %{year: 1445, month: 9, day: 1} = Icu.Calendar.convert!(date, :islamic_civil)
+      assert %{year: 1445, month: 9, day: 2} = Icu.Calendar.convert!(date, "islamic-tbla")
+      assert %{year: 1445} = Icu.Calendar.convert!(date, :islamic)
+
+      rgsa = Icu.Calendar.convert!(date, :islamic_rgsa)
+      observational = Icu.Calendar.convert!(date, :islamic)
+      assert Map.delete(rgsa, :calendar) == Map.delete(observational, :calendar)
+    end
+
+    test "round-trip through each variant" do
+      for calendar <- [:islamic, :islamic_civil, :islamic_tbla, :islamic_umalqura] do
+        date = Icu.Calendar.convert!(~D[2024-03-20], calendar)
+        assert %{year: 2024, month: 3, day: 20} = Icu.Calendar.convert!(date, :iso)
+      end
+    end
+  end
+
+  describe "weekends" do
+    test "follow the locale's region" do
+      assert {:ok, [6, 7]} = Icu.Calendar.weekend_days(locale: "de")
+      assert {:ok, [5, 6]} = Icu.Calendar.weekend_days(locale: "ar-EG")
+      assert {:ok, [6, 7]} = Icu.Calendar.weekend_days(locale: "en-GB")
+    end
+
+    test "weekend?/2 checks the day of week" do
+      assert Icu.Calendar.weekend?(~D[2024-06-08], locale: "en-US")
+      refute Icu.Calendar.weekend?(~D[2024-06-07], locale: "en-US")
+      assert Icu.Calendar.weekend?(~D[2024-06-07], locale: "ar-SA")
+    end
+
+    test "rejects invalid locales" do
+      assert {:error, _} = Icu.Calendar.weekend_days(locale: 42)
+      assert_raise RuntimeError, fn -> Icu.Calendar.weekend?(~D[2024-06-07], locale: 42) end
+    end
+  end
+
+  describe "add_business_days/3" do
+    test "skips weekends in both directions" do
+      assert {:ok, ~D[2024-06-14]} =
+               Icu.Calendar.add_business_days(~D[2024-06-07], 5, locale: "en")
+
+      assert {:ok, ~D[2024-06-03]} =
+               Icu.Calendar.add_business_days(~D[2024-06-10], -5, locale: "en")
+    end
+
+    test "starts counting from weekend days" do
+      assert {:ok, ~D[2024-06-14]} =
+               Icu.Calendar.add_business_days(~D[2024-06-08], 5, locale: "en")
+
+      assert {:ok, ~D[2024-06-10]} =
+               Icu.Calendar.add_business_days(~D[2024-06-08], 1, locale: "en")
+
+      assert {:ok, ~D[2024-06-07]} =
+               Icu.Calendar.add_business_days(~D[2024-06-09], -1, locale: "en")
+    end
+
+    test "returns the date itself for zero days" do
+      assert {:ok, ~D[2024-06-08]} =
+               Icu.Calendar.add_business_days(~D[2024-06-08], 0, locale: "en")
+    end
+
+    test "covers many weeks" do
+      assert {:ok, ~D[2025-06-09]} =
+               Icu.Calendar.add_business_days(~D[2024-06-07], 261, locale: "en-US")
+
+      assert {:ok, ~D[2024-06-07]} =
+               Icu.Calendar.add_business_days(~D[2025-06-09], -261, locale: "en-US")
+    end
+
+    test "uses the region's weekend" do
+      assert {:ok, ~D[2024-06-09]} =
+               Icu.Calendar.add_business_days(~D[2024-06-06], 1, locale: "ar-SA")
+
+      assert {:ok, ~D[2024-06-06]} =
+               Icu.Calendar.add_business_days(~D[2024-06-09], -1, locale: "ar-SA")
+    end
+  end
+
+  describe "year_info/2" do
+    test "reports leap years" do
+      assert {:ok, %{leap_year: true, months_in_year: 12, days_in_year: 366}} =
+               Icu.Calendar.year_info(2024)
+
+      assert {:ok, %{leap_year: false, months_in_year: 12, days_in_year: 355}} =
+               Icu.Calendar.year_info(5785, :hebrew)
+
+      assert {:ok, %{leap_year: true, months_in_year: 13}} =
+               Icu.Calendar.year_info(2023, :chinese)
+    end
+
+    test "rejects unknown calendars" do
+      assert {:error, :invalid_calendar} = Icu.Calendar.year_info(2024, :martian)
+    end
+  end
+
+  describe "months/3" do
+    test "lists the months of a Hebrew leap year" do
+      assert {:ok, months} = Icu.Calendar.months(5784, :hebrew, locale: "en")
+      assert length(months) == 13
+      assert Enum.map(months, & &1.ordinal) == Enum.to_list(1..13)
+      assert [%{month_code: "M05L", leap_month: true}] = Enum.filter(months, & &1.leap_month)
+    end
+
+    test "names Adar without a number in common years" do
+      assert {:ok, months} = Icu.Calendar.months(5785, :hebrew, locale: "en")
+      assert length(months) == 12
+      assert %{name: "Adar"} = Enum.find(months, &(&1.month_code == "M06"))
+    end
+
+    test "names months in the locale" do
+      assert {:ok, [%{name: "January"} | _]} = Icu.Calendar.months(2024, :gregorian, locale: "en")
+      assert {:ok, [%{name: "Januar"} | _]} = Icu.Calendar.months(2024, :gregorian, locale: "de")
+    end
+
+    test "has no names for calendars without formatting data" do
+      assert {:ok, [%{month_code: "M01", name: nil} | _]} =
+               Icu.Calendar.months(2024, :japanext, locale: "en")
+    end
+  end
 end
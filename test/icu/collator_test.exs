@@ -0,0 +1,187 @@
+defmodule Icu.CollatorTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Collator
+
+  alias Icu.Collator
+
+  describe "new/1" do
+    test "creates a collator" do
+      assert {:ok, %Collator{}} = Collator.new(locale: "en")
+    end
+
+    test "rejects unknown strengths" do
+      assert {:error, {:invalid_option_value, :strength}} = Collator.new(strength: :loose)
+    end
+
+    test "rejects unknown options" do
+      assert {:error, {:bad_option, :unknown}} = Collator.new(unknown: true)
+    end
+  end
+
+  describe "compare/3" do
+    test "honours the strength" do
+      tertiary = Collator.new!(locale: "en")
+      primary = Collator.new!(locale: "en", strength: :primary)
+
+      assert Collator.compare(tertiary, "a", "A") == :lt
+      assert Collator.compare(primary, "a", "A") == :eq
+      assert Collator.compare(primary, "resume", "résumé") == :eq
+    end
+  end
+
+  describe "equal?/3" do
+    test "follows the strength" do
+      tertiary = Collator.new!(locale: "en")
+      primary = Collator.new!(locale: "en", strength: :primary)
+
+      refute Collator.equal?(tertiary, "Muller", "müller")
+      assert Collator.equal?(primary, "Muller", "müller")
+      assert Collator.equal?(tertiary, "e\u0301", "é")
+    end
+
+    test "raises on a collator that is not a resource" do
+      assert_raise ArgumentError, ~r/invalid_resource/, fn ->
+        Collator.equal?(%Collator{resource: make_ref()}, "a", "b")
+      end
+    end
+  end
+
+  describe "equals_ignoring/4" do
+    test "ignores only the given differences" do
+      assert {:ok, true} = Collator.equals_ignoring("Straße", "STRASSE", :case, locale: "de")
+      assert {:ok, false} = Collator.equals_ignoring("Müller", "muller", :case, locale: "de")
+      assert {:ok, true} = Collator.equals_ignoring("Müller", "Muller", :accents, locale: "de")
+      assert {:ok, false} = Collator.equals_ignoring("Müller", "muller", :accents, locale: "de")
+      assert {:ok, true} = Collator.equals_ignoring("Müller", "muller", :both, locale: "de")
+    end
+
+    test "treats letters of the locale's alphabet as distinct" do
+      assert {:ok, true} = Collator.equals_ignoring("Ostberg", "Östberg", :accents, locale: "de")
+      assert {:ok, false} = Collator.equals_ignoring("Ostberg", "Östberg", :accents, locale: "sv")
+    end
+
+    test "rejects unknown differences" do
+      assert {:error, :invalid_options} = Collator.equals_ignoring("a", "A", :width)
+    end
+  end
+
+  describe "find/3" do
+    test "matches accent and case insensitively at primary strength" do
+      collator = Collator.new!(locale: "de", strength: :primary)
+      haystack = "MÜLLER, Mueller und Muller"
+
+      assert [{0, 7}, {21, 6}] = Collator.find(collator, haystack, "muller")
+    end
+
+    test "distinguishes accents at secondary strength" do
+      collator = Collator.new!(locale: "en", strength: :secondary)
+      assert [{0, 4}] = Collator.find(collator, "Cafe café", "cafe")
+    end
+
+    test "returns no matches for an empty needle" do
+      collator = Collator.new!(locale: "en")
+      assert [] = Collator.find(collator, "abc", "")
+    end
+
+    test "does not split grapheme clusters" do
+      collator = Collator.new!(locale: "en", strength: :primary)
+      assert [] = Collator.find(collator, "🇫🇷", "🇫")
+    end
+  end
+
+  describe "numeric option" do
+    test "compares digit sequences numerically" do
+      collator = Collator.new!(locale: "en", numeric: true)
+      assert Collator.compare(collator, "item2", "item10") == :lt
+      assert Collator.sort(collator, ["v10", "v9", "v100"]) == ["v9", "v10", "v100"]
+    end
+
+    test "compares digits lexically by default" do
+      collator = Collator.new!(locale: "en")
+      assert Collator.compare(collator, "item2", "item10") == :gt
+    end
+
+    test "rejects non-boolean values" do
+      assert {:error, {:invalid_option_value, :numeric}} = Collator.new(numeric: :yes)
+    end
+  end
+
+  describe "natural_sort/2" do
+    test "honours locale letter ordering" do
+      assert ["ä1", "a2", "a10", "z1"] =
+               Collator.natural_sort(["z1", "ä1", "a10", "a2"], locale: "de")
+
+      assert ["a2", "a10", "z1", "ä1"] =
+               Collator.natural_sort(["z1", "ä1", "a10", "a2"], locale: "sv")
+    end
+
+    test "raises on invalid options" do
+      assert_raise RuntimeError, fn -> Collator.natural_sort(["a"], strength: :strong) end
+    end
+  end
+
+  describe "case_first option" do
+    test "orders uppercase first" do
+      collator = Collator.new!(locale: "en", case_first: :upper)
+      assert Collator.sort(collator, ["a", "A", "b", "B"]) == ["A", "a", "B", "b"]
+    end
+
+    test "orders lowercase first" do
+      collator = Collator.new!(locale: "en", case_first: :lower)
+      assert Collator.sort(collator, ["A", "a", "B", "b"]) == ["a", "A", "b", "B"]
+    end
+
+    test "rejects unknown values" do
+      assert {:error, {:invalid_option_value, :case_first}} = Collator.new(case_first: :mixed)
+    end
+  end
+
+  describe "resolved_options/1" do
+    test "reports defaults" do
+      collator = Collator.new!(locale: "en")
+
+      assert %{strength: :tertiary, case_first: :off, numeric: false} =
+               Collator.resolved_options(collator)
+    end
+
+    test "reports explicit options" do
+      collator = Collator.new!(locale: "en", strength: :primary, numeric: true)
+      assert %{strength: :primary, numeric: true} = Collator.resolved_options(collator)
+    end
+
+    test "applies the built-in script order of the locale" do
+      collator = Collator.new!(locale: "ru")
+      assert Collator.compare(collator, "я", "a") == :lt
+    end
+  end
+
+  describe "unique/2" do
+    test "keeps strings that differ at the configured strength" do
+      tertiary = Collator.new!(locale: "en")
+      secondary = Collator.new!(locale: "en", strength: :secondary)
+
+      assert Collator.unique(tertiary, ["elixir", "Elixir", "élixir"]) ==
+               ["elixir", "Elixir", "élixir"]
+
+      assert Collator.unique(secondary, ["elixir", "Elixir", "élixir"]) == ["elixir", "élixir"]
+    end
+
+    test "preserves input order" do
+      collator = Collator.new!(locale: "en", strength: :primary)
+      assert Collator.unique(collator, ["b", "a", "B", "A", "c"]) == ["b", "a", "c"]
+    end
+
+    test "handles empty lists" do
+      assert Collator.unique(Collator.new!(), []) == []
+    end
+  end
+
+  describe "unique?/2" do
+    test "reports whether duplicates exist" do
+      collator = Collator.new!(locale: "en", strength: :primary)
+      assert Collator.unique?(collator, ["a", "b"])
+      refute Collator.unique?(collator, ["a", "b", "Á"])
+    end
+  end
+end
@@ -0,0 +1,80 @@
+defmodule Icu.DecimalTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Decimal
+
+  alias Icu.Experimental.Currency
+  alias Icu.Experimental.Units
+
+  describe "new/1" do
+    test "accepts integers, floats, Decimals and decimal strings" do
+      assert "42" = 42 |> Icu.Decimal.new!() |> Icu.Decimal.to_string()
+      assert "0.1" = 0.1 |> Icu.Decimal.new!() |> Icu.Decimal.to_string()
+      assert "-1.250" = "-1.250" |> Icu.Decimal.new!() |> Icu.Decimal.to_string()
+      assert "12.5" = Decimal.new("12.5") |> Icu.Decimal.new!() |> Icu.Decimal.to_string()
+    end
+
+    test "rejects values that are not numbers" do
+      assert {:error, :invalid_number} = Icu.Decimal.new("twelve")
+      assert {:error, :invalid_number} = Icu.Decimal.new(:nan)
+      assert {:error, :invalid_number} = Icu.Decimal.new(Decimal.new("NaN"))
+    end
+
+    test "returns an existing decimal as is" do
+      decimal = Icu.Decimal.new!(1)
+      assert {:ok, ^decimal} = Icu.Decimal.new(decimal)
+    end
+  end
+
+  describe "round/3" do
+    test "rounds with every mode" do
+      rounded =
+        for mode <- [:down, :up, :ceiling, :floor, :half_up, :half_down, :half_even] do
+          "-2.5" |> Icu.Decimal.new!() |> Icu.Decimal.round!(0, mode) |> to_string()
+        end
+
+      assert rounded == ["-2", "-3", "-2", "-3", "-3", "-2", "-2"]
+    end
+
+    test "leaves the original decimal untouched" do
+      decimal = Icu.Decimal.new!("1.26")
+      assert "1.3" = decimal |> Icu.Decimal.round!(1) |> to_string()
+      assert "1.26" = to_string(decimal)
+    end
+
+    test "rejects unknown modes and places" do
+      decimal = Icu.Decimal.new!(1)
+
+      assert {:error, {:invalid_option_value, :rounding_mode}} =
+               Icu.Decimal.round(decimal, 0, :nearest)
+
+      assert {:error, {:invalid_option_value, :places}} = Icu.Decimal.round(decimal, 1.5)
+    end
+  end
+
+  describe "formatting" do
+    test "is accepted by the number formatter" do
+      decimal = Icu.Decimal.new!("1234.5")
+
+      assert {:ok, "1,234.500"} = Icu.Number.format(decimal)
+
+      assert {:ok, "1.234,5"} =
+               Icu.Number.format(decimal, locale: "de", maximum_fraction_digits: 1)
+
+      assert {:ok, "1,234.500–2.000"} = Icu.Number.format_range(decimal, Icu.Decimal.new!(2))
+    end
+
+    test "is accepted by the currency and unit formatters" do
+      decimal = Icu.Decimal.new!("12.346")
+
+      assert {:ok, "$12.35"} = Currency.format(decimal, currency: "USD", locale: "en")
+
+      assert {:ok, "12 km"} =
+               Units.format(Icu.Decimal.round!(decimal), unit: "kilometer", locale: "en")
+    end
+
+    test "inspects as its digits" do
+      assert "#Icu.Decimal<-0.50>" = inspect(Icu.Decimal.new!("-0.50"))
+    end
+  end
+end
@@ -24,9 +24,188 @@ defmodule Icu.DisplayNamesTest do
       assert {:error, :invalid_value} = DisplayNames.format(:locale, 123)
     end
 
+    test "names the locale's extension keywords" do
+      assert {:ok, "English (United States, Buddhist Calendar)"} =
+               DisplayNames.format(:locale, "en-US-u-ca-buddhist", language_display: :standard)
+
+      assert {:ok, "German (Currency: EUR, 24 Hour System (0–23))"} =
+               DisplayNames.format(:locale, "de-u-cu-eur-hc-h23")
+    end
+
+    test "leaves out extension keywords with extensions: false" do
+      assert {:ok, "German"} =
+               DisplayNames.format(:locale, "de-u-ca-buddhist", extensions: false)
+    end
+
+    test "rejects extension keywords it cannot name in the display language" do
+      assert {:error, :unsupported_locale} =
+               DisplayNames.format(:locale, "en-u-ca-buddhist", locale: "de")
+
+      assert {:ok, "Englisch"} =
+               DisplayNames.format(:locale, "en-u-ca-buddhist", locale: "de", extensions: false)
+    end
+
     test "supports explicit options" do
       assert {:ok, "Mayan hieroglyphs"} =
                DisplayNames.format(:script, "Maya", style: :long, fallback: :code)
     end
   end
+
+  describe "language_display option" do
+    test "uses CLDR dialect names with :dialect" do
+      for {locale, name} <- [
+            {"en-GB", "British English"},
+            {"en-US", "American English"},
+            {"fr-CA", "Canadian French"},
+            {"zh-Hans", "Simplified Chinese"}
+          ] do
+        assert {:ok, ^name} = DisplayNames.format(:locale, locale, language_display: :dialect)
+      end
+    end
+
+    test "composes the language with its region or script with :standard" do
+      for {locale, name} <- [
+            {"en-GB", "English (United Kingdom)"},
+            {"en-US", "English (United States)"},
+            {"fr-CA", "French (Canada)"},
+            {"zh-Hans", "Chinese (Simplified)"}
+          ] do
+        assert {:ok, ^name} = DisplayNames.format(:locale, locale, language_display: :standard)
+      end
+    end
+
+    test "composes in both modes when there is no dialect name" do
+      for mode <- [:dialect, :standard] do
+        assert {:ok, "English (India)"} =
+                 DisplayNames.format(:locale, "en-IN", language_display: mode)
+      end
+    end
+
+    test "can be overridden for a single lookup" do
+      {:ok, formatter} = DisplayNames.Formatter.new(:locale, locale: "en")
+
+      assert {:ok, "British English"} = DisplayNames.Formatter.display_name(formatter, "en-GB")
+
+      assert {:ok, "English (United Kingdom)"} =
+               DisplayNames.Formatter.display_name(formatter, "en-GB",
+                 language_display: :standard
+               )
+
+      assert {:ok, "British English"} =
+               DisplayNames.Formatter.display_name(formatter, "en-GB", language_display: :dialect)
+
+      {:ok, standard} = DisplayNames.Formatter.new(:locale, language_display: :standard)
+
+      assert {:ok, "British English"} =
+               DisplayNames.Formatter.display_name(standard, "en-GB", language_display: :dialect)
+    end
+
+    test "rejects invalid per-lookup options" do
+      {:ok, formatter} = DisplayNames.Formatter.new(:locale, locale: "en")
+
+      assert {:error, {:invalid_option_value, :language_display}} =
+               DisplayNames.Formatter.display_name(formatter, "en-GB", language_display: :menu)
+
+      assert {:error, {:bad_option, :style}} =
+               DisplayNames.Formatter.display_name(formatter, "en-GB", style: :short)
+    end
+  end
+
+  describe "format_unit/2" do
+    test "formats unit names for the requested locale" do
+      assert {:ok, "kilometers per hour"} = DisplayNames.format_unit("kilometer-per-hour")
+
+      assert {:ok, "Kilometer pro Stunde"} =
+               DisplayNames.format_unit("kilometer-per-hour", locale: "de")
+    end
+
+    test "composes compounds without names of their own" do
+      assert {:ok, "liters per 100 kilometers"} =
+               DisplayNames.format_unit("liter-per-100-kilometer", locale: "en")
+    end
+
+    test "uses the style as the unit width" do
+      assert {:ok, "km/h"} = DisplayNames.format_unit("kilometer-per-hour", style: :short)
+    end
+
+    test "applies the fallback to unknown units" do
+      assert {:ok, nil} = DisplayNames.format_unit("furlong", fallback: :none)
+      assert {:ok, "furlong"} = DisplayNames.format_unit("furlong", fallback: :code)
+    end
+  end
+
+  describe "format_numbering_system/2" do
+    test "formats numbering system names" do
+      assert {:ok, "Devanagari Digits"} = DisplayNames.format_numbering_system("deva")
+      assert {:ok, "Arabic-Indic Digits"} = DisplayNames.format(:numbering_system, :arab)
+    end
+
+    test "applies the fallback when no name is available" do
+      assert {:ok, "deva"} =
+               DisplayNames.format_numbering_system("deva", locale: "de", fallback: :code)
+
+      assert {:ok, nil} = DisplayNames.format_numbering_system("nope", fallback: :none)
+    end
+  end
+
+  describe "format_with_style/3" do
+    test "reports the style each script name was found in" do
+      assert {:ok, {"UCAS", :short}} =
+               DisplayNames.format_with_style(:script, "Cans", style: :short)
+
+      for style <- [:narrow, :long, :menu] do
+        assert {:ok, {"Unified Canadian Aboriginal Syllabics", :long}} =
+                 DisplayNames.format_with_style(:script, "Cans", style: style)
+      end
+    end
+
+    test "applies menu names to languages" do
+      assert {:ok, {"Chinese, Cantonese", :menu}} =
+               DisplayNames.format_with_style(:language, "yue", style: :menu)
+
+      assert {"Chinese, Cantonese", :menu} =
+               DisplayNames.format_with_style!(:language, :yue, style: :menu)
+    end
+
+    test "falls back to the long name where menu data is absent" do
+      assert {:ok, {"German", :long}} =
+               DisplayNames.format_with_style(:language, "de", style: :menu)
+
+      assert {:ok, "German"} = DisplayNames.format(:language, "de", style: :menu)
+    end
+
+    test "reports the long style without a style option" do
+      assert {:ok, {"United Kingdom", :long}} = DisplayNames.format_with_style(:region, "GB")
+    end
+
+    test "returns nil when the name is unknown" do
+      assert {:ok, nil} = DisplayNames.format_with_style(:language, "zz", style: :menu)
+    end
+
+    test "rejects other kinds" do
+      assert {:invalid_kind, :unit} = DisplayNames.format_with_style(:unit, "meter")
+
+      assert_raise ArgumentError, fn ->
+        DisplayNames.format_with_style!(:locale, "en-GB", style: :menu)
+      end
+    end
+  end
+
+  describe "region_flag_emoji/1" do
+    test "builds the flag from the regional indicator symbols" do
+      assert {:ok, "🇩🇪"} = DisplayNames.region_flag_emoji("DE")
+      assert {:ok, "🇬🇧"} = DisplayNames.region_flag_emoji(:gb)
+      assert {:ok, "🇪🇺"} = DisplayNames.region_flag_emoji("EU")
+    end
+
+    test "returns nil for regions without a flag" do
+      assert {:ok, nil} = DisplayNames.region_flag_emoji("EZ")
+      assert {:ok, nil} = DisplayNames.region_flag_emoji("419")
+    end
+
+    test "rejects invalid region subtags" do
+      assert {:error, :invalid_region} = DisplayNames.region_flag_emoji("DEU")
+      assert_raise RuntimeError, fn -> DisplayNames.region_flag_emoji!("1") end
+    end
+  end
 end
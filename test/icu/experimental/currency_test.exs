@@ -39,6 +39,13 @@ defmodule Icu.Experimental.CurrencyTest do
       assert {:error, {:missing_option, :currency}} = Currency.format(42, [])
     end
 
+    test "returns the binary for iodata output" do
+      assert {:ok, "$5.00"} = Currency.format(5, currency: "USD", locale: "en", output: :iodata)
+
+      assert {:error, {:invalid_option_value, :output}} =
+               Currency.format(5, currency: "USD", output: :list)
+    end
+
     test "rejects non-numeric values" do
       assert {:error, :invalid_number} = Currency.format(:invalid, currency: "USD")
     end
@@ -62,6 +69,87 @@ defmodule Icu.Experimental.CurrencyTest do
     end
   end
 
+  describe "notation option" do
+    test "formats compact short amounts" do
+      assert {:ok, result} =
+               Currency.format(1_234_567, currency: "EUR", locale: "de", notation: :compact_short)
+
+      assert result =~ "1,2"
+      assert result =~ "Mio."
+      assert result =~ "€"
+    end
+
+    test "rejects compact notation with long width" do
+      assert {:error, :invalid_options} =
+               Currency.format(1_234_567,
+                 currency: "EUR",
+                 notation: :compact_short,
+                 width: :long
+               )
+    end
+
+    test "keeps the requested significant digits in compact amounts" do
+      assert {:ok, "$1.23M"} =
+               Currency.format(1_234_567,
+                 currency: "USD",
+                 notation: :compact_short,
+                 compact_rounding: {:significant, 3}
+               )
+    end
+
+    test "rounds compact amounts into the next magnitude" do
+      assert {:ok, "$1M"} =
+               Currency.format(999_999,
+                 currency: "USD",
+                 notation: :compact_short,
+                 compact_rounding: {:significant, 2}
+               )
+    end
+
+    test "uses standard notation below the compact threshold" do
+      opts = [currency: "USD", notation: :compact_short, compact_threshold: 10_000]
+
+      assert {:ok, "$9,500.00"} = Currency.format(9_500, opts)
+      assert {:ok, "-$9,500.00"} = Currency.format(-9_500, opts)
+      assert {:ok, "$12K"} = Currency.format(12_000, opts)
+    end
+
+    test "rejects invalid compact rounding" do
+      assert {:error, {:invalid_option_value, :compact_rounding}} =
+               Currency.format(1, currency: "USD", compact_rounding: {:significant, 0})
+    end
+
+    test "rejects unknown notation" do
+      assert {:error, _} = Currency.format(1, currency: "EUR", notation: :scientific)
+    end
+  end
+
+  describe "format_to_parts/2" do
+    test "separates the currency symbol from the number" do
+      assert {:ok, parts} = Currency.format_to_parts(42, currency: "USD")
+      assert Enum.map(parts, & &1.part_type) == [:currency, :number]
+      assert Enum.map_join(parts, & &1.value) == "$42.00"
+    end
+
+    test "separates the compact suffix from the currency symbol" do
+      assert {:ok, parts} =
+               Currency.format_to_parts(1_234_567,
+                 currency: "EUR",
+                 locale: "de",
+                 notation: :compact_short
+               )
+
+      assert %{part_type: :currency, value: "€"} in parts
+      assert %{part_type: :compact, value: "Mio."} in parts
+      assert %{part_type: :number, value: "1,2"} in parts
+
+      assert {:ok, formatted} =
+               Currency.format(1_234_567, currency: "EUR", locale: "de", notation: :compact_short)
+
+      assert Enum.map_join(parts, & &1.value) == formatted
+    end
+  end
+
   describe "format/2 with Decimal" do
     test "formats Decimal with correct precision" do
       assert {:ok, "$42.00"} = Currency.format(Decimal.new("42.00"), currency: "USD")
@@ -118,6 +206,39 @@ defmodule Icu.Experimental.CurrencyTest do
     end
   end
 
+  describe "region_currencies/2" do
+    test "returns the legal tenders of a region" do
+      assert {:ok, ["USD"]} = Currency.region_currencies("US")
+      assert {:ok, ["JPY"]} = Currency.region_currencies("jp")
+      assert {:ok, ["BTN", "INR"]} = Currency.region_currencies("BT")
+    end
+
+    test "adds the replaced currencies when asked for history" do
+      assert {:ok, ["EUR"]} = Currency.region_currencies("DE", historical: false)
+      assert {:ok, ["EUR", "DEM"]} = Currency.region_currencies("DE", historical: true)
+      assert {:ok, ["VES", "VEF", "VEB"]} = Currency.region_currencies("VE", historical: true)
+      assert {:ok, ["CHF"]} = Currency.region_currencies("CH", historical: true)
+    end
+
+    test "has no data for regions without a currency" do
+      assert {:error, :no_data} = Currency.region_currencies("AQ")
+      assert {:error, :no_data} = Currency.region_currencies("419")
+    end
+
+    test "rejects invalid regions and options" do
+      assert {:error, :invalid_region} = Currency.region_currencies("Germany")
+      assert {:error, :invalid_region} = Currency.region_currencies(:de)
+
+      assert {:error, {:invalid_option_value, :historical}} =
+               Currency.region_currencies("DE", historical: :yes)
+    end
+
+    test "region_currencies!/2 raises on error" do
+      assert ["EUR"] = Currency.region_currencies!("FR")
+      assert_raise RuntimeError, fn -> Currency.region_currencies!("AQ") end
+    end
+  end
+
   describe "round/2 with :iso digits (default)" do
     test "USD rounds to 2 decimal places" do
       assert {:ok, result} = Currency.round(123.456, currency: "USD")
@@ -238,6 +359,33 @@ defmodule Icu.Experimental.CurrencyTest do
     end
   end
 
+  describe "round/3" do
+    test "applies standard digits" do
+      assert {:ok, rounded} = Currency.round(123.456, "USD", :standard)
+      assert Decimal.equal?(rounded, Decimal.new("123.46"))
+
+      assert {:ok, rounded} = Currency.round(123.5, "JPY", :standard)
+      assert Decimal.equal?(rounded, Decimal.new("124"))
+    end
+
+    test "applies cash rounding increments" do
+      assert {:ok, rounded} = Currency.round(Decimal.new("1.03"), "CHF", :cash)
+      assert Decimal.equal?(rounded, Decimal.new("1.05"))
+    end
+
+    test "rejects unknown rounding kinds" do
+      assert {:error, :invalid_currency_digits} = Currency.round(1, "USD", :bankers)
+    end
+
+    test "raising variant" do
+      assert Decimal.equal?(Currency.round!(1.005, "EUR", :standard), Decimal.new("1.00"))
+
+      assert_raise RuntimeError, ~r/currency rounding failed/, fn ->
+        Currency.round!(1, "USD", :bankers)
+      end
+    end
+  end
+
   describe "round!/2" do
     test "returns Decimal on success" do
       result = Currency.round!(42.123, currency: "USD")
@@ -292,4 +440,77 @@ defmodule Icu.Experimental.CurrencyTest do
       end
     end
   end
+
+  describe "format_range/3" do
+    test "repeats a leading symbol on both ends" do
+      assert {:ok, "$10.00–$20.00"} = Currency.format_range(10, 20, currency: "USD")
+      assert {:ok, "¥10–¥20"} = Currency.format_range(10, 20, currency: "JPY")
+    end
+
+    test "shares a trailing currency" do
+      assert {:ok, "10,00–20,00 €"} =
+               Currency.format_range(10, 20, currency: "EUR", locale: "de")
+    end
+
+    test "rounds both ends" do
+      assert {:ok, "$10.00–$10.01"} = Currency.format_range(9.999, 10.005, currency: "USD")
+    end
+
+    test "uses the notation of the larger end" do
+      opts = [currency: "USD", notation: :compact_short, compact_threshold: 1000]
+      assert {:ok, "$900–$12K"} = Currency.format_range(900, 12_000, opts)
+      assert {:ok, "$100.00–$900.00"} = Currency.format_range(100, 900, opts)
+    end
+
+    test "rejects invalid ends" do
+      assert {:error, :invalid_number} = Currency.format_range(10, :twenty, currency: "USD")
+    end
+  end
+
+  describe "format_range_to_parts/3" do
+    test "tags the shared currency" do
+      assert {:ok, parts} = Currency.format_range_to_parts(10, 20, currency: "EUR", locale: "de")
+
+      assert Enum.map(parts, &{&1.part_type, &1.source}) == [
+               {:number, :start},
+               {:literal, :shared},
+               {:number, :end},
+               {:literal, :shared},
+               {:currency, :shared}
+             ]
+    end
+
+    test "adds offsets with an index mode" do
+      opts = [currency: "EUR", locale: "de", index_mode: :byte]
+      assert {:ok, parts} = Currency.format_range_to_parts(10, 20, opts)
+      assert {:ok, formatted} = Currency.format_range(10, 20, currency: "EUR", locale: "de")
+
+      for part <- parts do
+        assert binary_part(formatted, part.start, part.end - part.start) == part.value
+      end
+    end
+  end
+
+  describe "approximately option" do
+    test "prefixes the approximately sign" do
+      assert {:ok, "~$5.00"} = Currency.format(5, currency: "USD", approximately: true)
+    end
+
+    test "adds an approximately sign part" do
+      assert {:ok, [%{part_type: :approximately_sign, value: "~"} | _]} =
+               Currency.format_to_parts(5, currency: "USD", approximately: true)
+    end
+
+    test "applies below the compact threshold" do
+      opts = [
+        currency: "USD",
+        notation: :compact_short,
+        compact_threshold: 1000,
+        approximately: true
+      ]
+
+      assert {:ok, "~$950.00"} = Currency.format(950, opts)
+      assert {:ok, "~$12K"} = Currency.format(12_000, opts)
+    end
+  end
 end
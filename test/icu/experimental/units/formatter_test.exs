@@ -0,0 +1,63 @@
+defmodule Icu.Experimental.Units.FormatterTest do
+  use ExUnit.Case, async: true
+
+  alias Icu.Experimental.Units.Formatter
+
+  describe "new/1" do
+    test "creates a formatter for a simple unit" do
+      assert {:ok, %Formatter{}} = Formatter.new(locale: "en", unit: "meter")
+    end
+
+    test "creates a formatter for a precomposed compound unit" do
+      assert {:ok, %Formatter{}} = Formatter.new(locale: "en", unit: "kilometer-per-hour")
+    end
+
+    test "creates a formatter for a composed compound unit" do
+      assert {:ok, %Formatter{}} = Formatter.new(locale: "en", unit: "liter-per-100-kilometer")
+    end
+
+    test "requires unit option" do
+      assert {:error, {:missing_option, :unit}} = Formatter.new(locale: "en")
+    end
+
+    test "rejects unknown units" do
+      assert {:error, :invalid_unit} = Formatter.new(locale: "en", unit: "nope")
+      assert {:error, :invalid_unit} = Formatter.new(locale: "en", unit: "nope-per-hour")
+    end
+
+    test "rejects unknown options" do
+      assert {:error, {:bad_option, :unknown}} =
+               Formatter.new(locale: "en", unit: "meter", unknown: true)
+    end
+  end
+
+  describe "format/2" do
+    test "formats temperatures" do
+      formatter = Formatter.new!(locale: "en", unit: "fahrenheit")
+      assert {:ok, "72°F"} = Formatter.format(formatter, 72)
+    end
+
+    test "formats speeds" do
+      formatter = Formatter.new!(locale: "de", unit: "kilometer-per-hour")
+      assert {:ok, "100 km/h"} = Formatter.format(formatter, 100)
+    end
+
+    test "composes per units without precomposed data" do
+      formatter = Formatter.new!(locale: "en", unit: "kilowatt-hour-per-day")
+      assert {:ok, result} = Formatter.format(formatter, 12)
+      assert result =~ "12 kWh/"
+    end
+
+    test "formats long compound units" do
+      formatter = Formatter.new!(locale: "en", unit: "liter-per-100-kilometer", width: :long)
+      assert {:ok, result} = Formatter.format(formatter, 6.5)
+      assert result =~ "6.5 liters"
+      assert result =~ "100 kilometers"
+    end
+
+    test "rejects non-numeric values" do
+      formatter = Formatter.new!(locale: "en", unit: "meter")
+      assert {:error, :invalid_number} = Formatter.format(formatter, "1")
+    end
+  end
+end
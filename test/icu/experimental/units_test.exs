@@ -0,0 +1,265 @@
+defmodule Icu.Experimental.UnitsTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Experimental.Units
+
+  alias Icu.Experimental.Units
+
+  describe "convert/3" do
+    test "converts length" do
+      assert {:ok, km} = Units.convert(1, "mile", "kilometer")
+      assert_in_delta km, 1.609344, 1.0e-9
+    end
+
+    test "converts temperature with an offset" do
+      assert {:ok, celsius} = Units.convert(32, "fahrenheit", "celsius")
+      assert_in_delta celsius, 0.0, 1.0e-9
+    end
+
+    test "converts mass" do
+      assert {:ok, kg} = Units.convert(1, "pound", "kilogram")
+      assert_in_delta kg, 0.45359237, 1.0e-9
+    end
+
+    test "converts compound units" do
+      assert {:ok, mph} = Units.convert(100, "kilometer-per-hour", "mile-per-hour")
+      assert_in_delta mph, 62.137, 1.0e-3
+    end
+
+    test "rejects unknown units" do
+      assert {:error, :invalid_unit} = Units.convert(1, "furlong-ish", "meter")
+    end
+
+    test "rejects incompatible units" do
+      assert {:error, :incompatible_units} = Units.convert(1, "celsius", "meter")
+    end
+
+    test "rejects non-numeric values" do
+      assert {:error, :invalid_number} = Units.convert("1", "meter", "foot")
+    end
+  end
+
+  describe "convert_for_locale/3" do
+    test "uses US customary units for en-US" do
+      assert {:ok, {fahrenheit, "fahrenheit"}} =
+               Units.convert_for_locale(20, "celsius", locale: "en-US")
+
+      assert_in_delta fahrenheit, 68.0, 1.0e-9
+    end
+
+    test "uses metric units for de" do
+      assert {:ok, {kg, "kilogram"}} = Units.convert_for_locale(10, "pound", locale: "de")
+      assert_in_delta kg, 4.5359237, 1.0e-9
+    end
+
+    test "uses miles but metric mass for en-GB" do
+      assert {:ok, {_, "mile"}} = Units.convert_for_locale(5, "kilometer", locale: "en-GB")
+      assert {:ok, {_, "kilogram"}} = Units.convert_for_locale(5, "pound", locale: "en-GB")
+    end
+
+    test "honours the -u-ms- keyword" do
+      assert {:ok, {_, "kilometer"}} =
+               Units.convert_for_locale(5, "mile", locale: "en-US-u-ms-metric")
+    end
+
+    test "returns units without a counterpart unchanged" do
+      assert {:ok, {90.0, "minute"}} = Units.convert_for_locale(90, "minute", locale: "en-US")
+    end
+
+    test "rejects unknown units" do
+      assert {:error, :invalid_unit} = Units.convert_for_locale(1, "nope", locale: "en-US")
+    end
+  end
+
+  describe "preferred_units/3" do
+    test "breaks person height into feet and inches for en-US" do
+      assert {:ok, [{6, "foot"}, {inches, "inch"}]} =
+               Units.preferred_units({1.9, "meter"}, "person-height", locale: "en-US")
+
+      assert_in_delta inches, 2.803, 1.0e-3
+    end
+
+    test "uses stones and pounds for person weight in en-GB" do
+      assert {:ok, [{11, "stone"}, {pounds, "pound"}]} =
+               Units.preferred_units({70, "kilogram"}, "person", locale: "en-GB")
+
+      assert_in_delta pounds, 0.32, 1.0e-2
+    end
+
+    test "carries a remainder that rounds up to a whole larger unit" do
+      assert {:ok, [{6, "foot"}, {+0.0, "inch"}]} =
+               Units.preferred_units({5.99999, "foot"}, "person-height", locale: "en-US")
+    end
+
+    test "rounds the remainder to :maximum_fraction_digits" do
+      assert {:ok, [{5, "foot"}, {11.0, "inch"}]} =
+               Units.preferred_units({180, "centimeter"}, "person-height",
+                 locale: "en-US",
+                 maximum_fraction_digits: 0
+               )
+
+      assert {:error, {:invalid_option_value, :maximum_fraction_digits}} =
+               Units.preferred_units({180, "centimeter"}, "person-height",
+                 maximum_fraction_digits: -1
+               )
+    end
+
+    test "falls back to the default counterpart for unknown usages" do
+      assert {:ok, [{_, "mile"}]} =
+               Units.preferred_units({10, "kilometer"}, "something-else", locale: "en-US")
+    end
+
+    test "rejects units that do not match the usage" do
+      assert {:error, :incompatible_units} =
+               Units.preferred_units({10, "kilogram"}, "road", locale: "en-US")
+    end
+  end
+
+  describe "format/2" do
+    test "returns the binary for iodata output" do
+      assert {:ok, "21°C"} = Units.format(21, unit: "celsius", locale: "en", output: :iodata)
+
+      assert {:error, {:invalid_option_value, :output}} =
+               Units.format(21, unit: "celsius", output: :list)
+    end
+  end
+
+  describe "format_mixed/2" do
+    test "formats feet and inches" do
+      assert {:ok, "5 ft, 11 in"} =
+               Units.format_mixed([{5, "foot"}, {11, "inch"}], locale: "en-US")
+    end
+
+    test "formats hours and minutes with the narrow width" do
+      assert {:ok, result} =
+               Units.format_mixed([{1, "hour"}, {30, "minute"}], locale: "en", width: :narrow)
+
+      assert result =~ "1h"
+      assert result =~ "30m"
+    end
+
+    test "accepts the output of preferred_units/3" do
+      {:ok, measures} =
+        Units.preferred_units({70, "kilogram"}, "person", locale: "en-GB")
+
+      assert {:ok, result} = Units.format_mixed(measures, locale: "en-GB")
+      assert result =~ "11 st"
+    end
+
+    test "rejects empty input" do
+      assert {:error, :invalid_items} = Units.format_mixed([])
+    end
+
+    test "rejects unknown units" do
+      assert {:error, :invalid_unit} = Units.format_mixed([{1, "nope"}])
+    end
+
+    test "rejects unknown widths" do
+      assert {:error, {:invalid_option_value, :width}} =
+               Units.format_mixed([{1, "hour"}], width: :wide)
+    end
+  end
+
+  describe "patterns/2" do
+    test "returns a pattern per plural category" do
+      assert {:ok, %{one: "{0} day", other: "{0} days"}} =
+               Units.patterns("day", locale: "en", width: :long)
+    end
+
+    test "covers every category of the locale" do
+      assert {:ok, patterns} = Units.patterns("hour", locale: "ru", width: :long)
+      assert Map.keys(patterns) |> Enum.sort() == [:few, :many, :one, :other]
+      assert Enum.all?(Map.values(patterns), &String.contains?(&1, "{0}"))
+    end
+
+    test "matches the formatter output" do
+      assert {:ok, %{other: pattern}} = Units.patterns("meter", locale: "fr", width: :long)
+      formatted = Units.format!(3, unit: "meter", locale: "fr", width: :long)
+      assert String.replace(pattern, "{0}", "3") == formatted
+    end
+
+    test "supports composed per units" do
+      assert {:ok, %{other: "{0} L/100 km"}} =
+               Units.patterns("liter-per-100-kilometer", locale: "en")
+    end
+
+    test "composes per units with the locale's per pattern" do
+      assert {:ok, %{other: "{0} liters per 100 kilometers"}} =
+               Units.patterns("liter-per-100-kilometer", locale: "en", width: :long)
+    end
+
+    test "rejects unknown units" do
+      assert {:error, :invalid_unit} = Units.patterns("furlong-ish", locale: "en")
+    end
+  end
+
+  describe "format_range/3" do
+    test "writes a trailing unit once" do
+      assert {:ok, "3–5 km"} = Units.format_range(3, 5, unit: "kilometer", locale: "en")
+      assert {:ok, "3-5 km"} = Units.format_range(3, 5, unit: "kilometer", locale: "es")
+    end
+
+    test "collapses equal ends" do
+      assert {:ok, "5 km"} = Units.format_range(5, 5, unit: "kilometer", locale: "en")
+    end
+
+    test "rejects invalid ends" do
+      assert {:error, :invalid_number} = Units.format_range(3, "5", unit: "meter")
+    end
+  end
+
+  describe "format_to_parts/2" do
+    test "tags the number and the unit" do
+      assert {:ok, parts} = Units.format_to_parts(21, unit: "celsius", locale: "en")
+      assert Enum.map(parts, &{&1.part_type, &1.value}) == [{:number, "21"}, {:unit, "°C"}]
+    end
+
+    test "keeps digits of the unit in the unit" do
+      assert {:ok, parts} =
+               Units.format_to_parts(6.5, unit: "liter-per-100-kilometer", locale: "en")
+
+      assert Enum.map(parts, &{&1.part_type, &1.value}) == [
+               {:number, "6.5"},
+               {:literal, " "},
+               {:unit, "L/100 km"}
+             ]
+    end
+
+    test "adds the approximately sign and offsets" do
+      opts = [unit: "kilometer", locale: "en", approximately: true, index_mode: :byte]
+      assert {:ok, parts} = Units.format_to_parts(1234, opts)
+
+      assert Enum.map(parts, &{&1.part_type, &1.value, &1.start, &1.end}) == [
+               {:approximately_sign, "~", 0, 1},
+               {:number, "1,234", 1, 6},
+               {:literal, " ", 6, 7},
+               {:unit, "km", 7, 9}
+             ]
+    end
+
+    test "rejects invalid numbers" do
+      assert {:error, :invalid_number} = Units.format_to_parts("1", unit: "meter")
+    end
+  end
+
+  describe "resolved_options/1" do
+    test "reports the defaults" do
+      formatter = Icu.Experimental.Units.Formatter.new!(unit: "day", width: :long)
+
+      assert %{unit: "day", width: :long, approximately: false} =
+               Units.resolved_options(formatter)
+    end
+  end
+
+  describe "approximately option" do
+    test "prefixes the approximately sign" do
+      assert {:ok, "~42 km"} =
+               Units.format(42, unit: "kilometer", locale: "en", approximately: true)
+    end
+
+    test "marks the whole range once" do
+      assert {:ok, "~3–5 km"} =
+               Units.format_range(3, 5, unit: "kilometer", locale: "en", approximately: true)
+    end
+  end
+end
@@ -0,0 +1,84 @@
+defmodule Icu.Formatter.OptionsTest do
+  # Changes the global `:unknown_options` setting.
+  use ExUnit.Case, async: false
+
+  import ExUnit.CaptureLog
+
+  setup do
+    on_exit(fn -> Application.delete_env(:icu, :unknown_options) end)
+  end
+
+  describe "unknown_options policy" do
+    test "rejects unknown options by default" do
+      assert {:error, {:bad_option, :colour}} = Icu.Number.format(1, colour: :red)
+      assert {:error, {:bad_option, :colour}} = Icu.List.format(["a", "b"], colour: :red)
+    end
+
+    test "drops unknown options with :ignore" do
+      Application.put_env(:icu, :unknown_options, :ignore)
+
+      assert {:ok, "1"} = Icu.Number.format(1, colour: :red, maximum_fraction_digits: 0)
+      assert {:ok, "a and b"} = Icu.List.format(["a", "b"], colour: :red)
+
+      assert {:ok, "German"} =
+               Icu.DisplayNames.format(:language, "de", locale: "en", colour: :red)
+    end
+
+    test "logs dropped options with :warn" do
+      Application.put_env(:icu, :unknown_options, :warn)
+
+      log =
+        capture_log(fn ->
+          assert {:ok, "1"} = Icu.Number.format(1, colour: :red, maximum_fraction_digits: 0)
+        end)
+
+      assert log =~ "ignoring unknown number option :colour"
+    end
+
+    test "records dropped options as formatter warnings" do
+      Application.put_env(:icu, :unknown_options, :ignore)
+
+      formatter = Icu.List.Formatter.new!(locale: "en", colour: :red)
+      assert [{:ignored_option, :colour}] = Icu.warnings(formatter)
+    end
+
+    test "still validates the values of known options" do
+      Application.put_env(:icu, :unknown_options, :ignore)
+
+      assert {:error, {:invalid_option_value, :grouping}} =
+               Icu.Number.format(1, grouping: :sometimes)
+    end
+  end
+
+  describe "nil option values" do
+    test "are dropped before reaching the NIFs" do
+      assert Icu.Number.format(1234.5, grouping: nil, maximum_fraction_digits: nil) ==
+               Icu.Number.format(1234.5)
+
+      assert {:ok, "a, b, and c"} = Icu.List.format(["a", "b", "c"], type: nil, width: nil)
+    end
+
+    test "mean the default at the NIF boundary" do
+      assert {:ok, numbers} =
+               Icu.Nif.number_formatter_new("en", %{grouping: nil, maximum_fraction_digits: nil})
+
+      assert {:ok, "1,234.568"} = Icu.Nif.number_format(numbers, 1234.5678)
+
+      assert {:ok, list} = Icu.Nif.list_formatter_new("en", %{list_type: nil, width: nil})
+      assert {:ok, "a, b, and c"} = Icu.Nif.list_format(list, ["a", "b", "c"])
+
+      assert {:ok, names} =
+               Icu.Nif.display_names_formatter_new("en", :language, %{style: nil, fallback: nil})
+
+      assert {:ok, "German"} = Icu.Nif.display_names_of(names, "de", %{})
+
+      date = %{year: 2024, month: 1, day: 15}
+      assert {:ok, plain} = Icu.Nif.temporal_date_formatter_new("en", %{})
+
+      assert {:ok, with_nils} =
+               Icu.Nif.temporal_date_formatter_new("en", %{length: nil, calendar: nil})
+
+      assert Icu.Nif.temporal_format(with_nils, date) == Icu.Nif.temporal_format(plain, date)
+    end
+  end
+end
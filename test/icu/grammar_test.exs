@@ -0,0 +1,39 @@
+defmodule Icu.GrammarTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Grammar
+
+  alias Icu.Grammar
+
+  describe "info/2" do
+    test "reports unit genders" do
+      assert {:ok, %{gender: :masculine}} = Grammar.info({:unit, "day"}, locale: "de")
+      assert {:ok, %{gender: :neuter}} = Grammar.info({:unit, "kilogram"}, locale: "de-AT")
+      assert {:ok, %{gender: :feminine}} = Grammar.info({:unit, "hour"}, locale: "fr")
+      assert {:ok, %{gender: :masculine}} = Grammar.info({:unit, "minute"}, locale: "es")
+    end
+
+    test "reports weekday and month genders" do
+      assert {:ok, %{gender: :feminine}} = Grammar.info({:weekday, 1}, locale: "pt")
+      assert {:ok, %{gender: :masculine}} = Grammar.info({:weekday, 6}, locale: "pt")
+      assert {:ok, %{gender: :masculine}} = Grammar.info({:month, 5}, locale: "fr")
+    end
+
+    test "reports no gender without data" do
+      assert {:ok, %{gender: nil}} = Grammar.info({:unit, "hour"}, locale: "ja")
+      assert {:ok, %{gender: nil}} = Grammar.info({:unit, "parsec"}, locale: "de")
+    end
+
+    test "rejects invalid things" do
+      assert {:error, :invalid_options} = Grammar.info({:weekday, 8}, locale: "de")
+      assert {:error, :invalid_options} = Grammar.info({:month, "may"}, locale: "de")
+      assert {:error, :invalid_options} = Grammar.info({:era, 1}, locale: "de")
+      assert {:error, :invalid_options} = Grammar.info(:hour, locale: "de")
+    end
+
+    test "info!/2 raises on failure" do
+      assert %{gender: :feminine} = Grammar.info!({:unit, "week"}, locale: "it")
+      assert_raise RuntimeError, fn -> Grammar.info!({:weekday, 0}, locale: "it") end
+    end
+  end
+end
@@ -9,7 +9,7 @@ defmodule Icu.LanguageTagTest do
     LanguageTag.parse!("nb")
   end
 
-  describe "match_gettext/2" do
+  describe "match_gettext/3" do
     test "simple matches work" do
       assert {:ok, "en"} == LanguageTag.match_gettext(LanguageTag.parse!("en-US"), ["en", "fr"])
 
@@ -22,6 +22,38 @@ defmodule Icu.LanguageTagTest do
       assert {:error, :no_match} ==
                LanguageTag.match_gettext(LanguageTag.parse!("no-NB"), ["en-US", "fr"])
     end
+
+    test "matches case-insensitively" do
+      tag = LanguageTag.parse!("pt-BR")
+      assert {:ok, "pt"} = LanguageTag.match_gettext(tag, ["pt_br", "pt"])
+
+      assert {:ok, "pt_br"} =
+               LanguageTag.match_gettext(tag, ["pt_br", "pt"], case_insensitive: true)
+    end
+
+    test "strips scripts" do
+      tag = LanguageTag.parse!("zh-Hant-TW")
+      assert {:ok, "zh_TW"} = LanguageTag.match_gettext(tag, ["zh_TW"], strip_script: true)
+    end
+
+    test "returns canonical tags" do
+      tag = LanguageTag.parse!("en-US")
+      assert {:ok, "en-US"} = LanguageTag.match_gettext(tag, ["en_US"], output: :canonical)
+    end
+
+    test "returns the distance of the match" do
+      tag = LanguageTag.parse!("en-US")
+      assert {:ok, "en_US", 0} = LanguageTag.match_gettext_with_distance(tag, ["en_US", "en"])
+      assert {:ok, "en", distance} = LanguageTag.match_gettext_with_distance(tag, ["en"])
+      assert distance > 0
+    end
+
+    test "validates the options" do
+      tag = LanguageTag.parse!("en")
+
+      assert {:error, {:invalid_option_value, :output}} =
+               LanguageTag.match_gettext(tag, ["en"], output: :upcase)
+    end
   end
 
   describe "hour_cycle" do
@@ -64,6 +96,23 @@ defmodule Icu.LanguageTagTest do
       assert {:ok, :h23} = LanguageTag.get_hour_cycle(h23)
     end
 
+    test "hour_cycle resolves the cycle from locale data" do
+      assert {:ok, :h12} = LanguageTag.hour_cycle(LanguageTag.parse!("en-US"))
+      assert {:ok, :h23} = LanguageTag.hour_cycle(LanguageTag.parse!("en-GB"))
+      assert {:ok, :h23} = LanguageTag.hour_cycle(LanguageTag.parse!("ja"))
+    end
+
+    test "hour_cycle honours the -u-hc- extension" do
+      assert {:ok, :h23} = LanguageTag.hour_cycle(LanguageTag.parse!("en-US-u-hc-h23"))
+      assert {:ok, :h12} = LanguageTag.hour_cycle(LanguageTag.parse!("de-u-hc-h12"))
+    end
+
+    test "uses_24h? follows the resolved cycle" do
+      assert LanguageTag.uses_24h?(LanguageTag.parse!("de-DE"))
+      refute LanguageTag.uses_24h?(LanguageTag.parse!("en-US"))
+      refute LanguageTag.uses_24h?(LanguageTag.parse!("de-u-hc-h12"))
+    end
+
     test "set_hour_cycle rejects invalid hour cycle" do
       tag = LanguageTag.parse!("en-US")
       assert {:error, :invalid_options} = LanguageTag.set_hour_cycle(tag, :h24)
@@ -87,4 +136,199 @@ defmodule Icu.LanguageTagTest do
       assert str =~ "ca-buddhist"
     end
   end
+
+  describe "validate_extension_value/2 and set_extension/3" do
+    test "accepts the values CLDR defines" do
+      for {key, value} <- [
+            {"ca", "islamic-umalqura"},
+            {"co", "phonebk"},
+            {"hc", "h24"},
+            {"kn", "true"},
+            {"kn", "false"},
+            {"kr", "grek-digit"},
+            {"nu", "arab"},
+            {"fw", "mon"},
+            {"cu", "EUR"},
+            {"rg", "gbzzzz"},
+            {"sd", "usca"},
+            {"tz", "usnyc"}
+          ] do
+        assert :ok = LanguageTag.validate_extension_value(key, value), "#{key}-#{value}"
+      end
+    end
+
+    test "rejects values the key does not take" do
+      for {key, value} <- [
+            {"nu", "yes"},
+            {"ca", "gregorian"},
+            {"hc", "h13"},
+            {"kn", "maybe"},
+            {"cu", "euro"},
+            {"rg", "california"},
+            {"ca", "not a value"}
+          ] do
+        assert {:error, :invalid_extension_value} =
+                 LanguageTag.validate_extension_value(key, value),
+               "#{key}-#{value}"
+      end
+    end
+
+    test "rejects keys CLDR does not define" do
+      assert {:error, :unknown_extension_key} = LanguageTag.validate_extension_value("zz", "a")
+      assert {:error, :unknown_extension_key} = LanguageTag.validate_extension_value("cal", "a")
+    end
+
+    test "compares keys and values regardless of case" do
+      assert :ok = LanguageTag.validate_extension_value("CA", "Buddhist")
+    end
+
+    test "set_extension replaces an existing value" do
+      tag = LanguageTag.parse!("en-US-u-nu-thai")
+
+      assert {:ok, "en-US-u-nu-latn"} =
+               tag |> LanguageTag.set_extension!("nu", "latn") |> LanguageTag.to_string()
+    end
+
+    test "set_extension leaves the tag alone on error" do
+      tag = LanguageTag.parse!("en-US")
+
+      assert {:error, :unknown_extension_key} = LanguageTag.set_extension(tag, "xx", "latn")
+      assert {:ok, "en-US"} = LanguageTag.to_string(tag)
+      assert_raise ArgumentError, fn -> LanguageTag.set_extension!(tag, "nu", "yes") end
+    end
+  end
+
+  describe "compare/2, equal?/2 and hash/1" do
+    test "tags with the same canonical form are equal" do
+      a = LanguageTag.parse!("sr-latn-rs")
+      b = LanguageTag.parse!("sr-Latn-RS")
+
+      assert LanguageTag.equal?(a, b)
+      assert LanguageTag.hash(a) == LanguageTag.hash(b)
+      refute LanguageTag.equal?(a, LanguageTag.parse!("sr-Cyrl-RS"))
+    end
+
+    test "sorts tags" do
+      tags = Enum.map(["fr", "en-GB", "de", "en"], &LanguageTag.parse!/1)
+
+      assert ["de", "en", "en-GB", "fr"] ==
+               tags |> Enum.sort(LanguageTag) |> Enum.map(&LanguageTag.to_string!/1)
+    end
+
+    test "hashes deduplicate tags" do
+      tags = Enum.map(["en-us", "en-US", "EN-US", "de"], &LanguageTag.parse!/1)
+      assert length(Enum.uniq_by(tags, &LanguageTag.hash/1)) == 2
+    end
+
+    test "raise on a tag that is not a resource" do
+      bogus = %LanguageTag{resource: make_ref()}
+
+      assert_raise ArgumentError, ~r/invalid_resource/, fn ->
+        LanguageTag.compare(bogus, LanguageTag.parse!("en"))
+      end
+
+      assert_raise ArgumentError, ~r/invalid_resource/, fn -> LanguageTag.hash(bogus) end
+    end
+  end
+
+  describe "language_regions/1 and language_scripts/1" do
+    test "lists the regions of a language" do
+      assert {:ok, regions} = LanguageTag.language_regions("es")
+      assert Enum.all?(["AR", "CO", "ES", "MX"], &(&1 in regions))
+      refute "FR" in regions
+      assert regions == Enum.sort(regions)
+    end
+
+    test "ignores subtags other than the language" do
+      assert LanguageTag.language_regions("pt-BR") == LanguageTag.language_regions("pt")
+      assert "BR" in LanguageTag.language_regions!(LanguageTag.parse!("pt"))
+    end
+
+    test "lists the scripts of a language" do
+      assert {:ok, ["Hans", "Hant"]} = LanguageTag.language_scripts("zh")
+      assert {:ok, ["Latn"]} = LanguageTag.language_scripts("fr")
+      assert "Latn" in LanguageTag.language_scripts!("sr")
+      assert "Cyrl" in LanguageTag.language_scripts!("sr")
+    end
+
+    test "rejects invalid locales" do
+      assert {:error, :invalid_locale} = LanguageTag.language_regions("not a locale!")
+      assert_raise ArgumentError, fn -> LanguageTag.language_scripts!("not a locale!") end
+    end
+  end
+
+  describe "parents/1" do
+    defp parent_strings(locale) do
+      {:ok, parents} = LanguageTag.parents(LanguageTag.parse!(locale))
+      Enum.map(parents, &LanguageTag.to_string!/1)
+    end
+
+    test "follows the CLDR parent locales" do
+      assert ["es-AR", "es-419", "es"] == parent_strings("es-AR")
+      assert ["en-AU", "en-001", "en"] == parent_strings("en-AU")
+    end
+
+    test "truncates locales without an explicit parent" do
+      assert ["de-CH", "de"] == parent_strings("de-CH")
+      assert ["fr"] == parent_strings("fr")
+    end
+
+    test "drops extensions" do
+      assert ["de-CH", "de"] == parent_strings("de-CH-u-nu-latn")
+    end
+  end
+
+  describe "filter_supported/3" do
+    test "keeps the requested locales an available locale serves" do
+      assert {:ok, ["de-AT", "en"]} =
+               LanguageTag.filter_supported(["de-AT", "it", "en"], ["de", "en", "fr"])
+    end
+
+    test "canonicalizes and deduplicates the requested locales" do
+      assert {:ok, ["en-US"]} = LanguageTag.filter_supported(["EN-us", "en-US"], ["en"], :lookup)
+    end
+
+    test "keeps extensions but ignores them while matching" do
+      assert {:ok, ["de-u-nu-latn"]} =
+               LanguageTag.filter_supported(["de-u-nu-latn"], ["de"], :lookup)
+    end
+
+    test "accepts language tags" do
+      requested = [LanguageTag.parse!("fr-CA")]
+      assert {:ok, ["fr-CA"]} = LanguageTag.filter_supported(requested, [LanguageTag.parse!("fr")])
+    end
+
+    test "best fit follows likely scripts" do
+      assert {:ok, []} = LanguageTag.filter_supported(["zh-TW"], ["zh-Hant"], :lookup)
+      assert {:ok, ["zh-TW"]} = LanguageTag.filter_supported(["zh-TW"], ["zh-Hant"])
+    end
+
+    test "rejects invalid locales and matchers" do
+      assert {:error, :invalid_locale} = LanguageTag.filter_supported(["not a locale!"], ["en"])
+      assert {:error, :invalid_options} = LanguageTag.filter_supported(["en"], ["en"], :exact)
+    end
+  end
+
+  describe "distance/2" do
+    test "is zero for the same maximized locale" do
+      assert {:ok, 0} = LanguageTag.distance("en", "en-Latn-US")
+      assert {:ok, 0} = LanguageTag.distance(LanguageTag.parse!("de-DE"), "de")
+    end
+
+    test "grows with the subtags that differ" do
+      assert {:ok, 4} = LanguageTag.distance("de-AT", "de-CH")
+      assert {:ok, 5} = LanguageTag.distance("en-US", "en-GB")
+      assert {:ok, 4} = LanguageTag.distance("en-AU", "en-GB")
+      assert {:ok, 50} = LanguageTag.distance("zh-TW", "zh-Hans-TW")
+      assert {:ok, 80} = LanguageTag.distance("ja", "ko")
+    end
+
+    test "treats close languages as close" do
+      assert {:ok, 1} = LanguageTag.distance("nb", "no")
+    end
+
+    test "rejects invalid locales" do
+      assert {:error, :invalid_locale} = LanguageTag.distance("en", "not a locale!")
+    end
+  end
 end
@@ -204,6 +204,39 @@ defmodule Icu.ListTest do
         assert is_binary(part[:value])
       end)
     end
+
+    test "leaves out offsets without an index mode" do
+      assert {:ok, parts} = List.format_to_parts(["A", "B"])
+      refute Enum.any?(parts, &Map.has_key?(&1, :start))
+    end
+
+    test "counts offsets in the requested unit" do
+      items = ["e\u0301", "😀"]
+
+      offsets = fn mode ->
+        {:ok, parts} = List.format_to_parts(items, index_mode: mode)
+        Enum.map(parts, &{&1.start, &1.end})
+      end
+
+      assert offsets.(:byte) == [{0, 3}, {3, 8}, {8, 12}]
+      assert offsets.(:utf16) == [{0, 2}, {2, 7}, {7, 9}]
+      assert offsets.(:grapheme) == [{0, 1}, {1, 6}, {6, 7}]
+    end
+
+    test "byte offsets slice the formatted string" do
+      items = ["Äpfel", "Birnen", "Kirschen"]
+      {:ok, formatted} = List.format(items, locale: "de")
+      {:ok, parts} = List.format_to_parts(items, locale: "de", index_mode: :byte)
+
+      for part <- parts do
+        assert binary_part(formatted, part.start, part.end - part.start) == part.value
+      end
+    end
+
+    test "rejects an unknown index mode" do
+      assert {:error, {:invalid_option_value, :index_mode}} =
+               List.format_to_parts(["A"], index_mode: :codepoint)
+    end
   end
 
   describe "format_to_parts!/2" do
@@ -239,4 +272,54 @@ defmodule Icu.ListTest do
       assert parts_string =~ "Bar"
     end
   end
+
+  describe "iodata output" do
+    test "reuses the item binaries" do
+      assert {:ok, iodata} = List.format(["a", "b", "c"], output: :iodata, locale: "en")
+      assert ["a", ", ", "b", ", and ", "c"] = iodata
+      assert IO.iodata_to_binary(iodata) == List.format!(["a", "b", "c"], locale: "en")
+    end
+
+    test "skips empty items" do
+      {:ok, iodata} = List.format(["a", "", "c"], output: :iodata, locale: "en")
+      assert IO.iodata_to_binary(iodata) == List.format!(["a", "", "c"], locale: "en")
+    end
+
+    test "works with a prebuilt formatter" do
+      formatter = Icu.List.Formatter.new!(locale: "es", type: :or)
+      assert {:ok, iodata} = Icu.List.Formatter.format(formatter, ["x", "y"], output: :iodata)
+      assert IO.iodata_to_binary(iodata) == "x o y"
+    end
+
+    test "rejects unknown output modes" do
+      formatter = Icu.List.Formatter.new!(locale: "en")
+
+      assert {:error, :invalid_options} =
+               Icu.List.Formatter.format(formatter, ["x"], output: :charlist)
+    end
+  end
+
+  describe "large inputs" do
+    setup do
+      items = for i <- 1..2_000, do: String.duplicate("x", 99) <> Integer.to_string(rem(i, 10))
+      %{items: items}
+    end
+
+    test "format/2 handles large inputs", %{items: items} do
+      assert {:ok, result} = List.format(items, locale: "en")
+      assert String.starts_with?(result, hd(items) <> ", ")
+      assert String.ends_with?(result, ", and " <> Elixir.List.last(items))
+    end
+
+    test "format_to_parts/2 handles large inputs", %{items: items} do
+      assert {:ok, parts} = List.format_to_parts(items, locale: "en")
+      assert Enum.count(parts, &(&1.type == :element)) == 2_000
+    end
+
+    test "format_to_parts/2 adds offsets on the dirty scheduler", %{items: items} do
+      assert {:ok, parts} = List.format_to_parts(items, locale: "en", index_mode: :byte)
+      assert %{start: 0} = hd(parts)
+      assert Elixir.List.last(parts).end == byte_size(List.format!(items, locale: "en"))
+    end
+  end
 end
@@ -0,0 +1,80 @@
+defmodule Icu.NormalizerTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Normalizer
+
+  alias Icu.Normalizer
+
+  describe "nfc_concat/2" do
+    test "composes across the boundary" do
+      assert Normalizer.nfc_concat("e", "́") == "é"
+      assert Normalizer.nfc_concat("é", "") == "é"
+    end
+
+    test "reorders combining marks across the boundary" do
+      # A dot below (class 220) goes before an acute accent (class 230).
+      assert Normalizer.nfc_concat("á", "̣") == "ạ́"
+    end
+
+    test "normalizes each side" do
+      # The angstrom and ohm signs are singletons that never survive NFC.
+      assert Normalizer.nfc_concat("Å", "Ω") == "ÅΩ"
+    end
+
+    test "keeps normalized text as is" do
+      text = String.duplicate("Grüße, 世界! ", 100)
+
+      assert Normalizer.nfc_concat(text, text) == text <> text
+      assert Normalizer.nfc_concat("", "") == ""
+    end
+  end
+
+  describe "canonical_equal?/2" do
+    test "compares canonically equivalent encodings as equal" do
+      assert Normalizer.canonical_equal?("é", "é")
+      assert Normalizer.canonical_equal?("ạ́", "ạ́")
+      assert Normalizer.canonical_equal?("Å", "Å")
+    end
+
+    test "does not fold compatibility or case differences" do
+      refute Normalizer.canonical_equal?("ﬁ", "fi")
+      refute Normalizer.canonical_equal?("É", "é")
+      refute Normalizer.canonical_equal?("e", "é")
+    end
+  end
+
+  describe "normalized_up_to/2" do
+    test "covers the whole of normalized text" do
+      text = String.duplicate("Grüße, 世界! ", 1000)
+
+      for form <- [:nfc, :nfkc] do
+        assert Normalizer.normalized_up_to(text, form) == byte_size(text)
+      end
+    end
+
+    test "stops before text that needs normalizing" do
+      prefix = String.duplicate("abc ", 100)
+      text = prefix <> "cafe\u0301"
+
+      assert Normalizer.normalized_up_to(text, :nfc) in byte_size(prefix)..(byte_size(text) - 3)
+      assert Normalizer.normalized_up_to(text, :nfd) == byte_size(text)
+    end
+
+    test "returns a prefix whose normalization is unaffected by the rest" do
+      text = "Ångström e\u0301 \u1100\u1161"
+
+      for form <- [:nfc, :nfd, :nfkc, :nfkd] do
+        length = Normalizer.normalized_up_to(text, form)
+        prefix = binary_part(text, 0, length)
+
+        assert String.normalize(prefix, form) == prefix
+        assert prefix <> String.normalize(binary_slice(text, length..-1//1), form) ==
+                 String.normalize(text, form)
+      end
+    end
+
+    test "is zero for empty text" do
+      assert Normalizer.normalized_up_to("", :nfd) == 0
+    end
+  end
+end
@@ -11,6 +11,12 @@ defmodule Icu.NumberTest do
       assert {:error, :invalid_number} = Number.format(:invalid)
     end
 
+    test "returns the binary for iodata output" do
+      assert {:ok, "42.000"} = Number.format(42, output: :iodata, locale: "en")
+
+      assert {:error, {:invalid_option_value, :output}} = Number.format(1, output: :list)
+    end
+
     test "formats basic numbers with default options" do
       assert {:ok, formatted} = Number.format(1234.5)
       assert is_binary(formatted)
@@ -44,6 +50,19 @@ defmodule Icu.NumberTest do
       assert {:ok, formatted} = Number.format(0.00123)
       assert is_binary(formatted)
     end
+
+    test "formats decimal strings without losing digits" do
+      digits = "0.1000000000000000055511151231257827"
+      assert {:ok, ^digits} = Number.format(digits, maximum_fraction_digits: 34)
+    end
+
+    test "formats decimal strings with exponents and signs" do
+      assert {:ok, "-1,500"} = Number.format("-1.5E3", maximum_fraction_digits: 0)
+    end
+
+    test "rejects malformed decimal strings" do
+      assert {:error, :invalid_number} = Number.format("12abc")
+    end
   end
 
   describe "format!/2" do
@@ -195,6 +214,126 @@ defmodule Icu.NumberTest do
       assert {:ok, formatted} = Number.format(3.14, minimum_integer_digits: 4)
       assert is_binary(formatted)
     end
+
+    test "maximum_integer_digits truncates high-order digits" do
+      assert {:ok, "345.000"} = Number.format(12_345, maximum_integer_digits: 3)
+      assert {:ok, "9.990"} = Number.format(19.99, maximum_integer_digits: 1)
+    end
+
+    test "maximum_integer_digits leaves shorter numbers alone" do
+      assert {:ok, "42.000"} = Number.format(42, maximum_integer_digits: 4)
+    end
+
+    test "maximum_integer_digits with minimum_integer_digits" do
+      assert {:ok, "07"} =
+               Number.format(107,
+                 minimum_integer_digits: 2,
+                 maximum_integer_digits: 2,
+                 maximum_fraction_digits: 0
+               )
+    end
+
+    test "rejects a maximum below the minimum integer digits" do
+      assert {:error, :invalid_options} =
+               Number.format(1, minimum_integer_digits: 3, maximum_integer_digits: 2)
+    end
+  end
+
+  describe "float_precision option" do
+    test "round_trip keeps the float's shortest digits" do
+      assert {:ok, "0.30000000000000004"} =
+               Number.format(0.1 + 0.2, maximum_fraction_digits: 17)
+    end
+
+    test "magnitude rounds the float before formatting" do
+      assert {:ok, "0.30000000000000000"} =
+               Number.format(0.1 + 0.2,
+                 float_precision: {:magnitude, -2},
+                 maximum_fraction_digits: 17
+               )
+    end
+
+    test "integer accepts integral floats only" do
+      assert {:ok, "42.000"} = Number.format(42.0, float_precision: :integer)
+      assert {:error, :invalid_number} = Number.format(42.5, float_precision: :integer)
+    end
+
+    test "does not affect exact inputs" do
+      assert {:ok, "0.125"} = Number.format("0.125", float_precision: {:magnitude, -1})
+    end
+
+    test "rejects unknown values" do
+      assert {:error, {:invalid_option_value, :float_precision}} =
+               Number.format(1.5, float_precision: :shortest)
+    end
+  end
+
+  describe "scale option" do
+    test "multiplies by a power of ten without float error" do
+      assert 0.07 * 100 != 7
+      assert {:ok, "7.000"} = Number.format(0.07, scale: 100)
+      assert {:ok, "125.000"} = Number.format(0.0125, scale: 10_000)
+      assert {:ok, "12.500"} = Number.format("0.0125", scale: 1000)
+      assert {:ok, "1.000"} = Number.format(Decimal.new("0.0001"), scale: 10_000)
+    end
+
+    test "scales before rounding" do
+      assert {:ok, "33.3"} = Number.format(1 / 3, scale: 100, maximum_fraction_digits: 1)
+    end
+
+    test "applies to parts and ranges" do
+      assert {:ok, [%{part_type: :integer, value: "50"}]} =
+               Number.format_to_parts(0.5, scale: 100, maximum_fraction_digits: 0)
+      assert {:ok, "5.000–10.000"} = Number.format_range(0.05, 0.1, scale: 100)
+    end
+
+    test "rejects factors other than powers of ten" do
+      for scale <- [0, 12, -100, 0.01] do
+        assert {:error, {:invalid_option_value, :scale}} = Number.format(1, scale: scale)
+      end
+    end
+  end
+
+  describe "absolute_value option" do
+    test "formats the number without its sign" do
+      assert {:ok, "1,234.500"} = Number.format(-1234.5, absolute_value: true)
+      assert {:ok, "1,234.500"} = Number.format(1234.5, absolute_value: true)
+      assert {:ok, "5.000–3.000"} = Number.format_range(-5, -3, absolute_value: true)
+    end
+
+    test "marks negative numbers with an empty sign part" do
+      assert {:ok, [%{part_type: :minus_sign, value: ""}, %{part_type: :integer, value: "42"}]} =
+               Number.format_to_parts(-42, absolute_value: true, maximum_fraction_digits: 0)
+
+      assert {:ok, [%{part_type: :minus_sign, value: "", start: 0, end: 0} | _]} =
+               Number.format_to_parts(-42, absolute_value: true, index_mode: :byte)
+
+      assert {:ok, [%{part_type: :integer, value: "42"}]} =
+               Number.format_to_parts(42, absolute_value: true, maximum_fraction_digits: 0)
+    end
+
+    test "collapses range ends that only differ in sign" do
+      opts = [absolute_value: true, maximum_fraction_digits: 0]
+      assert {:ok, "3"} = Number.format_range(-3, 3, opts)
+
+      assert {:ok, [%{part_type: :integer, value: "3", source: :shared}]} =
+               Number.format_range_to_parts(-3, 3, opts)
+
+      assert {:ok, [%{part_type: :minus_sign, value: "", source: :shared}, %{value: "3"}]} =
+               Number.format_range_to_parts(-3, -3, opts)
+
+      assert {:ok, [%{part_type: :minus_sign, source: :start}, %{value: "5"} | _]} =
+               Number.format_range_to_parts(-5, -3, opts)
+    end
+
+    test "leaves the sign display to the absolute value" do
+      assert {:ok, "+42.000"} = Number.format(-42, absolute_value: true, sign_display: :always)
+    end
+
+    test "rejects non-boolean values" do
+      assert {:error, {:invalid_option_value, :absolute_value}} =
+               Number.format(1, absolute_value: :yes)
+    end
   end
 
   describe "combined options" do
@@ -292,6 +431,54 @@ defmodule Icu.NumberTest do
       assert length(parts) > 0
     end
 
+    test "splits the integer at each group separator" do
+      assert {:ok, parts} = Number.format_to_parts(1_234_567.5, maximum_fraction_digits: 1)
+
+      assert Enum.map(parts, &{&1.part_type, &1.value}) == [
+               {:integer, "1"},
+               {:group, ","},
+               {:integer, "234"},
+               {:group, ","},
+               {:integer, "567"},
+               {:decimal, "."},
+               {:fraction, "5"}
+             ]
+    end
+
+    test "adds offsets with an index mode" do
+      assert {:ok, parts} = Number.format_to_parts(-1234.5, index_mode: :grapheme)
+      assert {:ok, formatted} = Number.format(-1234.5)
+
+      for part <- parts do
+        assert String.slice(formatted, part.start, part.end - part.start) == part.value
+      end
+    end
+
+    test "offsets follow the approximately sign" do
+      assert {:ok, [sign | _] = parts} =
+               Number.format_to_parts(5, approximately: true, index_mode: :grapheme)
+
+      assert %{part_type: :approximately_sign, start: 0, end: 1} = sign
+      assert %{start: 1} = Enum.at(parts, 1)
+    end
+
+    test "emits every separator of lakh and crore grouping" do
+      assert {:ok, parts} =
+               Number.format_to_parts(123_456_789, locale: "hi-IN", maximum_fraction_digits: 0)
+
+      assert Enum.map(parts, &{&1.part_type, &1.value}) == [
+               {:integer, "12"},
+               {:group, ","},
+               {:integer, "34"},
+               {:group, ","},
+               {:integer, "56"},
+               {:group, ","},
+               {:integer, "789"}
+             ]
+
+      assert Enum.map_join(parts, & &1.value) == "12,34,56,789"
+    end
+
     test "parts with sign display always" do
       assert {:ok, parts} = Number.format_to_parts(42, sign_display: :always)
       assert is_list(parts)
@@ -393,4 +580,354 @@ defmodule Icu.NumberTest do
       assert is_binary(float_format)
     end
   end
+
+  describe "format_ordinal/2" do
+    test "uses English ordinal suffixes" do
+      assert Enum.map([1, 2, 3, 4, 11, 12, 13, 21, 102], &Number.format_ordinal!/1) ==
+               ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "102nd"]
+    end
+
+    test "groups large ordinals" do
+      assert {:ok, "1,001st"} = Number.format_ordinal(1001, locale: "en")
+    end
+
+    test "uses a period in German" do
+      assert {:ok, "3."} = Number.format_ordinal(3, locale: "de")
+    end
+
+    test "distinguishes the first ordinal in French" do
+      assert {:ok, "1er"} = Number.format_ordinal(1, locale: "fr")
+      assert {:ok, "2e"} = Number.format_ordinal(2, locale: "fr")
+    end
+
+    test "uses Swedish ordinal categories" do
+      assert {:ok, "2:a"} = Number.format_ordinal(2, locale: "sv")
+      assert {:ok, "12:e"} = Number.format_ordinal(12, locale: "sv")
+    end
+
+    test "rejects locales without ordinal patterns" do
+      assert {:error, :unsupported_locale} = Number.format_ordinal(1, locale: "ar")
+    end
+
+    test "accepts the inputs of format/2 with an integral value" do
+      assert {:ok, "2nd"} = Number.format_ordinal(Decimal.new("2.0"))
+      assert {:ok, "3rd"} = Number.format_ordinal(3.0)
+
+      assert {:ok, "100,000,000,000,000,000,000th"} =
+               Number.format_ordinal(100_000_000_000_000_000_000)
+    end
+
+    test "rejects non-integers" do
+      assert {:error, :invalid_number} = Number.format_ordinal(1.5)
+      assert {:error, :invalid_number} = Number.format_ordinal(Decimal.new("2.5"))
+      assert {:error, :invalid_number} = Number.format_ordinal(:one)
+    end
+  end
+
+  describe "format_spellout/2" do
+    test "spells out English cardinals" do
+      assert {:ok, "zero"} = Number.format_spellout(0, locale: "en")
+      assert {:ok, "nineteen"} = Number.format_spellout(19, locale: "en")
+      assert {:ok, "one hundred five"} = Number.format_spellout(105, locale: "en")
+
+      assert {:ok, "two thousand twenty-four"} = Number.format_spellout(2024, locale: "en")
+
+      assert {:ok, "minus three million four hundred thousand"} =
+               Number.format_spellout(-3_400_000, locale: "en")
+    end
+
+    test "spells out German cardinals" do
+      assert {:ok, "eins"} = Number.format_spellout(1, locale: "de")
+      assert {:ok, "einhunderteins"} = Number.format_spellout(101, locale: "de")
+
+      assert {:ok, "zweitausendvierundzwanzig"} =
+               Number.format_spellout(2024, locale: "de")
+
+      assert {:ok, "eine Million"} = Number.format_spellout(1_000_000, locale: "de")
+
+      assert {:ok, "zwei Millionen dreihunderttausend"} =
+               Number.format_spellout(2_300_000, locale: "de")
+    end
+
+    test "accepts the inputs of format/2 with an integral value" do
+      assert {:ok, "forty-two"} = Number.format_spellout(Decimal.new("42"), locale: "en")
+
+      assert {:ok, "nine quintillion nine hundred ninety-nine quadrillion" <> _} =
+               Number.format_spellout(9_999_999_999_999_999_999, locale: "en")
+
+      assert {:ok, "minus 1,000,000,000,000,000,000,000"} =
+               Number.format_spellout(-1_000_000_000_000_000_000_000, locale: "en")
+
+      assert {:error, :invalid_number} = Number.format_spellout(4.2, locale: "en")
+    end
+
+    test "accepts the numbering ruleset" do
+      assert {:ok, "forty-two"} =
+               Number.format_spellout(42, locale: "en", ruleset: "spellout-numbering")
+    end
+
+    test "rejects unknown rulesets" do
+      assert {:error, {:invalid_option_value, :ruleset}} =
+               Number.format_spellout(42, ruleset: "spellout-ordinal")
+    end
+
+    test "rejects unsupported locales" do
+      assert {:error, :unsupported_locale} = Number.format_spellout(42, locale: "fr")
+    end
+  end
+
+  describe "digits_to_latin/2" do
+    test "converts digits of any script" do
+      assert {:ok, "0123 456 789"} = Number.digits_to_latin("০১২৩ ४५६ ७८९")
+      assert {:ok, "42"} = Number.digits_to_latin("４２")
+    end
+
+    test "leaves non-digits untouched" do
+      assert {:ok, "Ⅻ and ½"} = Number.digits_to_latin("Ⅻ and ½")
+    end
+
+    test "rejects unknown numbering systems" do
+      assert {:error, :invalid_numbering_system} = Number.digits_to_latin("1", "nope")
+    end
+  end
+
+  describe "digits_from_latin/2" do
+    test "round-trips with digits_to_latin/2" do
+      assert {:ok, thai} = Number.digits_from_latin("0123456789", "thai")
+      assert thai == "๐๑๒๓๔๕๖๗๘๙"
+      assert {:ok, "0123456789"} = Number.digits_to_latin(thai, "thai")
+    end
+  end
+
+  describe "symbols/1" do
+    test "returns the locale's separators and signs" do
+      assert {:ok, symbols} = Number.symbols(locale: "en")
+      assert symbols.decimal == "."
+      assert symbols.group == ","
+      assert symbols.plus_sign == "+"
+      assert symbols.minus_sign == "-"
+      assert symbols.percent == "%"
+      assert symbols.digits == Enum.map(0..9, &Integer.to_string/1)
+    end
+
+    test "uses the symbols of the requested numbering system" do
+      assert {:ok, symbols} = Number.symbols(locale: "ar", numbering_system: "arab")
+      assert symbols.decimal == "٫"
+      assert List.last(symbols.digits) == "٩"
+    end
+
+    test "honours the -u-nu- keyword" do
+      assert {:ok, symbols} = Number.symbols(locale: "en-u-nu-deva")
+      assert hd(symbols.digits) == "०"
+    end
+
+    test "rejects unknown numbering systems" do
+      assert {:error, :invalid_numbering_system} = Number.symbols(numbering_system: "nope")
+    end
+  end
+
+  describe "numbering_systems/0" do
+    test "lists each numbering system with its digits" do
+      systems = Number.numbering_systems()
+
+      assert %{id: "latn", digits: digits} = Enum.find(systems, &(&1.id == "latn"))
+      assert digits == Enum.map(0..9, &Integer.to_string/1)
+      assert Enum.any?(systems, &(&1.id == "arab"))
+      assert Enum.all?(systems, &(length(&1.digits) == 10))
+    end
+
+    test "lists systems accepted by the symbols lookup" do
+      for %{id: id, digits: digits} <- Number.numbering_systems() do
+        assert {:ok, %{digits: ^digits}} = Number.symbols(numbering_system: id)
+      end
+    end
+  end
+
+  describe "locale strings at the NIF boundary" do
+    test "constructors accept a BCP-47 string instead of a resource" do
+      assert {:ok, formatter} = Icu.Nif.number_formatter_new("de", %{maximum_fraction_digits: 1})
+      assert {:ok, "1.234,5"} = Icu.Nif.number_format(formatter, 1234.5)
+    end
+
+    test "invalid strings are reported as invalid locales" do
+      assert {:error, :invalid_locale} = Icu.Nif.number_formatter_new("not a locale!", %{})
+      assert {:error, :invalid_locale} = Icu.Nif.list_formatter_new(42, %{})
+    end
+  end
+
+  describe "format_percent/3" do
+    test "rounds to whole percents by default" do
+      assert {:ok, "67%"} = Number.format_percent(2, 3)
+    end
+
+    test "drops trailing zeros left by rounding" do
+      assert {:ok, "50%"} = Number.format_percent(1, 2, maximum_fraction_digits: 2)
+    end
+
+    test "pads to the minimum fraction digits" do
+      assert {:ok, "50.0%"} = Number.format_percent(1, 2, minimum_fraction_digits: 1)
+    end
+
+    test "rounds to significant digits" do
+      assert {:ok, "0.0123%"} = Number.format_percent(1, 8123, significant_digits: 3)
+    end
+
+    test "computes the ratio without float error" do
+      assert {:ok, "100.000000000000000001%"} =
+               Number.format_percent(
+                 100_000_000_000_000_000_001,
+                 100_000_000_000_000_000_000,
+                 maximum_fraction_digits: 18,
+                 locale: "en"
+               )
+
+      assert {:ok, "-12.5%"} =
+               Number.format_percent(-1, 8, maximum_fraction_digits: 1, locale: "en")
+    end
+
+    test "uses the locale's percent pattern" do
+      assert {:ok, formatted} = Number.format_percent(1, 4, locale: "de")
+      assert formatted =~ ~r/^25\s%$/u
+    end
+
+    test "rejects a zero denominator" do
+      assert {:error, :invalid_number} = Number.format_percent(1, 0)
+    end
+
+    test "rejects non-numeric input" do
+      assert {:error, :invalid_number} = Number.format_percent("1", 2)
+    end
+  end
+
+  describe "grouping_sizes/1" do
+    test "returns uniform groups of three for en" do
+      assert {:ok, %{primary: 3, secondary: 3}} = Number.grouping_sizes(locale: "en")
+    end
+
+    test "returns Indian grouping for hi-IN" do
+      assert {:ok, %{primary: 3, secondary: 2}} = Number.grouping_sizes(locale: "hi-IN")
+    end
+
+    test "reports the minimum grouping digits" do
+      assert {:ok, %{min_grouping: 2}} = Number.grouping_sizes(locale: "es")
+    end
+  end
+
+  describe "format_range/3" do
+    test "joins both ends with the range separator" do
+      assert {:ok, "3–5"} = Number.format_range(3, 5, maximum_fraction_digits: 0)
+      assert {:ok, "1,000–2,500"} = Number.format_range(1000, 2500, maximum_fraction_digits: 0)
+    end
+
+    test "uses the separator of the locale" do
+      assert {:ok, "3-5"} = Number.format_range(3, 5, locale: "es", maximum_fraction_digits: 0)
+      assert {:ok, "3～5"} = Number.format_range(3, 5, locale: "ja", maximum_fraction_digits: 0)
+    end
+
+    test "collapses equal ends" do
+      assert {:ok, "3"} = Number.format_range(3, 3.0, maximum_fraction_digits: 0)
+    end
+
+    test "rejects invalid ends" do
+      assert {:error, :invalid_number} = Number.format_range(3, :five)
+    end
+  end
+
+  describe "format_range_to_parts/3" do
+    test "tags the parts with their source" do
+      assert {:ok, parts} = Number.format_range_to_parts(1.5, 20, maximum_fraction_digits: 1)
+
+      assert Enum.map(parts, &{&1.value, &1.source}) == [
+               {"1", :start},
+               {".", :start},
+               {"5", :start},
+               {"–", :shared},
+               {"20", :end},
+               {".", :end},
+               {"0", :end}
+             ]
+    end
+
+    test "marks equal ends as shared" do
+      assert {:ok, [%{value: "3", source: :shared}]} =
+               Number.format_range_to_parts(3, 3, maximum_fraction_digits: 0)
+    end
+
+    test "adds offsets across both ends" do
+      assert {:ok, parts} =
+               Number.format_range_to_parts(1.5, 20,
+                 maximum_fraction_digits: 1,
+                 index_mode: :utf16
+               )
+
+      assert Enum.map(parts, &{&1.start, &1.end}) == [
+               {0, 1},
+               {1, 2},
+               {2, 3},
+               {3, 4},
+               {4, 6},
+               {6, 7},
+               {7, 8}
+             ]
+    end
+  end
+
+  describe "approximately option" do
+    test "prefixes the approximately sign of the locale" do
+      opts = [maximum_fraction_digits: 0, approximately: true]
+      assert {:ok, "~5"} = Number.format(5, opts)
+      assert {:ok, "≈1.234"} = Number.format(1234, [locale: "de"] ++ opts)
+      assert {:ok, "≃5"} = Number.format(5, [locale: "fr"] ++ opts)
+    end
+
+    test "adds an approximately sign part" do
+      assert {:ok, [%{part_type: :approximately_sign, value: "~"} | _]} =
+               Number.format_to_parts(5, approximately: true)
+    end
+
+    test "marks the whole range once" do
+      assert {:ok, "~3–5"} =
+               Number.format_range(3, 5, maximum_fraction_digits: 0, approximately: true)
+    end
+
+    test "rejects non-boolean values" do
+      assert {:error, {:invalid_option_value, :approximately}} =
+               Number.format(5, approximately: :yes)
+    end
+  end
+
+  describe "preferences option" do
+    test "replaces the numbering system of the locale" do
+      assert {:ok, "١٢٣"} =
+               Number.format(123,
+                 maximum_fraction_digits: 0,
+                 preferences: [numbering_system: "arab"]
+               )
+
+      assert {:ok, "123"} =
+               Number.format(123,
+                 locale: "ar-u-nu-arab",
+                 maximum_fraction_digits: 0,
+                 preferences: %{numbering_system: "latn"}
+               )
+    end
+
+    test "leaves the locale alone without preferences" do
+      assert {:ok, "١٢٣"} = Number.format(123, locale: "ar-u-nu-arab", maximum_fraction_digits: 0)
+    end
+
+    test "rejects values CLDR does not define" do
+      assert {:error, {:invalid_option_value, :preferences}} =
+               Number.format(1, preferences: [numbering_system: "yes"])
+
+      assert {:error, {:invalid_option_value, :preferences}} =
+               Number.format(1, preferences: [region_override: "GB"])
+
+      assert {:error, {:invalid_option_value, :preferences}} =
+               Number.format(1, preferences: [numbering_system: :arab])
+
+      assert {:error, {:invalid_option_value, :preferences}} =
+               Number.format(1, preferences: [currency: "EUR"])
+    end
+  end
 end
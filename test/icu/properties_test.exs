@@ -0,0 +1,41 @@
+defmodule Icu.PropertiesTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Properties
+
+  alias Icu.Properties
+
+  describe "emoji?/1" do
+    test "accepts single and multi-codepoint emoji" do
+      assert Properties.emoji?("😀")
+      assert Properties.emoji?("👨‍👩‍👧")
+      assert Properties.emoji?("🇯🇵")
+      assert Properties.emoji?("❤️")
+    end
+
+    test "rejects text and text-presentation characters" do
+      refute Properties.emoji?("")
+      refute Properties.emoji?("a")
+      refute Properties.emoji?("❤")
+      refute Properties.emoji?("1")
+    end
+  end
+
+  describe "contains_emoji?/1" do
+    test "detects emoji inside text" do
+      assert Properties.contains_emoji?("see you 🙂")
+      refute Properties.contains_emoji?("see you :)")
+    end
+  end
+
+  describe "emoji_segments/1" do
+    test "merges consecutive emoji into one run" do
+      assert [text: "Party ", emoji: "🎉🥳", text: " now"] =
+               Properties.emoji_segments("Party 🎉🥳 now")
+    end
+
+    test "keeps ZWJ sequences intact" do
+      assert [emoji: "👩‍💻", text: " coding"] = Properties.emoji_segments("👩‍💻 coding")
+    end
+  end
+end
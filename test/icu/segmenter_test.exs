@@ -0,0 +1,54 @@
+defmodule Icu.SegmenterTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Segmenter
+
+  alias Icu.Segmenter
+
+  describe "next/2" do
+    test "hands out breaks in chunks until done" do
+      {:ok, segmenter} = Segmenter.new("a b c", :word)
+
+      assert {:ok, [0, 1, 2]} = Segmenter.next(segmenter, 3)
+      assert {:ok, [3, 4, 5]} = Segmenter.next(segmenter, 3)
+      assert :done = Segmenter.next(segmenter, 3)
+    end
+
+    test "rejects unknown kinds" do
+      assert {:error, :invalid_options} = Segmenter.new("text", :paragraph)
+    end
+  end
+
+  describe "stream/3" do
+    test "finds the same segments whatever the chunk size" do
+      german = String.duplicate("Ein Satz. Noch einer! Und dann 👨‍👩‍👧 noch etwas? ", 20)
+      thai = String.duplicate("ภาษาไทยเป็นภาษาที่ไม่เว้นวรรคระหว่างคำ ", 5)
+      japanese = String.duplicate("日本語の文章は単語の間に空白を入れません。", 5)
+
+      for text <- [german, thai, japanese], kind <- [:grapheme, :word, :sentence, :line] do
+        whole = Segmenter.stream(text, kind, chunk_size: 100_000) |> Enum.to_list()
+
+        assert Enum.join(whole) == text
+        assert Segmenter.stream(text, kind, chunk_size: 1) |> Enum.to_list() == whole
+        assert Segmenter.stream(text, kind, chunk_size: 7) |> Enum.to_list() == whole
+      end
+    end
+
+    test "segments words without spaces" do
+      segments = Segmenter.stream("你好世界", :word) |> Enum.to_list()
+
+      assert Enum.join(segments) == "你好世界"
+      assert length(segments) > 1
+    end
+
+    test "breaks lines after spaces" do
+      assert ["Hello ", "world"] = Segmenter.stream("Hello world", :line) |> Enum.to_list()
+    end
+
+    test "can be consumed partially" do
+      text = String.duplicate("word ", 10_000)
+
+      assert ["word", " ", "word"] = Segmenter.stream(text, :word, chunk_size: 2) |> Enum.take(3)
+    end
+  end
+end
@@ -0,0 +1,52 @@
+defmodule Icu.SnapshotTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Snapshot
+
+  alias Icu.Snapshot
+
+  describe "take/4" do
+    test "formats the built-in samples in every locale" do
+      assert {:ok, %{digest: digest, locales: locales}} = Snapshot.take(:number, ["en", "de"])
+
+      assert String.length(digest) == 16
+      assert %{"en" => %{outputs: en}, "de" => %{outputs: de}} = locales
+      assert "-1,234.568" in en
+      assert "-1.234,568" in de
+      assert length(en) == length(de)
+    end
+
+    test "does not depend on the order of the locales" do
+      forward = Snapshot.take!(:list, ["en", "fr", "ja"])
+      backward = Snapshot.take!(:list, ["ja", "fr", "en"])
+
+      assert forward.digest == backward.digest
+    end
+
+    test "changes with the output" do
+      standard = Snapshot.take!(:number, ["en"])
+      rounded = Snapshot.take!(:number, ["en"], maximum_fraction_digits: 0)
+
+      refute standard.digest == rounded.digest
+      refute standard.locales["en"].digest == rounded.locales["en"].digest
+    end
+
+    test "formats given samples with the options" do
+      assert {:ok, %{locales: %{"en" => %{outputs: ["A or B", "A, B, or C"]}}}} =
+               Snapshot.take(:list, ["en"], [type: :or], [["A", "B"], ["A", "B", "C"]])
+
+      assert {:ok, %{locales: %{"en" => %{outputs: ["1,234.5"]}}}} =
+               Snapshot.take(:number, ["en"], [maximum_fraction_digits: 1], [1234.5])
+    end
+
+    test "rejects invalid input" do
+      assert {:error, :invalid_locale} = Snapshot.take(:number, ["en", "not a locale"])
+      assert {:error, :invalid_number} = Snapshot.take(:number, ["en"], [], ["twelve"])
+      assert {:error, :invalid_items} = Snapshot.take(:list, ["en"], [], [[]])
+      assert {:error, :invalid_options} = Snapshot.take(:date, ["en"])
+
+      assert {:error, {:invalid_option_value, :grouping}} =
+               Snapshot.take(:number, ["en"], grouping: :sometimes)
+    end
+  end
+end
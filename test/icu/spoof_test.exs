@@ -0,0 +1,42 @@
+defmodule Icu.SpoofTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Spoof
+
+  alias Icu.Spoof
+
+  describe "skeleton/1" do
+    test "maps Cyrillic look-alikes to Latin" do
+      assert Spoof.skeleton("аррӏе") == Spoof.skeleton("apple")
+    end
+
+    test "maps fullwidth forms" do
+      assert Spoof.skeleton("ｇｏｏｇｌｅ") == Spoof.skeleton("google")
+    end
+
+    test "is stable under canonical equivalence" do
+      assert Spoof.skeleton("é") == Spoof.skeleton("é")
+    end
+
+    test "leaves unrelated characters unchanged" do
+      assert Spoof.skeleton("日本") == "日本"
+    end
+  end
+
+  describe "confusable?/2" do
+    test "flags mixed-script look-alikes" do
+      assert Spoof.confusable?("admin", "аdmin")
+      assert Spoof.confusable?("Microsoft", "Μicrosoft")
+    end
+
+    test "flags ASCII look-alikes" do
+      assert Spoof.confusable?("corn", "com")
+      assert Spoof.confusable?("g00gle", "gOOgle")
+    end
+
+    test "does not flag distinct identifiers" do
+      refute Spoof.confusable?("alice", "bob")
+      refute Spoof.confusable?("resume", "résumé")
+    end
+  end
+end
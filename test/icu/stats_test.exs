@@ -0,0 +1,72 @@
+defmodule Icu.StatsTest do
+  # The counters are global to the NIF library.
+  use ExUnit.Case, async: false
+
+  setup do
+    Icu.reset_stats()
+
+    on_exit(fn ->
+      Icu.enable_stats(false)
+      Icu.reset_stats()
+    end)
+  end
+
+  test "counts nothing while disabled" do
+    Icu.enable_stats(false)
+    assert {:ok, _} = Icu.Number.format(1, locale: "en")
+    assert Icu.stats() == %{}
+  end
+
+  test "counts calls and time per NIF" do
+    Icu.enable_stats(true)
+    formatter = Icu.Number.Formatter.new!(locale: "en")
+
+    for n <- 1..3, do: {:ok, _} = Icu.Number.Formatter.format(formatter, n)
+
+    assert %{
+             number_formatter_new: %{calls: 1, errors: 0},
+             number_format: %{calls: 3, errors: 0, total_time: time}
+           } = Icu.stats()
+
+    assert time > 0
+  end
+
+  test "counts calls returning errors" do
+    Icu.enable_stats(true)
+    formatter = Icu.Temporal.Formatter.new_date!(locale: "en")
+
+    assert {:error, _} = Icu.Temporal.Formatter.format(formatter, ~T[10:00:00])
+    assert {:ok, _} = Icu.Temporal.Formatter.format(formatter, ~D[2024-01-15])
+
+    assert %{temporal_format: %{calls: 2, errors: 1}} = Icu.stats()
+  end
+
+  test "counts the NIFs outside the formatters" do
+    Icu.enable_stats(true)
+
+    assert {:ok, _} = Icu.LanguageTag.parse("en-US")
+    assert {:error, _} = Icu.LanguageTag.parse("not a locale!")
+    assert {:ok, _} = Icu.Decimal.new("1.5")
+    assert {:ok, _} = Icu.Calendar.date_to_rata_die(~D[2024-01-15])
+    assert Icu.Normalizer.canonical_equal?("é", "e\u0301")
+
+    assert %{
+             locale_from_string: %{calls: 2, errors: 1},
+             decimal_new: %{calls: 1, errors: 0},
+             calendar_date_to_rata_die: %{calls: 1, errors: 0},
+             normalizer_canonical_equal: %{calls: 1, errors: 0}
+           } = Icu.stats()
+  end
+
+  test "keeps the counters when disabled and clears them on reset" do
+    Icu.enable_stats(true)
+    assert {:ok, _} = Icu.List.format(["a", "b"], locale: "en")
+    Icu.enable_stats(false)
+
+    assert {:ok, _} = Icu.List.format(["a", "b"], locale: "en")
+    assert %{list_format: %{calls: 1}} = Icu.stats()
+
+    assert :ok = Icu.reset_stats()
+    assert Icu.stats() == %{}
+  end
+end
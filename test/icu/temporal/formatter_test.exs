@@ -52,6 +52,14 @@ defmodule Icu.Temporal.FormatterTest do
                Formatter.normalize_options(%{length: :gigantic})
     end
 
+    test "accepts skeleton strings" do
+      assert {:ok, %{skeleton: "yMMMEd Hm"}} =
+               Formatter.normalize_options(skeleton: "yMMMEd Hm")
+
+      assert {:error, {:invalid_option_value, :skeleton}} =
+               Formatter.normalize_options(skeleton: :ymd)
+    end
+
     test "rejects invalid time precision tuples" do
       assert {:error, {:invalid_option_value, :time_precision}} =
                Formatter.normalize_options(%{time_precision: {:subsecond, 12}})
@@ -177,4 +185,119 @@ defmodule Icu.Temporal.FormatterTest do
                })
     end
   end
+
+  describe "from_pattern/2" do
+    test "formats with the exact pattern and localized names" do
+      assert {:ok, formatter} = Formatter.from_pattern("EEEE, dd.MM.yyyy HH:mm", locale: "de")
+      assert {:ok, formatted} = Formatter.format(formatter, ~N[2024-01-15 09:05:00])
+      assert formatted == "Montag, 15.01.2024 09:05"
+    end
+
+    test "formats Time with time-only patterns" do
+      formatter = Formatter.from_pattern!("HH:mm:ss", locale: "en")
+      assert {:ok, "17:30:45"} = Formatter.format(formatter, ~T[17:30:45])
+    end
+
+    test "rejects input missing the fields the pattern refers to" do
+      formatter = Formatter.from_pattern!("dd.MM.yyyy HH:mm", locale: "de")
+
+      time = %{hour: 9, minute: 5, second: 0}
+      assert {:error, {:missing_field, :year}} = Formatter.format(formatter, time)
+      assert {:error, {:missing_field, :hour}} = Formatter.format(formatter, ~D[2024-01-15])
+
+      assert {:error, {:missing_field, :year}} =
+               Formatter.format_to_parts(formatter, ~T[09:05:00])
+    end
+
+    test "reports parts for pattern fields" do
+      formatter = Formatter.from_pattern!("d MMMM y", locale: "en")
+      assert {:ok, parts} = Formatter.format_to_parts(formatter, ~D[2024-03-05])
+      assert %{part_type: :month, value: "March"} in parts
+    end
+
+    test "rejects invalid patterns" do
+      assert {:error, :invalid_pattern} = Formatter.from_pattern("'unterminated", locale: "en")
+      assert {:error, :invalid_pattern} = Formatter.from_pattern(:not_a_pattern)
+    end
+
+    test "rejects unknown options" do
+      assert {:error, {:invalid_options, {:bad_option, :length}}} =
+               Formatter.from_pattern("y", length: :long)
+    end
+  end
+
+  describe "pattern/2" do
+    test "returns the resolved pattern for a field set" do
+      formatter = Formatter.new!(date_fields: :ymd, length: :long, locale: "en")
+      assert {:ok, "MMMM d, y"} = Formatter.pattern(formatter)
+    end
+
+    test "resolves the pattern for a sample value" do
+      formatter = Formatter.new!(time_precision: :minute, locale: "de")
+      assert {:ok, "HH:mm"} = Formatter.pattern(formatter, ~T[09:30:00])
+    end
+
+    test "returns the pattern of pattern formatters verbatim" do
+      formatter = Formatter.from_pattern!("dd.MM.yyyy", locale: "de")
+      assert {:ok, "dd.MM.yyyy"} = Formatter.pattern(formatter)
+    end
+
+    test "rejects invalid samples" do
+      formatter = Formatter.new!(date_fields: :ymd, locale: "en")
+      assert {:error, :invalid_temporal} = Formatter.pattern(formatter, :invalid)
+    end
+  end
+
+  describe "new_date/1" do
+    test "formats dates" do
+      formatter = Formatter.new_date!(locale: "en")
+      assert {:ok, "Jan 15, 2024"} = Formatter.format(formatter, ~D[2024-01-15])
+    end
+
+    test "reports the first missing field" do
+      formatter = Formatter.new_date!(locale: "en")
+      assert {:error, {:missing_field, :year}} = Formatter.format(formatter, ~T[10:00:00])
+    end
+
+    test "rejects time options" do
+      assert {:error, {:invalid_options, {:bad_option, :time_precision}}} =
+               Formatter.new_date(time_precision: :minute)
+    end
+  end
+
+  describe "new_time/1" do
+    test "formats times" do
+      formatter = Formatter.new_time!(locale: "en", time_precision: :minute)
+      assert {:ok, formatted} = Formatter.format(formatter, ~T[17:30:00])
+      assert formatted =~ "5:30"
+    end
+
+    test "reports missing time fields" do
+      formatter = Formatter.new_time!(locale: "en")
+
+      assert {:error, {:missing_field, :hour}} =
+               Formatter.format_to_parts(formatter, ~D[2024-01-15])
+    end
+
+    test "rejects date options" do
+      assert {:error, {:invalid_options, {:bad_option, :date_fields}}} =
+               Formatter.new_time(date_fields: :ymd)
+    end
+  end
+
+  describe "new_zoned_datetime/1" do
+    test "formats zoned datetimes" do
+      formatter = Formatter.new_zoned_datetime!(locale: "en")
+      {:ok, datetime} = DateTime.new(~D[2024-06-15], ~T[14:30:45], "Etc/UTC")
+      assert {:ok, formatted} = Formatter.format(formatter, datetime)
+      assert formatted =~ "GMT"
+    end
+
+    test "rejects naive datetimes" do
+      formatter = Formatter.new_zoned_datetime!(locale: "en")
+
+      assert {:error, {:missing_field, :time_zone}} =
+               Formatter.format(formatter, ~N[2024-06-15 14:30:45])
+    end
+  end
 end
@@ -16,6 +16,14 @@ defmodule Icu.TemporalTest do
       assert {:error, :invalid_temporal} = Temporal.format(%{}, date_fields: :ymd)
     end
 
+    test "returns the binary for iodata output" do
+      assert {:ok, "Jan 15, 2024"} =
+               Temporal.format(~D[2024-01-15], output: :iodata, locale: "en")
+
+      assert {:error, {:invalid_option_value, :output}} =
+               Temporal.format(~D[2024-01-15], output: :list)
+    end
+
     test "works with no options using defaults" do
       # Date should format with defaults (date_fields: :ymd, length: :medium)
       assert {:ok, date_formatted} = Temporal.format(~D[2024-06-15], locale: "en")
@@ -497,6 +505,75 @@ defmodule Icu.TemporalTest do
     end
   end
 
+  describe "skeleton option" do
+    test "derives date fields and length from the skeleton" do
+      assert {:ok, formatted} = Temporal.format(~D[2024-03-15], locale: "en", skeleton: "yMMMMd")
+      assert formatted =~ "March"
+      assert formatted =~ "15"
+      assert formatted =~ "2024"
+    end
+
+    test "forces a 24-hour clock for H" do
+      assert {:ok, formatted} = Temporal.format(~T[17:05:00], locale: "en", skeleton: "Hm")
+      assert formatted =~ "17:05"
+      refute formatted =~ "PM"
+    end
+
+    test "explicit options override the skeleton" do
+      assert {:ok, formatted} =
+               Temporal.format(~D[2024-03-15], locale: "en", skeleton: "yMMMMd", date_fields: :y)
+
+      assert formatted =~ "2024"
+      refute formatted =~ "March"
+    end
+
+    test "rejects skeletons without a matching field set" do
+      assert {:error, :invalid_options} =
+               Temporal.format(~D[2024-03-15], locale: "en", skeleton: "yd")
+
+      assert {:error, :invalid_options} =
+               Temporal.format(~D[2024-03-15], locale: "en", skeleton: "yMMMq")
+    end
+  end
+
+  describe "field combinations" do
+    test "formats a weekday with a time" do
+      assert {:ok, formatted} =
+               Temporal.format(~N[2024-01-16 14:30:00],
+                 locale: "en-GB",
+                 date_fields: :e,
+                 time_precision: :minute
+               )
+
+      assert formatted =~ "Tue"
+      assert formatted =~ "14:30"
+    end
+
+    test "formats day-based date fields with a time" do
+      for date_fields <- [:d, :md, :de, :mde, :ymde] do
+        assert {:ok, formatted} =
+                 Temporal.format(~N[2024-01-16 14:30:00],
+                   locale: "en-GB",
+                   date_fields: date_fields,
+                   time_precision: :minute
+                 )
+
+        assert formatted =~ "16"
+        assert formatted =~ "14:30"
+      end
+    end
+
+    test "rejects calendar periods with a time" do
+      for date_fields <- [:m, :ym, :y] do
+        assert {:error, :invalid_field_combination} =
+                 Temporal.format(~N[2024-01-16 14:30:00],
+                   date_fields: date_fields,
+                   time_precision: :minute
+                 )
+      end
+    end
+  end
+
   describe "format!/2" do
     test "raises on error" do
       formatter = %Formatter{resource: :opaque}
@@ -508,6 +585,18 @@ defmodule Icu.TemporalTest do
   end
 
   describe "format_to_parts/3" do
+    test "gives nested parts the same offsets" do
+      assert {:ok, parts} =
+               Temporal.format_to_parts(~D[2024-01-15],
+                 locale: "en",
+                 date_fields: :ymd,
+                 index_mode: :byte
+               )
+
+      assert %{start: 4, end: 6} = Enum.find(parts, &(&1.part_type == :day))
+      assert Enum.filter(parts, &(&1.start == 4)) |> Enum.map(& &1.part_type) == [:integer, :day]
+    end
+
     test "respects subsecond precision in parts output" do
       datetime = ~N[2024-02-29 17:30:45.123456]
 
@@ -539,6 +628,22 @@ defmodule Icu.TemporalTest do
       assert Enum.any?(d_parts, &(&1.part_type == :day))
     end
 
+    test "formats dates from Icu.Calendar" do
+      {:ok, date} = Icu.Calendar.convert(~D[2024-06-01], :chinese)
+
+      assert Temporal.format(date, locale: "en", date_fields: :ymd) ==
+               Temporal.format(~D[2024-06-01], locale: "en", date_fields: :ymd)
+    end
+
+    test "cyclic years of the Chinese and Dangi calendars are year_name parts" do
+      for locale <- ["zh-u-ca-chinese", "ko-u-ca-dangi"] do
+        assert {:ok, parts} =
+                 Temporal.format_to_parts(~D[2024-06-01], locale: locale, date_fields: :ymd)
+
+        assert Enum.any?(parts, &(&1.part_type == :year_name))
+      end
+    end
+
     test "time_precision affects which time parts are returned" do
       time = ~T[14:30:45.123]
 
@@ -678,6 +783,73 @@ defmodule Icu.TemporalTest do
     end
   end
 
+  describe "subsecond input" do
+    test "defaults to the microsecond precision of the input" do
+      assert {:ok, formatted} = Temporal.format(~T[14:30:45.120], locale: "en")
+      assert formatted =~ "45.120"
+
+      assert {:ok, formatted} = Temporal.format(~T[14:30:45], locale: "en")
+      refute formatted =~ "45."
+    end
+
+    test "reports subsecond digits as a fraction part" do
+      assert {:ok, parts} =
+               Temporal.format_to_parts(~T[14:30:45.987],
+                 locale: "en",
+                 time_precision: {:subsecond, 3}
+               )
+
+      assert %{part_type: :fraction, value: "987"} in parts
+    end
+
+    test "accepts nanosecond fields at the NIF boundary" do
+      formatter = Formatter.new!(locale: "en", time_precision: {:subsecond, 9})
+      input = %{hour: 14, minute: 30, second: 45, nanosecond: 123_456_789}
+
+      assert {:ok, formatted} = Icu.Nif.temporal_format(formatter.resource, input)
+      assert formatted =~ "45.123456789"
+
+      assert {:error, :invalid_datetime} =
+               Icu.Nif.temporal_format(
+                 formatter.resource,
+                 Map.put(input, :microsecond, {123_456, 6})
+               )
+    end
+  end
+
+  describe "non-Gregorian calendars" do
+    test "reports the era for the Japanese calendar" do
+      assert {:ok, parts} =
+               Temporal.format_to_parts(~D[2024-01-15],
+                 locale: "ja-JP-u-ca-japanese",
+                 date_fields: :ymd,
+                 year_style: :with_era
+               )
+
+      assert %{part_type: :era, value: "令和"} in parts
+      assert Enum.any?(parts, &(&1.part_type == :year and &1.value =~ "6"))
+    end
+
+    test "reports the related ISO year and cyclic year name for the Chinese calendar" do
+      assert {:ok, parts} =
+               Temporal.format_to_parts(~D[2024-01-15],
+                 locale: "zh-u-ca-chinese",
+                 date_fields: :ymd,
+                 length: :long
+               )
+
+      assert %{part_type: :related_year, value: "2023"} in parts
+      assert %{part_type: :year_name, value: "癸卯"} in parts
+    end
+
+    test "converts dates into the formatter calendar" do
+      assert {:ok, formatted} =
+               Temporal.format(~D[2024-01-15], locale: "th-u-ca-buddhist", date_fields: :y)
+
+      assert formatted =~ "2567"
+    end
+  end
+
   describe "format_to_parts/2" do
     @tag :skip
     test "returns an error for invalid input" do
@@ -695,4 +867,632 @@ defmodule Icu.TemporalTest do
       end
     end
   end
+
+  describe "day_periods/1" do
+    test "lists AM and PM with their hour ranges" do
+      assert {:ok, [am, pm | _]} = Temporal.day_periods(locale: "en")
+      assert %{period: :am, name: "AM", start_hour: 0, end_hour: 12} = am
+      assert %{period: :pm, name: "PM", start_hour: 12, end_hour: 24} = pm
+    end
+
+    test "lists noon and midnight when the locale names them" do
+      assert {:ok, periods} = Temporal.day_periods(locale: "en", width: :wide)
+      noon = Enum.find(periods, &(&1.period == :noon))
+      assert %{name: "noon", start_hour: 12, end_hour: 12} = noon
+    end
+
+    test "matches the names used in formatted times" do
+      assert {:ok, [_am, pm | _]} = Temporal.day_periods(locale: "ko")
+
+      assert {:ok, formatted} =
+               Temporal.format(~T[15:00:00], time_precision: :minute, locale: "ko")
+
+      assert formatted =~ pm.name
+    end
+
+    test "rejects unknown widths" do
+      assert {:error, _} = Temporal.day_periods(width: :long)
+    end
+  end
+
+  describe "quarter fields" do
+    test "formats the quarter and year" do
+      assert {:ok, "Q3 2025"} = Temporal.format(~D[2025-08-01], date_fields: :yq, locale: "en")
+      assert {:ok, "T1 2025"} = Temporal.format(~D[2025-02-10], date_fields: :yq, locale: "fr")
+    end
+
+    test "uses wide names for the long length" do
+      assert {:ok, "4th quarter 2025"} =
+               Temporal.format(~D[2025-12-31], date_fields: :yq, length: :long, locale: "en")
+    end
+
+    test "formats the quarter alone" do
+      assert {:ok, "Q2"} = Temporal.format(~D[2025-04-01], date_fields: :q, locale: "en")
+    end
+
+    test "returns a quarter part" do
+      assert {:ok, parts} =
+               Temporal.format_to_parts(~D[2025-08-01], date_fields: :yq, locale: "en")
+
+      assert [
+               %{part_type: :quarter, value: "Q3"},
+               %{part_type: :literal, value: " "},
+               %{part_type: :year, value: "2025"}
+             ] = parts
+    end
+
+    test "rejects quarters combined with a time" do
+      assert {:error, _} =
+               Temporal.format(~N[2025-08-01 10:00:00],
+                 date_fields: :yq,
+                 time_precision: :minute,
+                 locale: "en"
+               )
+    end
+  end
+
+  describe "month and year widths" do
+    test "month width selects the matching length" do
+      date = ~D[2024-01-15]
+
+      assert {:ok, "January 15, 2024"} = Temporal.format(date, month: :long, locale: "en")
+      assert {:ok, "Jan 15, 2024"} = Temporal.format(date, month: :short, locale: "en")
+      assert {:ok, "1/15/24"} = Temporal.format(date, month: :numeric, locale: "en")
+    end
+
+    test "widths override the length-based pattern" do
+      assert {:ok, "Jan 15, 24"} =
+               Temporal.format(~D[2024-01-15], length: :medium, year: :two_digit, locale: "en")
+
+      assert {:ok, "1/15/2024"} =
+               Temporal.format(~D[2024-01-15], month: :numeric, year: :numeric, locale: "en")
+    end
+
+    test "narrow months" do
+      assert {:ok, "J"} = Temporal.format(~D[2024-01-15], date_fields: :m, month: :narrow)
+    end
+
+    test "rejects unknown widths" do
+      assert {:error, _} = Temporal.format(~D[2024-01-15], month: :tiny)
+      assert {:error, _} = Temporal.format(~D[2024-01-15], year: :short)
+    end
+
+    test "date formatters accept widths" do
+      formatter = Formatter.new_date!(month: :two_digit, locale: "en")
+      assert {:ok, "03/7/24"} = Formatter.format(formatter, ~D[2024-03-07])
+    end
+  end
+
+  describe "utc_offset input" do
+    setup do
+      %{input: %{year: 2024, month: 1, day: 15, hour: 12, minute: 0, second: 0}}
+    end
+
+    test "formats 45-minute offsets", %{input: input} do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :localized_offset_long)
+      assert {:ok, formatted} = Formatter.format(formatter, Map.put(input, :utc_offset, 20_700))
+      assert formatted =~ "GMT+05:45"
+
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :localized_offset_short)
+      assert {:ok, formatted} = Formatter.format(formatter, Map.put(input, :utc_offset, 20_700))
+      assert formatted =~ "GMT+5:45"
+    end
+
+    test "accepts hour and minute tuples and offset strings", %{input: input} do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :localized_offset_long)
+
+      for offset <- [{5, 45}, "+05:45", "+0545"] do
+        assert {:ok, formatted} = Formatter.format(formatter, Map.put(input, :utc_offset, offset))
+        assert formatted =~ "GMT+05:45"
+      end
+
+      for offset <- [{-3, 30}, "-03:30", "−03:30"] do
+        assert {:ok, formatted} = Formatter.format(formatter, Map.put(input, :utc_offset, offset))
+        assert formatted =~ "GMT-03:30"
+      end
+    end
+
+    test "rejects malformed offsets", %{input: input} do
+      formatter = Formatter.new_zoned_datetime!(locale: "en")
+
+      for offset <- [{5, 60}, {-3, -30}, "05:45", "+5:45", "+05:4"] do
+        assert {:error, _} = Formatter.format(formatter, Map.put(input, :utc_offset, offset))
+      end
+    end
+
+    test "includes the daylight saving offset of a DateTime" do
+      datetime = %DateTime{
+        year: 2024,
+        month: 7,
+        day: 1,
+        hour: 12,
+        minute: 0,
+        second: 0,
+        microsecond: {0, 0},
+        time_zone: "Europe/London",
+        zone_abbr: "BST",
+        utc_offset: 0,
+        std_offset: 3600
+      }
+
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :localized_offset_long)
+      assert {:ok, formatted} = Formatter.format(formatter, datetime)
+      assert formatted =~ "GMT+01:00"
+    end
+  end
+
+  describe "zone style fallback" do
+    setup do
+      %{
+        input: %{
+          year: 2024,
+          month: 1,
+          day: 15,
+          hour: 12,
+          minute: 0,
+          second: 0,
+          utc_offset: -18_000
+        }
+      }
+    end
+
+    test "exemplar city falls back to the localized offset without a zone", %{input: input} do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :exemplar_city)
+
+      assert {:ok, formatted} = Formatter.format(formatter, input)
+      assert formatted =~ "GMT-05:00"
+    end
+
+    test "parts report the zone style that was used", %{input: input} do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :location)
+      assert {:ok, parts} = Formatter.format_to_parts(formatter, input)
+
+      assert [%{value: "GMT-05:00", zone_style: :localized_offset_long}] =
+               Enum.filter(parts, &Map.has_key?(&1, :zone_style))
+    end
+
+    test "other zone styles report no fallback level", %{input: input} do
+      formatter = Formatter.new_zoned_datetime!(locale: "en")
+      assert {:ok, parts} = Formatter.format_to_parts(formatter, input)
+      refute Enum.any?(parts, &Map.has_key?(&1, :zone_style))
+    end
+  end
+
+  describe "calendar option" do
+    test "formats in the given calendar" do
+      date = ~D[2024-03-20]
+
+      assert {:ok, civil} =
+               Temporal.format(date, locale: "en", date_fields: :d, calendar: :islamic_civil)
+
+      assert {:ok, tbla} =
+               Temporal.format(date, locale: "en", date_fields: :d, calendar: "islamic-tbla")
+
+      assert {civil, tbla} == {"10", "11"}
+    end
+
+    test "overrides the locale's calendar keyword" do
+      assert Temporal.format(~D[2024-03-20], locale: "en-u-ca-hebrew", calendar: :gregorian) ==
+               Temporal.format(~D[2024-03-20], locale: "en")
+    end
+
+    test "is accepted by the date formatter" do
+      formatter = Icu.Temporal.Formatter.new_date!(locale: "en", calendar: :islamic_umalqura)
+      assert {:ok, formatted} = Icu.Temporal.Formatter.format(formatter, ~D[2024-03-20])
+      assert formatted =~ "1445"
+    end
+
+    test "rejects unknown calendars" do
+      assert {:error, :invalid_calendar} = Temporal.format(~D[2024-03-20], calendar: :martian)
+    end
+
+    test "cannot be combined with field widths" do
+      assert {:error, :invalid_options} =
+               Temporal.format(~D[2024-03-20], month: :long, calendar: :hebrew)
+    end
+  end
+
+  describe "ISO 8601 strings" do
+    test "format like the equivalent Elixir values" do
+      assert Temporal.format("2025-06-01") == Temporal.format(~D[2025-06-01])
+      assert Temporal.format("14:30:05") == Temporal.format(~T[14:30:05])
+
+      assert Temporal.format("2025-06-01T14:30:05") ==
+               Temporal.format(~N[2025-06-01 14:30:05])
+
+      assert Temporal.format("2025-06-01 14:30:05.250") ==
+               Temporal.format(~N[2025-06-01 14:30:05.250])
+    end
+
+    test "carry the UTC offset" do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :localized_offset_long)
+
+      assert {:ok, formatted} = Formatter.format(formatter, "2025-06-01T14:30:00+05:45")
+      assert formatted =~ "GMT+05:45"
+
+      assert {:ok, formatted} = Formatter.format(formatter, "2025-06-01T14:30:00Z")
+      assert formatted =~ "GMT"
+    end
+
+    test "carry the time zone annotation" do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :location)
+
+      assert {:ok, formatted} =
+               Formatter.format(formatter, "2025-06-01T14:30:00+02:00[Europe/Berlin]")
+
+      assert formatted =~ "Germany"
+    end
+
+    test "ignore annotations with a key" do
+      assert Temporal.format("2025-06-01[u-ca=japanese]") == Temporal.format("2025-06-01")
+    end
+
+    test "report missing fields and invalid strings" do
+      formatter = Formatter.new_zoned_datetime!(locale: "en", zone_style: :localized_offset_long)
+
+      assert {:error, {:missing_field, :time_zone}} =
+               Formatter.format(formatter, "2025-06-01T14:30:00")
+
+      date_formatter = Formatter.new_date!(locale: "en", date_fields: :ymd, length: :medium)
+      assert {:error, :invalid_datetime} = Formatter.format(date_formatter, "June 1st")
+
+      assert {:error, :invalid_datetime} =
+               Formatter.format(formatter, "2025-06-01T14:30:00+02:00[Mars/Olympus_Mons]")
+    end
+  end
+
+  describe "leap seconds" do
+    test "are formatted as the last second of the minute by default" do
+      input = %{year: 2016, month: 12, day: 31, hour: 23, minute: 59, second: 60}
+
+      assert Temporal.format(input, locale: "en-GB") ==
+               Temporal.format(%{input | second: 59}, locale: "en-GB")
+
+      assert Temporal.format("2016-12-31T23:59:60Z", locale: "en-GB", leap_second: :clamp) ==
+               Temporal.format("2016-12-31T23:59:59Z", locale: "en-GB")
+    end
+
+    test "are rejected with leap_second: :error" do
+      input = %{hour: 23, minute: 59, second: 60}
+
+      assert Temporal.format(input, locale: "en-GB", leap_second: :error) ==
+               {:error, {:out_of_range, :second, 0, 59}}
+
+      assert Temporal.format("23:59:60", locale: "en-GB", leap_second: :error) ==
+               {:error, {:out_of_range, :second, 0, 59}}
+
+      assert Temporal.format(%{input | second: 59}, locale: "en-GB", leap_second: :error) ==
+               {:ok, "23:59:59"}
+    end
+
+    test "never allow a 61st second" do
+      assert Temporal.format(%{hour: 23, minute: 59, second: 61}, locale: "en-GB") ==
+               {:error, {:out_of_range, :second, 0, 60}}
+    end
+
+    test "reject unknown treatments" do
+      assert {:error, {:invalid_option_value, :leap_second}} =
+               Formatter.new(locale: "en", leap_second: :smear)
+
+      assert {:error, {:invalid_options, {:bad_option, :leap_second}}} =
+               Formatter.new_date(locale: "en", leap_second: :clamp)
+    end
+  end
+
+  describe "far dates" do
+    test "format the expanded years of ISO 8601" do
+      assert {:ok, formatted} = Temporal.format(%{year: 275_760, month: 9, day: 13}, locale: "en")
+      assert formatted =~ "275760"
+
+      assert {:ok, formatted} = Temporal.format("-010000-01-01", locale: "en")
+      assert formatted =~ "10001"
+
+      assert {:ok, formatted} = Temporal.format("+275760-09-13T00:00:00Z", locale: "en")
+      assert formatted =~ "275760"
+    end
+
+    test "report the bounds of fields out of range" do
+      assert Temporal.format(%{year: 1_000_000, month: 1, day: 1}, locale: "en") ==
+               {:error, {:out_of_range, :year, -999_999, 999_999}}
+
+      assert Temporal.format(%{year: 275_760, month: 2, day: 30}, locale: "en") ==
+               {:error, {:out_of_range, :day, 1, 29}}
+
+      assert Temporal.format(%{year: 2024, month: 13, day: 1}, locale: "en") ==
+               {:error, {:out_of_range, :month, 1, 12}}
+    end
+
+    test "report the bounds of string fields out of range like map fields" do
+      assert Temporal.format("2024-02-30", locale: "en") ==
+               {:error, {:out_of_range, :day, 1, 29}}
+
+      assert Temporal.format("2024-13-01", locale: "en") ==
+               {:error, {:out_of_range, :month, 1, 12}}
+    end
+  end
+
+  describe "format_rfc/2" do
+    test "formats RFC 2822 dates in the input's offset" do
+      assert Temporal.format_rfc("2025-06-01T14:30:05+02:00", :rfc2822) ==
+               {:ok, "Sun, 01 Jun 2025 14:30:05 +0200"}
+
+      assert Temporal.format_rfc("2025-01-09T08:00:00-03:30", :rfc2822) ==
+               {:ok, "Thu, 09 Jan 2025 08:00:00 -0330"}
+
+      assert Temporal.format_rfc(~N[2025-06-01 14:30:05.250], :rfc2822) ==
+               {:ok, "Sun, 01 Jun 2025 14:30:05 -0000"}
+    end
+
+    test "formats HTTP dates in UTC" do
+      assert Temporal.format_rfc(~U[1994-11-06 08:49:37Z], :http) ==
+               {:ok, "Sun, 06 Nov 1994 08:49:37 GMT"}
+
+      assert Temporal.format_rfc("2024-12-31T22:00:00-05:00", :http) ==
+               {:ok, "Wed, 01 Jan 2025 03:00:00 GMT"}
+
+      assert Temporal.format_rfc(~N[2025-06-01 14:30:05], :http) ==
+               {:ok, "Sun, 01 Jun 2025 14:30:05 GMT"}
+    end
+
+    test "includes the daylight saving offset of a DateTime" do
+      datetime = %DateTime{
+        year: 2025,
+        month: 6,
+        day: 1,
+        hour: 14,
+        minute: 30,
+        second: 0,
+        microsecond: {0, 0},
+        time_zone: "Europe/Berlin",
+        zone_abbr: "CEST",
+        utc_offset: 3600,
+        std_offset: 3600
+      }
+
+      assert Temporal.format_rfc!(datetime, :rfc2822) == "Sun, 01 Jun 2025 14:30:00 +0200"
+      assert Temporal.format_rfc!(datetime, :http) == "Sun, 01 Jun 2025 12:30:00 GMT"
+    end
+
+    test "requires a date and a time" do
+      assert Temporal.format_rfc(~D[2025-06-01], :rfc2822) == {:error, {:missing_field, :hour}}
+      assert Temporal.format_rfc(~T[14:30:00], :http) == {:error, {:missing_field, :year}}
+      assert Temporal.format_rfc("2025-06-01", :http) == {:error, {:missing_field, :hour}}
+    end
+
+    test "rejects unknown formats" do
+      assert Temporal.format_rfc(~N[2025-06-01 14:30:00], :iso) == {:error, :invalid_options}
+    end
+  end
+
+  describe "validate/2" do
+    test "accepts dates and times that exist" do
+      assert Temporal.validate(~N[2024-02-29 23:59:59]) == :ok
+      assert Temporal.validate(%{year: 2024, month: 2, day: 29, hour: 0, minute: 0, second: 0}) ==
+               :ok
+
+      assert Temporal.validate("2024-02-29T12:00:00Z") == :ok
+    end
+
+    test "reports days past the end of the month" do
+      assert Temporal.validate(%{year: 2024, month: 2, day: 30}) ==
+               {:error, [day: {:out_of_range, 1, 29}]}
+
+      assert Temporal.validate(%{year: 2023, month: 2, day: 29}) ==
+               {:error, [day: {:out_of_range, 1, 28}]}
+
+      assert Temporal.validate(%{year: 2024, month: 4, day: 31}) ==
+               {:error, [day: {:out_of_range, 1, 30}]}
+    end
+
+    test "reports every rejected field" do
+      input = %{year: 2024, month: 13, day: 1, hour: 24, minute: 60, second: 0}
+
+      assert Temporal.validate(input) ==
+               {:error,
+                [
+                  hour: {:out_of_range, 0, 23},
+                  minute: {:out_of_range, 0, 59},
+                  month: {:out_of_range, 1, 12}
+                ]}
+    end
+
+    test "reports missing and mistyped fields" do
+      assert Temporal.validate(%{year: 2024, month: 2}) == {:error, [day: :missing]}
+      assert Temporal.validate(%{hour: 12}) == {:error, [minute: :missing, second: :missing]}
+
+      assert Temporal.validate(%{year: "2024", month: 1, day: 1}) ==
+               {:error, [year: :invalid]}
+    end
+
+    test "follows the months and leap years of the calendar" do
+      assert Temporal.validate(%{year: 1739, month: 13, day: 6}, :coptic) == :ok
+
+      assert Temporal.validate(%{year: 1740, month: 13, day: 6}, :coptic) ==
+               {:error, [day: {:out_of_range, 1, 5}]}
+
+      assert Temporal.validate(%{year: 2024, month: 13, day: 1}, :gregorian) ==
+               {:error, [month: {:out_of_range, 1, 12}]}
+
+      assert Temporal.validate(%{year: 5784, month: 13, day: 1}, :hebrew) ==
+               {:error, [month: :invalid]}
+    end
+
+    test "agrees with formatting" do
+      for input <- [
+            %{year: 2023, month: 2, day: 29},
+            %{year: 2024, month: 1, day: 1, hour: 24, minute: 0, second: 0},
+            "2024-02-30",
+            "2024-01-01T12:60:00"
+          ] do
+        assert {:error, [{field, {:out_of_range, min, max}}]} = Temporal.validate(input)
+        assert Temporal.format(input, locale: "en") == {:error, {:out_of_range, field, min, max}}
+      end
+
+      assert {:error, [year: :invalid]} = Temporal.validate(%{year: 1.5, month: 1, day: 1})
+      assert Temporal.format(%{year: 1.5, month: 1, day: 1}) == {:error, :invalid_datetime}
+    end
+
+    test "checks ISO 8601 strings like maps" do
+      assert Temporal.validate("2025-13-01") == {:error, [month: {:out_of_range, 1, 12}]}
+      assert Temporal.validate("2025-06-01T25:00") == {:error, [hour: {:out_of_range, 0, 23}]}
+
+      assert Temporal.validate("12:60:61") ==
+               {:error, [minute: {:out_of_range, 0, 59}, second: {:out_of_range, 0, 60}]}
+    end
+
+    test "rejects inputs that are not temporal" do
+      assert Temporal.validate("June 1st") == {:error, :invalid_datetime}
+      assert Temporal.validate(%{year: 2024, month: 1, day: 1}, :unknown) ==
+               {:error, :invalid_calendar}
+    end
+  end
+
+  describe "relative_day_name/2" do
+    test "names the days around today" do
+      assert Temporal.relative_day_name(-1, locale: "en") == {:ok, "yesterday"}
+      assert Temporal.relative_day_name(0, locale: "en") == {:ok, "today"}
+      assert Temporal.relative_day_name(1, locale: "en") == {:ok, "tomorrow"}
+      assert Temporal.relative_day_name(2, locale: "de") == {:ok, "übermorgen"}
+    end
+
+    test "formats days without a name numerically" do
+      assert Temporal.relative_day_name(3, locale: "en") == {:ok, "in 3 days"}
+      assert Temporal.relative_day_name(-5, locale: "en") == {:ok, "5 days ago"}
+    end
+
+    test "rejects invalid arguments" do
+      assert {:error, {:invalid_options, _}} =
+               Temporal.relative_day_name(1, locale: "en", width: :huge)
+
+      assert Temporal.relative_day_name(1.5, locale: "en") == {:error, :invalid_options}
+    end
+  end
+
+  describe "relative day options" do
+    test "replace dates near the reference date" do
+      options = [locale: "en", relative_to: ~D[2024-01-15]]
+
+      assert Temporal.format!(~D[2024-01-14], options) == "yesterday"
+      assert Temporal.format!(~D[2024-01-15], options) == "today"
+      assert Temporal.format!(~D[2024-01-16], options) == "tomorrow"
+      assert Temporal.format!(~D[2024-01-17], options) == "Jan 17, 2024"
+    end
+
+    test "keep the time of datetimes" do
+      options = [locale: "en", relative_to: "2024-01-15", time_precision: :minute]
+
+      assert {:ok, formatted} = Temporal.format(~N[2024-01-16 14:30:00], options)
+      assert String.starts_with?(formatted, "tomorrow")
+      assert formatted =~ "2:30"
+      refute formatted =~ "Jan"
+    end
+
+    test "widen the window with relative_days" do
+      options = [locale: "en", relative_to: ~N[2024-01-15 09:00:00], relative_days: 3]
+
+      assert Temporal.format!(~D[2024-01-12], options) == "3 days ago"
+      assert Temporal.format!(~D[2024-01-11], options) == "Jan 11, 2024"
+    end
+
+    test "report the name as a relative_day part" do
+      parts =
+        Temporal.format_to_parts!(~N[2024-01-14 14:30:00],
+          locale: "en",
+          relative_to: ~D[2024-01-15],
+          time_precision: :minute
+        )
+
+      assert [%{part_type: :relative_day, value: "yesterday"} | rest] = parts
+      assert Enum.any?(rest, &(&1.part_type == :hour))
+      refute Enum.any?(parts, &(&1.part_type in [:year, :month, :day]))
+    end
+
+    test "take a clock reading in its UTC offset" do
+      # 2023-11-14 22:13:20 UTC.
+      now = 1_700_000_000
+
+      assert Temporal.format!(~D[2023-11-14], locale: "en", relative_to: {now, 0}) == "today"
+      assert Temporal.format!(~D[2023-11-14], locale: "en", relative_to: {now, "+02:00"}) ==
+               "yesterday"
+
+      assert Temporal.format!(~D[2023-11-13], locale: "en", relative_to: {now, {-3, 0}}) ==
+               "yesterday"
+
+      assert {:error, :invalid_options} =
+               Formatter.new(locale: "en", relative_to: {now, "noon"})
+    end
+
+    test "read the clock in Elixir" do
+      {unix_seconds, 0} = Temporal.now()
+      assert_in_delta unix_seconds, System.os_time(:second), 5
+
+      today = Date.utc_today()
+      assert Temporal.format(today, locale: "en", relative_to: Temporal.now()) in [
+               {:ok, "today"},
+               {:ok, "yesterday"}
+             ]
+    end
+
+    test "are rejected without a date to replace" do
+      assert {:error, {:invalid_options, {:bad_option, :relative_to}}} =
+               Formatter.new_time(locale: "en", relative_to: ~D[2024-01-15])
+
+      assert {:error, :invalid_options} =
+               Formatter.new(locale: "en", date_fields: :yq, relative_to: ~D[2024-01-15])
+
+      assert {:error, :invalid_options} = Formatter.new(locale: "en", relative_days: 2)
+    end
+  end
+
+  describe "preferences option" do
+    test "replaces the hour cycle of the locale" do
+      assert {:ok, formatted} =
+               Temporal.format(~T[17:05:00], locale: "en-US", preferences: [hour_cycle: :h23])
+
+      assert formatted =~ "17:05"
+      refute formatted =~ "PM"
+    end
+
+    test "replaces the calendar of the locale" do
+      assert {:ok, formatted} =
+               Temporal.format(~D[2024-03-20], locale: "th", preferences: [calendar: :gregorian])
+
+      assert formatted =~ "2024"
+
+      assert {:ok, formatted} =
+               Temporal.format(~D[2024-03-20], locale: "en", preferences: [calendar: :buddhist])
+
+      assert formatted =~ "2567"
+    end
+
+    test "gives way to the calendar option" do
+      assert {:ok, formatted} =
+               Temporal.format(~D[2024-03-20],
+                 locale: "en",
+                 calendar: :gregorian,
+                 preferences: [calendar: :buddhist]
+               )
+
+      assert formatted =~ "2024"
+    end
+
+    test "is accepted by the date and time formatters" do
+      assert {:ok, formatter} =
+               Formatter.new_time(locale: "en-US", preferences: [hour_cycle: :h23])
+
+      assert {:ok, formatted} = Formatter.format(formatter, ~T[17:05:00])
+      assert formatted =~ "17:05"
+
+      assert {:ok, _formatter} =
+               Formatter.new_date(locale: "ar", preferences: [numbering_system: "latn"])
+    end
+
+    test "rejects unknown preferences" do
+      assert {:error, {:invalid_option_value, :preferences}} =
+               Temporal.format(~D[2024-03-20], preferences: [calendar: "lunar"])
+
+      assert {:error, {:invalid_option_value, :preferences}} =
+               Temporal.format(~D[2024-03-20], preferences: [hour_cycle: :h25])
+    end
+  end
 end
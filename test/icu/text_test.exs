@@ -0,0 +1,257 @@
+defmodule Icu.TextTest do
+  use ExUnit.Case, async: true
+
+  doctest Icu.Text
+
+  alias Icu.Text
+
+  describe "truncate/3" do
+    test "never splits grapheme clusters" do
+      assert {:ok, "🇩🇪🇫🇷…"} = Text.truncate("🇩🇪🇫🇷🇮🇹🇪🇸", 3)
+      assert {:ok, "éé…"} = Text.truncate("éééé", 3)
+    end
+
+    test "counts wide characters as one grapheme" do
+      assert {:ok, "漢字…"} = Text.truncate("漢字仮名交じり", 3)
+    end
+
+    test "drops trailing whitespace before the ellipsis" do
+      assert {:ok, "Hello…"} = Text.truncate("Hello world", 7)
+    end
+
+    test "accepts a custom ellipsis" do
+      assert {:ok, "Hell..."} = Text.truncate("Hello world", 7, ellipsis: "...")
+    end
+
+    test "cuts a single long word when breaking on words" do
+      assert {:ok, "Superc…"} =
+               Text.truncate("Supercalifragilistic", 7, word_boundary: true)
+    end
+
+    test "returns an error when the ellipsis does not fit" do
+      assert {:error, :invalid_options} = Text.truncate("Hello", 2, ellipsis: "...")
+    end
+
+    test "rejects unknown options" do
+      assert {:error, {:bad_option, :width}} = Text.truncate("Hello", 2, width: 3)
+    end
+  end
+
+  describe "display_width/2" do
+    test "counts fullwidth and halfwidth forms" do
+      assert {:ok, 4} = Text.display_width("ＡＢ")
+      assert {:ok, 2} = Text.display_width("ｱｲ")
+    end
+
+    test "counts emoji as two columns" do
+      assert {:ok, 2} = Text.display_width("👍🏽")
+      assert {:ok, 2} = Text.display_width("❤️")
+    end
+
+    test "ignores combining marks and control characters" do
+      assert {:ok, 1} = Text.display_width("e\u0301")
+      assert {:ok, 2} = Text.display_width("a\tb")
+    end
+
+    test "mixes scripts" do
+      assert {:ok, 10} = Text.display_width("Tokyo 東京")
+    end
+
+    test "rejects unknown ambiguous widths" do
+      assert {:error, {:invalid_option_value, :ambiguous}} =
+               Text.display_width("a", ambiguous: :medium)
+    end
+  end
+
+  describe "titlecase_words/2" do
+    test "applies the casing rules of the locale" do
+      assert {:ok, "İstanbul Ve İzmir"} = Text.titlecase_words("istanbul ve izmir", locale: "tr")
+      assert {:ok, "Istanbul Ve Izmir"} = Text.titlecase_words("istanbul ve izmir", locale: "en")
+    end
+
+    test "keeps apostrophes inside words" do
+      assert {:ok, "It's Five O'clock"} = Text.titlecase_words("it's five o'clock")
+    end
+
+    test "adjusts the start of words beginning with a digit" do
+      assert {:ok, "1st Place"} = Text.titlecase_words("1st place")
+      assert {:ok, "1St Place"} = Text.titlecase_words("1st place", leading_adjustment: :to_cased)
+    end
+
+    test "keeps stop words lowercase except at the edges" do
+      stop_words = ["a", "of", "THE"]
+
+      assert {:ok, "The Tale of a Town"} =
+               Text.titlecase_words("THE TALE OF A TOWN", stop_words: stop_words)
+
+      assert {:ok, "Tales of the"} = Text.titlecase_words("tales of the", stop_words: stop_words)
+    end
+
+    test "rejects invalid options" do
+      assert {:error, {:invalid_option_value, :leading_adjustment}} =
+               Text.titlecase_words("hello", leading_adjustment: :first)
+
+      assert {:error, {:invalid_option_value, :stop_words}} =
+               Text.titlecase_words("hello", stop_words: [:of])
+    end
+  end
+
+  describe "option maps passed to the NIFs" do
+    test "ignore nil values" do
+      assert {:ok, 1} = Icu.Nif.text_display_width("a", %{ambiguous: nil})
+      assert {:ok, "Hel…"} = Icu.Nif.text_truncate("Hello", 4, %{ellipsis: nil})
+      assert {:ok, "Hello"} = Icu.Nif.text_titlecase_words("hello", "en", %{stop_words: nil})
+    end
+
+    test "name the option with an invalid value" do
+      assert {:error, {:invalid_option_value, :ambiguous}} =
+               Icu.Nif.text_display_width("a", %{ambiguous: :medium})
+
+      assert {:error, {:invalid_option_value, :word_boundary}} =
+               Icu.Nif.text_truncate("Hello", 4, %{word_boundary: 1})
+
+      assert {:error, {:invalid_option_value, :trailing_case}} =
+               Icu.Nif.text_titlecase_words("hello", "en", %{trailing_case: :upper})
+    end
+
+    test "reject unknown keys" do
+      assert {:error, :invalid_options} = Icu.Nif.text_display_width("a", %{width: 2})
+    end
+  end
+
+  describe "word_stats/2" do
+    test "counts words regardless of case" do
+      assert {:ok, %{words: 6, unique: 4, top: [{"be", 2}, {"to", 2}, {"not", 1}]}} =
+               Text.word_stats("To be, or not to BE", top: 3)
+    end
+
+    test "folds case beyond lowercasing" do
+      assert {:ok, %{words: 2, unique: 1, top: [{"strasse", 2}]}} =
+               Text.word_stats("Straße STRASSE")
+    end
+
+    test "counts words in text without spaces" do
+      assert {:ok, %{words: words, unique: unique}} = Text.word_stats("我喜欢猫，我喜欢狗。")
+
+      assert words > unique
+      assert unique > 1
+    end
+
+    test "ignores spaces and punctuation" do
+      assert {:ok, %{words: 0, unique: 0, top: []}} = Text.word_stats(" ... !? ")
+      assert {:ok, %{words: 2, unique: 1}} = Text.word_stats("cat 🐈 cat")
+      assert {:ok, %{words: 0, unique: 0, top: []}} = Text.word_stats("")
+    end
+
+    test "returns at most the requested number of words" do
+      assert {:ok, %{unique: 4, top: []}} = Text.word_stats("a b c d", top: 0)
+      assert {:ok, %{top: [{"a", 1}, {"b", 1}]}} = Text.word_stats("d c b a", top: 2)
+    end
+
+    test "rejects invalid options" do
+      assert {:error, {:invalid_option_value, :top}} = Text.word_stats("hello", top: -1)
+    end
+  end
+
+  describe "insert_soft_breaks/2" do
+    test "inserts soft hyphens by default" do
+      assert {:ok, "and/\u00ADor"} = Text.insert_soft_breaks("and/or")
+    end
+
+    test "breaks Thai words found by dictionary" do
+      assert {:ok, broken} = Text.insert_soft_breaks("ภาษาไทยง่ายนิดเดียว", marker: "|")
+
+      assert broken =~ "|"
+      assert String.replace(broken, "|", "") == "ภาษาไทยง่ายนิดเดียว"
+    end
+
+    test "leaves breaks after spaces, dashes and line ends alone" do
+      text = "one two-three\nfour"
+
+      assert {:ok, ^text} = Text.insert_soft_breaks(text, marker: "|")
+    end
+
+    test "leaves breaks next to Chinese, Japanese and Korean characters alone" do
+      assert {:ok, "日本語の文章"} = Text.insert_soft_breaks("日本語の文章", locale: "ja")
+      assert {:ok, "カタカナとABC"} = Text.insert_soft_breaks("カタカナとABC", marker: "|", locale: "ja")
+      assert {:ok, "中文"} = Text.insert_soft_breaks("中文", marker: "|", locale: "zh")
+      assert {:ok, "한국어"} = Text.insert_soft_breaks("한국어", marker: "|", locale: "ko")
+    end
+
+    test "does not insert twice" do
+      {:ok, once} = Text.insert_soft_breaks("ภาษาไทยง่ายนิดเดียว", locale: "th")
+
+      assert {:ok, ^once} = Text.insert_soft_breaks(once, locale: "th")
+      assert {:ok, ^once} = Text.insert_soft_breaks(once, marker: "|", locale: "th")
+    end
+
+    test "rejects invalid options" do
+      assert {:error, {:invalid_option_value, :marker}} =
+               Text.insert_soft_breaks("text", marker: "")
+
+      assert {:error, {:invalid_option_value, :locale}} =
+               Text.insert_soft_breaks("text", locale: "not a locale")
+    end
+  end
+
+  describe "common_term/2" do
+    test "takes the list words from the list patterns" do
+      assert {:ok, "and"} = Text.common_term(:and, locale: "en")
+      assert {:ok, "or"} = Text.common_term(:or, locale: "en")
+      assert {:ok, "et"} = Text.common_term(:and, locale: "fr")
+    end
+
+    test "returns the quotation marks of the locale or its parent" do
+      assert {:ok, "«"} = Text.common_term(:quotation_start, locale: "fr")
+      assert {:ok, "»"} = Text.common_term(:quotation_end, locale: "fr-CA")
+      assert {:ok, "‹"} = Text.common_term(:alternate_quotation_start, locale: "de-CH")
+      assert {:ok, "「"} = Text.common_term(:quotation_start, locale: "zh-TW")
+    end
+
+    test "has no quotation marks for other locales" do
+      assert {:error, :no_data} = Text.common_term(:quotation_start, locale: "sw")
+      assert {:error, :no_data} = Text.common_term(:alternate_quotation_end, locale: "bg")
+      assert {:ok, "na"} = Text.common_term(:and, locale: "sw")
+    end
+
+    test "returns the ellipsis" do
+      assert "…" = Text.common_term!(:ellipsis, locale: "ja")
+    end
+
+    test "returns the ellipsis for locales without quotation marks" do
+      assert {:ok, "…"} = Text.common_term(:ellipsis, locale: "sw")
+      assert {:ok, "…"} = Text.common_term(:ellipsis, locale: "bg")
+    end
+
+    test "rejects unknown terms and options" do
+      assert {:error, :invalid_options} = Text.common_term(:more)
+      assert {:error, {:bad_option, :style}} = Text.common_term(:and, style: :short)
+      assert_raise RuntimeError, fn -> Text.common_term!(:and, locale: "not a locale") end
+    end
+  end
+
+  describe "quote_text/3" do
+    test "wraps text in the primary or secondary marks" do
+      assert {:ok, "«Привет»"} = Text.quote_text("Привет", :primary, locale: "ru")
+      assert {:ok, "„Привет“"} = Text.quote_text("Привет", :secondary, locale: "ru")
+      assert {:ok, "「こんにちは」"} = Text.quote_text("こんにちは", :primary, locale: "ja")
+    end
+
+    test "nests secondary quotes in primary ones" do
+      inner = Text.quote_text!("sí", :secondary, locale: "es")
+
+      assert {:ok, "«Dijo “sí”»"} = Text.quote_text("Dijo " <> inner, :primary, locale: "es")
+    end
+
+    test "does not guess the marks of other locales" do
+      assert {:error, :no_data} = Text.quote_text("שלום", :primary, locale: "he")
+    end
+
+    test "rejects unknown levels and locales" do
+      assert {:error, :invalid_options} = Text.quote_text("text", :tertiary)
+
+      assert {:error, {:invalid_option_value, :locale}} =
+               Text.quote_text("text", :primary, locale: "not a locale")
+    end
+  end
+end
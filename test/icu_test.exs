@@ -1,4 +1,200 @@
 defmodule ICUTest do
   use ExUnit.Case, async: true
   doctest Icu
+
+  describe "data_locale/1" do
+    test "reports the locale the data fell back to" do
+      formatter = Icu.Temporal.Formatter.new!(locale: "en-NZ")
+      assert {:ok, "en"} = Icu.data_locale(formatter)
+
+      formatter = Icu.Number.Formatter.new!(locale: "fr-FR")
+      assert {:ok, "fr"} = Icu.data_locale(formatter)
+    end
+
+    test "falls back to the root locale for unknown languages" do
+      formatter = Icu.Number.Formatter.new!(locale: "tlh")
+      assert {:ok, "und"} = Icu.data_locale(formatter)
+    end
+
+    test "accepts collators" do
+      assert {:ok, "sv"} = Icu.data_locale(Icu.Collator.new!(locale: "sv-SE"))
+    end
+
+    test "rejects values that are not formatters" do
+      assert {:error, :invalid_formatter} = Icu.data_locale(Icu.LanguageTag.parse!("en"))
+      assert {:error, :invalid_formatter} = Icu.data_locale(:nope)
+    end
+  end
+
+  describe "memory/1" do
+    test "reports a size for every resource type" do
+      formatters = [
+        Icu.Number.Formatter.new!(locale: "en"),
+        Icu.List.Formatter.new!(locale: "en"),
+        Icu.Temporal.Formatter.new!(locale: "en"),
+        Icu.Collator.new!(locale: "en"),
+        Icu.LanguageTag.parse!("en")
+      ]
+
+      for formatter <- formatters do
+        assert {:ok, bytes} = Icu.memory(formatter)
+        assert bytes > 0
+      end
+    end
+
+    test "counts locale extensions" do
+      {:ok, plain} = Icu.memory(Icu.LanguageTag.parse!("en"))
+      {:ok, extended} = Icu.memory(Icu.LanguageTag.parse!("en-u-ca-buddhist-nu-thai"))
+      assert extended > plain
+    end
+
+    test "rejects values that are not formatters" do
+      assert {:error, :invalid_formatter} = Icu.memory(:nope)
+    end
+  end
+
+  describe "formatter_to_config/1 and formatter_from_config/1" do
+    test "round-trips formatters through plain terms" do
+      {:ok, formatter} = Icu.Temporal.Formatter.new_date(locale: "de", length: :long)
+      {:ok, config} = Icu.formatter_to_config(formatter)
+
+      config = config |> :erlang.term_to_binary() |> :erlang.binary_to_term()
+      assert {:ok, rebuilt} = Icu.formatter_from_config(config)
+
+      assert Icu.Temporal.Formatter.format(rebuilt, ~D[2024-01-15]) ==
+               Icu.Temporal.Formatter.format(formatter, ~D[2024-01-15])
+    end
+
+    test "records the locale the formatter was built with" do
+      {:ok, number} =
+        Icu.with_locale("fr", fn -> Icu.Number.Formatter.new() end)
+
+      assert {:ok, %{args: [%{locale: "fr"}]}} = Icu.formatter_to_config(number)
+    end
+
+    test "keeps constructor arguments" do
+      {:ok, formatter} = Icu.Temporal.Formatter.from_pattern("dd.MM.yyyy", locale: "de")
+      {:ok, config} = Icu.formatter_to_config(formatter)
+      assert %{constructor: :from_pattern, args: ["dd.MM.yyyy", %{locale: "de"}]} = config
+
+      {:ok, names} = Icu.DisplayNames.Formatter.new(:region, locale: "en")
+      {:ok, config} = Icu.formatter_to_config(names)
+      {:ok, rebuilt} = Icu.formatter_from_config(config)
+      assert {:ok, "Germany"} = Icu.DisplayNames.Formatter.display_name(rebuilt, "DE")
+    end
+
+    test "rejects configs that do not name a formatter constructor" do
+      assert {:error, :invalid_config} =
+               Icu.formatter_from_config(%{formatter: File, constructor: :rm, args: ["x"]})
+
+      assert {:error, :invalid_config} = Icu.formatter_from_config(%{})
+      assert {:error, :invalid_formatter} = Icu.formatter_to_config(Icu.LanguageTag.parse!("en"))
+    end
+  end
+
+  describe "formatter_clone/1" do
+    test "builds an equivalent formatter with its own resource" do
+      formatter = Icu.Number.Formatter.new!(locale: "de", grouping: :always)
+      assert {:ok, copy} = Icu.formatter_clone(formatter)
+
+      refute copy.resource == formatter.resource
+      assert Icu.Number.Formatter.format(copy, 1234.5) == {:ok, "1.234,5"}
+      assert Icu.formatter_to_config(copy) == Icu.formatter_to_config(formatter)
+    end
+
+    test "rejects values that are not formatters" do
+      assert {:error, :invalid_formatter} = Icu.formatter_clone(:nope)
+    end
+  end
+
+  describe "concurrency" do
+    test "every formatter resource can be used from many processes at once" do
+      {:ok, display_names} = Icu.DisplayNames.Formatter.new(:locale, locale: "fr")
+      number = Icu.Number.Formatter.new!(locale: "de")
+      temporal = Icu.Temporal.Formatter.new_date!(locale: "ja", length: :long)
+      list = Icu.List.Formatter.new!(locale: "es")
+      currency = Icu.Experimental.Currency.Formatter.new!(locale: "de", currency: "EUR")
+      units = Icu.Experimental.Units.Formatter.new!(locale: "en", unit: "meter")
+      collator = Icu.Collator.new!(locale: "sv")
+      tags = ["en-GB", "de-AT", "pt-BR", "zh-Hant", "sr-Latn"]
+
+      format = fn i ->
+        {Icu.Number.Formatter.format(number, i * 1001),
+         Icu.Temporal.Formatter.format(temporal, Date.add(~D[2024-01-01], i)),
+         Icu.List.Formatter.format(list, Enum.map(0..rem(i, 5), &Integer.to_string/1)),
+         Icu.DisplayNames.Formatter.display_name(display_names, Enum.at(tags, rem(i, 5))),
+         Icu.Experimental.Currency.Formatter.format(currency, i / 4),
+         Icu.Experimental.Units.Formatter.format(units, i),
+         Icu.Collator.sort(collator, ["ö#{i}", "z#{i}", "a#{i}"])}
+      end
+
+      expected = Enum.map(1..2_000, format)
+
+      results =
+        1..2_000
+        |> Task.async_stream(format, max_concurrency: System.schedulers_online() * 4)
+        |> Enum.map(fn {:ok, result} -> result end)
+
+      assert results == expected
+    end
+  end
+
+  describe "warmup/2" do
+    test "warms up every kind by default" do
+      assert :ok = Icu.warmup(["en", "fr-CA"])
+    end
+
+    test "rejects invalid locales" do
+      assert {:error, :invalid_locale} = Icu.warmup(["en", "not a locale"], [:number])
+    end
+
+    test "rejects unknown kinds" do
+      assert {:error, :invalid_options} = Icu.warmup(["en"], [:number, :calendar])
+    end
+
+    test "warmup!/2 raises on failure" do
+      assert :ok = Icu.warmup!(["ja"], [:temporal])
+      assert_raise RuntimeError, fn -> Icu.warmup!(["en"], [:calendar]) end
+    end
+  end
+
+  describe "warnings/1" do
+    test "is empty for formatters using every option they were given" do
+      assert [] = Icu.warnings(Icu.List.Formatter.new!(locale: "en", type: :or))
+      assert [] = Icu.warnings(Icu.Collator.new!(locale: "en", numeric: true))
+    end
+
+    test "reports unknown options set to nil" do
+      formatter = Icu.Number.Formatter.new!(locale: "en", grouping: nil, colour: nil)
+      assert [{:ignored_option, :colour}] = Icu.warnings(formatter)
+
+      {:ok, formatter} = Icu.Temporal.Formatter.from_pattern("dd.MM.yyyy", length: nil)
+      assert [{:ignored_option, :length}] = Icu.warnings(formatter)
+    end
+
+    test "reports compact options without compact notation" do
+      formatter =
+        Icu.Experimental.Currency.Formatter.new!(
+          currency: "USD",
+          compact_rounding: :auto,
+          compact_threshold: 1000
+        )
+
+      assert [{:ignored_option, :compact_rounding}, {:ignored_option, :compact_threshold}] =
+               Icu.warnings(formatter)
+
+      formatter =
+        Icu.Experimental.Currency.Formatter.new!(
+          currency: "USD",
+          notation: :compact_short,
+          compact_threshold: 1000
+        )
+
+      assert [] = Icu.warnings(formatter)
+    end
+
+    test "returns no warnings for other values" do
+      assert [] = Icu.warnings(Icu.LanguageTag.parse!("en"))
+    end
+  end
 end